@@ -0,0 +1,92 @@
+use syn::{Attribute, LitInt, Result};
+
+/// Sort direction of a key field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+/// Options collected from the `#[lex(...)]` attributes on a field.
+#[derive(Debug, Clone)]
+pub struct FieldAttrs {
+    pub direction: Direction,
+    /// Part of the key of a `LexTable` row.
+    pub key: bool,
+    /// Left out by `Encode` and filled with `Default` by `Decode`.
+    pub skip: bool,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<FieldAttrs> {
+        let mut out = FieldAttrs { direction: Direction::Asc, key: false, skip: false };
+        for attr in attrs.iter().filter(|a| a.path().is_ident("lex")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("asc") {
+                    out.direction = Direction::Asc;
+                    Ok(())
+                } else if meta.path.is_ident("desc") {
+                    out.direction = Direction::Desc;
+                    Ok(())
+                } else if meta.path.is_ident("key") {
+                    out.key = true;
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    out.skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown lex attribute"))
+                }
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+/// Options collected from the `#[lex(...)]` attributes on an enum variant.
+#[derive(Debug, Clone)]
+pub struct VariantAttrs {
+    pub tag: Option<u32>,
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<VariantAttrs> {
+        let mut out = VariantAttrs { tag: None };
+        for attr in attrs.iter().filter(|a| a.path().is_ident("lex")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    let lit: LitInt = meta.value()?.parse()?;
+                    out.tag = Some(lit.base10_parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown lex attribute"))
+                }
+            })?;
+        }
+        Ok(out)
+    }
+}
+
+/// Options collected from the `#[lex(...)]` attributes on the type itself.
+#[derive(Debug, Clone)]
+pub struct ContainerAttrs {
+    /// Implement `lexcode::Project` for each field.
+    pub project: bool,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<ContainerAttrs> {
+        let mut out = ContainerAttrs { project: false };
+        for attr in attrs.iter().filter(|a| a.path().is_ident("lex")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("project") {
+                    out.project = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown lex attribute"))
+                }
+            })?;
+        }
+        Ok(out)
+    }
+}
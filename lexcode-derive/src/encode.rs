@@ -0,0 +1,227 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, Ident, Result};
+
+use crate::attr::{ContainerAttrs, Direction};
+use crate::lex_key::{bindings, construct, parse_fields, parse_variants, read_type, unwrap_read, with_bounds, Field};
+
+/// The shape of the type being derived, with its fields parsed.
+enum Shape {
+    Struct(Fields, Vec<Field>),
+    Enum(Vec<crate::lex_key::Variant>),
+}
+
+fn parse(input: &DeriveInput, derive: &str) -> Result<Shape> {
+    if ContainerAttrs::parse(&input.attrs)?.project {
+        return Err(Error::new_spanned(&input.ident, "#[lex(project)] is only supported by LexKey"));
+    }
+    let shape = match &input.data {
+        Data::Struct(data) => Shape::Struct(data.fields.clone(), parse_fields(&data.fields)?),
+        Data::Enum(data) => Shape::Enum(parse_variants(data)?),
+        Data::Union(_) => {
+            return Err(Error::new_spanned(&input.ident, format!("{derive} can only be derived for structs and enums")));
+        }
+    };
+    let fields: Vec<&Field> = match &shape {
+        Shape::Struct(_, fields) => fields.iter().collect(),
+        Shape::Enum(variants) => variants.iter().flat_map(|v| &v.fields).collect(),
+    };
+    if let Some(field) = fields.iter().find(|f| f.attrs.key) {
+        return Err(Error::new_spanned(&field.ty, "#[lex(key)] is only supported by LexTable"));
+    }
+    Ok(shape)
+}
+
+/// Bound the encoded fields of a generic type by `bound`, and the skipped
+/// ones by `Default` if they are decoded.
+fn bounds(input: &DeriveInput, shape: &Shape, bound: TokenStream, default: bool) -> syn::Generics {
+    let fields: Vec<&Field> = match shape {
+        Shape::Struct(_, fields) => fields.iter().collect(),
+        Shape::Enum(variants) => variants.iter().flat_map(|v| &v.fields).collect(),
+    };
+    let (skipped, encoded): (Vec<&Field>, Vec<&Field>) = fields.into_iter().partition(|f| f.attrs.skip);
+    let generics = with_bounds(&input.generics, encoded, bound);
+    if default {
+        with_bounds(&generics, skipped, quote!(::core::default::Default))
+    } else {
+        generics
+    }
+}
+
+/// Statements encoding each field that is not skipped, given expressions
+/// borrowing them.
+fn field_writes(fields: &[Field], values: &[TokenStream]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .zip(values)
+        .filter(|(f, _)| !f.attrs.skip)
+        .map(|(f, value)| match f.attrs.direction {
+            Direction::Asc => quote!(::lexcode::Encode::encode(#value, __out);),
+            Direction::Desc => quote!(::lexcode::Encode::encode(&::lexcode::Descending(#value), __out);),
+        })
+        .collect()
+}
+
+/// Statements decoding each field into the given bindings, in order.
+fn field_reads(fields: &[Field], bindings: &[Ident]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .zip(bindings)
+        .map(|(f, binding)| {
+            if f.attrs.skip {
+                return quote!(let #binding = ::core::default::Default::default(););
+            }
+            let read_ty = read_type(f);
+            let unwrap = unwrap_read(f);
+            quote!(let #binding = <#read_ty as ::lexcode::Decode>::decode(__decoder)? #unwrap;)
+        })
+        .collect()
+}
+
+pub fn expand_encode(input: &DeriveInput) -> Result<TokenStream> {
+    let shape = parse(input, "Encode")?;
+    let ident = &input.ident;
+    let generics = bounds(input, &shape, quote!(::lexcode::Encode), false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &shape {
+        Shape::Struct(_, fields) => {
+            let values: Vec<_> = fields
+                .iter()
+                .map(|f| {
+                    let member = &f.member;
+                    quote!(&self.#member)
+                })
+                .collect();
+            let writes = field_writes(fields, &values);
+            quote!(#( #writes )*)
+        }
+        Shape::Enum(variants) => {
+            let arms = variants.iter().map(|v| {
+                let variant = &v.ident;
+                let tag = v.tag;
+                let bindings = bindings(&v.fields);
+                let pattern = construct(quote!(#ident::#variant), &v.shape, &v.fields, &bindings);
+                let values: Vec<_> = bindings.iter().map(|b| quote!(#b)).collect();
+                let writes = field_writes(&v.fields, &values);
+                quote! {
+                    #[allow(unused_variables)]
+                    #pattern => {
+                        ::lexcode::Encode::encode(&#tag, __out);
+                        #( #writes )*
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #( #arms )*
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::lexcode::Encode for #ident #ty_generics #where_clause {
+            fn encode(&self, __out: &mut ::std::vec::Vec<u8>) {
+                #body
+            }
+        }
+    })
+}
+
+pub fn expand_decode(input: &DeriveInput) -> Result<TokenStream> {
+    let shape = parse(input, "Decode")?;
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let generics = bounds(input, &shape, quote!(::lexcode::Decode), true);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &shape {
+        Shape::Struct(fields_shape, fields) => {
+            let bindings = bindings(fields);
+            let reads = field_reads(fields, &bindings);
+            let construct = construct(quote!(#ident), fields_shape, fields, &bindings);
+            quote! {
+                #( #reads )*
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+        Shape::Enum(variants) => {
+            let arms = variants.iter().map(|v| {
+                let variant = &v.ident;
+                let tag = v.tag;
+                let bindings = bindings(&v.fields);
+                let reads = field_reads(&v.fields, &bindings);
+                let construct = construct(quote!(#ident::#variant), &v.shape, &v.fields, &bindings);
+                quote! {
+                    #tag => {
+                        #( #reads )*
+                        ::core::result::Result::Ok(#construct)
+                    }
+                }
+            });
+            quote! {
+                match <u32 as ::lexcode::Decode>::decode(__decoder)? {
+                    #( #arms )*
+                    __tag => ::core::result::Result::Err(::lexcode::Error::Message(
+                        ::std::format!("unknown variant tag {} for enum {}", __tag, #name),
+                    )),
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::lexcode::Decode for #ident #ty_generics #where_clause {
+            fn decode(__decoder: &mut ::lexcode::Decoder<'_>) -> ::lexcode::Result<Self> {
+                #body
+            }
+        }
+    })
+}
+
+/// The sum of the maximum lengths of the fields that are not skipped.
+fn fields_max_len(fields: &[Field]) -> TokenStream {
+    let lens = fields.iter().filter(|f| !f.attrs.skip).map(|f| {
+        let ty = &f.ty;
+        quote!(+ <#ty as ::lexcode::MaxEncodedLen>::MAX_ENCODED_LEN)
+    });
+    quote!(0 #( #lens )*)
+}
+
+pub fn expand_max_encoded_len(input: &DeriveInput) -> Result<TokenStream> {
+    let shape = parse(input, "MaxEncodedLen")?;
+    let ident = &input.ident;
+    let generics = bounds(input, &shape, quote!(::lexcode::MaxEncodedLen), false);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let len = match &shape {
+        Shape::Struct(_, fields) => fields_max_len(fields),
+        Shape::Enum(variants) => {
+            // The longest variant, tag included, in a constant expression.
+            let lens = variants.iter().map(|v| {
+                let tag = v.tag;
+                let fields = fields_max_len(&v.fields);
+                quote!(::lexcode::varint::encoded_len_uint(#tag as u128) + #fields)
+            });
+            quote! {{
+                let lens: &[usize] = &[#( #lens ),*];
+                let mut max = 0;
+                let mut i = 0;
+                while i < lens.len() {
+                    if lens[i] > max {
+                        max = lens[i];
+                    }
+                    i += 1;
+                }
+                max
+            }}
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::lexcode::MaxEncodedLen for #ident #ty_generics #where_clause {
+            const MAX_ENCODED_LEN: usize = #len;
+        }
+    })
+}
@@ -0,0 +1,123 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, Ident, Result, Type};
+
+use crate::lex_key;
+
+/// One `name(field, ...)` entry of `#[lex_index(...)]`.
+struct Index {
+    name: Ident,
+    fields: Vec<(Ident, Type)>,
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident, "LexIndex requires a struct with named fields")),
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "LexIndex can only be derived for structs")),
+    };
+    if input.generics.params.iter().next().is_some() {
+        return Err(Error::new_spanned(&input.generics, "LexIndex does not support generic structs"));
+    }
+
+    let mut indexes: Vec<Index> = Vec::new();
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("lex_index")) {
+        attr.parse_nested_meta(|meta| {
+            let name = meta.path.require_ident()?.clone();
+            if indexes.iter().any(|index| index.name == name) {
+                return Err(meta.error(format!("index `{name}` is defined twice")));
+            }
+            let mut index = Index { name, fields: Vec::new() };
+            meta.parse_nested_meta(|field| {
+                let ident = field.path.require_ident()?;
+                let ty = fields
+                    .iter()
+                    .find(|f| f.ident.as_ref() == Some(ident))
+                    .map(|f| f.ty.clone())
+                    .ok_or_else(|| field.error(format!("no field `{ident}`")))?;
+                index.fields.push((ident.clone(), ty));
+                Ok(())
+            })?;
+            if index.fields.is_empty() {
+                return Err(meta.error("an index needs at least one field"));
+            }
+            indexes.push(index);
+            Ok(())
+        })?;
+    }
+    if indexes.is_empty() {
+        return Err(Error::new_spanned(&input.ident, "LexIndex needs at least one #[lex_index(name(field, ...))]"));
+    }
+
+    indexes.iter().map(|index| expand_index(input, index)).collect()
+}
+
+/// The key struct of one index, its prefix ranges, and the method encoding
+/// a row's key.
+fn expand_index(input: &DeriveInput, index: &Index) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let name = &index.name;
+    let key_ident = format_ident!("{}{}", ident, camel_case(&name.to_string()));
+    let members: Vec<&Ident> = index.fields.iter().map(|(member, _)| member).collect();
+    let types: Vec<&Type> = index.fields.iter().map(|(_, ty)| ty).collect();
+
+    let key_doc = format!("Key of the `{name}` index of [`{ident}`], encoded as its fields in order.");
+    let key_struct: DeriveInput = parse_quote! {
+        #[doc = #key_doc]
+        #[derive(Debug, Clone, PartialEq)]
+        #vis struct #key_ident {
+            #( #vis #members: #types, )*
+        }
+    };
+    let codec = lex_key::expand(&key_struct)?;
+
+    // Every proper prefix of the fields, so a scan can fix the leading
+    // columns and leave the rest open.
+    let prefixes = (1..members.len()).map(|n| {
+        let (members, types) = (&members[..n], &types[..n]);
+        let method = format_ident!("prefix_{}", members.iter().map(|m| m.to_string()).collect::<Vec<_>>().join("_"));
+        let doc = format!(
+            "The byte range of every `{key_ident}` with the given leading field{}.",
+            if n > 1 { "s" } else { "" }
+        );
+        quote! {
+            #[doc = #doc]
+            #vis fn #method(#( #members: &#types ),*) -> ::lexcode::Result<(::std::vec::Vec<u8>, ::std::vec::Vec<u8>)> {
+                ::lexcode::prefix_range(&( #( #members, )* ))
+            }
+        }
+    });
+
+    let method = format_ident!("{}_key", name);
+    let method_doc = format!("Encode this row's `{name}` index key, as a [`{key_ident}`] would be.");
+    Ok(quote! {
+        #key_struct
+        #codec
+
+        impl #key_ident {
+            #( #prefixes )*
+        }
+
+        impl #ident {
+            #[doc = #method_doc]
+            #vis fn #method(&self) -> ::lexcode::Result<::std::vec::Vec<u8>> {
+                ::lexcode::to_bytes(&( #( &self.#members, )* ))
+            }
+        }
+    })
+}
+
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
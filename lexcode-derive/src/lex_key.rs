@@ -0,0 +1,526 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, Data, DataEnum, DeriveInput, Error, Fields, GenericParam, Generics, Ident, Index,
+    LifetimeParam, Member, Result, Type,
+};
+
+use crate::attr::{ContainerAttrs, Direction, FieldAttrs, VariantAttrs};
+
+pub struct Field {
+    pub member: Member,
+    pub ty: Type,
+    pub attrs: FieldAttrs,
+}
+
+pub struct Variant {
+    pub ident: Ident,
+    pub tag: u32,
+    pub shape: Fields,
+    pub fields: Vec<Field>,
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        Data::Enum(_) if attrs.project => {
+            return Err(Error::new_spanned(&input.ident, "#[lex(project)] is only supported on structs"));
+        }
+        Data::Enum(data) => return expand_enum(input, data),
+        Data::Union(_) => {
+            return Err(Error::new_spanned(&input.ident, "LexKey can only be derived for structs and enums"));
+        }
+    };
+    reject_key_fields(&data.fields)?;
+    let fields = parse_fields(&data.fields)?;
+
+    let serialize = expand_serialize(input, &data.fields, &fields);
+    let deserialize = expand_deserialize(input, &data.fields, &fields);
+    let project = if attrs.project { expand_project(input, &fields) } else { quote!() };
+    Ok(quote! {
+        #serialize
+        #deserialize
+        #project
+    })
+}
+
+pub fn parse_fields(fields: &Fields) -> Result<Vec<Field>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            Ok(Field {
+                member: match &f.ident {
+                    Some(ident) => Member::Named(ident.clone()),
+                    None => Member::Unnamed(Index::from(i)),
+                },
+                ty: f.ty.clone(),
+                attrs: FieldAttrs::parse(&f.attrs)?,
+            })
+        })
+        .collect()
+}
+
+/// `#[lex(key)]` only means something to `LexTable`, and `#[lex(skip)]`
+/// to `Encode` and `Decode`.
+fn reject_key_fields(fields: &Fields) -> Result<()> {
+    for f in fields {
+        let attrs = FieldAttrs::parse(&f.attrs)?;
+        if attrs.key {
+            return Err(Error::new_spanned(f, "#[lex(key)] is only supported by LexTable"));
+        }
+        reject_skip(f, &attrs)?;
+    }
+    Ok(())
+}
+
+pub fn reject_skip(field: &syn::Field, attrs: &FieldAttrs) -> Result<()> {
+    if attrs.skip {
+        return Err(Error::new_spanned(field, "#[lex(skip)] is only supported by Encode and Decode"));
+    }
+    Ok(())
+}
+
+/// Tags default to one more than the previous variant's, starting at 0,
+/// like Rust discriminants.
+pub fn parse_variants(data: &DataEnum) -> Result<Vec<Variant>> {
+    let mut variants: Vec<Variant> = Vec::new();
+    for v in &data.variants {
+        let attrs = VariantAttrs::parse(&v.attrs)?;
+        let tag = match (attrs.tag, variants.last()) {
+            (Some(tag), _) => tag,
+            (None, None) => 0,
+            (None, Some(prev)) => prev
+                .tag
+                .checked_add(1)
+                .ok_or_else(|| Error::new_spanned(&v.ident, "variant tag overflows u32"))?,
+        };
+        if let Some(other) = variants.iter().find(|other| other.tag == tag) {
+            return Err(Error::new_spanned(
+                &v.ident,
+                format!("variant tag {tag} is already used by `{}`", other.ident),
+            ));
+        }
+        variants.push(Variant {
+            ident: v.ident.clone(),
+            tag,
+            shape: v.fields.clone(),
+            fields: parse_fields(&v.fields)?,
+        });
+    }
+    Ok(variants)
+}
+
+fn field_names(fields: &[Field]) -> Vec<String> {
+    fields
+        .iter()
+        .map(|f| match &f.member {
+            Member::Named(ident) => ident.to_string(),
+            Member::Unnamed(_) => unreachable!(),
+        })
+        .collect()
+}
+
+/// The value to serialize for a field, given an expression borrowing it.
+pub fn field_value(field: &Field, value: TokenStream) -> TokenStream {
+    match field.attrs.direction {
+        Direction::Asc => value,
+        Direction::Desc => quote!(&::lexcode::Descending(#value)),
+    }
+}
+
+/// Statements reading each field from `__seq` into the given bindings.
+fn field_reads(fields: &[Field], bindings: &[Ident]) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .zip(bindings)
+        .enumerate()
+        .map(|(i, (f, binding))| {
+            let read_ty = read_type(f);
+            let unwrap = unwrap_read(f);
+            quote! {
+                let #binding = __seq
+                    .next_element::<#read_ty>()?
+                    .ok_or_else(|| ::lexcode::__private::serde::de::Error::invalid_length(#i, &self))?
+                    #unwrap;
+            }
+        })
+        .collect()
+}
+
+pub fn bindings(fields: &[Field]) -> Vec<Ident> {
+    (0..fields.len()).map(|i| format_ident!("__field{}", i)).collect()
+}
+
+/// Build `path` of the given shape from the bindings.
+pub fn construct(path: TokenStream, shape: &Fields, fields: &[Field], bindings: &[Ident]) -> TokenStream {
+    match shape {
+        Fields::Named(_) => {
+            let members = fields.iter().map(|f| &f.member);
+            quote!(#path { #( #members: #bindings ),* })
+        }
+        Fields::Unnamed(_) => quote!(#path ( #( #bindings ),* )),
+        Fields::Unit => quote!(#path),
+    }
+}
+
+fn expand_serialize(input: &DeriveInput, shape: &Fields, fields: &[Field]) -> TokenStream {
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let generics = with_bounds(&input.generics, fields, quote!(::lexcode::__private::serde::Serialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let len = fields.len();
+
+    let values = fields.iter().map(|f| {
+        let member = &f.member;
+        field_value(f, quote!(&self.#member))
+    });
+    let body = match shape {
+        Fields::Named(_) => {
+            let names = field_names(fields);
+            quote! {
+                use ::lexcode::__private::serde::ser::SerializeStruct;
+                let mut __state = __serializer.serialize_struct(#name, #len)?;
+                #( __state.serialize_field(#names, #values)?; )*
+                __state.end()
+            }
+        }
+        Fields::Unnamed(_) => quote! {
+            use ::lexcode::__private::serde::ser::SerializeTupleStruct;
+            let mut __state = __serializer.serialize_tuple_struct(#name, #len)?;
+            #( __state.serialize_field(#values)?; )*
+            __state.end()
+        },
+        Fields::Unit => quote! {
+            __serializer.serialize_unit_struct(#name)
+        },
+    };
+
+    quote! {
+        impl #impl_generics ::lexcode::__private::serde::Serialize for #ident #ty_generics #where_clause {
+            fn serialize<__S>(&self, __serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: ::lexcode::__private::serde::Serializer,
+            {
+                #body
+            }
+        }
+    }
+}
+
+fn expand_deserialize(input: &DeriveInput, shape: &Fields, fields: &[Field]) -> TokenStream {
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let generics = deserialize_generics(&input.generics, fields);
+    let (impl_generics, visitor_generics, where_clause) = generics.split_for_impl();
+
+    let bindings = bindings(fields);
+    let reads = field_reads(fields, &bindings);
+    let construct = construct(quote!(#ident), shape, fields, &bindings);
+    let len = fields.len();
+    let call = match shape {
+        Fields::Named(_) => {
+            let names = field_names(fields);
+            quote! {
+                const FIELDS: &[&str] = &[ #( #names ),* ];
+                __deserializer.deserialize_struct(#name, FIELDS, __visitor)
+            }
+        }
+        Fields::Unnamed(_) => quote!(__deserializer.deserialize_tuple_struct(#name, #len, __visitor)),
+        Fields::Unit => quote!(__deserializer.deserialize_unit_struct(#name, __visitor)),
+    };
+    let visit_unit = match shape {
+        Fields::Unit => quote! {
+            fn visit_unit<__E>(self) -> ::core::result::Result<Self::Value, __E>
+            where
+                __E: ::lexcode::__private::serde::de::Error,
+            {
+                ::core::result::Result::Ok(#ident)
+            }
+        },
+        _ => quote!(),
+    };
+
+    quote! {
+        impl #impl_generics ::lexcode::__private::serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+            fn deserialize<__D>(__deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+            where
+                __D: ::lexcode::__private::serde::Deserializer<'de>,
+            {
+                struct __Visitor #impl_generics #where_clause {
+                    marker: ::core::marker::PhantomData<fn() -> (#ident #ty_generics, &'de ())>,
+                }
+
+                impl #impl_generics ::lexcode::__private::serde::de::Visitor<'de> for __Visitor #visitor_generics #where_clause {
+                    type Value = #ident #ty_generics;
+
+                    fn expecting(&self, __f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        __f.write_str(concat!("struct ", #name))
+                    }
+
+                    #[allow(unused_mut)]
+                    fn visit_seq<__A>(self, mut __seq: __A) -> ::core::result::Result<Self::Value, __A::Error>
+                    where
+                        __A: ::lexcode::__private::serde::de::SeqAccess<'de>,
+                    {
+                        #( #reads )*
+                        ::core::result::Result::Ok(#construct)
+                    }
+
+                    #visit_unit
+                }
+
+                let __visitor = __Visitor { marker: ::core::marker::PhantomData };
+                #call
+            }
+        }
+    }
+}
+
+/// `Project<N>` for each field, which exposes the field types, so it is
+/// opt-in. The prefix is nested pairs
+/// `(F0, (F1, (F2,)))`, which encode as the fields back to back whatever
+/// their number.
+fn expand_project(input: &DeriveInput, fields: &[Field]) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let impls = (0..fields.len()).map(|n| {
+        let field = &fields[n];
+        let ty = &field.ty;
+        let read_ty = read_type(field);
+        let mut prefix_ty = quote!((#read_ty,));
+        let mut pattern = quote!((__field,));
+        for f in fields[..n].iter().rev() {
+            let read_ty = read_type(f);
+            prefix_ty = quote!((#read_ty, #prefix_ty));
+            pattern = quote!((_, #pattern));
+        }
+        let unwrap = unwrap_read(field);
+        quote! {
+            impl #impl_generics ::lexcode::Project<#n> for #ident #ty_generics #where_clause {
+                type Field = #ty;
+                type Prefix = #prefix_ty;
+
+                fn pick(__prefix: Self::Prefix) -> #ty {
+                    let #pattern = __prefix;
+                    __field #unwrap
+                }
+            }
+        }
+    });
+    quote!(#( #impls )*)
+}
+
+/// The type a field is read as.
+pub fn read_type(field: &Field) -> TokenStream {
+    let ty = &field.ty;
+    match field.attrs.direction {
+        Direction::Asc => quote!(#ty),
+        Direction::Desc => quote!(::lexcode::Descending<#ty>),
+    }
+}
+
+/// The suffix turning a value of the field's read type into the field.
+pub fn unwrap_read(field: &Field) -> TokenStream {
+    match field.attrs.direction {
+        Direction::Asc => quote!(),
+        Direction::Desc => quote!(.0),
+    }
+}
+
+fn expand_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
+    for v in &data.variants {
+        reject_key_fields(&v.fields)?;
+    }
+    let variants = parse_variants(data)?;
+    let all_fields: Vec<&Field> = variants.iter().flat_map(|v| &v.fields).collect();
+    let serialize = expand_enum_serialize(input, &variants, &all_fields);
+    let deserialize = expand_enum_deserialize(input, &variants, &all_fields);
+    Ok(quote! {
+        #serialize
+        #deserialize
+    })
+}
+
+fn expand_enum_serialize(input: &DeriveInput, variants: &[Variant], all_fields: &[&Field]) -> TokenStream {
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let generics = with_bounds(&input.generics, all_fields.iter().copied(), quote!(::lexcode::__private::serde::Serialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let arms = variants.iter().map(|v| {
+        let variant = &v.ident;
+        let variant_name = variant.to_string();
+        let tag = v.tag;
+        let len = v.fields.len();
+        let bindings = bindings(&v.fields);
+        let pattern = construct(quote!(#ident::#variant), &v.shape, &v.fields, &bindings);
+        let values: Vec<_> = v.fields.iter().zip(&bindings).map(|(f, b)| field_value(f, quote!(#b))).collect();
+        let body = match &v.shape {
+            Fields::Named(_) => {
+                let names = field_names(&v.fields);
+                quote! {
+                    use ::lexcode::__private::serde::ser::SerializeStructVariant;
+                    let mut __state = __serializer.serialize_struct_variant(#name, #tag, #variant_name, #len)?;
+                    #( __state.serialize_field(#names, #values)?; )*
+                    __state.end()
+                }
+            }
+            Fields::Unnamed(_) => quote! {
+                use ::lexcode::__private::serde::ser::SerializeTupleVariant;
+                let mut __state = __serializer.serialize_tuple_variant(#name, #tag, #variant_name, #len)?;
+                #( __state.serialize_field(#values)?; )*
+                __state.end()
+            },
+            Fields::Unit => quote! {
+                __serializer.serialize_unit_variant(#name, #tag, #variant_name)
+            },
+        };
+        quote!(#pattern => { #body })
+    });
+
+    quote! {
+        impl #impl_generics ::lexcode::__private::serde::Serialize for #ident #ty_generics #where_clause {
+            fn serialize<__S>(&self, __serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: ::lexcode::__private::serde::Serializer,
+            {
+                match self {
+                    #( #arms )*
+                }
+            }
+        }
+    }
+}
+
+fn expand_enum_deserialize(input: &DeriveInput, variants: &[Variant], all_fields: &[&Field]) -> TokenStream {
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let generics = deserialize_generics(&input.generics, all_fields.iter().copied());
+    let (impl_generics, visitor_generics, where_clause) = generics.split_for_impl();
+    let variant_names: Vec<String> = variants.iter().map(|v| v.ident.to_string()).collect();
+
+    let arms = variants.iter().map(|v| {
+        let variant = &v.ident;
+        let tag = v.tag;
+        let len = v.fields.len();
+        let bindings = bindings(&v.fields);
+        let reads = field_reads(&v.fields, &bindings);
+        let construct = construct(quote!(#ident::#variant), &v.shape, &v.fields, &bindings);
+        let expecting = format!("variant {name}::{variant}");
+        let access = match &v.shape {
+            Fields::Named(_) => {
+                let names = field_names(&v.fields);
+                quote! {
+                    const FIELDS: &[&str] = &[ #( #names ),* ];
+                    ::lexcode::__private::serde::de::VariantAccess::struct_variant(__variant, FIELDS, __visitor)
+                }
+            }
+            Fields::Unnamed(_) => quote! {
+                ::lexcode::__private::serde::de::VariantAccess::tuple_variant(__variant, #len, __visitor)
+            },
+            Fields::Unit => {
+                return quote! {
+                    #tag => {
+                        ::lexcode::__private::serde::de::VariantAccess::unit_variant(__variant)?;
+                        ::core::result::Result::Ok(#construct)
+                    }
+                };
+            }
+        };
+        quote! {
+            #tag => {
+                struct __VariantVisitor #impl_generics #where_clause {
+                    marker: ::core::marker::PhantomData<fn() -> (#ident #ty_generics, &'de ())>,
+                }
+
+                impl #impl_generics ::lexcode::__private::serde::de::Visitor<'de> for __VariantVisitor #visitor_generics #where_clause {
+                    type Value = #ident #ty_generics;
+
+                    fn expecting(&self, __f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        __f.write_str(#expecting)
+                    }
+
+                    #[allow(unused_mut)]
+                    fn visit_seq<__A>(self, mut __seq: __A) -> ::core::result::Result<Self::Value, __A::Error>
+                    where
+                        __A: ::lexcode::__private::serde::de::SeqAccess<'de>,
+                    {
+                        #( #reads )*
+                        ::core::result::Result::Ok(#construct)
+                    }
+                }
+
+                let __visitor = __VariantVisitor { marker: ::core::marker::PhantomData };
+                #access
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics ::lexcode::__private::serde::Deserialize<'de> for #ident #ty_generics #where_clause {
+            fn deserialize<__D>(__deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+            where
+                __D: ::lexcode::__private::serde::Deserializer<'de>,
+            {
+                struct __Visitor #impl_generics #where_clause {
+                    marker: ::core::marker::PhantomData<fn() -> (#ident #ty_generics, &'de ())>,
+                }
+
+                impl #impl_generics ::lexcode::__private::serde::de::Visitor<'de> for __Visitor #visitor_generics #where_clause {
+                    type Value = #ident #ty_generics;
+
+                    fn expecting(&self, __f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        __f.write_str(concat!("enum ", #name))
+                    }
+
+                    fn visit_enum<__A>(self, __data: __A) -> ::core::result::Result<Self::Value, __A::Error>
+                    where
+                        __A: ::lexcode::__private::serde::de::EnumAccess<'de>,
+                    {
+                        let (__tag, __variant) = ::lexcode::__private::serde::de::EnumAccess::variant::<u32>(__data)?;
+                        match __tag {
+                            #( #arms )*
+                            _ => ::core::result::Result::Err(::lexcode::__private::serde::de::Error::custom(
+                                ::core::format_args!("unknown variant tag {} for enum {}", __tag, #name),
+                            )),
+                        }
+                    }
+                }
+
+                const VARIANTS: &[&str] = &[ #( #variant_names ),* ];
+                let __visitor = __Visitor { marker: ::core::marker::PhantomData };
+                __deserializer.deserialize_enum(#name, VARIANTS, __visitor)
+            }
+        }
+    }
+}
+
+/// The impl generics of a `Deserialize` impl: the type's own, bounded by
+/// `Deserialize<'de>`, with `'de` prepended.
+fn deserialize_generics<'a>(generics: &Generics, fields: impl IntoIterator<Item = &'a Field>) -> Generics {
+    let mut generics = with_bounds(generics, fields, quote!(::lexcode::__private::serde::Deserialize<'de>));
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(LifetimeParam::new(syn::Lifetime::new("'de", Span::call_site()))));
+    generics
+}
+
+/// Add `bound` to every field type when the type is generic.
+pub fn with_bounds<'a>(generics: &Generics, fields: impl IntoIterator<Item = &'a Field>, bound: TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    if generics.type_params().next().is_some() {
+        let where_clause = generics.make_where_clause();
+        for field in fields {
+            let ty = &field.ty;
+            where_clause.predicates.push(parse_quote!(#ty: #bound));
+        }
+    }
+    generics
+}
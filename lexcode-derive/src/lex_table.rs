@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Ident, Result};
+
+use crate::attr::Direction;
+use crate::lex_key::{bindings, construct, field_value, parse_fields, read_type, reject_skip, unwrap_read, Field};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => return Err(Error::new_spanned(&input.ident, "LexTable can only be derived for structs")),
+    };
+    if input.generics.params.iter().next().is_some() {
+        return Err(Error::new_spanned(&input.generics, "LexTable does not support generic structs"));
+    }
+    let fields = parse_fields(&data.fields)?;
+    for (field, parsed) in data.fields.iter().zip(&fields) {
+        reject_skip(field, &parsed.attrs)?;
+    }
+    let (key, value): (Vec<&Field>, Vec<&Field>) = fields.iter().partition(|f| f.attrs.key);
+    if key.is_empty() {
+        return Err(Error::new_spanned(&input.ident, "LexTable needs at least one #[lex(key)] field"));
+    }
+    // Each half goes through serde as a tuple, which it implements for up
+    // to 16 elements.
+    if key.len() > 16 || value.len() > 16 {
+        return Err(Error::new_spanned(&input.ident, "LexTable supports at most 16 key and 16 value fields"));
+    }
+
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let encode_key = encode(&key);
+    let encode_value = encode(&value);
+
+    // Read each half as a tuple, then reassemble the fields in declaration
+    // order.
+    let bindings = bindings(&fields);
+    let key_reads = decode(&key, quote!(__key), &fields, &bindings);
+    let value_reads = decode(&value, quote!(__value), &fields, &bindings);
+    let construct = construct(quote!(#ident), &data.fields, &fields, &bindings);
+
+    Ok(quote! {
+        impl #ident {
+            /// Encode the `#[lex(key)]` fields, in declaration order.
+            #vis fn encode_key(&self) -> ::lexcode::Result<::std::vec::Vec<u8>> {
+                #encode_key
+            }
+
+            /// Encode the fields not in the key, in declaration order.
+            #vis fn encode_value(&self) -> ::lexcode::Result<::std::vec::Vec<u8>> {
+                #encode_value
+            }
+
+            /// Rebuild a row from the output of `encode_key` and
+            /// `encode_value`.
+            #vis fn decode_row(__key: &[u8], __value: &[u8]) -> ::lexcode::Result<Self> {
+                #key_reads
+                #value_reads
+                ::core::result::Result::Ok(#construct)
+            }
+        }
+    })
+}
+
+/// Encode the given fields of `self` as a tuple.
+fn encode(fields: &[&Field]) -> TokenStream {
+    let values = fields.iter().map(|f| {
+        let member = &f.member;
+        field_value(f, quote!(&self.#member))
+    });
+    quote!(::lexcode::to_bytes(&( #( #values, )* )))
+}
+
+/// Decode `input` as a tuple of the fields in `part`, binding each to its
+/// binding among all of the struct's fields. Trailing bytes are an error.
+fn decode(part: &[&Field], input: TokenStream, all: &[Field], bindings: &[Ident]) -> TokenStream {
+    let names: Vec<_> = part
+        .iter()
+        .map(|f| &bindings[all.iter().position(|other| other.member == f.member).unwrap()])
+        .collect();
+    let types = part.iter().map(|f| read_type(f));
+    let unwraps = part.iter().zip(&names).filter(|(f, _)| f.attrs.direction == Direction::Desc).map(|(f, name)| {
+        let unwrap = unwrap_read(f);
+        quote!(let #name = #name #unwrap;)
+    });
+    quote! {
+        let ( #( #names, )* ) = {
+            let mut __deserializer = ::lexcode::Deserializer::from_bytes(#input);
+            let __fields = <( #( #types, )* ) as ::lexcode::__private::serde::Deserialize>::deserialize(&mut __deserializer)?;
+            __deserializer.end()?;
+            __fields
+        };
+        #( #unwraps )*
+    }
+}
@@ -0,0 +1,253 @@
+//! Derive macros for [lexcode](https://docs.rs/lexcode).
+//!
+//! Use them through the `derive` feature of `lexcode`, which re-exports
+//! every macro in this crate.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod attr;
+mod encode;
+mod lex_index;
+mod lex_key;
+mod lex_table;
+
+/// Derive `Serialize` and `Deserialize` for a key struct or enum, honoring
+/// a sort direction on each field.
+///
+/// Fields are encoded in declaration order. A field marked `#[lex(desc)]`
+/// is encoded as if wrapped in `lexcode::Descending`, so it sorts in
+/// reverse; `#[lex(asc)]` is the default.
+///
+/// ```
+/// use lexcode::LexKey;
+///
+/// #[derive(Debug, PartialEq, LexKey)]
+/// struct Event {
+///     user: u64,
+///     #[lex(desc)]
+///     ts: i64,
+/// }
+///
+/// let old = lexcode::to_bytes(&Event { user: 1, ts: 10 }).unwrap();
+/// let new = lexcode::to_bytes(&Event { user: 1, ts: 20 }).unwrap();
+/// assert!(new < old);
+/// assert_eq!(lexcode::from_bytes::<Event>(&new).unwrap(), Event { user: 1, ts: 20 });
+/// ```
+///
+/// On an enum, each variant is encoded as its tag followed by its fields.
+/// The tag defaults to one more than the previous variant's (starting at
+/// 0, like Rust discriminants); `#[lex(tag = N)]` pins it, so variants can
+/// be inserted or reordered without changing existing keys. Variants sort
+/// by tag.
+///
+/// ```
+/// use lexcode::LexKey;
+///
+/// #[derive(Debug, PartialEq, LexKey)]
+/// enum Entity {
+///     #[lex(tag = 10)]
+///     User(u64),
+///     #[lex(tag = 15)]
+///     Team(String),
+///     #[lex(tag = 20)]
+///     Order { id: u64 },
+/// }
+///
+/// let team = lexcode::to_bytes(&Entity::Team("core".into())).unwrap();
+/// assert!(lexcode::to_bytes(&Entity::User(1)).unwrap() < team);
+/// assert!(team < lexcode::to_bytes(&Entity::Order { id: 1 }).unwrap());
+/// assert_eq!(lexcode::from_bytes::<Entity>(&team).unwrap(), Entity::Team("core".into()));
+/// ```
+///
+/// On a struct, `#[lex(project)]` also implements `lexcode::Project` for
+/// each field, so `lexcode::project::<T, N>` can decode field `N` alone.
+/// It is opt-in because the impls make the field types part of the
+/// struct's public interface.
+///
+/// ```
+/// use lexcode::LexKey;
+///
+/// #[derive(LexKey)]
+/// #[lex(project)]
+/// struct Row {
+///     user: u64,
+///     #[lex(desc)]
+///     ts: i64,
+///     payload: Vec<u8>,
+/// }
+///
+/// let key = lexcode::to_bytes(&Row { user: 7, ts: 30, payload: vec![0; 64] }).unwrap();
+/// assert_eq!(lexcode::project::<Row, 1>(&key).unwrap(), 30);
+/// ```
+#[proc_macro_derive(LexKey, attributes(lex))]
+pub fn derive_lex_key(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    lex_key::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive key structs for secondary indexes of a row struct.
+///
+/// Each `name(field, ...)` in `#[lex_index(...)]` defines an index over
+/// the listed fields, in that order. For an index `by_email` on `User` the
+/// derive generates:
+///
+/// - a struct `UserByEmail` with those fields, encoded as they are listed
+///   and decoded back with `lexcode::from_bytes`;
+/// - `UserByEmail::prefix_email(&email)` and so on, the byte range of
+///   every key starting with the given fields, for each proper prefix;
+/// - `User::by_email_key(&self)`, the row's encoded index key, built from
+///   borrowed fields.
+///
+/// The key structs derive `Debug`, `Clone` and `PartialEq`, so the index
+/// fields must implement them. Generic structs are not supported.
+///
+/// ```
+/// use lexcode::LexIndex;
+///
+/// #[derive(LexIndex)]
+/// #[lex_index(by_email(email, id), by_city(city, name, id))]
+/// struct User {
+///     id: u64,
+///     name: String,
+///     email: String,
+///     city: String,
+/// }
+///
+/// let user = User { id: 7, name: "Ada".into(), email: "ada@example.com".into(), city: "London".into() };
+/// let key = user.by_email_key().unwrap();
+/// assert_eq!(
+///     lexcode::from_bytes::<UserByEmail>(&key).unwrap(),
+///     UserByEmail { email: "ada@example.com".into(), id: 7 },
+/// );
+///
+/// let (start, end) = UserByCity::prefix_city(&"London".to_string()).unwrap();
+/// let key = user.by_city_key().unwrap();
+/// assert!(start <= key && key < end);
+/// ```
+#[proc_macro_derive(LexIndex, attributes(lex_index))]
+pub fn derive_lex_index(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    lex_index::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive a row codec for a table in a key-value store, splitting a
+/// struct's fields between the key and the value.
+///
+/// Fields marked `#[lex(key)]` form the key and the rest the value, each
+/// encoded as a tuple of its fields in declaration order. Key fields may
+/// also be `#[lex(desc)]`. The derive adds three functions:
+///
+/// - `encode_key(&self)`, the stored key;
+/// - `encode_value(&self)`, the stored value;
+/// - `decode_row(key, value)`, the row back from the two, failing if
+///   either holds bytes past its last field.
+///
+/// At least one field must be in the key. Each half is encoded as a serde
+/// tuple, so it may have at most 16 fields. Generic structs are not
+/// supported.
+///
+/// ```
+/// use lexcode::LexTable;
+///
+/// #[derive(Debug, PartialEq, LexTable)]
+/// struct Order {
+///     #[lex(key)]
+///     customer: u64,
+///     total_cents: u64,
+///     #[lex(key, desc)]
+///     placed_at: i64,
+///     note: String,
+/// }
+///
+/// let order = Order { customer: 7, total_cents: 1250, placed_at: 1_700_000_000, note: "gift".into() };
+/// let (key, value) = (order.encode_key().unwrap(), order.encode_value().unwrap());
+/// assert_eq!(key, lexcode::to_bytes(&(7u64, lexcode::Descending(1_700_000_000i64))).unwrap());
+/// assert_eq!(value, lexcode::to_bytes(&(1250u64, "gift")).unwrap());
+/// assert_eq!(Order::decode_row(&key, &value).unwrap(), order);
+/// ```
+#[proc_macro_derive(LexTable, attributes(lex))]
+pub fn derive_lex_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    lex_table::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `lexcode::Encode` for a struct or enum, without serde.
+///
+/// The output is what `LexKey` would serialize: the fields in
+/// declaration order, and for an enum the variant tag first. Fields take
+/// the same `#[lex(desc)]` and variants the same `#[lex(tag = N)]`
+/// attributes. A field marked `#[lex(skip)]` is left out of the key, and
+/// [`Decode`] fills it with `Default::default()`.
+///
+/// ```
+/// use lexcode::{Decode, Encode};
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Event {
+///     user: u64,
+///     #[lex(desc)]
+///     ts: i64,
+///     #[lex(skip)]
+///     cached_len: usize,
+/// }
+///
+/// let old = lexcode::encode(&Event { user: 1, ts: 10, cached_len: 3 });
+/// let new = lexcode::encode(&Event { user: 1, ts: 20, cached_len: 3 });
+/// assert!(new < old);
+/// assert_eq!(new, lexcode::to_bytes(&(1u64, lexcode::Descending(20i64))).unwrap());
+/// assert_eq!(lexcode::decode::<Event>(&new).unwrap(), Event { user: 1, ts: 20, cached_len: 0 });
+/// ```
+#[proc_macro_derive(Encode, attributes(lex))]
+pub fn derive_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    encode::expand_encode(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `lexcode::Decode`, reading what [`Encode`] writes.
+///
+/// Skipped fields must implement `Default`.
+#[proc_macro_derive(Decode, attributes(lex))]
+pub fn derive_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    encode::expand_decode(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `lexcode::MaxEncodedLen`, bounding the length of what [`Encode`]
+/// writes.
+///
+/// A struct's bound is the sum of its fields' bounds, skipped fields left
+/// out; an enum's is that of its longest variant, tag included. Every
+/// field that is not skipped must implement `MaxEncodedLen`.
+///
+/// ```
+/// use lexcode::{Encode, MaxEncodedLen};
+///
+/// #[derive(Encode, MaxEncodedLen)]
+/// enum Shape {
+///     Point(i32, i32),
+///     Circle { x: i32, y: i32, radius: u16 },
+/// }
+///
+/// const BUF_LEN: usize = lexcode::max_encoded_len::<Shape>();
+/// assert_eq!(BUF_LEN, 1 + 5 + 5 + 3);
+/// let key = lexcode::encode(&Shape::Circle { x: -1, y: 2, radius: 300 });
+/// assert!(key.len() <= BUF_LEN);
+/// ```
+#[proc_macro_derive(MaxEncodedLen, attributes(lex))]
+pub fn derive_max_encoded_len(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    encode::expand_max_encoded_len(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
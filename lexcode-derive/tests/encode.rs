@@ -0,0 +1,143 @@
+use lexcode::{max_encoded_len, Decode, Encode, LexKey, MaxEncodedLen};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, LexKey, Encode, Decode)]
+struct Event {
+    user: u64,
+    #[lex(desc)]
+    ts: i64,
+    kind: String,
+    tags: Vec<Option<u16>>,
+}
+
+#[derive(Debug, Clone, PartialEq, LexKey, Encode, Decode)]
+enum Entity {
+    #[lex(tag = 10)]
+    User(u64),
+    Team(#[lex(desc)] String),
+    #[lex(tag = 20)]
+    Order { id: u64, #[lex(desc)] placed: i32 },
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, LexKey, Encode, Decode)]
+struct Generic<T> {
+    #[lex(desc)]
+    inner: T,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Cached {
+    id: u32,
+    #[lex(skip)]
+    hits: u64,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Shape {
+    Circle { r: u8, #[lex(skip)] area: Option<f64> },
+    Empty,
+}
+
+#[derive(Debug, PartialEq, LexKey, Encode, Decode, MaxEncodedLen)]
+struct Marker;
+
+#[derive(Encode, MaxEncodedLen)]
+struct Bounded<T> {
+    id: u64,
+    #[lex(desc)]
+    at: Option<i32>,
+    #[lex(skip)]
+    #[allow(dead_code)]
+    note: String,
+    inner: T,
+}
+
+#[derive(Encode, MaxEncodedLen)]
+enum Command {
+    Stop,
+    Move(i16),
+    #[lex(tag = 300)]
+    Wait { ms: u32 },
+}
+
+#[derive(MaxEncodedLen)]
+enum Never {}
+
+fn event() -> impl Strategy<Value = Event> {
+    (any::<u64>(), any::<i64>(), ".{0,4}", any::<Vec<Option<u16>>>())
+        .prop_map(|(user, ts, kind, tags)| Event { user, ts, kind, tags })
+}
+
+fn entity() -> impl Strategy<Value = Entity> {
+    prop_oneof![
+        any::<u64>().prop_map(Entity::User),
+        ".{0,4}".prop_map(Entity::Team),
+        any::<(u64, i32)>().prop_map(|(id, placed)| Entity::Order { id, placed }),
+        Just(Entity::Unknown),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn structs_encode_like_lex_key(event in event(), inner in any::<(i8, String)>()) {
+        let bytes = lexcode::encode(&event);
+        prop_assert_eq!(&bytes, &lexcode::to_bytes(&event)?);
+        prop_assert_eq!(lexcode::decode::<Event>(&bytes)?, event);
+
+        let generic = Generic { inner };
+        let bytes = lexcode::encode(&generic);
+        prop_assert_eq!(&bytes, &lexcode::to_bytes(&generic)?);
+        prop_assert_eq!(lexcode::decode::<Generic<(i8, String)>>(&bytes)?, generic);
+    }
+
+    #[test]
+    fn enums_encode_like_lex_key(entity in entity()) {
+        let bytes = lexcode::encode(&entity);
+        prop_assert_eq!(&bytes, &lexcode::to_bytes(&entity)?);
+        prop_assert_eq!(lexcode::decode::<Entity>(&bytes)?, entity);
+    }
+}
+
+#[test]
+fn skipped_fields_are_left_out_and_defaulted() {
+    let cached = Cached { id: 7, hits: 99, name: "a".into() };
+    let bytes = lexcode::encode(&cached);
+    assert_eq!(bytes, lexcode::to_bytes(&(7u32, "a")).unwrap());
+    assert_eq!(lexcode::decode::<Cached>(&bytes).unwrap(), Cached { id: 7, hits: 0, name: "a".into() });
+
+    let circle = Shape::Circle { r: 3, area: Some(28.3) };
+    let bytes = lexcode::encode(&circle);
+    assert_eq!(bytes, lexcode::to_bytes(&(0u32, 3u8)).unwrap());
+    assert_eq!(lexcode::decode::<Shape>(&bytes).unwrap(), Shape::Circle { r: 3, area: None });
+}
+
+#[test]
+fn unit_structs_and_variants() {
+    assert!(lexcode::encode(&Marker).is_empty());
+    assert_eq!(lexcode::decode::<Marker>(&[]).unwrap(), Marker);
+    assert_eq!(lexcode::decode::<Shape>(&lexcode::encode(&Shape::Empty)).unwrap(), Shape::Empty);
+}
+
+#[test]
+fn unknown_tags_are_an_error() {
+    assert!(lexcode::decode::<Entity>(&lexcode::encode(&5u32)).is_err());
+}
+
+#[test]
+fn max_encoded_len_is_reached_by_the_longest_values() {
+    type Inner = (bool, lexcode::FixedBytes<4>);
+    assert_eq!(max_encoded_len::<Bounded<Inner>>(), 10 + 1 + 5 + 1 + 4);
+    let longest = Bounded { id: u64::MAX, at: Some(i32::MIN), note: "x".repeat(100), inner: (true, lexcode::FixedBytes([0xFF; 4])) };
+    assert_eq!(lexcode::encode(&longest).len(), max_encoded_len::<Bounded<Inner>>());
+
+    // The tag of `Wait` takes two bytes, which makes it the longest.
+    assert_eq!(max_encoded_len::<Command>(), 2 + 5);
+    assert_eq!(lexcode::encode(&Command::Wait { ms: u32::MAX }).len(), 2 + 5);
+    assert_eq!(lexcode::encode(&Command::Move(i16::MIN)).len(), 1 + 3);
+    assert_eq!(lexcode::encode(&Command::Stop).len(), 1);
+
+    assert_eq!(Marker::MAX_ENCODED_LEN, 0);
+    assert_eq!(Never::MAX_ENCODED_LEN, 0);
+}
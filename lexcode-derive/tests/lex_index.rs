@@ -0,0 +1,62 @@
+use lexcode::LexIndex;
+
+#[derive(Debug, LexIndex)]
+#[lex_index(by_email(email, id))]
+#[lex_index(by_team(team, joined, id))]
+struct User {
+    id: u64,
+    email: String,
+    team: String,
+    joined: i64,
+}
+
+fn user(id: u64, team: &str, joined: i64) -> User {
+    User { id, email: format!("{id}@example.com"), team: team.into(), joined }
+}
+
+#[test]
+fn index_key_matches_tuple_and_roundtrips() {
+    let row = user(7, "core", 1700);
+    let key = row.by_team_key().unwrap();
+    assert_eq!(key, lexcode::to_bytes(&("core", 1700i64, 7u64)).unwrap());
+    assert_eq!(
+        lexcode::from_bytes::<UserByTeam>(&key).unwrap(),
+        UserByTeam { team: "core".into(), joined: 1700, id: 7 },
+    );
+    let typed = UserByEmail { email: row.email.clone(), id: row.id };
+    assert_eq!(lexcode::to_bytes(&typed).unwrap(), row.by_email_key().unwrap());
+}
+
+#[test]
+fn prefixes_cover_exactly_the_matching_rows() {
+    let rows = [
+        user(1, "core", 10),
+        user(2, "core", 20),
+        user(3, "core", 20),
+        user(4, "corex", 20),
+        user(5, "ops", 20),
+    ];
+    let (start, end) = UserByTeam::prefix_team(&"core".to_string()).unwrap();
+    let ids: Vec<u64> = rows
+        .iter()
+        .filter(|row| (start.clone()..end.clone()).contains(&row.by_team_key().unwrap()))
+        .map(|row| row.id)
+        .collect();
+    assert_eq!(ids, [1, 2, 3]);
+
+    let (start, end) = UserByTeam::prefix_team_joined(&"core".to_string(), &20).unwrap();
+    let ids: Vec<u64> = rows
+        .iter()
+        .filter(|row| (start.clone()..end.clone()).contains(&row.by_team_key().unwrap()))
+        .map(|row| row.id)
+        .collect();
+    assert_eq!(ids, [2, 3]);
+}
+
+#[test]
+fn index_keys_sort_by_their_fields() {
+    let mut rows = [user(3, "ops", 5), user(1, "core", 30), user(2, "core", 10)];
+    rows.sort_by_key(|row| row.by_team_key().unwrap());
+    let ids: Vec<u64> = rows.iter().map(|row| row.id).collect();
+    assert_eq!(ids, [2, 1, 3]);
+}
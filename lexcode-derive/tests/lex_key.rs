@@ -0,0 +1,147 @@
+use lexcode::{Descending, LexKey};
+
+#[derive(Debug, Clone, PartialEq, LexKey)]
+#[lex(project)]
+struct Event {
+    user: u64,
+    #[lex(desc)]
+    ts: i64,
+    #[lex(asc)]
+    kind: String,
+}
+
+#[derive(Debug, PartialEq, LexKey)]
+#[lex(project)]
+struct Pair(#[lex(desc)] String, u8);
+
+#[derive(Debug, PartialEq, LexKey)]
+#[lex(project)]
+struct Generic<T> {
+    #[lex(desc)]
+    inner: T,
+}
+
+#[derive(Debug, PartialEq, LexKey)]
+struct Marker;
+
+#[test]
+fn derive_matches_manual_descending_tuple() {
+    let event = Event { user: 3, ts: -40, kind: "login".into() };
+    let manual = lexcode::to_bytes(&(3u64, Descending(-40i64), "login")).unwrap();
+    let bytes = lexcode::to_bytes(&event).unwrap();
+    assert_eq!(bytes, manual);
+    assert_eq!(lexcode::from_bytes::<Event>(&bytes).unwrap(), event);
+}
+
+#[test]
+fn derive_orders_by_field_direction() {
+    let mut events = [
+        Event { user: 2, ts: 5, kind: "a".into() },
+        Event { user: 1, ts: 5, kind: "b".into() },
+        Event { user: 1, ts: 9, kind: "a".into() },
+        Event { user: 1, ts: 5, kind: "a".into() },
+    ];
+    events.sort_by_key(|e| lexcode::to_bytes(e).unwrap());
+    let order: Vec<(u64, i64, &str)> = events.iter().map(|e| (e.user, e.ts, e.kind.as_str())).collect();
+    assert_eq!(order, [(1, 9, "a"), (1, 5, "a"), (1, 5, "b"), (2, 5, "a")]);
+}
+
+#[test]
+fn derive_tuple_generic_and_unit_structs() {
+    let pair = Pair("x".into(), 1);
+    let bytes = lexcode::to_bytes(&pair).unwrap();
+    assert_eq!(lexcode::from_bytes::<Pair>(&bytes).unwrap(), pair);
+
+    let a = lexcode::to_bytes(&Generic { inner: 1u32 }).unwrap();
+    let b = lexcode::to_bytes(&Generic { inner: 2u32 }).unwrap();
+    assert!(b < a);
+    assert_eq!(lexcode::from_bytes::<Generic<u32>>(&a).unwrap(), Generic { inner: 1 });
+
+    assert!(lexcode::to_bytes(&Marker).unwrap().is_empty());
+    assert_eq!(lexcode::from_bytes::<Marker>(&[]).unwrap(), Marker);
+}
+
+#[derive(Debug, Clone, PartialEq, LexKey)]
+enum Entity {
+    #[lex(tag = 10)]
+    User { id: u64, #[lex(desc)] ts: i64 },
+    // Inserted later between the other two without disturbing their tags.
+    #[lex(tag = 15)]
+    Team(String),
+    #[lex(tag = 20)]
+    Order(u64, u8),
+    Archived,
+}
+
+#[derive(Debug, PartialEq, LexKey)]
+enum Wrapper<T> {
+    Some(#[lex(desc)] T),
+    None,
+}
+
+#[test]
+fn derive_enum_uses_pinned_tags() {
+    let user = Entity::User { id: 1, ts: 5 };
+    let bytes = lexcode::to_bytes(&user).unwrap();
+    assert_eq!(bytes, lexcode::to_bytes(&(10u32, 1u64, Descending(5i64))).unwrap());
+    assert_eq!(lexcode::from_bytes::<Entity>(&bytes).unwrap(), user);
+
+    let team = Entity::Team("core".into());
+    assert_eq!(lexcode::to_bytes(&team).unwrap(), lexcode::to_bytes(&(15u32, "core")).unwrap());
+    let order = Entity::Order(7, 2);
+    assert_eq!(lexcode::to_bytes(&order).unwrap(), [20, 7, 2]);
+    // Untagged variants continue after the previous tag.
+    assert_eq!(lexcode::to_bytes(&Entity::Archived).unwrap(), [21]);
+
+    for entity in [team, order, Entity::Archived] {
+        let bytes = lexcode::to_bytes(&entity).unwrap();
+        assert_eq!(lexcode::from_bytes::<Entity>(&bytes).unwrap(), entity);
+    }
+    assert!(lexcode::from_bytes::<Entity>(&[11]).is_err());
+}
+
+#[test]
+fn derive_generic_enum() {
+    assert_eq!(lexcode::to_bytes(&Wrapper::<u8>::None).unwrap(), [1]);
+    let a = lexcode::to_bytes(&Wrapper::Some(1u8)).unwrap();
+    let b = lexcode::to_bytes(&Wrapper::Some(2u8)).unwrap();
+    assert!(b < a);
+    assert_eq!(lexcode::from_bytes::<Wrapper<u8>>(&a).unwrap(), Wrapper::Some(1));
+}
+
+#[test]
+fn derive_projects_fields() {
+    let event = Event { user: 7, ts: -3, kind: "login".into() };
+    let bytes = lexcode::to_bytes(&event).unwrap();
+    assert_eq!(lexcode::project::<Event, 0>(&bytes).unwrap(), 7);
+    assert_eq!(lexcode::project::<Event, 1>(&bytes).unwrap(), -3);
+    assert_eq!(lexcode::project::<Event, 2>(&bytes).unwrap(), "login");
+
+    let pair = lexcode::to_bytes(&Pair("b".into(), 9)).unwrap();
+    assert_eq!(lexcode::project::<Pair, 0>(&pair).unwrap(), "b");
+    assert_eq!(lexcode::project::<Pair, 1>(&pair).unwrap(), 9);
+
+    let generic = lexcode::to_bytes(&Generic { inner: 2i8 }).unwrap();
+    assert_eq!(lexcode::project::<Generic<i8>, 0>(&generic).unwrap(), 2);
+}
+
+#[derive(Debug, Clone, PartialEq, LexKey)]
+enum WideTags {
+    #[lex(tag = 128)]
+    Unit,
+    #[lex(tag = 200)]
+    Newtype(u64),
+    #[lex(tag = 300)]
+    Tuple(u64, u8),
+    #[lex(tag = 70_000)]
+    Struct { id: u64 },
+}
+
+#[test]
+fn derive_enum_tags_roundtrip_under_sqlite4_varints() {
+    let config = lexcode::LexConfig::new().varint_format(lexcode::varint::VarintFormat::Sqlite4);
+    for value in [WideTags::Unit, WideTags::Newtype(300), WideTags::Tuple(300, 2), WideTags::Struct { id: 300 }] {
+        let bytes = lexcode::to_bytes_with_config(&value, &config).unwrap();
+        assert_eq!(lexcode::from_bytes_with_config::<WideTags>(&bytes, &config).unwrap(), value);
+    }
+}
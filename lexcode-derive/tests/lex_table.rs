@@ -0,0 +1,70 @@
+use lexcode::{Descending, LexTable};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, LexTable)]
+struct Order {
+    #[lex(key)]
+    customer: u64,
+    total_cents: u64,
+    #[lex(key, desc)]
+    placed_at: i64,
+    note: String,
+    #[lex(key)]
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, LexTable)]
+struct Flag(#[lex(key)] String);
+
+#[test]
+fn halves_encode_as_tuples_of_their_fields() {
+    let order = Order { customer: 7, total_cents: 1250, placed_at: 30, note: "gift".into(), id: 2 };
+    assert_eq!(order.encode_key().unwrap(), lexcode::to_bytes(&(7u64, Descending(30i64), 2u32)).unwrap());
+    assert_eq!(order.encode_value().unwrap(), lexcode::to_bytes(&(1250u64, "gift")).unwrap());
+}
+
+#[test]
+fn key_only_row_has_empty_value() {
+    let flag = Flag("beta".into());
+    let value = flag.encode_value().unwrap();
+    assert!(value.is_empty());
+    assert_eq!(Flag::decode_row(&flag.encode_key().unwrap(), &value).unwrap(), flag);
+}
+
+#[test]
+fn decode_rejects_swapped_halves() {
+    let order = Order { customer: 1, total_cents: 2, placed_at: 3, note: "x".into(), id: 4 };
+    let (key, value) = (order.encode_key().unwrap(), order.encode_value().unwrap());
+    assert!(Order::decode_row(&value, &key).is_err());
+}
+
+#[test]
+fn decode_rejects_extra_fields() {
+    let order = Order { customer: 1, total_cents: 2, placed_at: 3, note: "x".into(), id: 4 };
+    let (key, value) = (order.encode_key().unwrap(), order.encode_value().unwrap());
+    let long_key = lexcode::to_bytes(&(1u64, Descending(3i64), 4u32, 5u8)).unwrap();
+    let long_value = lexcode::to_bytes(&(2u64, "x", 5u8)).unwrap();
+    assert!(Order::decode_row(&long_key, &value).is_err());
+    assert!(Order::decode_row(&key, &long_value).is_err());
+    assert_eq!(Order::decode_row(&key, &value).unwrap(), order);
+}
+
+fn order() -> impl Strategy<Value = Order> {
+    (any::<u64>(), any::<u64>(), any::<i64>(), ".*", any::<u32>()).prop_map(
+        |(customer, total_cents, placed_at, note, id)| Order { customer, total_cents, placed_at, note, id },
+    )
+}
+
+proptest! {
+    #[test]
+    fn rows_roundtrip(order in order()) {
+        let (key, value) = (order.encode_key().unwrap(), order.encode_value().unwrap());
+        prop_assert_eq!(Order::decode_row(&key, &value).unwrap(), order);
+    }
+
+    #[test]
+    fn keys_sort_by_key_fields(a in order(), b in order()) {
+        let expected = (a.customer, Descending(a.placed_at), a.id).cmp(&(b.customer, Descending(b.placed_at), b.id));
+        prop_assert_eq!(a.encode_key().unwrap().cmp(&b.encode_key().unwrap()), expected);
+    }
+}
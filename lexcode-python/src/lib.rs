@@ -0,0 +1,197 @@
+//! Python bindings for lexcode, built with `maturin build` from the
+//! repository root.
+//!
+//! ```python
+//! import lexcode
+//!
+//! key = lexcode.encode("(u64, str)", (7, "seven"))
+//! assert lexcode.decode("(u64, str)", key) == (7, "seven")
+//!
+//! schema = lexcode.Schema("[option<f64>]")
+//! schema.decode(schema.encode([1.5, None]))
+//! ```
+//!
+//! Python values map onto schemas as follows: `unit` is `None`, integers
+//! and floats are `int` and `float`, `char` and `str` are `str`, `bytes`
+//! and `fixed<N>` are `bytes`, sequences are `list`, tuples are `tuple`,
+//! maps are `dict`, `option<T>` is `None` or the inner value, and an enum
+//! variant is an `(index, payload)` tuple. As `None` could then mean more
+//! than one value, `option<unit>` and `option<option<T>>` are rejected.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList, PyTuple};
+use pyo3::IntoPyObjectExt;
+
+use lexcode::{Schema, Value};
+
+#[pyclass(name = "Schema", frozen)]
+struct PySchema {
+    schema: Schema,
+}
+
+#[pymethods]
+impl PySchema {
+    #[new]
+    fn new(schema: &str) -> PyResult<Self> {
+        Ok(PySchema { schema: parse(schema)? })
+    }
+
+    fn encode<'py>(&self, obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyBytes>> {
+        encode_with(&self.schema, obj)
+    }
+
+    fn decode<'py>(&self, data: &Bound<'py, PyBytes>) -> PyResult<Bound<'py, PyAny>> {
+        decode_with(&self.schema, data)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Schema(\"{}\")", self.schema)
+    }
+}
+
+/// Encode `obj` according to the schema string `schema`.
+#[pyfunction]
+fn encode<'py>(schema: &str, obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyBytes>> {
+    encode_with(&parse(schema)?, obj)
+}
+
+/// Decode `data` according to the schema string `schema`.
+#[pyfunction]
+fn decode<'py>(schema: &str, data: &Bound<'py, PyBytes>) -> PyResult<Bound<'py, PyAny>> {
+    decode_with(&parse(schema)?, data)
+}
+
+/// The `lexcode` Python module.
+#[pymodule]
+#[pyo3(name = "lexcode")]
+pub fn lexcode_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySchema>()?;
+    m.add_function(wrap_pyfunction!(encode, m)?)?;
+    m.add_function(wrap_pyfunction!(decode, m)?)?;
+    Ok(())
+}
+
+fn parse(schema: &str) -> PyResult<Schema> {
+    let schema: Schema = schema.parse().map_err(|e: lexcode::Error| PyValueError::new_err(e.to_string()))?;
+    schema.check_unambiguous().map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(schema)
+}
+
+fn encode_with<'py>(schema: &Schema, obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyBytes>> {
+    let value = to_value(schema, obj)?;
+    let bytes = lexcode::to_bytes(&value).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(PyBytes::new(obj.py(), &bytes))
+}
+
+fn decode_with<'py>(schema: &Schema, data: &Bound<'py, PyBytes>) -> PyResult<Bound<'py, PyAny>> {
+    let value = schema.decode(data.as_bytes()).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    to_python(data.py(), &value)
+}
+
+fn mismatch(schema: &Schema, obj: &Bound<'_, PyAny>) -> PyErr {
+    PyTypeError::new_err(format!("expected {schema}, found {obj:?}"))
+}
+
+fn to_value(schema: &Schema, obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    let value = match schema {
+        Schema::Unit => {
+            if !obj.is_none() {
+                return Err(mismatch(schema, obj));
+            }
+            Value::Unit
+        }
+        Schema::Bool => Value::Bool(obj.extract()?),
+        Schema::U8 => Value::UInt(obj.extract::<u8>()? as u128),
+        Schema::U16 => Value::UInt(obj.extract::<u16>()? as u128),
+        Schema::U32 => Value::UInt(obj.extract::<u32>()? as u128),
+        Schema::U64 => Value::UInt(obj.extract::<u64>()? as u128),
+        Schema::U128 => Value::UInt(obj.extract()?),
+        Schema::I8 => Value::Int(obj.extract::<i8>()? as i128),
+        Schema::I16 => Value::Int(obj.extract::<i16>()? as i128),
+        Schema::I32 => Value::Int(obj.extract::<i32>()? as i128),
+        Schema::I64 => Value::Int(obj.extract::<i64>()? as i128),
+        Schema::I128 => Value::Int(obj.extract()?),
+        Schema::F32 => Value::F32(obj.extract()?),
+        Schema::F64 => Value::F64(obj.extract()?),
+        Schema::Char => Value::Char(obj.extract()?),
+        Schema::Str => Value::String(obj.extract()?),
+        Schema::Bytes => Value::Bytes(obj.extract()?),
+        Schema::Fixed(n) => {
+            let bytes: Vec<u8> = obj.extract()?;
+            if bytes.len() != *n {
+                return Err(PyValueError::new_err(format!("expected {n} bytes, found {}", bytes.len())));
+            }
+            Value::Fixed(bytes)
+        }
+        Schema::Option(inner) => {
+            if obj.is_none() {
+                Value::Option(None)
+            } else {
+                Value::Option(Some(Box::new(to_value(inner, obj)?)))
+            }
+        }
+        Schema::Seq(inner) => {
+            let items = obj.try_iter()?.map(|item| to_value(inner, &item?)).collect::<PyResult<_>>()?;
+            Value::Seq(items)
+        }
+        Schema::Tuple(fields) => {
+            let items: Vec<Bound<'_, PyAny>> = obj.try_iter()?.collect::<PyResult<_>>()?;
+            if items.len() != fields.len() {
+                return Err(mismatch(schema, obj));
+            }
+            Value::Tuple(fields.iter().zip(&items).map(|(f, item)| to_value(f, item)).collect::<PyResult<_>>()?)
+        }
+        Schema::Map(key, value) => {
+            let dict = obj.cast::<PyDict>().map_err(|_| mismatch(schema, obj))?;
+            let mut entries = Vec::with_capacity(dict.len());
+            for (k, v) in dict.iter() {
+                let k = to_value(key, &k)?;
+                let encoded = lexcode::to_bytes(&k).map_err(|e| PyValueError::new_err(e.to_string()))?;
+                entries.push((encoded, k, to_value(value, &v)?));
+            }
+            // Emit entries in key order, matching how a `BTreeMap` encodes.
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Map(entries.into_iter().map(|(_, k, v)| (k, v)).collect())
+        }
+        Schema::Enum(variants) => {
+            let (index, payload): (u32, Bound<'_, PyAny>) = obj.extract().map_err(|_| mismatch(schema, obj))?;
+            let payload_schema = variants
+                .get(index as usize)
+                .ok_or_else(|| PyValueError::new_err(format!("variant {index} out of range for {schema}")))?;
+            Value::Variant(index, Box::new(to_value(payload_schema, &payload)?))
+        }
+    };
+    Ok(value)
+}
+
+fn to_python<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        Value::Unit | Value::Option(None) => Ok(py.None().into_bound(py)),
+        Value::Bool(v) => v.into_bound_py_any(py),
+        Value::UInt(v) => v.into_bound_py_any(py),
+        Value::Int(v) => v.into_bound_py_any(py),
+        Value::F32(v) => v.into_bound_py_any(py),
+        Value::F64(v) => v.into_bound_py_any(py),
+        Value::Char(v) => v.into_bound_py_any(py),
+        Value::String(v) => v.into_bound_py_any(py),
+        Value::Bytes(v) | Value::Fixed(v) => PyBytes::new(py, v).into_bound_py_any(py),
+        Value::Option(Some(v)) => to_python(py, v),
+        Value::Seq(items) => {
+            let items = items.iter().map(|item| to_python(py, item)).collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_bound_py_any(py)
+        }
+        Value::Tuple(items) => {
+            let items = items.iter().map(|item| to_python(py, item)).collect::<PyResult<Vec<_>>>()?;
+            PyTuple::new(py, items)?.into_bound_py_any(py)
+        }
+        Value::Map(entries) => {
+            let dict = PyDict::new(py);
+            for (k, v) in entries {
+                dict.set_item(to_python(py, k)?, to_python(py, v)?)?;
+            }
+            dict.into_bound_py_any(py)
+        }
+        Value::Variant(index, payload) => (*index, to_python(py, payload)?).into_bound_py_any(py),
+    }
+}
@@ -0,0 +1,67 @@
+use std::ffi::CStr;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Run `code` with the module imported as `lexcode`.
+fn run(code: &CStr) -> PyResult<()> {
+    Python::initialize();
+    Python::attach(|py| {
+        let globals = PyDict::new(py);
+        globals.set_item("lexcode", pyo3::wrap_pymodule!(lexcode_python::lexcode_module)(py))?;
+        py.run(code, Some(&globals), None)
+    })
+}
+
+#[test]
+fn encode_decode_roundtrip() {
+    run(c"
+key = lexcode.encode('(u64, str)', (7, 'seven'))
+assert key == bytes.fromhex('07736576656e0000'), key
+assert lexcode.decode('(u64, str)', key) == (7, 'seven')
+
+schema = lexcode.Schema('{str: option<f64>}')
+assert schema.decode(schema.encode({'b': None, 'a': 1.5})) == {'a': 1.5, 'b': None}
+assert repr(schema) == 'Schema(\"{str: option<f64>}\")'
+
+enum = lexcode.Schema('enum<unit, [u8]>')
+for value in [(0, None), (1, [1, 2])]:
+    assert enum.decode(enum.encode(value)) == value
+")
+    .unwrap();
+}
+
+#[test]
+fn keys_match_rust() {
+    let key = lexcode::to_bytes(&(7u64, "seven", Some(-1i32))).unwrap();
+    Python::initialize();
+    Python::attach(|py| {
+        let module = pyo3::wrap_pymodule!(lexcode_python::lexcode_module)(py);
+        let encode = module.getattr(py, "encode").unwrap();
+        let encoded: Vec<u8> = encode.call1(py, ("(u64, str, option<i32>)", (7, "seven", -1))).unwrap().extract(py).unwrap();
+        assert_eq!(encoded, key);
+    });
+}
+
+#[test]
+fn ambiguous_none_is_rejected() {
+    for schema in [c"lexcode.Schema('option<unit>')", c"lexcode.Schema('[option<option<u8>>]')"] {
+        let err = run(schema).unwrap_err();
+        assert!(err.to_string().contains("ambiguous"), "{err}");
+    }
+    run(c"lexcode.Schema('option<(unit, u8)>')").unwrap();
+}
+
+#[test]
+fn mismatched_values_are_errors() {
+    run(c"
+for schema, value in [('u8', 256), ('unit', 1), ('(u8, u8)', (1,)), ('fixed<2>', b'abc'), ('enum<unit>', (1, None))]:
+    try:
+        lexcode.encode(schema, value)
+    except (TypeError, ValueError, OverflowError):
+        pass
+    else:
+        raise AssertionError(schema)
+")
+    .unwrap();
+}
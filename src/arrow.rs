@@ -0,0 +1,290 @@
+//! Encoding keys for whole Arrow record batches at once (feature `arrow`).
+//!
+//! [`encode_columns`] takes one array per key column and writes the key of
+//! every row into a single buffer, with an offset per row. Each column is
+//! encoded on its own, with its type, nullability and direction resolved
+//! once: values are written straight into a column buffer without going
+//! through serde, and the columns are then interleaved into the keys.
+//!
+//! The key of a row is exactly what [`to_bytes`](crate::to_bytes) produces
+//! for the tuple of its values, each column mapped as follows:
+//!
+//! | Arrow type | Rust equivalent |
+//! |---|---|
+//! | `Boolean` | `bool` |
+//! | `Int8`–`Int64`, `UInt8`–`UInt64` | the same integer |
+//! | `Float32`, `Float64` | the same float |
+//! | `Date32`, `Time32` | `i32` |
+//! | `Date64`, `Time64`, `Timestamp`, `Duration` | `i64` |
+//! | `Utf8`, `LargeUtf8`, `Utf8View` | `&str` |
+//! | `Binary`, `LargeBinary`, `BinaryView` | `Vec<u8>` with [`as_bytes`](crate::as_bytes) |
+//! | `FixedSizeBinary(n)` | [`FixedBytes<n>`](crate::FixedBytes) |
+//!
+//! A [`nullable`](KeyColumn::nullable) column wraps the value in `Option`,
+//! and a [`descending`](KeyColumn::descending) one in
+//! [`Descending`](crate::Descending), outside the `Option`.
+//!
+//! ```
+//! use arrow_array::{Int64Array, StringArray};
+//! use lexcode::arrow::{encode_columns, KeyColumn};
+//! use lexcode::Descending;
+//!
+//! let users = StringArray::from(vec!["ann", "bob"]);
+//! let times = Int64Array::from(vec![Some(1700), None]);
+//! let keys = encode_columns(&[KeyColumn::new(&users), KeyColumn::new(&times).nullable(true).descending(true)])?;
+//!
+//! assert_eq!(keys.len(), 2);
+//! assert_eq!(keys.get(0), lexcode::to_bytes(&("ann", Descending(Some(1700i64))))?);
+//! assert_eq!(keys.get(1), lexcode::to_bytes(&("bob", Descending(None::<i64>)))?);
+//! # Ok::<(), lexcode::Error>(())
+//! ```
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Date64Type, DurationMicrosecondType, DurationMillisecondType, DurationNanosecondType,
+    DurationSecondType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, Time32MillisecondType,
+    Time32SecondType, Time64MicrosecondType, Time64NanosecondType, TimestampMicrosecondType,
+    TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type,
+    UInt8Type,
+};
+use arrow_array::{Array, ArrowPrimitiveType};
+use arrow_schema::{DataType, TimeUnit};
+
+use crate::encode::{encode_terminated, Encode};
+use crate::error::{Error, Result};
+
+/// One key column: its values and how they are encoded.
+#[derive(Clone, Copy)]
+pub struct KeyColumn<'a> {
+    array: &'a dyn Array,
+    descending: bool,
+    nullable: bool,
+}
+
+impl<'a> KeyColumn<'a> {
+    /// An ascending, non-nullable column.
+    pub fn new(array: &'a dyn Array) -> Self {
+        KeyColumn { array, descending: false, nullable: false }
+    }
+
+    /// Reverse the column's sort order.
+    pub fn descending(mut self, descending: bool) -> Self {
+        self.descending = descending;
+        self
+    }
+
+    /// Encode the values as `Option`s, so the column may hold nulls, which
+    /// sort first (last if descending).
+    ///
+    /// Nullability is a property of the key layout, not of a batch: a
+    /// column whose keys are decoded as `Option` must be encoded with this
+    /// set even for batches without nulls. Nulls in a column without it
+    /// are an error.
+    pub fn nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+}
+
+/// The keys of a batch: every key in one buffer, delimited by offsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedKeys {
+    data: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl EncodedKeys {
+    /// The number of keys.
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The key of row `row`. Panics if `row` is out of bounds.
+    pub fn get(&self, row: usize) -> &[u8] {
+        &self.data[self.offsets[row]..self.offsets[row + 1]]
+    }
+
+    /// The keys in row order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &[u8]> + '_ {
+        self.offsets.windows(2).map(|w| &self.data[w[0]..w[1]])
+    }
+
+    /// All keys, back to back.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The start of each key in [`data`](Self::data), followed by the end
+    /// of the last one.
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// The buffer and offsets, for handing to a store's batch writer.
+    pub fn into_parts(self) -> (Vec<u8>, Vec<usize>) {
+        (self.data, self.offsets)
+    }
+}
+
+/// Encode the key of every row of `columns`, which must all have the same
+/// length.
+///
+/// Fails on a column type without a lexcode equivalent (see the
+/// [module documentation](self)), on columns of different lengths, and on
+/// a null in a column not marked [`nullable`](KeyColumn::nullable).
+pub fn encode_columns(columns: &[KeyColumn<'_>]) -> Result<EncodedKeys> {
+    let rows = columns.first().map_or(0, |column| column.array.len());
+    if let Some(column) = columns.iter().find(|column| column.array.len() != rows) {
+        return Err(Error::Message(format!("key columns have {} and {} rows", rows, column.array.len())));
+    }
+    let encoded = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| encode_column(i, *column))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut data = Vec::with_capacity(encoded.iter().map(|column| column.data.len()).sum());
+    let mut offsets = Vec::with_capacity(rows + 1);
+    offsets.push(0);
+    for row in 0..rows {
+        for column in &encoded {
+            data.extend_from_slice(column.value(row));
+        }
+        offsets.push(data.len());
+    }
+    Ok(EncodedKeys { data, offsets })
+}
+
+/// One column's encoded values, back to back, delimited by offsets.
+struct EncodedColumn {
+    data: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl EncodedColumn {
+    fn value(&self, row: usize) -> &[u8] {
+        &self.data[self.offsets[row]..self.offsets[row + 1]]
+    }
+}
+
+/// Encode a whole column, resolving its type once.
+fn encode_column(index: usize, column: KeyColumn<'_>) -> Result<EncodedColumn> {
+    let array = column.array;
+    match array.data_type() {
+        DataType::Boolean => {
+            let values = array.as_boolean();
+            write_column(index, column, |row, out| out.push(values.value(row) as u8))
+        }
+        DataType::Int8 => primitive::<Int8Type>(index, column),
+        DataType::Int16 => primitive::<Int16Type>(index, column),
+        DataType::Int32 => primitive::<Int32Type>(index, column),
+        DataType::Int64 => primitive::<Int64Type>(index, column),
+        DataType::UInt8 => primitive::<UInt8Type>(index, column),
+        DataType::UInt16 => primitive::<UInt16Type>(index, column),
+        DataType::UInt32 => primitive::<UInt32Type>(index, column),
+        DataType::UInt64 => primitive::<UInt64Type>(index, column),
+        DataType::Float32 => primitive::<Float32Type>(index, column),
+        DataType::Float64 => primitive::<Float64Type>(index, column),
+        DataType::Date32 => primitive::<Date32Type>(index, column),
+        DataType::Date64 => primitive::<Date64Type>(index, column),
+        DataType::Time32(TimeUnit::Second) => primitive::<Time32SecondType>(index, column),
+        DataType::Time32(TimeUnit::Millisecond) => primitive::<Time32MillisecondType>(index, column),
+        DataType::Time64(TimeUnit::Microsecond) => primitive::<Time64MicrosecondType>(index, column),
+        DataType::Time64(TimeUnit::Nanosecond) => primitive::<Time64NanosecondType>(index, column),
+        DataType::Timestamp(TimeUnit::Second, _) => primitive::<TimestampSecondType>(index, column),
+        DataType::Timestamp(TimeUnit::Millisecond, _) => primitive::<TimestampMillisecondType>(index, column),
+        DataType::Timestamp(TimeUnit::Microsecond, _) => primitive::<TimestampMicrosecondType>(index, column),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => primitive::<TimestampNanosecondType>(index, column),
+        DataType::Duration(TimeUnit::Second) => primitive::<DurationSecondType>(index, column),
+        DataType::Duration(TimeUnit::Millisecond) => primitive::<DurationMillisecondType>(index, column),
+        DataType::Duration(TimeUnit::Microsecond) => primitive::<DurationMicrosecondType>(index, column),
+        DataType::Duration(TimeUnit::Nanosecond) => primitive::<DurationNanosecondType>(index, column),
+        DataType::Utf8 => {
+            let values = array.as_string::<i32>();
+            write_column(index, column, |row, out| encode_terminated(values.value(row).as_bytes(), 0x00, out))
+        }
+        DataType::LargeUtf8 => {
+            let values = array.as_string::<i64>();
+            write_column(index, column, |row, out| encode_terminated(values.value(row).as_bytes(), 0x00, out))
+        }
+        DataType::Utf8View => {
+            let values = array.as_string_view();
+            write_column(index, column, |row, out| encode_terminated(values.value(row).as_bytes(), 0x00, out))
+        }
+        // `as_bytes` escapes bytes exactly as a `str`.
+        DataType::Binary => {
+            let values = array.as_binary::<i32>();
+            write_column(index, column, |row, out| encode_terminated(values.value(row), 0x00, out))
+        }
+        DataType::LargeBinary => {
+            let values = array.as_binary::<i64>();
+            write_column(index, column, |row, out| encode_terminated(values.value(row), 0x00, out))
+        }
+        DataType::BinaryView => {
+            let values = array.as_binary_view();
+            write_column(index, column, |row, out| encode_terminated(values.value(row), 0x00, out))
+        }
+        DataType::FixedSizeBinary(_) => {
+            let values = array.as_fixed_size_binary();
+            write_column(index, column, |row, out| out.extend_from_slice(values.value(row)))
+        }
+        other => Err(Error::Message(format!("key column {index} has unsupported type {other}"))),
+    }
+}
+
+fn primitive<T>(index: usize, column: KeyColumn<'_>) -> Result<EncodedColumn>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Encode,
+{
+    let values = column.array.as_primitive::<T>().values();
+    write_column(index, column, |row, out| values[row].encode(out))
+}
+
+/// Write every value of a column with `write`, choosing the null and
+/// direction handling once for the whole column.
+fn write_column(index: usize, column: KeyColumn<'_>, write: impl Fn(usize, &mut Vec<u8>)) -> Result<EncodedColumn> {
+    match (column.nullable, column.descending) {
+        (false, false) => write_values::<false, false>(index, column.array, write),
+        (false, true) => write_values::<false, true>(index, column.array, write),
+        (true, false) => write_values::<true, false>(index, column.array, write),
+        (true, true) => write_values::<true, true>(index, column.array, write),
+    }
+}
+
+/// A nullable column writes the `Option` tag of each value, and a
+/// descending one complements each value's bytes, tag included.
+fn write_values<const NULLABLE: bool, const DESCENDING: bool>(
+    index: usize,
+    array: &dyn Array,
+    write: impl Fn(usize, &mut Vec<u8>),
+) -> Result<EncodedColumn> {
+    let rows = array.len();
+    let nulls = array.nulls();
+    let mut data = Vec::with_capacity(rows * 2);
+    let mut offsets = Vec::with_capacity(rows + 1);
+    offsets.push(0);
+    for row in 0..rows {
+        let start = data.len();
+        let is_null = nulls.is_some_and(|nulls| nulls.is_null(row));
+        if NULLABLE {
+            data.push(if is_null { 0x00 } else { 0x01 });
+        } else if is_null {
+            return Err(Error::Message(format!("null in non-nullable key column {index} at row {row}")));
+        }
+        if !is_null {
+            write(row, &mut data);
+        }
+        if DESCENDING {
+            for byte in &mut data[start..] {
+                *byte = !*byte;
+            }
+        }
+        offsets.push(data.len());
+    }
+    Ok(EncodedColumn { data, offsets })
+}
@@ -0,0 +1,39 @@
+//! `#[serde(with = "lexcode::as_bytes")]` for byte-string fields such as
+//! `Vec<u8>`.
+//!
+//! serde writes a `Vec<u8>` as a sequence, one tag byte and one varint per
+//! element, so 100 bytes of data take about 200 bytes of key. This helper
+//! writes the bytes like a `str` with those bytes instead: verbatim, with
+//! `0x00` escaped as `0x00 0x01` and a `0x00 0x00` terminator. The key
+//! sorts like the bytes (a prefix before its extensions), unlike
+//! `serialize_bytes`, whose `0x7F` terminator does not.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Blob(#[serde(with = "lexcode::as_bytes")] Vec<u8>);
+//!
+//! let bytes = lexcode::to_bytes(&Blob(vec![1, 2, 0xFF])).unwrap();
+//! assert_eq!(bytes, [1, 2, 0xFF, 0x00, 0x00]);
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::bytes::{Escaped, Terminated};
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + AsRef<[u8]>,
+    S: Serializer,
+{
+    Escaped(value.as_ref()).serialize(serializer)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: From<Vec<u8>>,
+    D: Deserializer<'de>,
+{
+    Terminated::deserialize(deserializer).map(|bytes| T::from(bytes.0))
+}
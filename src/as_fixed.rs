@@ -0,0 +1,28 @@
+//! `#[serde(with = "lexcode::as_fixed")]` for `[u8; N]` fields.
+//!
+//! serde writes an array as a tuple of `N` varints, and bytes of `0x80` or
+//! more take two bytes each. This helper writes the array verbatim, exactly
+//! like [`FixedBytes<N>`](crate::FixedBytes): `N` bytes of key that sort
+//! like the array, without changing the field's type.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Digest(#[serde(with = "lexcode::as_fixed")] [u8; 4]);
+//!
+//! let bytes = lexcode::to_bytes(&Digest([0xde, 0xad, 0xbe, 0xef])).unwrap();
+//! assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::fixed_bytes::FixedBytes;
+
+pub fn serialize<const N: usize, S: Serializer>(value: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+    FixedBytes(*value).serialize(serializer)
+}
+
+pub fn deserialize<'de, const N: usize, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; N], D::Error> {
+    FixedBytes::<N>::deserialize(deserializer).map(|bytes| bytes.0)
+}
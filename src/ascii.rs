@@ -0,0 +1,84 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::de::from_bytes;
+use crate::error::{Error, Result};
+use crate::ser::to_bytes;
+
+/// The base32hex digits, in ascending ASCII order.
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+
+/// Encode a value as an ASCII key that sorts like its lexcode bytes.
+///
+/// The bytes are written in base32hex with lowercase digits and no
+/// padding: five bits per character, most significant first, the last
+/// character filled with zero bits. Since the digits ascend in ASCII,
+/// comparing two armored keys as strings gives the same result as
+/// comparing the bytes, a shorter key that is a prefix included. The
+/// result only holds `0`–`9` and `a`–`v`, so it is usable as an S3 object
+/// key, a URL path segment or a DNS label (at most 63 characters, so 39
+/// bytes), at a cost of 8 characters per 5 bytes.
+///
+/// ```
+/// let keys = [(1u32, "a"), (1, "b"), (300, "")].map(|key| lexcode::to_ascii_key(&key).unwrap());
+/// assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+/// assert_eq!(lexcode::from_ascii_key::<(u32, String)>(&keys[1]).unwrap(), (1, "b".into()));
+/// ```
+pub fn to_ascii_key<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(encode_ascii(&to_bytes(&value)?))
+}
+
+/// Decode a key written by [`to_ascii_key`].
+///
+/// Uppercase digits are accepted too, for stores that do not preserve
+/// case, such as DNS.
+pub fn from_ascii_key<T: DeserializeOwned>(key: &str) -> Result<T> {
+    from_bytes(&decode_ascii(key)?)
+}
+
+/// The ASCII armor of [`to_ascii_key`] for bytes that are already encoded.
+pub fn encode_ascii(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = buffer << 8 | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[(buffer >> bits) as usize & 0x1F] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[(buffer << (5 - bits)) as usize & 0x1F] as char);
+    }
+    out
+}
+
+/// Undo [`encode_ascii`].
+///
+/// Fails on characters outside the alphabet, and on lengths or final
+/// characters that [`encode_ascii`] never produces.
+pub fn decode_ascii(key: &str) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(key.len() * 5 / 8);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for c in key.bytes() {
+        let digit = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'v' => c - b'a' + 10,
+            b'A'..=b'V' => c - b'A' + 10,
+            _ => return Err(Error::Message(format!("invalid character {:?} in ASCII key", c as char))),
+        };
+        buffer = buffer << 5 | digit as u16;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+        return Err(Error::Message("ASCII key has trailing bits".into()));
+    }
+    Ok(out)
+}
@@ -0,0 +1,312 @@
+//! Command-line tooling for inspecting lexcode keys.
+//!
+//! ```text
+//! lexcode encode <schema> <json>   print the hex encoding of a JSON literal
+//! lexcode decode <schema> <hex>    print encoded bytes as JSON
+//! lexcode compare <hex> <hex>      report how two encoded keys order
+//! ```
+//!
+//! Schemas use the syntax of `lexcode::Schema`, e.g. `"(u64, str, option<i32>)"`.
+
+use std::cmp::Ordering;
+use std::process::ExitCode;
+
+use lexcode::{Schema, Value};
+use serde_json::{Map, Number, Value as Json};
+
+const USAGE: &str = "usage:
+  lexcode encode <schema> <json>
+  lexcode decode <schema> <hex>
+  lexcode compare <hex> <hex>";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        ["encode", schema, json] => encode(schema, json),
+        ["decode", schema, hex] => decode(schema, hex),
+        ["compare", a, b] => compare(a, b),
+        ["help" | "-h" | "--help"] => Ok(USAGE.to_string()),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(output) => {
+            println!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn encode(schema: &str, json: &str) -> Result<String, String> {
+    let schema = parse(schema)?;
+    let json: Json = serde_json::from_str(json).map_err(|e| format!("invalid JSON: {e}"))?;
+    let value = from_json(&schema, &json)?;
+    let bytes = lexcode::to_bytes(&value).map_err(|e| format!("{e}"))?;
+    Ok(to_hex(&bytes))
+}
+
+fn decode(schema: &str, hex: &str) -> Result<String, String> {
+    let schema = parse(schema)?;
+    let bytes = from_hex(hex)?;
+    let value = schema.decode(&bytes).map_err(|e| format!("{e}"))?;
+    Ok(to_json(&value).to_string())
+}
+
+/// JSON has a single `null`, so schemas that need two are rejected.
+fn parse(schema: &str) -> Result<Schema, String> {
+    let schema: Schema = schema.parse().map_err(|e| format!("{e}"))?;
+    schema.check_unambiguous().map_err(|e| format!("{e}"))?;
+    Ok(schema)
+}
+
+fn compare(a: &str, b: &str) -> Result<String, String> {
+    let a = from_hex(a)?;
+    let b = from_hex(b)?;
+    let ordering = match a.cmp(&b) {
+        Ordering::Less => "less",
+        Ordering::Equal => return Ok("equal".to_string()),
+        Ordering::Greater => "greater",
+    };
+    let common = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    Ok(format!("{ordering} (first difference at byte {common})"))
+}
+
+// ── Hex ────────────────────────────────────────────────────────────────
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> = s
+        .trim_start_matches("0x")
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("odd number of hex digits in `{s}`"));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|p| u8::from_str_radix(p, 16).ok())
+                .ok_or_else(|| format!("invalid hex in `{s}`"))
+        })
+        .collect()
+}
+
+// ── JSON conversion ────────────────────────────────────────────────────
+
+fn from_json(schema: &Schema, json: &Json) -> Result<Value, String> {
+    let mismatch = || format!("expected {schema}, found {json}");
+    let value = match schema {
+        Schema::Unit => match json {
+            Json::Null => Value::Unit,
+            Json::Array(items) if items.is_empty() => Value::Unit,
+            _ => return Err(mismatch()),
+        },
+        Schema::Bool => Value::Bool(json.as_bool().ok_or_else(mismatch)?),
+        Schema::U8 | Schema::U16 | Schema::U32 | Schema::U64 | Schema::U128 => {
+            let v = json_u128(json).ok_or_else(mismatch)?;
+            let max = match schema {
+                Schema::U8 => u8::MAX as u128,
+                Schema::U16 => u16::MAX as u128,
+                Schema::U32 => u32::MAX as u128,
+                Schema::U64 => u64::MAX as u128,
+                _ => u128::MAX,
+            };
+            if v > max {
+                return Err(format!("{v} is out of range for {schema}"));
+            }
+            Value::UInt(v)
+        }
+        Schema::I8 | Schema::I16 | Schema::I32 | Schema::I64 | Schema::I128 => {
+            let v = json_i128(json).ok_or_else(mismatch)?;
+            let (min, max) = match schema {
+                Schema::I8 => (i8::MIN as i128, i8::MAX as i128),
+                Schema::I16 => (i16::MIN as i128, i16::MAX as i128),
+                Schema::I32 => (i32::MIN as i128, i32::MAX as i128),
+                Schema::I64 => (i64::MIN as i128, i64::MAX as i128),
+                _ => (i128::MIN, i128::MAX),
+            };
+            if v < min || v > max {
+                return Err(format!("{v} is out of range for {schema}"));
+            }
+            Value::Int(v)
+        }
+        Schema::F32 => Value::F32(json_f64(json).ok_or_else(mismatch)? as f32),
+        Schema::F64 => Value::F64(json_f64(json).ok_or_else(mismatch)?),
+        Schema::Char => {
+            let s = json.as_str().ok_or_else(mismatch)?;
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Value::Char(c),
+                _ => return Err(mismatch()),
+            }
+        }
+        Schema::Str => Value::String(json.as_str().ok_or_else(mismatch)?.to_string()),
+        Schema::Bytes => Value::Bytes(json_bytes(json).ok_or_else(mismatch)?),
+        Schema::Fixed(n) => {
+            let bytes = json_bytes(json).ok_or_else(mismatch)?;
+            if bytes.len() != *n {
+                return Err(format!("expected {n} bytes, found {}", bytes.len()));
+            }
+            Value::Fixed(bytes)
+        }
+        Schema::Option(inner) => match json {
+            Json::Null => Value::Option(None),
+            _ => Value::Option(Some(Box::new(from_json(inner, json)?))),
+        },
+        Schema::Seq(inner) => {
+            let items = json.as_array().ok_or_else(mismatch)?;
+            Value::Seq(items.iter().map(|item| from_json(inner, item)).collect::<Result<_, _>>()?)
+        }
+        Schema::Tuple(fields) => {
+            let items = json.as_array().ok_or_else(mismatch)?;
+            if items.len() != fields.len() {
+                return Err(mismatch());
+            }
+            Value::Tuple(fields.iter().zip(items).map(|(f, item)| from_json(f, item)).collect::<Result<_, _>>()?)
+        }
+        Schema::Map(key, value) => {
+            let mut entries = Vec::new();
+            match json {
+                Json::Object(object) => {
+                    for (k, v) in object {
+                        let k = match **key {
+                            Schema::Str => Json::String(k.clone()),
+                            _ => serde_json::from_str(k).map_err(|e| format!("invalid map key `{k}`: {e}"))?,
+                        };
+                        entries.push((from_json(key, &k)?, from_json(value, v)?));
+                    }
+                }
+                Json::Array(pairs) => {
+                    for pair in pairs {
+                        match pair.as_array().map(Vec::as_slice) {
+                            Some([k, v]) => entries.push((from_json(key, k)?, from_json(value, v)?)),
+                            _ => return Err(mismatch()),
+                        }
+                    }
+                }
+                _ => return Err(mismatch()),
+            }
+            // Emit entries in key order, matching how a `BTreeMap` encodes.
+            let mut entries = entries
+                .into_iter()
+                .map(|(k, v)| Ok((lexcode::to_bytes(&k).map_err(|e| format!("{e}"))?, k, v)))
+                .collect::<Result<Vec<_>, String>>()?;
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            if let Some(pair) = entries.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+                return Err(format!("duplicate map key {}", to_json(&pair[0].1)));
+            }
+            Value::Map(entries.into_iter().map(|(_, k, v)| (k, v)).collect())
+        }
+        Schema::Enum(variants) => {
+            let (index, payload) = match json {
+                Json::Number(_) => (json_u128(json).ok_or_else(mismatch)?, &Json::Null),
+                Json::Object(object) if object.len() == 1 => {
+                    let (k, v) = object.iter().next().unwrap();
+                    (k.parse::<u128>().map_err(|_| mismatch())?, v)
+                }
+                _ => return Err(mismatch()),
+            };
+            let out_of_range = || format!("variant {index} out of range for {schema}");
+            let payload_schema = usize::try_from(index)
+                .ok()
+                .and_then(|i| variants.get(i))
+                .ok_or_else(out_of_range)?;
+            let index = u32::try_from(index).map_err(|_| out_of_range())?;
+            Value::Variant(index, Box::new(from_json(payload_schema, payload)?))
+        }
+    };
+    Ok(value)
+}
+
+fn json_u128(json: &Json) -> Option<u128> {
+    match json {
+        Json::Number(n) => n.as_u64().map(u128::from),
+        Json::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_i128(json: &Json) -> Option<i128> {
+    match json {
+        Json::Number(n) => n.as_i64().map(i128::from),
+        Json::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_f64(json: &Json) -> Option<f64> {
+    match json {
+        Json::Number(n) => n.as_f64(),
+        Json::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn json_bytes(json: &Json) -> Option<Vec<u8>> {
+    match json {
+        Json::String(s) => from_hex(s).ok(),
+        Json::Array(items) => items.iter().map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok())).collect(),
+        _ => None,
+    }
+}
+
+fn to_json(value: &Value) -> Json {
+    match value {
+        Value::Unit | Value::Option(None) => Json::Null,
+        Value::Bool(v) => Json::Bool(*v),
+        Value::UInt(v) => match u64::try_from(*v) {
+            Ok(v) => Json::Number(v.into()),
+            Err(_) => Json::String(v.to_string()),
+        },
+        Value::Int(v) => match i64::try_from(*v) {
+            Ok(v) => Json::Number(v.into()),
+            Err(_) => Json::String(v.to_string()),
+        },
+        Value::F32(v) => float_json(*v as f64),
+        Value::F64(v) => float_json(*v),
+        Value::Char(c) => Json::String(c.to_string()),
+        Value::String(s) => Json::String(s.clone()),
+        Value::Bytes(b) | Value::Fixed(b) => Json::String(to_hex(b)),
+        Value::Option(Some(v)) => to_json(v),
+        Value::Seq(items) | Value::Tuple(items) => Json::Array(items.iter().map(to_json).collect()),
+        Value::Map(entries) => {
+            if entries.iter().all(|(k, _)| matches!(k, Value::String(_))) {
+                let object: Map<String, Json> = entries
+                    .iter()
+                    .map(|(k, v)| match k {
+                        Value::String(k) => (k.clone(), to_json(v)),
+                        _ => unreachable!(),
+                    })
+                    .collect();
+                Json::Object(object)
+            } else {
+                Json::Array(entries.iter().map(|(k, v)| Json::Array(vec![to_json(k), to_json(v)])).collect())
+            }
+        }
+        Value::Variant(index, payload) => match **payload {
+            Value::Unit => Json::Number((*index).into()),
+            ref payload => {
+                let mut object = Map::new();
+                object.insert(index.to_string(), to_json(payload));
+                Json::Object(object)
+            }
+        },
+    }
+}
+
+fn float_json(v: f64) -> Json {
+    match Number::from_f64(v) {
+        Some(n) => Json::Number(n),
+        None => Json::String(v.to_string()),
+    }
+}
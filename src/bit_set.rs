@@ -0,0 +1,120 @@
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::cmp::Ordering;
+
+use crate::magnitude::Magnitude;
+
+/// A set of bit indices backed by a byte vector, ordered like the integer
+/// whose set bits it holds.
+///
+/// Two sets compare by the highest bit in which they differ: the set
+/// containing it is greater, so `{3} > {0, 1, 2}` and every set is greater
+/// than its subsets. The encoding is that integer, length-graded: the
+/// number of bytes up to the highest set bit as a varint, then those bytes
+/// big-endian. Permission or feature masks of any width can therefore be
+/// compared bytewise.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::BitSet;
+///
+/// let low: BitSet = [0, 1, 2].into_iter().collect();
+/// let high: BitSet = [3].into_iter().collect();
+/// assert!(lexcode::to_bytes(&low).unwrap() < lexcode::to_bytes(&high).unwrap());
+///
+/// let decoded: BitSet = lexcode::from_bytes(&lexcode::to_bytes(&high).unwrap()).unwrap();
+/// assert_eq!(decoded.iter().collect::<Vec<_>>(), [3]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BitSet {
+    /// Bit `i` is bit `i % 8` of byte `i / 8`; no trailing zero bytes.
+    bytes: Vec<u8>,
+}
+
+impl BitSet {
+    pub fn new() -> BitSet {
+        BitSet::default()
+    }
+
+    /// Whether bit `index` is set.
+    pub fn get(&self, index: usize) -> bool {
+        self.bytes.get(index / 8).is_some_and(|&b| b & (1 << (index % 8)) != 0)
+    }
+
+    /// Set or clear bit `index`.
+    pub fn set(&mut self, index: usize, value: bool) {
+        let (byte, mask) = (index / 8, 1 << (index % 8));
+        if value {
+            if byte >= self.bytes.len() {
+                self.bytes.resize(byte + 1, 0);
+            }
+            self.bytes[byte] |= mask;
+        } else if let Some(b) = self.bytes.get_mut(byte) {
+            *b &= !mask;
+            self.trim();
+        }
+    }
+
+    /// The set bit indices in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bytes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &b)| (0..8).filter(move |bit| b & (1 << bit) != 0).map(move |bit| i * 8 + bit))
+    }
+
+    /// The number of set bits.
+    pub fn len(&self) -> usize {
+        self.bytes.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn trim(&mut self) {
+        while self.bytes.last() == Some(&0) {
+            self.bytes.pop();
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> BitSet {
+        let mut set = BitSet::new();
+        for index in iter {
+            set.set(index, true);
+        }
+        set
+    }
+}
+
+impl PartialOrd for BitSet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BitSet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes
+            .len()
+            .cmp(&other.bytes.len())
+            .then_with(|| self.bytes.iter().rev().cmp(other.bytes.iter().rev()))
+    }
+}
+
+impl Serialize for BitSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let be: Vec<u8> = self.bytes.iter().rev().copied().collect();
+        Magnitude::from_be(&be).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for BitSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let magnitude = Magnitude::deserialize(deserializer)?;
+        Ok(BitSet { bytes: magnitude.as_bytes().iter().rev().copied().collect() })
+    }
+}
@@ -0,0 +1,95 @@
+//! `#[serde(with = ...)]` encodings for [`std::ops::Bound`] that sort as
+//! interval bounds.
+//!
+//! serde writes `Bound` as an enum, so bounds sort by variant (`Unbounded`,
+//! then every `Included`, then every `Excluded`) before value. As the start
+//! of an interval a bound should instead sort by value, with `Unbounded`
+//! first and `Excluded(v)` just after `Included(v)`; as the end, by value
+//! with `Excluded(v)` just before `Included(v)` and `Unbounded` last. The
+//! two submodules write exactly those orders:
+//!
+//! | Module | `Unbounded` | `Included(v)` | `Excluded(v)` |
+//! |---|---|---|---|
+//! | `start` | `0x00` | `0x01` + `v` + `0x00` | `0x01` + `v` + `0x01` |
+//! | `end` | `0x01` | `0x00` + `v` + `0x01` | `0x00` + `v` + `0x00` |
+//!
+//! [`Interval`](crate::Interval) uses both.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use std::ops::Bound;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Since(#[serde(with = "lexcode::bound::start")] Bound<u32>);
+//!
+//! let keys: Vec<_> = [Bound::Unbounded, Bound::Included(5), Bound::Excluded(5), Bound::Included(6)]
+//!     .map(|b| lexcode::to_bytes(&Since(b)).unwrap())
+//!     .into();
+//! assert!(keys.is_sorted());
+//! ```
+
+/// A bound as the start of an interval.
+pub mod start {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::ops::Bound;
+
+    pub fn serialize<T, S>(value: &Bound<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        // `(value, excluded)`; `None` sorts first.
+        match value {
+            Bound::Unbounded => None::<(&T, bool)>,
+            Bound::Included(v) => Some((v, false)),
+            Bound::Excluded(v) => Some((v, true)),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Bound<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(match Option::<(T, bool)>::deserialize(deserializer)? {
+            None => Bound::Unbounded,
+            Some((v, false)) => Bound::Included(v),
+            Some((v, true)) => Bound::Excluded(v),
+        })
+    }
+}
+
+/// A bound as the end of an interval.
+pub mod end {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::ops::Bound;
+
+    use crate::option_last::OptionLast;
+
+    pub fn serialize<T, S>(value: &Bound<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        // `(value, included)`; `None` sorts last.
+        OptionLast(match value {
+            Bound::Unbounded => None::<(&T, bool)>,
+            Bound::Included(v) => Some((v, true)),
+            Bound::Excluded(v) => Some((v, false)),
+        })
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Bound<T>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(match OptionLast::<(T, bool)>::deserialize(deserializer)?.0 {
+            None => Bound::Unbounded,
+            Some((v, true)) => Bound::Included(v),
+            Some((v, false)) => Bound::Excluded(v),
+        })
+    }
+}
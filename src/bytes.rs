@@ -0,0 +1,74 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+use crate::fixed_bytes::{FIXED_BYTES_NAME, TERMINATED_BYTES_NAME};
+
+/// An arbitrary byte string encoded exactly like a `str` with those bytes:
+/// `0x00` is escaped as `0x00 0x01` and the end is marked by `0x00 0x00`.
+///
+/// Since the terminator is below every data byte, a string sorts before
+/// all of its extensions, so the encoding preserves the lexicographic order
+/// of the bytes. (`serialize_bytes` uses the `0x7F` sentinel, which does
+/// not.)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub(crate) struct Terminated(pub Vec<u8>);
+
+impl Serialize for Terminated {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Escaped(&self.0).serialize(serializer)
+    }
+}
+
+/// Borrowed bytes serialized as [`Terminated`] does, escaping on the fly
+/// rather than into a buffer.
+pub(crate) struct Escaped<'a>(pub &'a [u8]);
+
+impl Serialize for Escaped<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTupleStruct;
+        let zeros = self.0.iter().filter(|&&byte| byte == 0x00).count();
+        let mut ts = serializer.serialize_tuple_struct(FIXED_BYTES_NAME, self.0.len() + zeros + 2)?;
+        for byte in self.0 {
+            ts.serialize_field(byte)?;
+            if *byte == 0x00 {
+                ts.serialize_field(&0x01u8)?;
+            }
+        }
+        ts.serialize_field(&0x00u8)?;
+        ts.serialize_field(&0x00u8)?;
+        ts.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Terminated {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TerminatedVisitor;
+
+        impl<'de> Visitor<'de> for TerminatedVisitor {
+            type Value = Terminated;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a zero-terminated byte string")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Terminated, A::Error> {
+                let mut out = Vec::new();
+                loop {
+                    let byte: u8 = seq.next_element()?.ok_or_else(|| de::Error::custom("unterminated byte string"))?;
+                    if byte != 0x00 {
+                        out.push(byte);
+                        continue;
+                    }
+                    match seq.next_element::<u8>()? {
+                        Some(0x00) => return Ok(Terminated(out)),
+                        Some(0x01) => out.push(0x00),
+                        _ => return Err(de::Error::custom("invalid escape in byte string")),
+                    }
+                }
+            }
+        }
+
+        deserializer.deserialize_tuple_struct(TERMINATED_BYTES_NAME, usize::MAX, TerminatedVisitor)
+    }
+}
@@ -0,0 +1,105 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::ser::to_bytes;
+
+/// A string that sorts case-insensitively but keeps its original text.
+///
+/// `CaseInsensitive(s)` encodes as the tuple `(s.to_lowercase(), s)`: the
+/// lowercased key portion first, so `"alice"`, `"Alice"` and `"ALICE"` are
+/// adjacent and sort before `"Bob"`, followed by the original string so it
+/// round-trips. To enforce case-insensitive uniqueness, look for any key
+/// starting with [`fold_prefix`](CaseInsensitive::fold_prefix).
+///
+/// Lowercasing follows `str::to_lowercase`, which handles all of Unicode
+/// but is not full case folding (`"ß"` and `"SS"` stay distinct).
+///
+/// # Example
+///
+/// ```
+/// use lexcode::CaseInsensitive;
+///
+/// let alice = lexcode::to_bytes(&CaseInsensitive("Alice")).unwrap();
+/// let bob = lexcode::to_bytes(&CaseInsensitive("bob")).unwrap();
+/// assert!(alice < bob);
+/// assert!(alice.starts_with(&CaseInsensitive("ALICE").fold_prefix()));
+///
+/// let decoded: CaseInsensitive<String> = lexcode::from_bytes(&alice).unwrap();
+/// assert_eq!(decoded.0, "Alice");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CaseInsensitive<T = String>(pub T);
+
+impl<T: AsRef<str>> CaseInsensitive<T> {
+    /// The lowercased text that keys are grouped by.
+    pub fn folded(&self) -> String {
+        self.0.as_ref().to_lowercase()
+    }
+
+    /// The encoding of the lowercased portion alone: the common prefix of
+    /// the keys of every casing of this string.
+    pub fn fold_prefix(&self) -> Vec<u8> {
+        to_bytes(&self.folded()).expect("serializing strings cannot fail")
+    }
+}
+
+impl<T: AsRef<str> + Eq> PartialOrd for CaseInsensitive<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: AsRef<str> + Eq> Ord for CaseInsensitive<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded()
+            .cmp(&other.folded())
+            .then_with(|| self.0.as_ref().cmp(other.0.as_ref()))
+    }
+}
+
+impl<T> From<T> for CaseInsensitive<T> {
+    fn from(value: T) -> Self {
+        CaseInsensitive(value)
+    }
+}
+
+impl<T: AsRef<str>> Serialize for CaseInsensitive<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.folded())?;
+        tuple.serialize_element(self.0.as_ref())?;
+        tuple.end()
+    }
+}
+
+impl<'de, T: From<String>> Deserialize<'de> for CaseInsensitive<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CaseInsensitiveVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: From<String>> Visitor<'de> for CaseInsensitiveVisitor<T> {
+            type Value = CaseInsensitive<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a lowercased string followed by the original")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<CaseInsensitive<T>, A::Error> {
+                let folded: String = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let original: String = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                if original.to_lowercase() != folded {
+                    return Err(de::Error::custom("lowercased key does not match the original string"));
+                }
+                Ok(CaseInsensitive(T::from(original)))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, CaseInsensitiveVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,85 @@
+//! Compact order-preserving encodings for [`chrono`](::chrono) dates and
+//! times (feature `chrono`).
+//!
+//! chrono's own `Serialize` impls write RFC 3339 strings, which are long
+//! and only sort correctly within a fixed year range and format. The helper
+//! modules here write integers instead:
+//!
+//! - `NaiveDate`: days since 0001-01-01 as a signed varint (3 bytes for
+//!   present-day dates);
+//! - `NaiveDateTime` and `DateTime<Utc>`: seconds since the Unix epoch as a
+//!   signed varint, then the nanosecond of the second as an unsigned
+//!   varint. Leap seconds (nanoseconds ≥ 1,000,000,000) sort after the
+//!   second they extend.
+//!
+//! ```
+//! use chrono::{DateTime, NaiveDate, Utc};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(with = "lexcode::chrono::date_time")]
+//!     at: DateTime<Utc>,
+//!     #[serde(with = "lexcode::chrono::naive_date")]
+//!     day: NaiveDate,
+//! }
+//!
+//! let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+//! let event = Event { at, day: at.date_naive() };
+//! assert_eq!(lexcode::to_bytes(&event).unwrap().len(), 9);
+//! ```
+
+use ::chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+fn timestamp_parts(value: &NaiveDateTime) -> (i64, u32) {
+    let utc = value.and_utc();
+    (utc.timestamp(), utc.timestamp_subsec_nanos())
+}
+
+fn from_timestamp_parts<E: de::Error>((secs, nanos): (i64, u32)) -> Result<NaiveDateTime, E> {
+    DateTime::from_timestamp(secs, nanos)
+        .map(|utc| utc.naive_utc())
+        .ok_or_else(|| E::custom("timestamp out of range"))
+}
+
+/// `#[serde(with = "lexcode::chrono::naive_date")]` for `NaiveDate`.
+pub mod naive_date {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+        ::chrono::Datelike::num_days_from_ce(value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+        let days = i32::deserialize(deserializer)?;
+        NaiveDate::from_num_days_from_ce_opt(days).ok_or_else(|| de::Error::custom("date out of range"))
+    }
+}
+
+/// `#[serde(with = "lexcode::chrono::naive_date_time")]` for `NaiveDateTime`.
+pub mod naive_date_time {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error> {
+        timestamp_parts(value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDateTime, D::Error> {
+        from_timestamp_parts(Deserialize::deserialize(deserializer)?)
+    }
+}
+
+/// `#[serde(with = "lexcode::chrono::date_time")]` for `DateTime<Utc>`.
+pub mod date_time {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+        timestamp_parts(&value.naive_utc()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+        from_timestamp_parts(Deserialize::deserialize(deserializer)?).map(|naive| naive.and_utc())
+    }
+}
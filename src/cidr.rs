@@ -0,0 +1,150 @@
+use ipnet::IpNet;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::ops::RangeInclusive;
+
+use crate::net::Ip;
+
+/// An IP network keyed by (network address, prefix length), for
+/// routing-table style lookups (feature `ipnet`).
+///
+/// The network address is encoded like [`net::ip_addr`](crate::net::ip_addr)
+/// (family tag, then raw big-endian bytes) followed by the prefix length as
+/// one byte. Host bits are cleared on construction, so `10.1.2.3/8` is
+/// stored as `10.0.0.0/8`.
+///
+/// Every network containing an address `a` has a network address `≤ a`, and
+/// a longer containing prefix never has a smaller network address. So all
+/// candidates for a longest-prefix match of `a` lie in
+/// [`CidrKey::scan_range(a)`](CidrKey::scan_range), and scanning that range
+/// backwards, the first key that [`contains`](CidrKey::contains) `a` is the
+/// longest match.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::CidrKey;
+/// use std::net::IpAddr;
+///
+/// let mut table: Vec<Vec<u8>> = ["10.0.0.0/8", "10.1.0.0/16", "10.2.0.0/16", "192.168.0.0/16"]
+///     .iter()
+///     .map(|net| lexcode::to_bytes(&CidrKey::new(net.parse().unwrap())).unwrap())
+///     .collect();
+/// table.sort();
+///
+/// let addr: IpAddr = "10.1.7.7".parse().unwrap();
+/// let range = CidrKey::scan_range(addr);
+/// let (lo, hi) = (lexcode::to_bytes(range.start()).unwrap(), lexcode::to_bytes(range.end()).unwrap());
+/// let best = table
+///     .iter()
+///     .rev()
+///     .filter(|key| lo <= **key && **key <= hi)
+///     .map(|key| lexcode::from_bytes::<CidrKey>(key).unwrap())
+///     .find(|net| net.contains(addr))
+///     .unwrap();
+/// assert_eq!(best.net().to_string(), "10.1.0.0/16");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CidrKey(IpNet);
+
+impl CidrKey {
+    /// Key for `net`, with its host bits cleared.
+    pub fn new(net: IpNet) -> CidrKey {
+        CidrKey(net.trunc())
+    }
+
+    pub fn net(&self) -> IpNet {
+        self.0
+    }
+
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        self.0.contains(&addr)
+    }
+
+    /// The inclusive key range holding every network that contains `addr`.
+    pub fn scan_range(addr: IpAddr) -> RangeInclusive<CidrKey> {
+        let (zero, max_len) = match addr {
+            IpAddr::V4(_) => (IpAddr::V4(Ipv4Addr::UNSPECIFIED), 32),
+            IpAddr::V6(_) => (IpAddr::V6(Ipv6Addr::UNSPECIFIED), 128),
+        };
+        let start = IpNet::new(zero, 0).expect("zero prefix is valid");
+        let end = IpNet::new(addr, max_len).expect("full-length prefix is valid");
+        CidrKey(start)..=CidrKey(end)
+    }
+
+    fn sort_key(&self) -> (IpAddr, u8) {
+        (self.0.network(), self.0.prefix_len())
+    }
+}
+
+impl PartialOrd for CidrKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CidrKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl From<IpNet> for CidrKey {
+    fn from(net: IpNet) -> CidrKey {
+        CidrKey::new(net)
+    }
+}
+
+impl From<CidrKey> for IpNet {
+    fn from(key: CidrKey) -> IpNet {
+        key.0
+    }
+}
+
+impl fmt::Display for CidrKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Serialize for CidrKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&Ip(self.0.network()))?;
+        tuple.serialize_element(&self.0.prefix_len())?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for CidrKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CidrKeyVisitor;
+
+        impl<'de> Visitor<'de> for CidrKeyVisitor {
+            type Value = CidrKey;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a network address followed by a prefix length")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<CidrKey, A::Error> {
+                let Ip(addr) = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let prefix_len: u8 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let net = IpNet::new(addr, prefix_len).map_err(de::Error::custom)?;
+                if net.trunc() != net {
+                    return Err(de::Error::custom("network address has host bits set"));
+                }
+                Ok(CidrKey(net))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, CidrKeyVisitor)
+    }
+}
@@ -0,0 +1,95 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::{Bound, RangeBounds};
+
+use crate::de::from_bytes;
+use crate::error::Result;
+use crate::range::{prefix_successor, range, ByteRange};
+use crate::ser::to_bytes;
+
+/// How a store adapter turns values of type `T` into bytes and back.
+///
+/// Codecs are types rather than values, like the key and value types of
+/// most stores, so an adapter takes the codec as a type parameter and
+/// calls its functions directly.
+pub trait ValueCodec<T> {
+    fn encode(value: &T) -> Result<Vec<u8>>;
+
+    /// Fails if `bytes` is not an encoded `T`.
+    fn decode(bytes: &[u8]) -> Result<T>;
+}
+
+/// A [`ValueCodec`] for keys, whose encodings sort as the keys do, so
+/// that typed bounds can become byte bounds.
+pub trait KeyCodec<T>: ValueCodec<T> {
+    /// The byte bounds selecting the encodings of the keys in `range`.
+    fn range<R: RangeBounds<T>>(range: R) -> Result<ByteRange>;
+
+    /// The byte bounds selecting the keys whose encoding starts with that
+    /// of `prefix`, such as the leading fields of a tuple key. The end is
+    /// unbounded if no byte string follows every such key.
+    fn prefix_range<P: Serialize + ?Sized>(prefix: &P) -> Result<ByteRange>;
+}
+
+/// The lexcode format as a [`KeyCodec`] and [`ValueCodec`] for every
+/// serde type.
+///
+/// # Example
+///
+/// A store adapter written against the traits, here over a `BTreeMap`:
+///
+/// ```
+/// use lexcode::{KeyCodec, LexCodec};
+/// use std::collections::BTreeMap;
+/// use std::marker::PhantomData;
+///
+/// struct Table<K, C> {
+///     rows: BTreeMap<Vec<u8>, ()>,
+///     codec: PhantomData<(K, C)>,
+/// }
+///
+/// impl<K, C: KeyCodec<K>> Table<K, C> {
+///     fn insert(&mut self, key: &K) -> lexcode::Result<()> {
+///         self.rows.insert(C::encode(key)?, ());
+///         Ok(())
+///     }
+///
+///     fn with_prefix<P: serde::Serialize>(&self, prefix: &P) -> lexcode::Result<Vec<K>> {
+///         self.rows.range(C::prefix_range(prefix)?).map(|(key, _)| C::decode(key)).collect()
+///     }
+/// }
+///
+/// let mut table = Table::<(u32, String), LexCodec> { rows: BTreeMap::new(), codec: PhantomData };
+/// table.insert(&(1, "b".into()))?;
+/// table.insert(&(2, "a".into()))?;
+/// table.insert(&(1, "a".into()))?;
+/// assert_eq!(table.with_prefix(&1u32)?, [(1, "a".into()), (1, "b".into())]);
+/// # Ok::<(), lexcode::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LexCodec;
+
+impl<T: Serialize + DeserializeOwned> ValueCodec<T> for LexCodec {
+    fn encode(value: &T) -> Result<Vec<u8>> {
+        to_bytes(value)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<T> {
+        from_bytes(bytes)
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> KeyCodec<T> for LexCodec {
+    fn range<R: RangeBounds<T>>(bounds: R) -> Result<ByteRange> {
+        range(bounds)
+    }
+
+    fn prefix_range<P: Serialize + ?Sized>(prefix: &P) -> Result<ByteRange> {
+        let start = to_bytes(&prefix)?;
+        let end = match prefix_successor(&start) {
+            Some(end) => Bound::Excluded(end),
+            None => Bound::Unbounded,
+        };
+        Ok((Bound::Included(start), end))
+    }
+}
@@ -0,0 +1,125 @@
+use icu_collator::CollatorBorrowed;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::bytes::Terminated;
+use crate::ser::to_bytes;
+
+/// A string that sorts by a locale-aware collation key (feature `icu`) but
+/// keeps its original text.
+///
+/// `Collated::new(&collator, s)` computes the ICU4X sort key of `s` once;
+/// the value encodes as the tuple `(sort_key, s)`. Keys therefore follow the
+/// collator's rules (in Swedish `"ö"` sorts after `"z"`, in German next to
+/// `"o"`) instead of UTF-8 byte order, and the original string round-trips.
+/// Strings the collator considers equal are adjacent and share
+/// [`key_prefix`](Collated::key_prefix).
+///
+/// Decoding cannot recompute the sort key without the collator, so it is
+/// trusted as stored. Sort keys may change with CLDR, Unicode or ICU4X
+/// upgrades; stored keys must be rebuilt when that happens.
+///
+/// # Example
+///
+/// ```
+/// use icu_collator::{options::CollatorOptions, Collator};
+/// use lexcode::Collated;
+///
+/// let sv: icu_locale_core::Locale = "sv".parse().unwrap();
+/// let collator = Collator::try_new(sv.into(), CollatorOptions::default()).unwrap();
+/// let oe = lexcode::to_bytes(&Collated::new(&collator, "öl")).unwrap();
+/// let z = lexcode::to_bytes(&Collated::new(&collator, "zebra")).unwrap();
+/// assert!(z < oe);
+///
+/// let decoded: Collated = lexcode::from_bytes(&oe).unwrap();
+/// assert_eq!(decoded.get(), "öl");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Collated<T = String> {
+    key: Vec<u8>,
+    value: T,
+}
+
+impl<T: AsRef<str>> Collated<T> {
+    /// Compute the sort key of `value` with `collator`.
+    pub fn new(collator: &CollatorBorrowed<'_>, value: T) -> Self {
+        let mut key = Vec::new();
+        let Ok(()) = collator.write_sort_key_to(value.as_ref(), &mut key);
+        Collated { key, value }
+    }
+}
+
+impl<T> Collated<T> {
+    /// The collation sort key.
+    pub fn sort_key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// The original value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Unwrap the original value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// The encoding of the sort key alone: the common prefix of the keys of
+    /// every string that collates equal to this one.
+    pub fn key_prefix(&self) -> Vec<u8> {
+        to_bytes(&Terminated(self.key.clone())).expect("serializing bytes cannot fail")
+    }
+}
+
+impl<T: AsRef<str> + Eq> PartialOrd for Collated<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: AsRef<str> + Eq> Ord for Collated<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| self.value.as_ref().cmp(other.value.as_ref()))
+    }
+}
+
+impl<T: AsRef<str>> Serialize for Collated<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&Terminated(self.key.clone()))?;
+        tuple.serialize_element(self.value.as_ref())?;
+        tuple.end()
+    }
+}
+
+impl<'de, T: From<String>> Deserialize<'de> for Collated<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CollatedVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: From<String>> Visitor<'de> for CollatedVisitor<T> {
+            type Value = Collated<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a collation sort key followed by the original string")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Collated<T>, A::Error> {
+                let key: Terminated = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let value: String = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Ok(Collated { key: key.0, value: T::from(value) })
+            }
+        }
+
+        deserializer.deserialize_tuple(2, CollatedVisitor(PhantomData))
+    }
+}
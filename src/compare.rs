@@ -0,0 +1,423 @@
+//! Comparing a value with an encoded key without encoding the value first.
+
+use serde::{ser, Serialize};
+use std::cmp::Ordering;
+
+use crate::descending::DESCENDING_NAME;
+use crate::error::{Error, Result};
+use crate::fixed_bytes::FIXED_BYTES_NAME;
+use crate::varint;
+
+/// Compare the encoding of `value` with `key`, as `to_bytes(value)?.cmp(key)`
+/// would, without building the encoding.
+///
+/// The value is serialized into a backend that checks each byte against
+/// `key` as it is produced and stops at the first one that differs, so a
+/// comparison costs only as much of the value as the two share. Meant for
+/// hot loops such as merge joins and seeks, where encoding every probe
+/// dominates. Uses the default encoding options. Fails only if `value`
+/// fails to serialize before the order is decided.
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// let key = lexcode::to_bytes(&(7u64, "eu-west")).unwrap();
+/// assert_eq!(lexcode::cmp_value_to_key(&(7u64, "eu-north"), &key).unwrap(), Ordering::Less);
+/// assert_eq!(lexcode::cmp_value_to_key(&(7u64, "eu-west"), &key).unwrap(), Ordering::Equal);
+/// assert_eq!(lexcode::cmp_value_to_key(&(8u64, ""), &key).unwrap(), Ordering::Greater);
+/// ```
+pub fn cmp_value_to_key<T>(value: &T, key: &[u8]) -> Result<Ordering>
+where
+    T: ?Sized + Serialize,
+{
+    let mut comparer = Comparer {
+        key,
+        pos: 0,
+        invert: false,
+        raw_byte_mode: false,
+        scratch: Vec::new(),
+        decided: None,
+    };
+    let result = value.serialize(&mut comparer);
+    match (comparer.decided, result) {
+        (Some(ordering), _) => Ok(ordering),
+        (None, Err(e)) => Err(e),
+        (None, Ok(())) if comparer.pos < key.len() => Ok(Ordering::Less),
+        (None, Ok(())) => Ok(Ordering::Equal),
+    }
+}
+
+struct Comparer<'k> {
+    key: &'k [u8],
+    /// Bytes of `key` matched so far.
+    pos: usize,
+    /// Inside an odd number of `Descending` wrappers.
+    invert: bool,
+    raw_byte_mode: bool,
+    /// Holds one varint at a time.
+    scratch: Vec<u8>,
+    decided: Option<Ordering>,
+}
+
+impl Comparer<'_> {
+    /// Check the next bytes of the encoding against the key. Once they
+    /// differ, records the order and fails to stop serialization.
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        let mask = if self.invert { 0xFF } else { 0x00 };
+        for &byte in bytes {
+            let byte = byte ^ mask;
+            match self.key.get(self.pos) {
+                Some(&expected) if expected == byte => self.pos += 1,
+                Some(&expected) => return self.decide(byte.cmp(&expected)),
+                None => return self.decide(Ordering::Greater),
+            }
+        }
+        Ok(())
+    }
+
+    fn decide(&mut self, ordering: Ordering) -> Result<()> {
+        self.decided = Some(ordering);
+        Err(Error::Message(String::new()))
+    }
+
+    fn write_uint(&mut self, v: u128) -> Result<()> {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.clear();
+        varint::encode_uint(v, &mut scratch);
+        let result = self.write(&scratch);
+        self.scratch = scratch;
+        result
+    }
+
+    fn write_sint(&mut self, v: i128) -> Result<()> {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        scratch.clear();
+        varint::encode_sint(v, &mut scratch);
+        let result = self.write(&scratch);
+        self.scratch = scratch;
+        result
+    }
+
+    fn write_with_sentinel(&mut self, data: &[u8], sentinel: u8) -> Result<()> {
+        for chunk in data.split_inclusive(|&byte| byte == sentinel) {
+            self.write(chunk)?;
+            if chunk.last() == Some(&sentinel) {
+                self.write(&[0x01])?;
+            }
+        }
+        self.write(&[sentinel, 0x00])
+    }
+}
+
+impl ser::Serializer for &mut Comparer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.write(&[v as u8])
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.write_sint(v as i128)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.write_sint(v as i128)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.write_sint(v as i128)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.write_sint(v as i128)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        self.write_sint(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        if self.raw_byte_mode {
+            self.write(&[v])
+        } else {
+            self.write_uint(v as u128)
+        }
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.write_uint(v as u128)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.write_uint(v as u128)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.write_uint(v as u128)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        self.write_uint(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        let bits = v.to_bits();
+        let bits = if bits >> 31 != 0 { !bits } else { bits ^ (1 << 31) };
+        self.write(&bits.to_be_bytes())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        let bits = v.to_bits();
+        let bits = if bits >> 63 != 0 { !bits } else { bits ^ (1 << 63) };
+        self.write(&bits.to_be_bytes())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.write_uint(v as u128)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.write_with_sentinel(v.as_bytes(), 0x00)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.write_with_sentinel(v, 0x7F)
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.write(&[0x00])
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.write(&[0x01])?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str) -> Result<()> {
+        self.write_uint(variant_index as u128)
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        if name == DESCENDING_NAME {
+            self.invert = !self.invert;
+            value.serialize(&mut *self)?;
+            self.invert = !self.invert;
+            Ok(())
+        } else if name == FIXED_BYTES_NAME {
+            self.raw_byte_mode = true;
+            let result = value.serialize(&mut *self);
+            self.raw_byte_mode = false;
+            result
+        } else {
+            value.serialize(self)
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        !self.raw_byte_mode
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.write_uint(variant_index as u128)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, name: &'static str, _len: usize) -> Result<Self> {
+        if name == FIXED_BYTES_NAME {
+            self.raw_byte_mode = true;
+        }
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.write_uint(variant_index as u128)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.write_uint(variant_index as u128)?;
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut Comparer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.write(&[0x01])?;
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.write(&[0x00])
+    }
+}
+
+impl ser::SerializeTuple for &mut Comparer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut Comparer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.raw_byte_mode = false;
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut Comparer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for &mut Comparer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.write(&[0x01])?;
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        self.write(&[0x00])
+    }
+}
+
+impl ser::SerializeStruct for &mut Comparer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut Comparer<'_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
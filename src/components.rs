@@ -0,0 +1,148 @@
+//! Working with the top-level components of an encoded composite key.
+
+use serde::de::DeserializeSeed;
+use serde::Deserialize;
+use std::cmp::Ordering;
+
+use crate::de::{component_ends, Deserializer};
+use crate::error::Result;
+use crate::schema::Schema;
+
+/// Split the encoding of a `T` into the bytes of each of its top-level
+/// fields.
+///
+/// A tuple or struct encodes as its fields back to back, so each slice is
+/// exactly the encoding of one field and can be compared, hashed or
+/// grouped on without decoding it into a value. The encoding is not
+/// self-describing, so `T` supplies the shape: the fields are walked with
+/// their own types to find where each ends. A `T` that is not a tuple or
+/// struct yields a single slice. Fails if `key` is not a complete encoding
+/// of a `T`.
+///
+/// # Example
+///
+/// ```
+/// let key = lexcode::to_bytes(&(7u64, "eu-west".to_string(), -3i32)).unwrap();
+/// let parts = lexcode::component_slices::<(u64, String, i32)>(&key).unwrap();
+/// assert_eq!(parts.len(), 3);
+/// assert_eq!(parts[1], lexcode::to_bytes(&"eu-west").unwrap());
+/// assert_eq!(parts.concat(), key);
+/// ```
+pub fn component_slices<'a, T>(key: &'a [u8]) -> Result<Vec<&'a [u8]>>
+where
+    T: Deserialize<'a>,
+{
+    let mut start = 0;
+    Ok(component_ends::<T>(key)?
+        .into_iter()
+        .map(|end| {
+            let slice = &key[start..end];
+            start = end;
+            slice
+        })
+        .collect())
+}
+
+/// Decode the leading fields of a longer encoded tuple or struct as a `T`,
+/// ignoring the rest.
+///
+/// Because fields are encoded back to back, the encoding of `(a, b, c)`
+/// begins with the encoding of `(a, b)`; decoding a shorter tuple (or a
+/// struct declaring only the leading fields) reads just those and stops.
+/// Secondary-index scans can use it to pull the indexed columns out of a
+/// key without decoding the primary-key payload after them.
+///
+/// # Example
+///
+/// ```
+/// let key = lexcode::to_bytes(&(7u64, "eu-west", vec![0u8; 64])).unwrap();
+/// let (user, region): (u64, String) = lexcode::decode_prefix(&key).unwrap();
+/// assert_eq!((user, region.as_str()), (7, "eu-west"));
+/// ```
+pub fn decode_prefix<'a, T>(key: &'a [u8]) -> Result<T>
+where
+    T: Deserialize<'a>,
+{
+    T::deserialize(&mut Deserializer::from_bytes(key))
+}
+
+/// The length of the longest common prefix of `a` and `b`.
+///
+/// ```
+/// assert_eq!(lexcode::common_prefix_len(b"apple", b"apricot"), 2);
+/// assert_eq!(lexcode::common_prefix_len(b"ab", b"abc"), 2);
+/// ```
+pub fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+/// The index of the first top-level field on which two encodings of a `T`
+/// differ, or `None` if they are equal.
+///
+/// Fields are split as in [`component_slices`]; fails if either input is
+/// not a complete encoding of a `T`.
+///
+/// ```
+/// let a = lexcode::to_bytes(&(7u64, "eu-west", 1u8)).unwrap();
+/// let b = lexcode::to_bytes(&(7u64, "eu-north", 1u8)).unwrap();
+/// let field = lexcode::first_divergent_component::<(u64, String, u8)>(&a, &b).unwrap();
+/// assert_eq!(field, Some(1));
+/// ```
+pub fn first_divergent_component<'a, T>(a: &'a [u8], b: &'a [u8]) -> Result<Option<usize>>
+where
+    T: Deserialize<'a>,
+{
+    let a = component_slices::<T>(a)?;
+    let b = component_slices::<T>(b)?;
+    Ok(a.iter().zip(&b).position(|(x, y)| x != y))
+}
+
+/// How two encoded keys compare, as returned by [`compare_encoded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ComponentOrdering {
+    /// The order of the keys, which is their byte order.
+    pub ordering: Ordering,
+    /// The index of the top-level field that decided the order, or `None`
+    /// if the keys are equal.
+    pub component: Option<usize>,
+}
+
+/// Compare two encoded keys of the given shape, reporting which top-level
+/// field decided the order as well as the order itself.
+///
+/// Only the bytes the keys share are decoded: the deciding field is the
+/// first one that does not end within them. The keys need not be complete,
+/// so a scan bound or a key cut short can be compared against a full key;
+/// if one is a prefix of the other, the deciding field is the one it ends
+/// in. A `shape` that is not a tuple counts as a single field. If the keys
+/// agree on every field and differ only after them, the component is the
+/// number of fields.
+///
+/// ```
+/// use lexcode::Schema;
+/// use std::cmp::Ordering;
+///
+/// let shape: Schema = "(u64, str, i32)".parse().unwrap();
+/// let a = lexcode::to_bytes(&(7u64, "eu-north", 5i32)).unwrap();
+/// let b = lexcode::to_bytes(&(7u64, "eu-west", 1i32)).unwrap();
+/// let result = lexcode::compare_encoded(&a, &b, &shape);
+/// assert_eq!(result.ordering, Ordering::Less);
+/// assert_eq!(result.component, Some(1));
+/// ```
+pub fn compare_encoded(a: &[u8], b: &[u8], shape: &Schema) -> ComponentOrdering {
+    let ordering = a.cmp(b);
+    if ordering == Ordering::Equal {
+        return ComponentOrdering { ordering, component: None };
+    }
+    let fields = match shape {
+        Schema::Tuple(fields) => fields.as_slice(),
+        other => std::slice::from_ref(other),
+    };
+    // Fields that end within the shared bytes are equal in both keys.
+    let mut deserializer = Deserializer::from_bytes(&a[..common_prefix_len(a, b)]);
+    let equal = fields
+        .iter()
+        .take_while(|field| field.deserialize(&mut deserializer).is_ok())
+        .count();
+    ComponentOrdering { ordering, component: Some(equal) }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use crate::de::Deserializer;
+use crate::error::{Error, Result};
+use crate::ser::{NanPolicy, Serializer};
+use crate::varint::VarintFormat;
+
+/// Encoding options shared by a [`Serializer`] and a [`Deserializer`].
+///
+/// The default configuration is what [`to_bytes`](crate::to_bytes) and
+/// [`from_bytes`](crate::from_bytes) use. Options that change the bytes
+/// (such as [`none_last`](LexConfig::none_last) or
+/// [`omit_trailing_terminator`](LexConfig::omit_trailing_terminator)) must
+/// be the same on both sides, so build one `LexConfig` and pass it to
+/// [`to_bytes_with_config`] and [`from_bytes_with_config`].
+///
+/// # Example
+///
+/// ```
+/// use lexcode::{LexConfig, NanPolicy};
+///
+/// let config = LexConfig::new().nan_policy(NanPolicy::Canonicalize).none_last(true);
+/// let some = lexcode::to_bytes_with_config(&Some(u64::MAX), &config).unwrap();
+/// let none = lexcode::to_bytes_with_config(&None::<u64>, &config).unwrap();
+/// assert!(some < none);
+///
+/// let decoded: Option<u64> = lexcode::from_bytes_with_config(&none, &config).unwrap();
+/// assert_eq!(decoded, None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LexConfig {
+    pub(crate) nan_policy: NanPolicy,
+    pub(crate) normalize_zero: bool,
+    pub(crate) finite_only: bool,
+    #[cfg(feature = "nfc")]
+    pub(crate) normalize_nfc: bool,
+    pub(crate) omit_trailing_terminator: bool,
+    pub(crate) sort_maps: bool,
+    pub(crate) canonical: bool,
+    pub(crate) none_last: bool,
+    pub(crate) raw_byte_arrays: bool,
+    pub(crate) varint_format: VarintFormat,
+    pub(crate) max_len: Option<usize>,
+}
+
+impl LexConfig {
+    pub fn new() -> Self {
+        LexConfig::default()
+    }
+
+    /// See [`Serializer::nan_policy`].
+    pub fn nan_policy(mut self, policy: NanPolicy) -> Self {
+        self.nan_policy = policy;
+        self
+    }
+
+    /// See [`Serializer::normalize_zero`].
+    pub fn normalize_zero(mut self, normalize: bool) -> Self {
+        self.normalize_zero = normalize;
+        self
+    }
+
+    /// See [`Serializer::finite_only`].
+    pub fn finite_only(mut self, finite_only: bool) -> Self {
+        self.finite_only = finite_only;
+        self
+    }
+
+    /// See [`Serializer::normalize_nfc`].
+    #[cfg(feature = "nfc")]
+    pub fn normalize_nfc(mut self, normalize: bool) -> Self {
+        self.normalize_nfc = normalize;
+        self
+    }
+
+    /// See [`Serializer::omit_trailing_terminator`]; applies to both sides.
+    pub fn omit_trailing_terminator(mut self, omit: bool) -> Self {
+        self.omit_trailing_terminator = omit;
+        self
+    }
+
+    /// See [`Serializer::sort_maps`].
+    pub fn sort_maps(mut self, sort: bool) -> Self {
+        self.sort_maps = sort;
+        self
+    }
+
+    /// See [`Serializer::canonical`].
+    pub fn canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    /// See [`Serializer::none_last`]; applies to both sides.
+    pub fn none_last(mut self, none_last: bool) -> Self {
+        self.none_last = none_last;
+        self
+    }
+
+    /// See [`Serializer::raw_byte_arrays`]; applies to both sides.
+    pub fn raw_byte_arrays(mut self, raw: bool) -> Self {
+        self.raw_byte_arrays = raw;
+        self
+    }
+
+    /// See [`Serializer::varint_format`]; applies to both sides.
+    pub fn varint_format(mut self, format: VarintFormat) -> Self {
+        self.varint_format = format;
+        self
+    }
+
+    /// Fail when an encoded value is longer than `len` bytes, such as a key
+    /// too large for the store. Checked by [`to_bytes_with_config`] on the
+    /// output and by [`from_bytes_with_config`] on the input. Unlimited by
+    /// default.
+    pub fn max_len(mut self, len: usize) -> Self {
+        self.max_len = Some(len);
+        self
+    }
+
+    fn check_len(&self, len: usize) -> Result<()> {
+        match self.max_len {
+            Some(max) if len > max => Err(Error::Message(format!("encoded value of {len} bytes exceeds the limit of {max}"))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Serialize `value` with the options in `config`.
+pub fn to_bytes_with_config<T>(value: &T, config: &LexConfig) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    let mut serializer = Serializer::with_config(*config);
+    value.serialize(&mut serializer)?;
+    let bytes = serializer.into_bytes();
+    config.check_len(bytes.len())?;
+    Ok(bytes)
+}
+
+/// Deserialize a `T` from `input` with the options in `config`.
+pub fn from_bytes_with_config<'a, T>(input: &'a [u8], config: &LexConfig) -> Result<T>
+where
+    T: Deserialize<'a>,
+{
+    config.check_len(input.len())?;
+    let mut deserializer = Deserializer::with_config(input, *config);
+    T::deserialize(&mut deserializer)
+}
@@ -4,26 +4,99 @@ use serde::de::{
     VariantAccess, Visitor,
 };
 
+use crate::config::LexConfig;
 use crate::error::{Error, Result};
-use crate::varint;
+use crate::varint::{self, VarintFormat};
 
 pub struct Deserializer<'de> {
   input: &'de [u8],
   raw_byte_mode: bool,
+  /// As in [`Serializer`](crate::Serializer): set while an element of a
+  /// tuple or array is read with `raw_byte_arrays` on.
+  array_element: bool,
+  invert: bool,
+  config: LexConfig,
+  /// While set, the input length at which a tuple or struct must start for
+  /// the ends of its fields to be recorded in `component_ends`.
+  record_from: Option<usize>,
+  /// Input remaining after each recorded field, once recording started.
+  component_ends: Option<Vec<usize>>,
 }
 
 impl<'de> Deserializer<'de> {
   pub fn from_bytes(input: &'de [u8]) -> Self {
-    Deserializer { input, raw_byte_mode: false }
+    Self::with_config(input, LexConfig::default())
+  }
+
+  /// Create a deserializer with the options in `config` that affect
+  /// decoding.
+  pub fn with_config(input: &'de [u8], config: LexConfig) -> Self {
+    Deserializer {
+      input,
+      raw_byte_mode: false,
+      array_element: false,
+      invert: false,
+      config,
+      record_from: None,
+      component_ends: None,
+    }
+  }
+
+  /// Accept the end of the input in place of a string, sequence or map
+  /// terminator, to read keys written by a `Serializer` with
+  /// [`omit_trailing_terminator`](crate::Serializer::omit_trailing_terminator).
+  pub fn omit_trailing_terminator(mut self, omit: bool) -> Self {
+    self.config.omit_trailing_terminator = omit;
+    self
+  }
+
+  /// Read `Option`s written by a `Serializer` with
+  /// [`none_last`](crate::Serializer::none_last).
+  pub fn none_last(mut self, none_last: bool) -> Self {
+    self.config.none_last = none_last;
+    self
+  }
+
+  /// Read keys written by a `Serializer` with
+  /// [`raw_byte_arrays`](crate::Serializer::raw_byte_arrays).
+  pub fn raw_byte_arrays(mut self, raw: bool) -> Self {
+    self.config.raw_byte_arrays = raw;
+    self
+  }
+
+  /// Read unsigned integers in `format`, as written by a `Serializer`
+  /// with the same [`varint_format`](crate::Serializer::varint_format).
+  pub fn varint_format(mut self, format: VarintFormat) -> Self {
+    self.config.varint_format = format;
+    self
+  }
+
+  /// The input not read yet.
+  pub fn remaining(&self) -> &'de [u8] {
+    self.input
+  }
+
+  /// Succeeds only if the whole input has been consumed.
+  pub fn end(&self) -> Result<()> {
+    if self.input.is_empty() {
+      Ok(())
+    } else {
+      Err(Error::TrailingCharacters)
+    }
   }
 
-  fn read_bytes(&mut self, n: usize) -> Result<&'de [u8]> {
-    if self.input.len() < n {
+  fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+    if self.input.len() < N {
       return Err(Error::Eof);
     }
-    let (head, tail) = self.input.split_at(n);
+    let (head, tail) = self.input.split_at(N);
     self.input = tail;
-    Ok(head)
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(head);
+    if self.invert {
+      bytes.iter_mut().for_each(|b| *b = !*b);
+    }
+    Ok(bytes)
   }
 
   fn read_u8(&mut self) -> Result<u8> {
@@ -32,31 +105,83 @@ impl<'de> Deserializer<'de> {
     }
     let b = self.input[0];
     self.input = &self.input[1..];
-    Ok(b)
+    Ok(if self.invert { !b } else { b })
   }
 
-  fn read_u32_varint(&mut self) -> Result<u32> {
-    let (v, consumed) = varint::decode_uint(self.input)?;
+  /// Run a varint decoder over the input, un-complementing a copy of the
+  /// longest possible varint first when reading a `Descending` value.
+  fn read_varint<T>(&mut self, decode: fn(&[u8]) -> Result<(T, usize)>) -> Result<T> {
+    let (v, consumed) = if self.invert {
+      let mut buf = [0u8; varint::MAX_LEN];
+      let n = self.input.len().min(varint::MAX_LEN);
+      for (dst, src) in buf.iter_mut().zip(&self.input[..n]) {
+        *dst = !*src;
+      }
+      decode(&buf[..n])?
+    } else {
+      decode(self.input)?
+    };
     self.input = &self.input[consumed..];
-    u32::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in u32".into()))
+    Ok(v)
+  }
+
+  fn read_uint(&mut self) -> Result<u128> {
+    self.read_varint(varint::decode_uint)
+  }
+
+  fn read_sint(&mut self) -> Result<i128> {
+    self.read_varint(varint::decode_sint)
+  }
+
+  /// Read an unsigned integer value in the configured varint format.
+  fn read_unsigned(&mut self) -> Result<u128> {
+    match self.config.varint_format {
+      VarintFormat::Lexcode => self.read_uint(),
+      VarintFormat::Sqlite4 => self.read_unsigned_u64().map(u128::from),
+    }
+  }
+
+  /// [`read_unsigned`](Self::read_unsigned) for a value of at most 64 bits.
+  fn read_unsigned_u64(&mut self) -> Result<u64> {
+    match self.config.varint_format {
+      VarintFormat::Lexcode => self.read_varint(varint::decode_u64),
+      VarintFormat::Sqlite4 => self.read_varint(varint::decode_sqlite4),
+    }
+  }
+
+  fn read_u32_varint(&mut self) -> Result<u32> {
+    self.read_varint(varint::decode_u32)
   }
 
   fn read_raw_u32(&mut self) -> Result<u32> {
-    let bytes = self.read_bytes(4)?;
-    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    Ok(u32::from_be_bytes(self.read_array()?))
   }
 
   fn read_raw_u64(&mut self) -> Result<u64> {
-    let bytes = self.read_bytes(8)?;
-    Ok(u64::from_be_bytes([
-      bytes[0], bytes[1], bytes[2], bytes[3],
-      bytes[4], bytes[5], bytes[6], bytes[7],
-    ]))
+    Ok(u64::from_be_bytes(self.read_array()?))
+  }
+
+  /// Whether the first tuple or struct read should record its fields,
+  /// which it does only if it spans the start of the input.
+  fn take_record(&mut self) -> bool {
+    let record = self.record_from.take() == Some(self.input.len());
+    if record {
+      self.component_ends = Some(Vec::new());
+    }
+    record
+  }
+
+  /// Whether the input ends where a terminator may have been omitted.
+  fn at_omitted_terminator(&self) -> bool {
+    self.config.omit_trailing_terminator && !self.invert && self.input.is_empty()
   }
 
   fn deserialize_with_sentinel(&mut self, sentinel: u8) -> Result<Vec<u8>> {
     let mut bytes: Vec<u8> = Vec::new();
     loop {
+      if sentinel == 0x00 && self.at_omitted_terminator() {
+        break;
+      }
       let byte = self.read_u8()?;
       if byte == sentinel {
         let next_byte = self.read_u8()?;
@@ -85,7 +210,23 @@ where
   Ok(t)
 }
 
-impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+/// The offsets in `input` at which each top-level field of the `T` it
+/// encodes ends. A `T` that is not a tuple or struct is one field.
+pub(crate) fn component_ends<'a, T>(input: &'a [u8]) -> Result<Vec<usize>>
+where
+  T: Deserialize<'a>,
+{
+  let mut deserializer = Deserializer::from_bytes(input);
+  deserializer.record_from = Some(input.len());
+  T::deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  Ok(match deserializer.component_ends {
+    Some(ends) => ends.iter().map(|rest| input.len() - rest).collect(),
+    None => vec![input.len()],
+  })
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
   type Error = Error;
 
   fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value>
@@ -110,8 +251,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_sint(self.input)?;
-    self.input = &self.input[consumed..];
+    let v = self.read_sint()?;
     let v = i8::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in i8".into()))?;
     visitor.visit_i8(v)
   }
@@ -120,8 +260,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_sint(self.input)?;
-    self.input = &self.input[consumed..];
+    let v = self.read_sint()?;
     let v = i16::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in i16".into()))?;
     visitor.visit_i16(v)
   }
@@ -130,9 +269,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_sint(self.input)?;
-    self.input = &self.input[consumed..];
-    let v = i32::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in i32".into()))?;
+    let v = self.read_varint(varint::decode_i32)?;
     visitor.visit_i32(v)
   }
 
@@ -140,9 +277,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_sint(self.input)?;
-    self.input = &self.input[consumed..];
-    let v = i64::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in i64".into()))?;
+    let v = self.read_varint(varint::decode_i64)?;
     visitor.visit_i64(v)
   }
 
@@ -150,8 +285,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_sint(self.input)?;
-    self.input = &self.input[consumed..];
+    let v = self.read_sint()?;
     visitor.visit_i128(v)
   }
 
@@ -159,12 +293,11 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    if self.raw_byte_mode {
+    if self.raw_byte_mode || std::mem::take(&mut self.array_element) {
       let b = self.read_u8()?;
       return visitor.visit_u8(b);
     }
-    let (v, consumed) = varint::decode_uint(self.input)?;
-    self.input = &self.input[consumed..];
+    let v = self.read_unsigned_u64()?;
     let v = u8::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in u8".into()))?;
     visitor.visit_u8(v)
   }
@@ -173,8 +306,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_uint(self.input)?;
-    self.input = &self.input[consumed..];
+    let v = self.read_unsigned_u64()?;
     let v = u16::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in u16".into()))?;
     visitor.visit_u16(v)
   }
@@ -183,8 +315,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_uint(self.input)?;
-    self.input = &self.input[consumed..];
+    let v = self.read_unsigned_u64()?;
     let v = u32::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in u32".into()))?;
     visitor.visit_u32(v)
   }
@@ -193,9 +324,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_uint(self.input)?;
-    self.input = &self.input[consumed..];
-    let v = u64::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in u64".into()))?;
+    let v = self.read_unsigned_u64()?;
     visitor.visit_u64(v)
   }
 
@@ -203,8 +332,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_uint(self.input)?;
-    self.input = &self.input[consumed..];
+    let v = self.read_unsigned()?;
     visitor.visit_u128(v)
   }
 
@@ -217,7 +345,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     if (v & SIGN_MASK) == 0 {
       v = !v;
     } else {
-      v = v ^ SIGN_MASK;
+      v ^= SIGN_MASK;
     }
     visitor.visit_f32(f32::from_bits(v))
   }
@@ -231,7 +359,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     if (v & SIGN_MASK) == 0 {
       v = !v;
     } else {
-      v = v ^ SIGN_MASK;
+      v ^= SIGN_MASK;
     }
     visitor.visit_f64(f64::from_bits(v))
   }
@@ -240,8 +368,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
-    let (v, consumed) = varint::decode_uint(self.input)?;
-    self.input = &self.input[consumed..];
+    let v = self.read_uint()?;
     let code_point = v as u32;
     match std::char::from_u32(code_point) {
       Some(c) => visitor.visit_char(c),
@@ -288,9 +415,11 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
+    self.array_element = false;
+    let (none, some) = if self.config.none_last { (1, 0) } else { (0, 1) };
     match self.read_u8()? {
-      0 => visitor.visit_none(),
-      1 => visitor.visit_some(self),
+      tag if tag == none => visitor.visit_none(),
+      tag if tag == some => visitor.visit_some(self),
       _ => Err(Error::Message("Invalid option encoding".to_string())),
     }
   }
@@ -315,19 +444,38 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
 
   fn deserialize_newtype_struct<V>(
       self,
-      _name: &'static str,
+      name: &'static str,
       visitor: V,
   ) -> Result<V::Value>
   where
     V: Visitor<'de>,
   {
-    visitor.visit_newtype_struct(self)
+    self.array_element = false;
+    if name == crate::descending::DESCENDING_NAME {
+      self.invert = !self.invert;
+      let result = visitor.visit_newtype_struct(&mut *self);
+      self.invert = !self.invert;
+      result
+    } else if name == crate::fixed_bytes::FIXED_BYTES_NAME {
+      self.raw_byte_mode = true;
+      let result = visitor.visit_newtype_struct(&mut *self);
+      self.raw_byte_mode = false;
+      result
+    } else {
+      visitor.visit_newtype_struct(self)
+    }
+  }
+
+  /// Matches [`Serializer`](crate::Serializer).
+  fn is_human_readable(&self) -> bool {
+    !self.raw_byte_mode
   }
 
   fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
   where
     V: Visitor<'de>,
   {
+    self.array_element = false;
     visitor.visit_seq(SeqAccessor { deserializer: self })
   }
 
@@ -335,9 +483,14 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
+    self.array_element = false;
+    let record = self.take_record();
+    let array = self.config.raw_byte_arrays;
     visitor.visit_seq(FixedLenAccessor {
       deserializer: self,
       remaining: len,
+      record,
+      array,
     })
   }
 
@@ -350,16 +503,42 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
+    self.array_element = false;
     if name == crate::fixed_bytes::FIXED_BYTES_NAME {
+      // A known number of raw bytes goes to the visitor as one slice, so
+      // every visitor of such a tuple struct implements `visit_bytes`. The
+      // terminated forms have a name of their own and are read byte by
+      // byte.
+      if self.input.len() < len {
+        return Err(Error::Eof);
+      }
+      let (bytes, rest) = self.input.split_at(len);
+      self.input = rest;
+      if self.invert {
+        let bytes: Vec<u8> = bytes.iter().map(|b| !b).collect();
+        visitor.visit_bytes(&bytes)
+      } else {
+        visitor.visit_borrowed_bytes(bytes)
+      }
+    } else if name == crate::fixed_bytes::TERMINATED_BYTES_NAME || name == crate::tail::TAIL_NAME {
+      let remaining = self.input.len();
       self.raw_byte_mode = true;
       let result = visitor.visit_seq(FixedLenAccessor {
         deserializer: &mut *self,
-        remaining: len,
+        remaining,
+        record: false,
+        array: false,
       });
       self.raw_byte_mode = false;
       result
     } else {
-      self.deserialize_tuple(len, visitor)
+      let record = self.take_record();
+      visitor.visit_seq(FixedLenAccessor {
+        deserializer: self,
+        remaining: len,
+        record,
+        array: false,
+      })
     }
   }
 
@@ -367,6 +546,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
+    self.array_element = false;
     visitor.visit_map(MapAccessor { deserializer: self })
   }
 
@@ -379,9 +559,13 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
+    self.array_element = false;
+    let record = self.take_record();
     visitor.visit_seq(FixedLenAccessor {
       deserializer: self,
       remaining: fields.len(),
+      record,
+      array: false,
     })
   }
 
@@ -394,6 +578,7 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
   where
     V: Visitor<'de>,
   {
+    self.array_element = false;
     visitor.visit_enum(EnumAccessor { deserializer: self })
   }
 
@@ -424,6 +609,9 @@ impl<'de, 'a> SeqAccess<'de> for SeqAccessor<'a, 'de> {
   where
     T: DeserializeSeed<'de>,
   {
+    if self.deserializer.at_omitted_terminator() {
+      return Ok(None);
+    }
     match self.deserializer.read_u8()? {
       0x00 => Ok(None),
       0x01 => {
@@ -439,6 +627,10 @@ impl<'de, 'a> SeqAccess<'de> for SeqAccessor<'a, 'de> {
 struct FixedLenAccessor<'a, 'de> {
   deserializer: &'a mut Deserializer<'de>,
   remaining: usize,
+  /// Whether to record where each element ends.
+  record: bool,
+  /// Whether this is a tuple or array whose `u8` elements are raw.
+  array: bool,
 }
 
 impl<'de, 'a> SeqAccess<'de> for FixedLenAccessor<'a, 'de> {
@@ -452,7 +644,16 @@ impl<'de, 'a> SeqAccess<'de> for FixedLenAccessor<'a, 'de> {
       return Ok(None);
     }
     self.remaining -= 1;
-    let value = seed.deserialize(&mut *self.deserializer)?;
+    self.deserializer.array_element = self.array;
+    let value = seed.deserialize(&mut *self.deserializer);
+    self.deserializer.array_element = false;
+    let value = value?;
+    if self.record {
+      let rest = self.deserializer.input.len();
+      if let Some(ends) = &mut self.deserializer.component_ends {
+        ends.push(rest);
+      }
+    }
     Ok(Some(value))
   }
 }
@@ -469,6 +670,9 @@ impl<'de, 'a> MapAccess<'de> for MapAccessor<'a, 'de> {
   where
     K: DeserializeSeed<'de>,
   {
+    if self.deserializer.at_omitted_terminator() {
+      return Ok(None);
+    }
     match self.deserializer.read_u8()? {
       0x00 => Ok(None),
       0x01 => {
@@ -527,6 +731,8 @@ impl<'de, 'a> VariantAccess<'de> for EnumAccessor<'a, 'de> {
     visitor.visit_seq(FixedLenAccessor {
       deserializer: self.deserializer,
       remaining: len,
+      record: false,
+      array: false,
     })
   }
 
@@ -541,6 +747,8 @@ impl<'de, 'a> VariantAccess<'de> for EnumAccessor<'a, 'de> {
     visitor.visit_seq(FixedLenAccessor {
       deserializer: self.deserializer,
       remaining: fields.len(),
+      record: false,
+      array: false,
     })
   }
 }
\ No newline at end of file
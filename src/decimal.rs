@@ -0,0 +1,151 @@
+//! Order-preserving encoding for [`rust_decimal::Decimal`] (feature
+//! `decimal`).
+//!
+//! `Decimal` serializes as a string by default, which sorts `"10"` before
+//! `"9"`. The helper module here writes the value in normalized scientific
+//! form instead, so encoded bytes compare exactly like the numbers:
+//!
+//! - a sign byte: `0x00` negative, `0x01` zero, `0x02` positive;
+//! - for non-zero values, the decimal exponent `e` of `0.d₁d₂… × 10^e` as a
+//!   signed varint, followed by the significant digits packed two per byte
+//!   (`digit + 1` per nibble, a trailing `0` nibble pads an odd count) and
+//!   terminated by `0x00 0x00`, which sorts below any digit byte;
+//! - for negative values, the exponent and digits are wrapped in
+//!   [`Descending`](crate::Descending) so larger magnitudes sort first.
+//!
+//! Trailing zeros are not significant: `1.5` and `1.50` compare equal as
+//! `Decimal`s and encode to the same bytes, so decoding yields the
+//! normalized value (`1.5`).
+//!
+//! ```
+//! use rust_decimal::Decimal;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Price(#[serde(with = "lexcode::decimal")] Decimal);
+//!
+//! let nine = lexcode::to_bytes(&Price(Decimal::new(9, 0))).unwrap();
+//! let ten = lexcode::to_bytes(&Price(Decimal::new(1000, 2))).unwrap();
+//! assert!(nine < ten);
+//!
+//! let decoded: Price = lexcode::from_bytes(&ten).unwrap();
+//! assert_eq!(decoded.0.to_string(), "10");
+//! ```
+
+use rust_decimal::Decimal;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::{SerializeTuple, Serializer};
+use std::fmt;
+
+use crate::bytes::Terminated;
+use crate::descending::Descending;
+
+const NEGATIVE: u8 = 0;
+const ZERO: u8 = 1;
+const POSITIVE: u8 = 2;
+
+/// Split `|value|` into its exponent and significant digits (no leading or
+/// trailing zeros). `value` must be non-zero.
+fn split(value: &Decimal) -> (i16, Terminated) {
+    let mantissa = value.mantissa().unsigned_abs().to_string();
+    let exponent = mantissa.len() as i16 - value.scale() as i16;
+    let digits = mantissa.trim_end_matches('0').as_bytes();
+    let packed = digits
+        .chunks(2)
+        .map(|pair| {
+            let hi = pair[0] - b'0' + 1;
+            let lo = pair.get(1).map_or(0, |d| d - b'0' + 1);
+            hi << 4 | lo
+        })
+        .collect();
+    (exponent, Terminated(packed))
+}
+
+fn join<E: de::Error>(negative: bool, exponent: i16, packed: &[u8]) -> Result<Decimal, E> {
+    let mut mantissa: i128 = 0;
+    let mut len: i16 = 0;
+    for (i, nibble) in packed.iter().flat_map(|b| [b >> 4, b & 0x0F]).enumerate() {
+        match nibble {
+            0 if i % 2 == 1 && i == packed.len() * 2 - 1 => break,
+            1..=10 => {
+                mantissa = mantissa
+                    .checked_mul(10)
+                    .and_then(|m| m.checked_add(i128::from(nibble - 1)))
+                    .ok_or_else(|| E::custom("decimal has too many digits"))?;
+                len += 1;
+            }
+            _ => return Err(E::custom("invalid decimal digit")),
+        }
+    }
+    if len == 0 {
+        return Err(E::custom("decimal has no digits"));
+    }
+    let mut scale = len - exponent;
+    while scale < 0 {
+        mantissa = mantissa
+            .checked_mul(10)
+            .ok_or_else(|| E::custom("decimal out of range"))?;
+        scale += 1;
+    }
+    if negative {
+        mantissa = -mantissa;
+    }
+    Decimal::try_from_i128_with_scale(mantissa, scale as u32).map_err(E::custom)
+}
+
+/// `#[serde(with = "lexcode::decimal")]` serializer for `Decimal`.
+pub fn serialize<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+    if value.is_zero() {
+        let mut tuple = serializer.serialize_tuple(1)?;
+        tuple.serialize_element(&ZERO)?;
+        return tuple.end();
+    }
+    let parts = split(value);
+    let mut tuple = serializer.serialize_tuple(2)?;
+    if value.is_sign_negative() {
+        tuple.serialize_element(&NEGATIVE)?;
+        tuple.serialize_element(&Descending(parts))?;
+    } else {
+        tuple.serialize_element(&POSITIVE)?;
+        tuple.serialize_element(&parts)?;
+    }
+    tuple.end()
+}
+
+/// `#[serde(with = "lexcode::decimal")]` deserializer for `Decimal`.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+    struct DecimalVisitor;
+
+    impl<'de> Visitor<'de> for DecimalVisitor {
+        type Value = Decimal;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a sign tag followed by an exponent and digits")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Decimal, A::Error> {
+            let sign: u8 = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let (negative, (exponent, digits)) = match sign {
+                ZERO => return Ok(Decimal::ZERO),
+                NEGATIVE => {
+                    let parts: Descending<(i16, Terminated)> = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                    (true, parts.0)
+                }
+                POSITIVE => {
+                    let parts: (i16, Terminated) = seq
+                        .next_element()?
+                        .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                    (false, parts)
+                }
+                _ => return Err(de::Error::custom("invalid decimal sign tag")),
+            };
+            join(negative, exponent, &digits.0)
+        }
+    }
+
+    deserializer.deserialize_tuple(2, DecimalVisitor)
+}
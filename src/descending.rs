@@ -0,0 +1,93 @@
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+pub(crate) const DESCENDING_NAME: &str = "lexcode::Descending";
+
+/// A wrapper that reverses the sort order of its contents.
+///
+/// `Descending<T>` encodes `T` as usual and then complements every byte of
+/// the result. The default encodings are prefix-free (strings and
+/// sequences carry an explicit terminator, integers a length header), so
+/// two distinct encodings differ at some byte inside both of them, and
+/// complementing flips exactly that comparison. That makes the wrapper
+/// usable anywhere in a composite key to express a descending column.
+///
+/// Two encodings are not prefix-free and do not reverse: [`TailBytes`] and
+/// [`TailString`], which the serializer rejects inside `Descending`, and
+/// the output of
+/// [`omit_trailing_terminator`](crate::Serializer::omit_trailing_terminator),
+/// which keeps the terminator of a descending string or sequence.
+///
+/// [`TailBytes`]: crate::TailBytes
+/// [`TailString`]: crate::TailString
+///
+/// Its `Ord` impl is reversed too, like [`std::cmp::Reverse`].
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Descending;
+///
+/// // (user_id ASC, timestamp DESC)
+/// let older = lexcode::to_bytes(&(7u64, Descending(1_000i64))).unwrap();
+/// let newer = lexcode::to_bytes(&(7u64, Descending(2_000i64))).unwrap();
+/// assert!(newer < older);
+///
+/// let (user, Descending(ts)): (u64, Descending<i64>) = lexcode::from_bytes(&newer).unwrap();
+/// assert_eq!((user, ts), (7, 2_000));
+/// ```
+///
+/// `std::cmp::Reverse` serializes transparently as its contents, so it does
+/// not change the encoding; use `Descending` for keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Descending<T>(pub T);
+
+impl<T: PartialOrd> PartialOrd for Descending<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        other.0.partial_cmp(&self.0)
+    }
+}
+
+impl<T: Ord> Ord for Descending<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl<T> From<T> for Descending<T> {
+    fn from(value: T) -> Self {
+        Descending(value)
+    }
+}
+
+impl<T: Serialize> Serialize for Descending<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(DESCENDING_NAME, &self.0)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Descending<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DescendingVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for DescendingVisitor<T> {
+            type Value = Descending<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a descending-ordered value")
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Descending<T>, D::Error> {
+                T::deserialize(deserializer).map(Descending)
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(DESCENDING_NAME, DescendingVisitor(PhantomData))
+    }
+}
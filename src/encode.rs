@@ -0,0 +1,526 @@
+//! Encoding without serde.
+//!
+//! [`Encode`] and [`Decode`] write and read the same bytes as
+//! [`to_bytes`](crate::to_bytes) and [`from_bytes`](crate::from_bytes)
+//! with the default options, through plain monomorphized calls instead of
+//! the serde data model. They suit key types that cannot depend on serde,
+//! and hot paths: there is no error to report while encoding, and no
+//! visitor between the bytes and the value while decoding.
+
+use crate::descending::Descending;
+use crate::error::{Error, Result};
+use crate::fixed_bytes::FixedBytes;
+use crate::varint;
+
+/// A value that can be encoded as a lexcode key without serde.
+///
+/// The output is byte-identical to serializing the value with
+/// [`to_bytes`](crate::to_bytes). Implement it for a struct by encoding
+/// each field in order, or derive it with `#[derive(Encode)]` (feature
+/// `derive`).
+///
+/// ```
+/// use lexcode::Encode;
+///
+/// let key = (7u64, "eu-west", Some(-1.5f64));
+/// assert_eq!(lexcode::encode(&key), lexcode::to_bytes(&key).unwrap());
+/// ```
+pub trait Encode {
+    /// Append the encoding of `self` to `out`.
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+/// A value that can be decoded from a lexcode key without serde, the
+/// counterpart of [`Encode`].
+pub trait Decode: Sized {
+    /// Read one value from the front of `decoder`.
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self>;
+}
+
+/// A type with a bound on the length of its encoding, known at compile
+/// time.
+///
+/// Implemented for the types that have one: integers, floats, `bool`,
+/// `char`, `()`, and `Option`s, arrays and tuples of such types,
+/// [`FixedBytes`] and [`Descending`]. Strings and sequences have none. The
+/// bound holds for [`Encode`] and for [`to_bytes`](crate::to_bytes) with
+/// the default options, so it can size a stack or slab buffer. Derive it
+/// with `#[derive(MaxEncodedLen)]` (feature `derive`).
+///
+/// ```
+/// let key = (7u64, -1i32, Some(2.5f64));
+/// let mut buf = [0u8; lexcode::max_encoded_len::<(u64, i32, Option<f64>)>()];
+/// let bytes = lexcode::encode(&key);
+/// buf[..bytes.len()].copy_from_slice(&bytes);
+/// ```
+pub trait MaxEncodedLen {
+    /// No encoding of a value of the type is longer than this.
+    const MAX_ENCODED_LEN: usize;
+}
+
+/// [`MaxEncodedLen::MAX_ENCODED_LEN`] of `T`, usable in constant
+/// expressions such as array lengths.
+pub const fn max_encoded_len<T: MaxEncodedLen + ?Sized>() -> usize {
+    T::MAX_ENCODED_LEN
+}
+
+/// Encode `value` on its own.
+pub fn encode<T: Encode + ?Sized>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.encode(&mut out);
+    out
+}
+
+/// Decode a `T` from the start of `input`. Like
+/// [`from_bytes`](crate::from_bytes), bytes after it are ignored.
+pub fn decode<T: Decode>(input: &[u8]) -> Result<T> {
+    T::decode(&mut Decoder::new(input))
+}
+
+/// The input of [`Decode::decode`]: the bytes not read yet, and whether
+/// they are being read inside a [`Descending`].
+#[derive(Debug, Clone)]
+pub struct Decoder<'a> {
+    input: &'a [u8],
+    invert: bool,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Decoder { input, invert: false }
+    }
+
+    /// The input not read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+
+    /// Succeeds only if the whole input has been consumed.
+    pub fn end(&self) -> Result<()> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::TrailingCharacters)
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        let (&b, rest) = self.input.split_first().ok_or(Error::Eof)?;
+        self.input = rest;
+        Ok(if self.invert { !b } else { b })
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let (head, rest) = self.input.split_first_chunk::<N>().ok_or(Error::Eof)?;
+        self.input = rest;
+        Ok(if self.invert { head.map(|b| !b) } else { *head })
+    }
+
+    /// Run a varint decoder, un-complementing a copy of the longest
+    /// possible varint first inside a `Descending`.
+    fn read_varint<T>(&mut self, decode: fn(&[u8]) -> Result<(T, usize)>) -> Result<T> {
+        let (v, consumed) = if self.invert {
+            let mut buf = [0u8; varint::MAX_LEN];
+            let n = self.input.len().min(varint::MAX_LEN);
+            for (dst, src) in buf.iter_mut().zip(&self.input[..n]) {
+                *dst = !*src;
+            }
+            decode(&buf[..n])?
+        } else {
+            decode(self.input)?
+        };
+        self.input = &self.input[consumed..];
+        Ok(v)
+    }
+
+    fn read_uint(&mut self) -> Result<u128> {
+        self.read_varint(varint::decode_uint)
+    }
+
+    /// Read bytes escaped with `sentinel`, up to and including their
+    /// terminator.
+    fn read_terminated(&mut self, sentinel: u8) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        loop {
+            let byte = self.read_u8()?;
+            if byte != sentinel {
+                bytes.push(byte);
+                continue;
+            }
+            match self.read_u8()? {
+                0x00 => return Ok(bytes),
+                0x01 => bytes.push(sentinel),
+                _ => return Err(Error::Message("Invalid encoding".to_string())),
+            }
+        }
+    }
+
+    /// Whether a sequence has another element, after which it is read.
+    fn next_element(&mut self) -> Result<bool> {
+        match self.read_u8()? {
+            0x00 => Ok(false),
+            0x01 => Ok(true),
+            _ => Err(Error::Message("Invalid sequence encoding".to_string())),
+        }
+    }
+}
+
+pub(crate) fn encode_terminated(data: &[u8], sentinel: u8, out: &mut Vec<u8>) {
+    for &byte in data {
+        out.push(byte);
+        if byte == sentinel {
+            out.push(0x01);
+        }
+    }
+    out.extend_from_slice(&[sentinel, 0x00]);
+}
+
+fn overflow<T>() -> Error {
+    Error::Message(format!("integer overflow: value does not fit in {}", std::any::type_name::<T>()))
+}
+
+impl Encode for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+
+impl Decode for bool {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        match decoder.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::Message("Invalid boolean value".to_string())),
+        }
+    }
+}
+
+macro_rules! unsigned {
+    ($($t:ty => $wide:ty, $encode:path, $decode:path;)*) => {$(
+        impl Encode for $t {
+            fn encode(&self, out: &mut Vec<u8>) {
+                $encode(*self as $wide, out);
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+                <$t>::try_from(decoder.read_varint($decode)?).map_err(|_| overflow::<$t>())
+            }
+        }
+    )*};
+}
+
+macro_rules! signed {
+    ($($t:ty => $wide:ty, $encode:path, $decode:path;)*) => {$(
+        impl Encode for $t {
+            fn encode(&self, out: &mut Vec<u8>) {
+                $encode(*self as $wide, out);
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+                <$t>::try_from(decoder.read_varint($decode)?).map_err(|_| overflow::<$t>())
+            }
+        }
+    )*};
+}
+
+// Up to 64 bits, the fast paths of the varint module.
+unsigned! {
+    u8 => u64, varint::encode_u64, varint::decode_u64;
+    u16 => u64, varint::encode_u64, varint::decode_u64;
+    u32 => u64, varint::encode_u64, varint::decode_u64;
+    u64 => u64, varint::encode_u64, varint::decode_u64;
+    usize => u128, varint::encode_uint, varint::decode_uint;
+    u128 => u128, varint::encode_uint, varint::decode_uint;
+}
+signed! {
+    i8 => i64, varint::encode_i64, varint::decode_i64;
+    i16 => i64, varint::encode_i64, varint::decode_i64;
+    i32 => i64, varint::encode_i64, varint::decode_i64;
+    i64 => i64, varint::encode_i64, varint::decode_i64;
+    isize => i128, varint::encode_sint, varint::decode_sint;
+    i128 => i128, varint::encode_sint, varint::decode_sint;
+}
+
+macro_rules! float {
+    ($($t:ty, $bits:ty;)*) => {$(
+        impl Encode for $t {
+            fn encode(&self, out: &mut Vec<u8>) {
+                const SIGN_MASK: $bits = 1 << (<$bits>::BITS - 1);
+                let v = self.to_bits();
+                let v = if v & SIGN_MASK != 0 { !v } else { v ^ SIGN_MASK };
+                out.extend_from_slice(&v.to_be_bytes());
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+                const SIGN_MASK: $bits = 1 << (<$bits>::BITS - 1);
+                let v = <$bits>::from_be_bytes(decoder.read_array()?);
+                let v = if v & SIGN_MASK == 0 { !v } else { v ^ SIGN_MASK };
+                Ok(<$t>::from_bits(v))
+            }
+        }
+    )*};
+}
+
+float! {
+    f32, u32;
+    f64, u64;
+}
+
+impl Encode for char {
+    fn encode(&self, out: &mut Vec<u8>) {
+        varint::encode_u64(*self as u64, out);
+    }
+}
+
+impl Decode for char {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        let v = decoder.read_uint()?;
+        u32::try_from(v)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| Error::Message("Invalid char code point".to_string()))
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, out: &mut Vec<u8>) {
+        encode_terminated(self.as_bytes(), 0x00, out);
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_str().encode(out);
+    }
+}
+
+impl Decode for String {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        String::from_utf8(decoder.read_terminated(0x00)?)
+            .map_err(|_| Error::Message("Invalid UTF-8 string".to_string()))
+    }
+}
+
+impl Encode for () {
+    fn encode(&self, _out: &mut Vec<u8>) {}
+}
+
+impl Decode for () {
+    fn decode(_decoder: &mut Decoder<'_>) -> Result<Self> {
+        Ok(())
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (**self).encode(out);
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for Box<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (**self).encode(out);
+    }
+}
+
+impl<T: Decode> Decode for Box<T> {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        T::decode(decoder).map(Box::new)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            None => out.push(0x00),
+            Some(value) => {
+                out.push(0x01);
+                value.encode(out);
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        match decoder.read_u8()? {
+            0x00 => Ok(None),
+            0x01 => T::decode(decoder).map(Some),
+            _ => Err(Error::Message("Invalid option encoding".to_string())),
+        }
+    }
+}
+
+/// A sequence, like serde's: each element after `0x01`, then `0x00`.
+impl<T: Encode> Encode for [T] {
+    fn encode(&self, out: &mut Vec<u8>) {
+        for item in self {
+            out.push(0x01);
+            item.encode(out);
+        }
+        out.push(0x00);
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_slice().encode(out);
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        let mut items = Vec::new();
+        while decoder.next_element()? {
+            items.push(T::decode(decoder)?);
+        }
+        Ok(items)
+    }
+}
+
+/// The elements in order with no framing, as serde writes arrays (as
+/// tuples).
+impl<T: Encode, const N: usize> Encode for [T; N] {
+    fn encode(&self, out: &mut Vec<u8>) {
+        for item in self {
+            item.encode(out);
+        }
+    }
+}
+
+impl<T: Decode, const N: usize> Decode for [T; N] {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        let items = (0..N).map(|_| T::decode(decoder)).collect::<Result<Vec<T>>>()?;
+        match items.try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => unreachable!("decoded exactly N elements"),
+        }
+    }
+}
+
+macro_rules! tuple {
+    ($($name:ident)+) => {
+        impl<$($name: Encode),+> Encode for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn encode(&self, out: &mut Vec<u8>) {
+                let ($($name,)+) = self;
+                $($name.encode(out);)+
+            }
+        }
+
+        impl<$($name: Decode),+> Decode for ($($name,)+) {
+            fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+                Ok(($($name::decode(decoder)?,)+))
+            }
+        }
+
+        impl<$($name: MaxEncodedLen),+> MaxEncodedLen for ($($name,)+) {
+            const MAX_ENCODED_LEN: usize = 0 $(+ $name::MAX_ENCODED_LEN)+;
+        }
+    };
+}
+
+tuple!(A);
+tuple!(A B);
+tuple!(A B C);
+tuple!(A B C D);
+tuple!(A B C D E);
+tuple!(A B C D E F);
+tuple!(A B C D E F G);
+tuple!(A B C D E F G H);
+tuple!(A B C D E F G H I);
+tuple!(A B C D E F G H I J);
+tuple!(A B C D E F G H I J K);
+tuple!(A B C D E F G H I J K L);
+
+impl<const N: usize> Encode for FixedBytes<N> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.0);
+    }
+}
+
+impl<const N: usize> Decode for FixedBytes<N> {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        decoder.read_array().map(FixedBytes)
+    }
+}
+
+impl<T: Encode> Encode for Descending<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let start = out.len();
+        self.0.encode(out);
+        for b in &mut out[start..] {
+            *b = !*b;
+        }
+    }
+}
+
+impl<T: Decode> Decode for Descending<T> {
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self> {
+        decoder.invert = !decoder.invert;
+        let result = T::decode(decoder);
+        decoder.invert = !decoder.invert;
+        result.map(Descending)
+    }
+}
+
+// ── MaxEncodedLen ──────────────────────────────────────────────────────
+
+macro_rules! max_len {
+    ($($t:ty => $len:expr;)*) => {$(
+        impl MaxEncodedLen for $t {
+            const MAX_ENCODED_LEN: usize = $len;
+        }
+    )*};
+}
+
+max_len! {
+    bool => 1;
+    u8 => varint::encoded_len_uint(u8::MAX as u128);
+    u16 => varint::encoded_len_uint(u16::MAX as u128);
+    u32 => varint::encoded_len_uint(u32::MAX as u128);
+    u64 => varint::encoded_len_uint(u64::MAX as u128);
+    u128 => varint::encoded_len_uint(u128::MAX);
+    usize => varint::encoded_len_uint(usize::MAX as u128);
+    // The most negative value has the largest magnitude.
+    i8 => varint::encoded_len_sint(i8::MIN as i128);
+    i16 => varint::encoded_len_sint(i16::MIN as i128);
+    i32 => varint::encoded_len_sint(i32::MIN as i128);
+    i64 => varint::encoded_len_sint(i64::MIN as i128);
+    i128 => varint::encoded_len_sint(i128::MIN);
+    isize => varint::encoded_len_sint(isize::MIN as i128);
+    f32 => 4;
+    f64 => 8;
+    char => varint::encoded_len_uint(char::MAX as u128);
+    () => 0;
+}
+
+impl<T: MaxEncodedLen + ?Sized> MaxEncodedLen for &T {
+    const MAX_ENCODED_LEN: usize = T::MAX_ENCODED_LEN;
+}
+
+impl<T: MaxEncodedLen + ?Sized> MaxEncodedLen for Box<T> {
+    const MAX_ENCODED_LEN: usize = T::MAX_ENCODED_LEN;
+}
+
+impl<T: MaxEncodedLen> MaxEncodedLen for Option<T> {
+    const MAX_ENCODED_LEN: usize = 1 + T::MAX_ENCODED_LEN;
+}
+
+impl<T: MaxEncodedLen, const N: usize> MaxEncodedLen for [T; N] {
+    const MAX_ENCODED_LEN: usize = N * T::MAX_ENCODED_LEN;
+}
+
+impl<const N: usize> MaxEncodedLen for FixedBytes<N> {
+    const MAX_ENCODED_LEN: usize = N;
+}
+
+impl<T: MaxEncodedLen> MaxEncodedLen for Descending<T> {
+    const MAX_ENCODED_LEN: usize = T::MAX_ENCODED_LEN;
+}
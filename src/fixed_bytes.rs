@@ -1,8 +1,16 @@
-use serde::de::{self, SeqAccess, Visitor};
+use serde::de::{self, DeserializeSeed, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::array::TryFromSliceError;
 use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::error::Error;
 
 pub(crate) const FIXED_BYTES_NAME: &str = "lexcode::FixedBytes";
+/// Raw bytes read up to a terminator of their own rather than for a known
+/// length.
+pub(crate) const TERMINATED_BYTES_NAME: &str = "lexcode::TerminatedBytes";
 
 /// A fixed-size byte array that serializes as raw bytes with zero overhead.
 ///
@@ -13,6 +21,9 @@ pub(crate) const FIXED_BYTES_NAME: &str = "lexcode::FixedBytes";
 /// Lexicographic ordering is preserved: byte-wise comparison of the encoded
 /// output matches comparison of the original arrays.
 ///
+/// Human-readable formats such as JSON get the bytes as a lowercase hex
+/// string instead, the same as its `Display` form.
+///
 /// # Example
 ///
 /// ```
@@ -47,14 +58,97 @@ impl<const N: usize> AsRef<[u8]> for FixedBytes<N> {
     }
 }
 
+impl<const N: usize> Deref for FixedBytes<N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8]> for FixedBytes<N> {
+    type Error = TryFromSliceError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, TryFromSliceError> {
+        slice.try_into().map(FixedBytes)
+    }
+}
+
+/// Lowercase hex, two digits per byte.
+impl<const N: usize> fmt::Display for FixedBytes<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Hex(&self.0).fmt(f)
+    }
+}
+
+/// Parses exactly `2 * N` hex digits, in either case.
+impl<const N: usize> FromStr for FixedBytes<N> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> crate::error::Result<Self> {
+        if s.len() != 2 * N {
+            return Err(Error::Message(format!("expected {} hex digits, found {}", 2 * N, s.len())));
+        }
+        let mut arr = [0u8; N];
+        for (slot, pair) in arr.iter_mut().zip(s.as_bytes().chunks_exact(2)) {
+            *slot = hex_digit(pair[0])? << 4 | hex_digit(pair[1])?;
+        }
+        Ok(FixedBytes(arr))
+    }
+}
+
+fn hex_digit(c: u8) -> crate::error::Result<u8> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(Error::Message(format!("invalid hex digit {:?}", c as char))),
+    }
+}
+
+#[cfg(feature = "random")]
+impl<const N: usize> FixedBytes<N> {
+    /// `N` bytes from the operating system's random number generator, for
+    /// example a random identifier (feature `random`).
+    ///
+    /// # Panics
+    ///
+    /// If the generator is unavailable or fails.
+    pub fn random() -> Self {
+        let mut arr = [0u8; N];
+        getrandom::fill(&mut arr).expect("operating system random number generator failed");
+        FixedBytes(arr)
+    }
+}
+
+// `FixedBytes` is a newtype around its contents so the lexcode serializers
+// can report themselves as binary while the contents are written: they
+// are human-readable everywhere else, which would turn the bytes into hex.
+
 impl<const N: usize> Serialize for FixedBytes<N> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        use serde::ser::SerializeTupleStruct;
-        let mut ts = serializer.serialize_tuple_struct(FIXED_BYTES_NAME, N)?;
-        for &byte in &self.0 {
-            ts.serialize_field(&byte)?;
+        serializer.serialize_newtype_struct(FIXED_BYTES_NAME, &Contents(&self.0))
+    }
+}
+
+/// Hex for human-readable formats, raw bytes otherwise.
+struct Contents<'a>(&'a [u8]);
+
+impl Serialize for Contents<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&Hex(self.0))
+        } else {
+            RawBytes(self.0).serialize(serializer)
         }
-        ts.end()
+    }
+}
+
+struct Hex<'a>(&'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
     }
 }
 
@@ -66,7 +160,26 @@ impl<'de, const N: usize> Deserialize<'de> for FixedBytes<N> {
             type Value = FixedBytes<M>;
 
             fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                write!(f, "{M} raw bytes")
+                write!(f, "{M} raw bytes or {} hex digits", 2 * M)
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<FixedBytes<M>, D::Error> {
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(self)
+                } else {
+                    deserializer.deserialize_tuple_struct(FIXED_BYTES_NAME, M, self)
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<FixedBytes<M>, E> {
+                v.parse().map_err(E::custom)
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<FixedBytes<M>, E> {
+                v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
             }
 
             fn visit_seq<A: SeqAccess<'de>>(
@@ -83,6 +196,59 @@ impl<'de, const N: usize> Deserialize<'de> for FixedBytes<N> {
             }
         }
 
-        deserializer.deserialize_tuple_struct(FIXED_BYTES_NAME, N, FixedBytesVisitor::<N>)
+        deserializer.deserialize_newtype_struct(FIXED_BYTES_NAME, FixedBytesVisitor::<N>)
+    }
+}
+
+/// A byte slice written verbatim, like `FixedBytes`, but with the length
+/// chosen at runtime. The reader must learn the length some other way.
+pub(crate) struct RawBytes<'a>(pub &'a [u8]);
+
+impl Serialize for RawBytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTupleStruct;
+        let mut ts = serializer.serialize_tuple_struct(FIXED_BYTES_NAME, self.0.len())?;
+        for byte in self.0 {
+            ts.serialize_field(byte)?;
+        }
+        ts.end()
+    }
+}
+
+/// Reads back `RawBytes` of a known length.
+pub(crate) struct RawBytesSeed(pub usize);
+
+impl<'de> DeserializeSeed<'de> for RawBytesSeed {
+    type Value = Vec<u8>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let len = self.0;
+        deserializer.deserialize_tuple_struct(FIXED_BYTES_NAME, len, self)
+    }
+}
+
+impl<'de> Visitor<'de> for RawBytesSeed {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} raw bytes", self.0)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        if v.len() != self.0 {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        Ok(v.to_vec())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        // The length may come from untrusted input, so only trust it as far
+        // as a small allocation.
+        let mut bytes = Vec::with_capacity(self.0.min(4096));
+        for i in 0..self.0 {
+            let byte = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+            bytes.push(byte);
+        }
+        Ok(bytes)
     }
 }
@@ -0,0 +1,86 @@
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::fixed_bytes::{RawBytes, RawBytesSeed};
+
+/// A string of at most `N` bytes that encodes as exactly `N` bytes.
+///
+/// The UTF-8 bytes are written verbatim and padded with `0x00` up to `N`,
+/// so every value occupies the same width, as in fixed-width legacy record
+/// layouts. Since the padding byte sorts below every data byte, the
+/// encoding sorts like the padded bytes, which is also the order of the
+/// strings themselves. Strings longer than `N` bytes or containing `'\0'`
+/// (which would be indistinguishable from padding) are rejected.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::FixedString;
+///
+/// let ab = lexcode::to_bytes(&FixedString::<4>::new("ab").unwrap()).unwrap();
+/// assert_eq!(ab, b"ab\0\0");
+/// assert!(FixedString::<4>::new("abcde").is_err());
+///
+/// let decoded: FixedString<4> = lexcode::from_bytes(&ab).unwrap();
+/// assert_eq!(decoded.as_str(), "ab");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FixedString<const N: usize>(String);
+
+impl<const N: usize> FixedString<N> {
+    /// Wrap `s`, which must be at most `N` bytes and contain no `'\0'`.
+    pub fn new(s: impl Into<String>) -> Result<FixedString<N>> {
+        let s = s.into();
+        if s.len() > N {
+            return Err(Error::Message(format!("{s:?} is longer than {N} bytes")));
+        }
+        if s.contains('\0') {
+            return Err(Error::Message(format!("{s:?} contains a NUL character")));
+        }
+        Ok(FixedString(s))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl<const N: usize> fmt::Display for FixedString<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<const N: usize> AsRef<str> for FixedString<N> {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> Serialize for FixedString<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut padded = self.0.clone().into_bytes();
+        padded.resize(N, 0x00);
+        RawBytes(&padded).serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for FixedString<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let mut bytes = RawBytesSeed(N).deserialize(deserializer)?;
+        let len = bytes.iter().position(|&b| b == 0x00).unwrap_or(N);
+        if bytes[len..].iter().any(|&b| b != 0x00) {
+            return Err(de::Error::custom("fixed-width string has data after its padding"));
+        }
+        bytes.truncate(len);
+        String::from_utf8(bytes)
+            .map(FixedString)
+            .map_err(|_| de::Error::custom("fixed-width string is not valid UTF-8"))
+    }
+}
@@ -0,0 +1,83 @@
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use crate::fixed_bytes::{RawBytes, RawBytesSeed};
+
+/// `N` booleans packed eight to a byte.
+///
+/// A `bool` field takes a whole byte of key. `Flags<N>` packs the flags
+/// most significant bit first into `N.div_ceil(8)` raw bytes, padding the
+/// last byte with zero bits, so the key sorts exactly like the array of
+/// flags (`[false, true] < [true, false]`). Decoding rejects non-zero
+/// padding bits.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Flags;
+///
+/// let flags = Flags([true, false, true, false, false, false, false, false, true]);
+/// let bytes = lexcode::to_bytes(&flags).unwrap();
+/// assert_eq!(bytes, [0b1010_0000, 0b1000_0000]);
+///
+/// let decoded: Flags<9> = lexcode::from_bytes(&bytes).unwrap();
+/// assert!(decoded.get(8));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Flags<const N: usize>(pub [bool; N]);
+
+impl<const N: usize> Flags<N> {
+    /// The flag at `index`. Panics if `index >= N`.
+    pub fn get(&self, index: usize) -> bool {
+        self.0[index]
+    }
+
+    /// Set the flag at `index`. Panics if `index >= N`.
+    pub fn set(&mut self, index: usize, value: bool) {
+        self.0[index] = value;
+    }
+}
+
+impl<const N: usize> Default for Flags<N> {
+    fn default() -> Self {
+        Flags([false; N])
+    }
+}
+
+impl<const N: usize> From<[bool; N]> for Flags<N> {
+    fn from(flags: [bool; N]) -> Self {
+        Flags(flags)
+    }
+}
+
+impl<const N: usize> From<Flags<N>> for [bool; N] {
+    fn from(flags: Flags<N>) -> Self {
+        flags.0
+    }
+}
+
+impl<const N: usize> Serialize for Flags<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut bytes = vec![0u8; N.div_ceil(8)];
+        for (i, &flag) in self.0.iter().enumerate() {
+            if flag {
+                bytes[i / 8] |= 0x80 >> (i % 8);
+            }
+        }
+        RawBytes(&bytes).serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Flags<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = RawBytesSeed(N.div_ceil(8)).deserialize(deserializer)?;
+        let mut flags = [false; N];
+        for (i, flag) in flags.iter_mut().enumerate() {
+            *flag = bytes[i / 8] & (0x80 >> (i % 8)) != 0;
+        }
+        if !N.is_multiple_of(8) && bytes[N / 8] & (0xFF >> (N % 8)) != 0 {
+            return Err(de::Error::custom("flags have non-zero padding bits"));
+        }
+        Ok(Flags(flags))
+    }
+}
@@ -0,0 +1,185 @@
+//! Fractional indexing: order keys for lists the user can reorder.
+//!
+//! Each item stores a short string key and the list is sorted by it.
+//! Inserting or moving an item gives it a new key between its neighbours'
+//! ([`between`]), or before the first or after the last ([`before`],
+//! [`after`]), so no other item is rewritten. Keys are base-62 digits
+//! (`0-9A-Za-z`) read as a fraction after the point, and never end in `0`,
+//! so there is always room on both sides of a key. Being ASCII, they sort
+//! the same as strings, as raw bytes and in their lexcode encoding, and fit
+//! directly into a composite key such as `(list_id, order_key)`.
+//!
+//! Keys stay short under the common edits. [`after`] and [`before`] step a
+//! digit by one, so a list grows by a character only every 61 or so
+//! consecutive appends or prepends, and [`between`] returns the shortest
+//! key roughly halfway, growing by a character every six insertions into
+//! the same gap. When keys outgrow their budget, reassign them: [`spread`]
+//! spaces keys for a whole list evenly at the minimum length, and
+//! [`spread_between`] re-keys a crowded run between two neighbours.
+//!
+//! ```
+//! use lexcode::frac;
+//!
+//! let first = frac::spread(1).remove(0);
+//! let last = frac::after(&first)?;
+//! let middle = frac::between(&first, &last)?;
+//! let top = frac::before(&first)?;
+//! assert!(top < first && first < middle && middle < last);
+//!
+//! let a = lexcode::to_bytes(&(7u64, &first))?;
+//! let b = lexcode::to_bytes(&(7u64, &middle))?;
+//! assert!(a < b);
+//! # Ok::<(), lexcode::Error>(())
+//! ```
+
+use crate::error::{Error, Result};
+
+const DIGITS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const BASE: u8 = 62;
+
+/// A key strictly between `a` and `b`, as short as possible.
+///
+/// Fails if either is not a valid key, or if `a` is not before `b`.
+pub fn between(a: &str, b: &str) -> Result<String> {
+    let (lo, hi) = (digits(a)?, digits(b)?);
+    if lo >= hi {
+        return Err(Error::Message(format!("{a:?} is not before {b:?}")));
+    }
+    Ok(to_key(&mid(&lo, Some(&hi))))
+}
+
+/// A key before `key`, close to it.
+///
+/// Fails if `key` is not a valid key.
+pub fn before(key: &str) -> Result<String> {
+    let digits = digits(key)?;
+    // Every key has a non-zero digit: its last.
+    let i = digits.iter().position(|&d| d > 0).unwrap();
+    let mut out = digits[..i].to_vec();
+    if digits[i] > 1 {
+        out.push(digits[i] - 1);
+    } else {
+        // Stepping down would end the key in `0`.
+        out.extend([0, BASE - 1]);
+    }
+    Ok(to_key(&out))
+}
+
+/// A key after `key`, close to it.
+///
+/// Fails if `key` is not a valid key.
+pub fn after(key: &str) -> Result<String> {
+    let digits = digits(key)?;
+    let out = match digits.iter().position(|&d| d < BASE - 1) {
+        Some(i) => {
+            let mut out = digits[..i].to_vec();
+            out.push(digits[i] + 1);
+            out
+        }
+        None => {
+            let mut out = digits;
+            out.push(1);
+            out
+        }
+    };
+    Ok(to_key(&out))
+}
+
+/// `n` ascending keys spaced evenly over the whole key space, all of the
+/// shortest length that fits `n` (trailing zeros aside).
+///
+/// ```
+/// assert_eq!(lexcode::frac::spread(3), ["F", "V", "k"]);
+/// ```
+pub fn spread(n: usize) -> Vec<String> {
+    let mut len = 1;
+    let mut total = BASE as u128;
+    while total <= n as u128 {
+        len += 1;
+        total *= BASE as u128;
+    }
+    let (parts, n) = (n as u128 + 1, n as u128);
+    let (step, rest) = (total / parts, total % parts);
+    (1..=n)
+        .map(|k| {
+            let mut value = k * step + k * rest / parts;
+            let mut out = vec![0; len];
+            for digit in out.iter_mut().rev() {
+                *digit = (value % BASE as u128) as u8;
+                value /= BASE as u128;
+            }
+            while out.last() == Some(&0) {
+                out.pop();
+            }
+            to_key(&out)
+        })
+        .collect()
+}
+
+/// `n` ascending keys strictly between `a` and `b`, found by repeated
+/// halving, so they grow by about a character per six-fold increase in `n`.
+///
+/// Fails if either is not a valid key, or if `a` is not before `b`.
+pub fn spread_between(a: &str, b: &str, n: usize) -> Result<Vec<String>> {
+    let (lo, hi) = (digits(a)?, digits(b)?);
+    if lo >= hi {
+        return Err(Error::Message(format!("{a:?} is not before {b:?}")));
+    }
+    let mut out = Vec::with_capacity(n);
+    bisect(&lo, &hi, n, &mut out);
+    Ok(out.iter().map(|digits| to_key(digits)).collect())
+}
+
+fn bisect(lo: &[u8], hi: &[u8], n: usize, out: &mut Vec<Vec<u8>>) {
+    if n == 0 {
+        return;
+    }
+    let m = mid(lo, Some(hi));
+    let left = (n - 1) / 2;
+    bisect(lo, &m, left, out);
+    out.push(m.clone());
+    bisect(&m, hi, n - 1 - left, out);
+}
+
+/// The shortest digit string roughly halfway between `a` (empty for the
+/// start of the key space) and `b` (`None` for the end). Requires `a < b`
+/// and neither ending in a zero digit; the result does not either.
+fn mid(a: &[u8], b: Option<&[u8]>) -> Vec<u8> {
+    let digit = |s: &[u8], i: usize| s.get(i).copied().unwrap_or(0);
+    if let Some(b) = b {
+        // Shared leading digits, reading `a` as padded with zeros.
+        let n = (0..b.len()).take_while(|&i| digit(a, i) == b[i]).count();
+        if n > 0 {
+            let mut out = b[..n].to_vec();
+            out.extend(mid(a.get(n..).unwrap_or(&[]), Some(&b[n..])));
+            return out;
+        }
+    }
+    let lo = digit(a, 0);
+    let hi = b.map_or(BASE, |b| b[0]);
+    if hi - lo > 1 {
+        vec![(lo + hi) / 2]
+    } else if b.is_some_and(|b| b.len() > 1) {
+        // `b`'s first digit alone is above `a` and below the rest of `b`.
+        vec![hi]
+    } else {
+        let mut out = vec![lo];
+        out.extend(mid(a.get(1..).unwrap_or(&[]), None));
+        out
+    }
+}
+
+fn digits(key: &str) -> Result<Vec<u8>> {
+    let digits: Option<Vec<u8>> = key
+        .bytes()
+        .map(|c| DIGITS.iter().position(|&d| d == c).map(|d| d as u8))
+        .collect();
+    match digits {
+        Some(digits) if digits.last().is_some_and(|&d| d != 0) => Ok(digits),
+        _ => Err(Error::Message(format!("{key:?} is not a fractional index key"))),
+    }
+}
+
+fn to_key(digits: &[u8]) -> String {
+    digits.iter().map(|&d| DIGITS[d as usize] as char).collect()
+}
@@ -0,0 +1,226 @@
+//! Geographic points as Z-order keys, so that points near each other on
+//! the map are mostly near each other in the keyspace.
+//!
+//! A [`GeoPoint`] quantizes latitude and longitude to 32 bits each (about
+//! 1 cm) and interleaves their bits into a 64-bit Morton code, stored as 8
+//! raw big-endian bytes. Every aligned square of the grid, down to a
+//! single cell, is then one contiguous run of codes, which
+//! [`covering_ranges`] uses to turn a bounding box into a few byte ranges
+//! to scan. The ranges cover whole squares, so they also return some
+//! points outside the box; filter them with [`GeoPoint::is_within`].
+//!
+//! ```
+//! use lexcode::geo::{self, GeoPoint};
+//!
+//! let paris = GeoPoint::new(48.8566, 2.3522)?;
+//! let key = lexcode::to_bytes(&(paris, 17u64))?;
+//!
+//! let (sw, ne) = (GeoPoint::new(48.0, 2.0)?, GeoPoint::new(49.0, 3.0)?);
+//! let ranges = geo::covering_ranges(sw, ne, 16)?;
+//! assert!(ranges.len() <= 16);
+//! assert!(ranges.iter().any(|range| lexcode::range_contains(range, &key)));
+//! # Ok::<(), lexcode::Error>(())
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::Bound;
+
+use crate::error::{Error, Result};
+use crate::fixed_bytes::FixedBytes;
+use crate::range::ByteRange;
+
+/// A latitude and longitude, quantized to a cell of a 2³² × 2³² grid.
+///
+/// Latitude spans `-90..=90` and longitude `-180..=180` degrees. The
+/// accessors return the center of the point's cell, within about 5 mm of
+/// the coordinates it was made from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GeoPoint {
+    lat: u32,
+    lon: u32,
+}
+
+impl GeoPoint {
+    /// Fails if either coordinate is out of range or NaN.
+    pub fn new(lat: f64, lon: f64) -> Result<GeoPoint> {
+        if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+            return Err(Error::Message(format!("({lat}, {lon}) is not a valid coordinate")));
+        }
+        Ok(GeoPoint { lat: quantize(lat, 90.0), lon: quantize(lon, 180.0) })
+    }
+
+    pub fn lat(&self) -> f64 {
+        dequantize(self.lat, 90.0)
+    }
+
+    pub fn lon(&self) -> f64 {
+        dequantize(self.lon, 180.0)
+    }
+
+    /// The Morton code: longitude bits in the odd positions, latitude bits
+    /// in the even ones, most significant first.
+    pub fn z_order(&self) -> u64 {
+        spread(self.lon) << 1 | spread(self.lat)
+    }
+
+    pub fn from_z_order(z: u64) -> GeoPoint {
+        GeoPoint { lat: compact(z), lon: compact(z >> 1) }
+    }
+
+    /// Whether the point lies in the box with corners `sw` and `ne`,
+    /// edges included.
+    pub fn is_within(&self, sw: GeoPoint, ne: GeoPoint) -> bool {
+        (sw.lat..=ne.lat).contains(&self.lat) && (sw.lon..=ne.lon).contains(&self.lon)
+    }
+}
+
+impl Serialize for GeoPoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        FixedBytes(self.z_order().to_be_bytes()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for GeoPoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        FixedBytes::<8>::deserialize(deserializer).map(|b| GeoPoint::from_z_order(u64::from_be_bytes(b.0)))
+    }
+}
+
+/// Byte ranges, ascending and at most `max_ranges` of them, covering every
+/// key that starts with a point in the box with corners `sw` (south-west)
+/// and `ne` (north-east).
+///
+/// The box is split into the aligned squares of the Z-order grid, as
+/// finely as `max_ranges` allows; each run of adjacent squares is one
+/// range. More ranges fit the box more tightly, so fewer points outside it
+/// are scanned. A box crossing the antimeridian has to be split into two
+/// by the caller. Fails if `sw` is north or east of `ne`, or if
+/// `max_ranges` is zero.
+pub fn covering_ranges(sw: GeoPoint, ne: GeoPoint, max_ranges: usize) -> Result<Vec<ByteRange>> {
+    if sw.lat > ne.lat || sw.lon > ne.lon {
+        return Err(Error::Message("south-west corner is not south-west of the north-east one".into()));
+    }
+    if max_ranges == 0 {
+        return Err(Error::Message("at least one range is needed".into()));
+    }
+    let (lat, lon) = ((sw.lat, ne.lat), (sw.lon, ne.lon));
+
+    // Squares as (Morton prefix, level): the square at level `l` holds the
+    // codes starting with the `2 * l` bits of its prefix.
+    let mut inside: Vec<(u64, u32)> = Vec::new();
+    let mut partial = vec![(0u64, 0u32)];
+    while partial.first().is_some_and(|&(_, level)| level < 32) {
+        let mut next_inside = Vec::new();
+        let mut next_partial = Vec::new();
+        for &(prefix, level) in &partial {
+            for quadrant in 0..4 {
+                let child = (prefix << 2 | quadrant, level + 1);
+                match overlap(child, lat, lon) {
+                    Overlap::None => {}
+                    Overlap::Inside => next_inside.push(child),
+                    Overlap::Partial => next_partial.push(child),
+                }
+            }
+        }
+        // Squares along the edges multiply even when they merge into few
+        // runs, so cap them too.
+        let squares = inside.len() + next_inside.len() + next_partial.len();
+        if squares > 4 * max_ranges
+            || runs(inside.iter().chain(&next_inside).chain(&next_partial).copied()).len() > max_ranges
+        {
+            break;
+        }
+        inside.extend(next_inside);
+        partial = next_partial;
+    }
+
+    Ok(runs(inside.into_iter().chain(partial))
+        .into_iter()
+        .map(|(lo, hi)| {
+            let end = match hi.checked_add(1) {
+                Some(end) => Bound::Excluded(end.to_be_bytes().to_vec()),
+                None => Bound::Unbounded,
+            };
+            (Bound::Included(lo.to_be_bytes().to_vec()), end)
+        })
+        .collect())
+}
+
+enum Overlap {
+    None,
+    Inside,
+    Partial,
+}
+
+fn overlap((prefix, level): (u64, u32), lat: (u32, u32), lon: (u32, u32)) -> Overlap {
+    let corner = GeoPoint::from_z_order(codes((prefix, level)).0);
+    let size = u32::MAX.checked_shr(level).unwrap_or(0) as u64;
+    let cell_lat = (corner.lat as u64, corner.lat as u64 + size);
+    let cell_lon = (corner.lon as u64, corner.lon as u64 + size);
+    let axis = |cell: (u64, u64), range: (u32, u32)| {
+        let range = (range.0 as u64, range.1 as u64);
+        if cell.1 < range.0 || cell.0 > range.1 {
+            Overlap::None
+        } else if range.0 <= cell.0 && cell.1 <= range.1 {
+            Overlap::Inside
+        } else {
+            Overlap::Partial
+        }
+    };
+    match (axis(cell_lat, lat), axis(cell_lon, lon)) {
+        (Overlap::None, _) | (_, Overlap::None) => Overlap::None,
+        (Overlap::Inside, Overlap::Inside) => Overlap::Inside,
+        _ => Overlap::Partial,
+    }
+}
+
+/// The first and last code of a square.
+fn codes((prefix, level): (u64, u32)) -> (u64, u64) {
+    let rest = u64::MAX.checked_shr(2 * level).unwrap_or(0);
+    let lo = prefix.checked_shl(64 - 2 * level).unwrap_or(0);
+    (lo, lo | rest)
+}
+
+/// Sorted squares merged into runs of consecutive codes.
+fn runs(squares: impl Iterator<Item = (u64, u32)>) -> Vec<(u64, u64)> {
+    let mut ranges: Vec<(u64, u64)> = squares.map(codes).collect();
+    ranges.sort_unstable();
+    let mut out: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (lo, hi) in ranges {
+        match out.last_mut() {
+            Some(last) if last.1.checked_add(1) == Some(lo) => last.1 = hi,
+            _ => out.push((lo, hi)),
+        }
+    }
+    out
+}
+
+fn quantize(degrees: f64, half_span: f64) -> u32 {
+    let scaled = (degrees + half_span) / (2.0 * half_span) * 4_294_967_296.0;
+    // `as` saturates, which puts the top edge in the last cell.
+    scaled as u32
+}
+
+fn dequantize(cell: u32, half_span: f64) -> f64 {
+    (cell as f64 + 0.5) / 4_294_967_296.0 * (2.0 * half_span) - half_span
+}
+
+/// Spread the bits of `x` into the even positions of a `u64`.
+fn spread(x: u32) -> u64 {
+    let mut x = x as u64;
+    x = (x | x << 16) & 0x0000_FFFF_0000_FFFF;
+    x = (x | x << 8) & 0x00FF_00FF_00FF_00FF;
+    x = (x | x << 4) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | x << 2) & 0x3333_3333_3333_3333;
+    (x | x << 1) & 0x5555_5555_5555_5555
+}
+
+/// Gather the even-position bits of `z`.
+fn compact(z: u64) -> u32 {
+    let mut x = z & 0x5555_5555_5555_5555;
+    x = (x | x >> 1) & 0x3333_3333_3333_3333;
+    x = (x | x >> 2) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | x >> 4) & 0x00FF_00FF_00FF_00FF;
+    x = (x | x >> 8) & 0x0000_FFFF_0000_FFFF;
+    (x | x >> 16) as u32
+}
@@ -0,0 +1,64 @@
+//! Two-byte order-preserving encodings for the [`half`](::half) crate's
+//! `f16` and `bf16` (feature `half`).
+//!
+//! The float is encoded exactly like `f32`/`f64`, only narrower: positive
+//! values have their sign bit flipped and negative values have all bits
+//! flipped, then the 16 bits are written big-endian as two raw bytes.
+//!
+//! ```
+//! use half::{bf16, f16};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Feature {
+//!     #[serde(with = "lexcode::half::f16")]
+//!     weight: f16,
+//!     #[serde(with = "lexcode::half::bf16")]
+//!     bias: bf16,
+//! }
+//!
+//! let key = Feature { weight: f16::from_f32(0.5), bias: bf16::from_f32(-1.0) };
+//! assert_eq!(lexcode::to_bytes(&key).unwrap().len(), 4);
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::fixed_bytes::FixedBytes;
+
+const SIGN_MASK: u16 = 1 << 15;
+
+fn encode_bits(bits: u16) -> FixedBytes<2> {
+    let bits = if bits & SIGN_MASK != 0 { !bits } else { bits ^ SIGN_MASK };
+    FixedBytes(bits.to_be_bytes())
+}
+
+fn decode_bits(bytes: FixedBytes<2>) -> u16 {
+    let bits = u16::from_be_bytes(bytes.0);
+    if bits & SIGN_MASK == 0 { !bits } else { bits ^ SIGN_MASK }
+}
+
+/// `#[serde(with = "lexcode::half::f16")]` for `half::f16`.
+pub mod f16 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &::half::f16, serializer: S) -> Result<S::Ok, S::Error> {
+        encode_bits(value.to_bits()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<::half::f16, D::Error> {
+        FixedBytes::deserialize(deserializer).map(|b| ::half::f16::from_bits(decode_bits(b)))
+    }
+}
+
+/// `#[serde(with = "lexcode::half::bf16")]` for `half::bf16`.
+pub mod bf16 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &::half::bf16, serializer: S) -> Result<S::Ok, S::Error> {
+        encode_bits(value.to_bits()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<::half::bf16, D::Error> {
+        FixedBytes::deserialize(deserializer).map(|b| ::half::bf16::from_bits(decode_bits(b)))
+    }
+}
@@ -0,0 +1,56 @@
+//! lexcode-encoded keys and values for LMDB databases opened with
+//! [heed](::heed) (feature `heed`).
+//!
+//! LMDB compares keys byte by byte unless told otherwise, which is the
+//! order lexcode preserves. With [`Lex<T>`] as the key codec of a
+//! `heed::Database`, iteration follows `T`'s order and `range` takes
+//! typed bounds, with no custom comparator.
+//!
+//! ```no_run
+//! use heed::{Database, EnvOpenOptions};
+//! use lexcode::heed::Lex;
+//!
+//! let env = unsafe { EnvOpenOptions::new().open("/tmp/events")? };
+//! let mut wtxn = env.write_txn()?;
+//! let db: Database<Lex<(u64, i64)>, Lex<String>> = env.create_database(&mut wtxn, None)?;
+//! db.put(&mut wtxn, &(7, 1700), &"login".to_string())?;
+//! wtxn.commit()?;
+//!
+//! let rtxn = env.read_txn()?;
+//! for row in db.range(&rtxn, &((7, i64::MIN)..(8, i64::MIN)))? {
+//!     let ((user, ts), event) = row?;
+//!     println!("{user} {ts} {event}");
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::heed::{BoxedError, BytesDecode, BytesEncode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use crate::de::from_bytes;
+use crate::ser::to_bytes;
+
+/// A heed codec storing a `T` as its lexcode encoding.
+///
+/// It encodes from a `&T` and decodes to an owned `T`; encoding and
+/// decoding errors are passed on to heed.
+pub struct Lex<T>(PhantomData<T>);
+
+impl<'a, T: Serialize + 'a> BytesEncode<'a> for Lex<T> {
+    type EItem = T;
+
+    fn bytes_encode(item: &'a T) -> Result<Cow<'a, [u8]>, BoxedError> {
+        Ok(Cow::Owned(to_bytes(item)?))
+    }
+}
+
+impl<'a, T: DeserializeOwned + 'a> BytesDecode<'a> for Lex<T> {
+    type DItem = T;
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<T, BoxedError> {
+        Ok(from_bytes(bytes)?)
+    }
+}
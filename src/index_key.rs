@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::ops::{Bound, RangeBounds};
+
+use crate::components::decode_prefix;
+use crate::error::{Error, Result};
+use crate::range::{prefix_range, prefix_successor, ByteRange};
+use crate::ser::to_bytes;
+
+/// A non-unique secondary-index key made unique by a trailing id, such as
+/// the row's primary key or a [`Ulid`](crate::Ulid).
+///
+/// `IndexKey<K, Id>` encodes as the tuple `(key, id)`, so entries sort by
+/// `key` and then `id`, and every entry for one `key` shares its encoding
+/// as a prefix. Scanning by `key` alone has to account for the suffix: an
+/// inclusive upper bound on `key` must reach past every id after it, and
+/// an exclusive lower bound must skip them. [`range`](IndexKey::range) and
+/// [`prefix`](IndexKey::prefix) produce such bounds, and
+/// [`decode_key`](IndexKey::decode_key) reads the key without the id.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::IndexKey;
+/// use std::collections::BTreeMap;
+///
+/// type ByCity = IndexKey<String, u64>;
+///
+/// let mut index = BTreeMap::new();
+/// for (city, user) in [("Oslo", 3), ("Oslo", 1), ("Paris", 2), ("Rome", 4)] {
+///     index.insert(lexcode::to_bytes(&ByCity::new(city.into(), user))?, ());
+/// }
+///
+/// let users: Vec<u64> = index
+///     .range(ByCity::range("Oslo".to_string()..="Paris".to_string())?)
+///     .map(|(bytes, _)| lexcode::from_bytes::<ByCity>(bytes).map(|entry| entry.id))
+///     .collect::<Result<_, _>>()?;
+/// assert_eq!(users, [1, 3, 2]);
+///
+/// let (start, end) = ByCity::prefix(&"Oslo".to_string())?;
+/// assert_eq!(index.range(start..end).count(), 2);
+/// # Ok::<(), lexcode::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub struct IndexKey<K, Id> {
+    pub key: K,
+    pub id: Id,
+}
+
+impl<K, Id> IndexKey<K, Id> {
+    pub fn new(key: K, id: Id) -> Self {
+        IndexKey { key, id }
+    }
+}
+
+impl<K: Serialize, Id> IndexKey<K, Id> {
+    /// The `[start, end)` byte range of every entry for `key`. Fails if
+    /// `key` cannot be encoded, or encodes as all `0xFF` and so has no upper
+    /// bound.
+    pub fn prefix(key: &K) -> Result<(Vec<u8>, Vec<u8>)> {
+        prefix_range(key)
+    }
+
+    /// The byte bounds selecting every entry whose key lies in `range`,
+    /// whatever its id. Fails if an endpoint cannot be encoded, or if the
+    /// range excludes a start key that encodes as all `0xFF`.
+    pub fn range<R: RangeBounds<K>>(range: R) -> Result<ByteRange> {
+        let start = match range.start_bound() {
+            Bound::Included(key) => Bound::Included(to_bytes(key)?),
+            Bound::Excluded(key) => Bound::Included(
+                prefix_successor(&to_bytes(key)?)
+                    .ok_or_else(|| Error::Message("no entry sorts after an all-0xFF key".into()))?,
+            ),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => match prefix_successor(&to_bytes(key)?) {
+                Some(next) => Bound::Excluded(next),
+                None => Bound::Unbounded,
+            },
+            Bound::Excluded(key) => Bound::Excluded(to_bytes(key)?),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        Ok((start, end))
+    }
+}
+
+impl<'de, K: Deserialize<'de>, Id> IndexKey<K, Id> {
+    /// Decode only the key of an encoded entry, ignoring the id.
+    pub fn decode_key(bytes: &'de [u8]) -> Result<K> {
+        decode_prefix(bytes)
+    }
+}
@@ -0,0 +1,99 @@
+//! Sorted encodings for the [`indexmap`](::indexmap) crate's `IndexMap`
+//! and `IndexSet` (feature `indexmap`).
+//!
+//! The feature turns on `indexmap`'s serde support, so both types encode
+//! like any other map or sequence and decode back into an `IndexMap` or
+//! `IndexSet`. By default the entries are written in insertion order,
+//! which is **not canonical**: two equal maps built in a different order
+//! produce different keys. Either serialize with a
+//! [`Serializer`](crate::Serializer) configured with `.sort_maps(true)`
+//! (maps only), or annotate fields with the helper modules below, which
+//! write the entries sorted by their encoded keys regardless of serializer
+//! settings. A sorted `IndexMap` encodes exactly like a `BTreeMap` with
+//! the same entries, and decodes with its entries in that order.
+//!
+//! ```
+//! use indexmap::{IndexMap, IndexSet};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "lexcode::indexmap::sorted")]
+//!     limits: IndexMap<String, u32>,
+//!     #[serde(with = "lexcode::indexmap::sorted_set")]
+//!     tags: IndexSet<String>,
+//! }
+//!
+//! let a = Config {
+//!     limits: IndexMap::from([("b".into(), 2), ("a".into(), 1)]),
+//!     tags: IndexSet::from(["y".into(), "x".into()]),
+//! };
+//! let b = Config {
+//!     limits: IndexMap::from([("a".into(), 1), ("b".into(), 2)]),
+//!     tags: IndexSet::from(["x".into(), "y".into()]),
+//! };
+//! assert_eq!(lexcode::to_bytes(&a).unwrap(), lexcode::to_bytes(&b).unwrap());
+//! ```
+
+use ::indexmap::{IndexMap, IndexSet};
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+use std::hash::{BuildHasher, Hash};
+
+use crate::ser::to_bytes;
+
+/// Pair each item with its encoding and sort by it.
+fn sorted_by_encoding<T: Serialize, U>(items: impl Iterator<Item = (T, U)>) -> crate::Result<Vec<(T, U)>> {
+    let mut encoded = items
+        .map(|(item, rest)| Ok((to_bytes(&item)?, item, rest)))
+        .collect::<crate::Result<Vec<_>>>()?;
+    encoded.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(encoded.into_iter().map(|(_, item, rest)| (item, rest)).collect())
+}
+
+/// `#[serde(with = "lexcode::indexmap::sorted")]` for `IndexMap<K, V>`.
+pub mod sorted {
+    use super::*;
+
+    pub fn serialize<K, V, H, S>(map: &IndexMap<K, V, H>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        let entries = sorted_by_encoding(map.iter()).map_err(ser::Error::custom)?;
+        serializer.collect_map(entries)
+    }
+
+    pub fn deserialize<'de, K, V, H, D>(deserializer: D) -> Result<IndexMap<K, V, H>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+        D: Deserializer<'de>,
+    {
+        IndexMap::deserialize(deserializer)
+    }
+}
+
+/// `#[serde(with = "lexcode::indexmap::sorted_set")]` for `IndexSet<T>`.
+pub mod sorted_set {
+    use super::*;
+
+    pub fn serialize<T, H, S>(set: &IndexSet<T, H>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Serialize,
+        S: Serializer,
+    {
+        let items = sorted_by_encoding(set.iter().map(|item| (item, ()))).map_err(ser::Error::custom)?;
+        serializer.collect_seq(items.into_iter().map(|(item, ())| item))
+    }
+
+    pub fn deserialize<'de, T, H, D>(deserializer: D) -> Result<IndexSet<T, H>, D::Error>
+    where
+        T: Deserialize<'de> + Eq + Hash,
+        H: BuildHasher + Default,
+        D: Deserializer<'de>,
+    {
+        IndexSet::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,171 @@
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::cmp::Ordering;
+
+use crate::fixed_bytes::{RawBytes, RawBytesSeed};
+
+/// A tuple of unsigned integers encoded with their bits interleaved, a
+/// Z-order (Morton) key over any number of dimensions.
+///
+/// The bits are taken most significant first, one from each component in
+/// turn, and written as raw bytes. Components of different widths are
+/// aligned at their least significant bit, so a narrower one joins in once
+/// the rounds reach its width. Points close in every dimension mostly
+/// share leading bytes, and every aligned box of the grid is one
+/// contiguous run of keys. The encoding has a fixed length,
+/// the total width in bytes, and `Ord` compares as the encoding does.
+///
+/// Implemented for tuples of two to eight of `u8`, `u16`, `u32`, `u64` and
+/// `u128`.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Interleaved;
+///
+/// let key = lexcode::to_bytes(&Interleaved((0b11u8, 0b00u8))).unwrap();
+/// assert_eq!(key, [0b0000_0000, 0b0000_1010]);
+///
+/// // (x, y, t) cells: (3, 3, 3) sorts between (0, 0, 0) and (4, 4, 4).
+/// let keys: Vec<_> = [(0u16, 0u16, 0u32), (3, 3, 3), (4, 4, 4)]
+///     .map(|p| lexcode::to_bytes(&Interleaved(p)).unwrap())
+///     .into();
+/// assert!(keys.is_sorted());
+/// let back: Interleaved<(u16, u16, u32)> = lexcode::from_bytes(&keys[1]).unwrap();
+/// assert_eq!(back.0, (3, 3, 3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Interleaved<T>(pub T);
+
+/// A tuple whose components can be interleaved by [`Interleaved`].
+pub trait Interleave: Sized {
+    /// The width in bits of each component.
+    const WIDTHS: &'static [u32];
+
+    /// The components, widened.
+    fn to_parts(&self) -> Vec<u128>;
+
+    /// Rebuild the tuple from components that fit their widths.
+    fn from_parts(parts: &[u128]) -> Self;
+}
+
+mod sealed {
+    /// An unsigned integer that can be a component of an [`Interleaved`](super::Interleaved).
+    pub trait Component: Copy {
+        const BITS: u32;
+        fn widen(self) -> u128;
+        fn narrow(value: u128) -> Self;
+    }
+}
+
+use sealed::Component;
+
+macro_rules! component {
+    ($($t:ty)*) => {$(
+        impl Component for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            fn widen(self) -> u128 {
+                self as u128
+            }
+
+            fn narrow(value: u128) -> Self {
+                value as $t
+            }
+        }
+    )*};
+}
+
+component!(u8 u16 u32 u64 u128);
+
+macro_rules! interleave_tuple {
+    ($($t:ident $n:tt),+) => {
+        impl<$($t: Component),+> Interleave for ($($t,)+) {
+            const WIDTHS: &'static [u32] = &[$($t::BITS),+];
+
+            fn to_parts(&self) -> Vec<u128> {
+                vec![$(self.$n.widen()),+]
+            }
+
+            fn from_parts(parts: &[u128]) -> Self {
+                ($($t::narrow(parts[$n]),)+)
+            }
+        }
+    };
+}
+
+interleave_tuple!(A 0, B 1);
+interleave_tuple!(A 0, B 1, C 2);
+interleave_tuple!(A 0, B 1, C 2, D 3);
+interleave_tuple!(A 0, B 1, C 2, D 3, E 4);
+interleave_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+interleave_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+interleave_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl<T: Interleave> Interleaved<T> {
+    /// The encoded length in bytes.
+    pub fn encoded_len() -> usize {
+        T::WIDTHS.iter().sum::<u32>() as usize / 8
+    }
+
+    /// The interleaved bits, as encoded.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let parts = self.0.to_parts();
+        let mut bytes = vec![0u8; Self::encoded_len()];
+        for (pos, (round, i)) in rounds::<T>().enumerate() {
+            if (parts[i] >> round) & 1 == 1 {
+                bytes[pos / 8] |= 0x80 >> (pos % 8);
+            }
+        }
+        bytes
+    }
+
+    /// Read back the interleaved bits, or `None` if `bytes` has the wrong
+    /// length.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Interleaved<T>> {
+        if bytes.len() != Self::encoded_len() {
+            return None;
+        }
+        let mut parts = vec![0u128; T::WIDTHS.len()];
+        for (pos, (round, i)) in rounds::<T>().enumerate() {
+            if bytes[pos / 8] & (0x80 >> (pos % 8)) != 0 {
+                parts[i] |= 1 << round;
+            }
+        }
+        Some(Interleaved(T::from_parts(&parts)))
+    }
+}
+
+/// `(bit, component)` in encoding order: each bit position from the top,
+/// across the components wide enough to have it.
+fn rounds<T: Interleave>() -> impl Iterator<Item = (u32, usize)> {
+    let max = T::WIDTHS.iter().copied().max().unwrap_or(0);
+    (0..max).rev().flat_map(|round| {
+        T::WIDTHS.iter().enumerate().filter(move |&(_, &width)| round < width).map(move |(i, _)| (round, i))
+    })
+}
+
+impl<T: Interleave + Eq> PartialOrd for Interleaved<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Interleave + Eq> Ord for Interleaved<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_bytes().cmp(&other.to_bytes())
+    }
+}
+
+impl<T: Interleave> Serialize for Interleaved<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawBytes(&self.to_bytes()).serialize(serializer)
+    }
+}
+
+impl<'de, T: Interleave> Deserialize<'de> for Interleaved<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = RawBytesSeed(Self::encoded_len()).deserialize(deserializer)?;
+        Interleaved::from_bytes(&bytes).ok_or_else(|| de::Error::invalid_length(bytes.len(), &"an interleaved key"))
+    }
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::ops::{Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::option_last::OptionLast;
+
+/// An interval of `T` that sorts by start, then by end.
+///
+/// Starts compare by value, an unbounded start first and an excluded one
+/// just after the included start of the same value; ends compare by value,
+/// an excluded end just before the included end of the same value and an
+/// unbounded end last. The encoding is the start then the end, written
+/// with [`bound::start`](crate::bound::start) and
+/// [`bound::end`](crate::bound::end), and sorts the same way, so an
+/// interval index keyed by `Interval<T>` can be scanned in start order:
+/// every interval starting at or before `x` lies below the encoding of
+/// `Interval::new(Bound::Excluded(x), Bound::Unbounded)`.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Interval;
+/// use std::ops::Bound;
+///
+/// let keys: Vec<_> = [
+///     Interval::from(..5u32),
+///     Interval::from(1u32..3),
+///     Interval::from(1u32..=3),
+///     Interval::from(1u32..),
+///     Interval::new(Bound::Excluded(1u32), Bound::Included(2)),
+/// ]
+/// .map(|i| lexcode::to_bytes(&i).unwrap())
+/// .into();
+/// assert!(keys.is_sorted());
+///
+/// let decoded: Interval<u32> = lexcode::from_bytes(&keys[1]).unwrap();
+/// assert_eq!(decoded, Interval::from(1..3));
+/// assert!(decoded.contains(&2) && !decoded.contains(&3));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Serialize", deserialize = "T: Deserialize<'de>"))]
+pub struct Interval<T> {
+    #[serde(with = "crate::bound::start")]
+    pub start: Bound<T>,
+    #[serde(with = "crate::bound::end")]
+    pub end: Bound<T>,
+}
+
+impl<T> Interval<T> {
+    pub fn new(start: Bound<T>, end: Bound<T>) -> Interval<T> {
+        Interval { start, end }
+    }
+
+    /// Whether `value` lies in the interval.
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialOrd,
+    {
+        RangeBounds::contains(self, value)
+    }
+}
+
+impl<T> RangeBounds<T> for Interval<T> {
+    fn start_bound(&self) -> Bound<&T> {
+        self.start.as_ref()
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        self.end.as_ref()
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Interval<T> {
+        Interval::new(Bound::Included(range.start), Bound::Excluded(range.end))
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Interval<T> {
+        let (start, end) = range.into_inner();
+        Interval::new(Bound::Included(start), Bound::Included(end))
+    }
+}
+
+impl<T> From<RangeFrom<T>> for Interval<T> {
+    fn from(range: RangeFrom<T>) -> Interval<T> {
+        Interval::new(Bound::Included(range.start), Bound::Unbounded)
+    }
+}
+
+impl<T> From<RangeTo<T>> for Interval<T> {
+    fn from(range: RangeTo<T>) -> Interval<T> {
+        Interval::new(Bound::Unbounded, Bound::Excluded(range.end))
+    }
+}
+
+impl<T> From<RangeToInclusive<T>> for Interval<T> {
+    fn from(range: RangeToInclusive<T>) -> Interval<T> {
+        Interval::new(Bound::Unbounded, Bound::Included(range.end))
+    }
+}
+
+impl<T> From<RangeFull> for Interval<T> {
+    fn from(_: RangeFull) -> Interval<T> {
+        Interval::new(Bound::Unbounded, Bound::Unbounded)
+    }
+}
+
+impl<T> From<(Bound<T>, Bound<T>)> for Interval<T> {
+    fn from((start, end): (Bound<T>, Bound<T>)) -> Interval<T> {
+        Interval::new(start, end)
+    }
+}
+
+impl<T: Ord> PartialOrd for Interval<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Interval<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        start_key(&self.start)
+            .cmp(&start_key(&other.start))
+            .then_with(|| end_key(&self.end).cmp(&end_key(&other.end)))
+    }
+}
+
+/// The start as `(value, excluded)`, with `None` for unbounded sorting
+/// first, as [`bound::start`](crate::bound::start) encodes it.
+fn start_key<T>(bound: &Bound<T>) -> Option<(&T, bool)> {
+    match bound {
+        Bound::Unbounded => None,
+        Bound::Included(v) => Some((v, false)),
+        Bound::Excluded(v) => Some((v, true)),
+    }
+}
+
+/// The end as `(value, included)`, with unbounded sorting last, as
+/// [`bound::end`](crate::bound::end) encodes it.
+fn end_key<T>(bound: &Bound<T>) -> OptionLast<(&T, bool)> {
+    OptionLast(match bound {
+        Bound::Unbounded => None,
+        Bound::Included(v) => Some((v, true)),
+        Bound::Excluded(v) => Some((v, false)),
+    })
+}
@@ -0,0 +1,187 @@
+//! Order-preserving encoding for [`serde_json::Value`] (feature `json`).
+//!
+//! serde writes a JSON value as whatever it holds, with nothing to tell
+//! the kinds apart, so values of different kinds compare arbitrarily and
+//! cannot be decoded. The helpers here prefix every value with a kind tag
+//! instead, giving the CouchDB-style collation
+//!
+//! > null < false < true < numbers < strings < arrays < objects
+//!
+//! within which:
+//!
+//! - numbers compare numerically, whether integers or floats: each is
+//!   written as the nearest `f64`, then the integer's distance from it as a
+//!   signed varint, then `0x00` for a float or `0x01` for an integer. Large
+//!   integers therefore keep their exact order and value, and a float sorts
+//!   just before the equal integer (`1.0` < `1`); `-0.0` sorts before `0.0`;
+//! - strings compare by their UTF-8 bytes, as lexcode strings do;
+//! - arrays compare element by element, a prefix first;
+//! - objects compare as the arrays of their `(key, value)` entries in key
+//!   order, whatever order the `Map` keeps them in.
+//!
+//! ```
+//! use serde_json::json;
+//!
+//! let values = [json!(null), json!(false), json!(-1.5), json!(2), json!(2.5), json!("a"), json!([1]), json!({"a": 1})];
+//! let keys = values.iter().map(lexcode::json::to_bytes).collect::<lexcode::Result<Vec<_>>>()?;
+//! assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+//! assert_eq!(lexcode::json::from_bytes(&keys[3])?, json!(2));
+//! # Ok::<(), lexcode::Error>(())
+//! ```
+//!
+//! Inside a key, use the helpers with `#[serde(with = "lexcode::json")]`.
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, SerializeTuple, Serializer};
+use serde_json::{Map, Number, Value};
+use std::fmt;
+
+use crate::error::Result;
+
+const NULL: u8 = 0;
+const FALSE: u8 = 1;
+const TRUE: u8 = 2;
+const NUMBER: u8 = 3;
+const STRING: u8 = 4;
+const ARRAY: u8 = 5;
+const OBJECT: u8 = 6;
+
+const FLOAT: u8 = 0;
+const INTEGER: u8 = 1;
+
+/// `#[serde(with = "lexcode::json")]` serializer for `serde_json::Value`.
+pub fn serialize<S: Serializer>(value: &Value, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    Tagged(value).serialize(serializer)
+}
+
+/// `#[serde(with = "lexcode::json")]` deserializer for `serde_json::Value`.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Value, D::Error> {
+    Owned::deserialize(deserializer).map(|owned| owned.0)
+}
+
+/// Encode `value` on its own.
+pub fn to_bytes(value: &Value) -> Result<Vec<u8>> {
+    crate::ser::to_bytes(&Tagged(value))
+}
+
+/// Decode a value encoded by [`to_bytes`].
+pub fn from_bytes(bytes: &[u8]) -> Result<Value> {
+    crate::de::from_bytes::<Owned>(bytes).map(|owned| owned.0)
+}
+
+/// A borrowed value, written with its kind tag.
+struct Tagged<'a>(&'a Value);
+
+impl Serialize for Tagged<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        match self.0 {
+            Value::Null => tuple.serialize_element(&NULL)?,
+            Value::Bool(false) => tuple.serialize_element(&FALSE)?,
+            Value::Bool(true) => tuple.serialize_element(&TRUE)?,
+            Value::Number(number) => {
+                tuple.serialize_element(&NUMBER)?;
+                tuple.serialize_element(&split(number))?;
+            }
+            Value::String(s) => {
+                tuple.serialize_element(&STRING)?;
+                tuple.serialize_element(s)?;
+            }
+            Value::Array(items) => {
+                tuple.serialize_element(&ARRAY)?;
+                tuple.serialize_element(&Items(items.iter().map(Tagged).collect()))?;
+            }
+            Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().map(|(k, v)| (k.as_str(), Tagged(v))).collect();
+                entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+                tuple.serialize_element(&OBJECT)?;
+                tuple.serialize_element(&Items(entries))?;
+            }
+        }
+        tuple.end()
+    }
+}
+
+/// Elements written as a lexcode sequence.
+struct Items<T>(Vec<T>);
+
+impl<T: Serialize> Serialize for Items<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for item in &self.0 {
+            seq.serialize_element(item)?;
+        }
+        seq.end()
+    }
+}
+
+/// The nearest `f64` to `number`, the distance of an integer from it, and
+/// whether it is an integer.
+fn split(number: &Number) -> (f64, i128, u8) {
+    let integer = number.as_u64().map(i128::from).or_else(|| number.as_i64().map(i128::from));
+    match integer {
+        Some(n) => {
+            let nearest = n as f64;
+            (nearest, n - nearest as i128, INTEGER)
+        }
+        None => (number.as_f64().unwrap_or(f64::NAN), 0, FLOAT),
+    }
+}
+
+fn join<E: de::Error>((nearest, distance, kind): (f64, i128, u8)) -> std::result::Result<Number, E> {
+    match kind {
+        FLOAT if distance == 0 => Number::from_f64(nearest).ok_or_else(|| E::custom("JSON number is not finite")),
+        INTEGER => {
+            let n = (nearest as i128)
+                .checked_add(distance)
+                .ok_or_else(|| E::custom("JSON integer out of range"))?;
+            if let Ok(n) = u64::try_from(n) {
+                Ok(n.into())
+            } else {
+                i64::try_from(n).map(Number::from).map_err(|_| E::custom("JSON integer out of range"))
+            }
+        }
+        _ => Err(E::custom("invalid JSON number")),
+    }
+}
+
+/// A decoded value.
+struct Owned(Value);
+
+impl<'de> Deserialize<'de> for Owned {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct OwnedVisitor;
+
+        impl<'de> Visitor<'de> for OwnedVisitor {
+            type Value = Owned;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON kind tag followed by the value")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Owned, A::Error> {
+                let tag: u8 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let missing = || de::Error::invalid_length(1, &self);
+                let value = match tag {
+                    NULL => Value::Null,
+                    FALSE => Value::Bool(false),
+                    TRUE => Value::Bool(true),
+                    NUMBER => Value::Number(join(seq.next_element()?.ok_or_else(missing)?)?),
+                    STRING => Value::String(seq.next_element()?.ok_or_else(missing)?),
+                    ARRAY => {
+                        let items: Vec<Owned> = seq.next_element()?.ok_or_else(missing)?;
+                        Value::Array(items.into_iter().map(|item| item.0).collect())
+                    }
+                    OBJECT => {
+                        let entries: Vec<(String, Owned)> = seq.next_element()?.ok_or_else(missing)?;
+                        Value::Object(entries.into_iter().map(|(k, v)| (k, v.0)).collect::<Map<_, _>>())
+                    }
+                    _ => return Err(de::Error::custom("invalid JSON kind tag")),
+                };
+                Ok(Owned(value))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, OwnedVisitor)
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use crate::de::from_bytes;
+use crate::error::Result;
+use crate::ser::to_bytes;
+
+/// The encoding of a `T`, tagged with its type.
+///
+/// A `Key<T>` compares, hashes and sorts as its bytes, which is the order
+/// of the `T` values it was encoded from. The type parameter only records
+/// what the bytes hold, so it needs no bounds for the comparison impls.
+///
+/// It borrows as `[u8]`, so a `HashMap<Key<T>, V>` or `BTreeMap<Key<T>, V>`
+/// can be queried with raw bytes read back from a store.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Key;
+///
+/// let a = Key::encode(&(7u64, "a".to_string())).unwrap();
+/// let b = Key::encode(&(7u64, "b".to_string())).unwrap();
+/// assert!(a < b);
+/// assert_eq!(a.as_bytes(), lexcode::to_bytes(&(7u64, "a")).unwrap());
+/// assert_eq!(a.decode().unwrap(), (7, "a".to_string()));
+/// ```
+pub struct Key<T> {
+    bytes: Vec<u8>,
+    marker: PhantomData<fn() -> T>,
+}
+
+/// Another name for [`Key`], for code that spells out that it holds bytes.
+pub type EncodedKey<T> = Key<T>;
+
+impl<T: Serialize> Key<T> {
+    pub fn encode(value: &T) -> Result<Key<T>> {
+        to_bytes(value).map(Key::from_bytes)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Key<T> {
+    /// Decode the `T` these bytes encode.
+    pub fn decode(&'de self) -> Result<T> {
+        from_bytes(&self.bytes)
+    }
+}
+
+impl<T> Key<T> {
+    /// Wrap bytes already known to be the encoding of a `T`.
+    pub fn from_bytes(bytes: Vec<u8>) -> Key<T> {
+        Key { bytes, marker: PhantomData }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl<T> From<Key<T>> for Vec<u8> {
+    fn from(key: Key<T>) -> Vec<u8> {
+        key.bytes
+    }
+}
+
+impl<T> AsRef<[u8]> for Key<T> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<T> Borrow<[u8]> for Key<T> {
+    fn borrow(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<T> Clone for Key<T> {
+    fn clone(&self) -> Self {
+        Key::from_bytes(self.bytes.clone())
+    }
+}
+
+impl<T> fmt::Debug for Key<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Key").field(&self.bytes).finish()
+    }
+}
+
+impl<T> PartialEq for Key<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<T> Eq for Key<T> {}
+
+impl<T> PartialOrd for Key<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Key<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes.cmp(&other.bytes)
+    }
+}
+
+impl<T> Hash for Key<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state)
+    }
+}
@@ -0,0 +1,58 @@
+use serde::Serialize;
+
+use crate::config::LexConfig;
+use crate::error::Result;
+use crate::ser::Serializer;
+
+/// Builds a composite key one component at a time.
+///
+/// A tuple encodes as its elements back to back, so pushing `a` and then
+/// `b` yields the same bytes as encoding `(a, b)`. Stopping early gives a
+/// prefix of the full key, ready for [`prefix_range`](crate::prefix_range)
+/// or [`prefix_successor`](crate::prefix_successor), without a tuple type
+/// for every combination of leading columns.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::{Descending, KeyBuilder};
+///
+/// let mut key = KeyBuilder::new();
+/// key.push(&7u64)?.push(&Descending(1_000i64))?;
+/// assert_eq!(key.finish(), lexcode::to_bytes(&(7u64, Descending(1_000i64)))?);
+///
+/// let mut prefix = KeyBuilder::new();
+/// prefix.push(&7u64)?;
+/// assert_eq!(prefix.finish(), lexcode::to_bytes(&7u64)?);
+/// # Ok::<(), lexcode::Error>(())
+/// ```
+#[derive(Default)]
+pub struct KeyBuilder {
+    serializer: Serializer,
+}
+
+impl KeyBuilder {
+    pub fn new() -> Self {
+        KeyBuilder::default()
+    }
+
+    /// Encode components with the options in `config`.
+    pub fn with_config(config: LexConfig) -> Self {
+        KeyBuilder { serializer: Serializer::with_config(config) }
+    }
+
+    /// Append the encoding of `component`. After an error the key may end
+    /// with part of it, so the builder should be discarded.
+    pub fn push<T>(&mut self, component: &T) -> Result<&mut Self>
+    where
+        T: ?Sized + Serialize,
+    {
+        component.serialize(&mut self.serializer)?;
+        Ok(self)
+    }
+
+    /// The key built so far.
+    pub fn finish(self) -> Vec<u8> {
+        self.serializer.into_bytes()
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+use crate::config::LexConfig;
+use crate::de::Deserializer;
+use crate::error::Result;
+
+/// Reads a composite key one component at a time.
+///
+/// The counterpart of [`KeyBuilder`](crate::KeyBuilder): each
+/// [`next`](KeyReader::next) decodes the following component and leaves
+/// the rest of the key untouched, so a scan that only needs the leading
+/// columns does not pay for decoding the others.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::KeyReader;
+///
+/// let key = lexcode::to_bytes(&(7u64, "2024-01-01", vec![1u8, 2, 3])).unwrap();
+///
+/// let mut reader = KeyReader::new(&key);
+/// let user: u64 = reader.next()?;
+/// assert_eq!(user, 7);
+/// assert_eq!(reader.remaining(), &lexcode::to_bytes(&("2024-01-01", vec![1u8, 2, 3]))?[..]);
+/// # Ok::<(), lexcode::Error>(())
+/// ```
+pub struct KeyReader<'de> {
+    deserializer: Deserializer<'de>,
+}
+
+impl<'de> KeyReader<'de> {
+    pub fn new(key: &'de [u8]) -> Self {
+        KeyReader { deserializer: Deserializer::from_bytes(key) }
+    }
+
+    /// Decode components with the options in `config`.
+    pub fn with_config(key: &'de [u8], config: LexConfig) -> Self {
+        KeyReader { deserializer: Deserializer::with_config(key, config) }
+    }
+
+    /// Decode the next component. After an error the reader's position is
+    /// unspecified.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T: Deserialize<'de>>(&mut self) -> Result<T> {
+        T::deserialize(&mut self.deserializer)
+    }
+
+    /// The bytes of the components not read yet.
+    pub fn remaining(&self) -> &'de [u8] {
+        self.deserializer.remaining()
+    }
+
+    /// Whether every component has been read.
+    pub fn is_empty(&self) -> bool {
+        self.remaining().is_empty()
+    }
+}
@@ -1,11 +1,156 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod as_bytes;
+pub mod as_fixed;
+mod ascii;
+mod bit_set;
+pub mod bound;
+mod bytes;
+mod case_insensitive;
+#[cfg(feature = "chrono")]
+pub mod chrono;
+#[cfg(feature = "ipnet")]
+mod cidr;
+mod codec;
+#[cfg(feature = "icu")]
+mod collated;
+mod compare;
+mod components;
+mod config;
 mod de;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+mod descending;
+mod encode;
 mod error;
 mod fixed_bytes;
+mod fixed_string;
+mod flags;
+pub mod frac;
+pub mod geo;
+#[cfg(feature = "half")]
+pub mod half;
+#[cfg(feature = "heed")]
+pub mod heed;
+mod index_key;
+#[cfg(feature = "indexmap")]
+pub mod indexmap;
+mod interleaved;
+mod interval;
+#[cfg(feature = "json")]
+pub mod json;
+mod key;
+mod key_builder;
+mod key_reader;
+mod magnitude;
+pub mod memcomparable;
+mod migrate;
+mod money;
+mod namespace;
+pub mod net;
+#[cfg(feature = "bigint")]
+pub mod num_bigint;
+mod numeric_string;
+mod option_last;
+pub mod option_non_zero;
+pub mod os;
+mod partition;
+mod path_key;
+mod pattern;
+mod project;
+#[cfg(feature = "ordered-float")]
+pub mod ordered_float;
+#[cfg(feature = "primitive-types")]
+pub mod primitive_types;
+mod range;
+#[cfg(feature = "redb")]
+pub mod redb;
+mod reverse_timestamp;
+mod reversed_domain;
+#[cfg(feature = "rocksdb")]
+pub mod rocksdb;
+mod scan;
+mod schema;
 mod ser;
-mod varint;
+mod sharded;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "storekey")]
+pub mod storekey;
+pub mod system_time;
+mod tail;
+mod tags;
+#[cfg(feature = "time")]
+pub mod time;
+mod time_bucket;
+#[cfg(feature = "transcode")]
+pub mod transcode;
+mod ulid;
+mod unescaped_str;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+mod value;
+pub mod varint;
+#[cfg(feature = "semver")]
+mod version_key;
 
+pub use ascii::{decode_ascii, encode_ascii, from_ascii_key, to_ascii_key};
+pub use bit_set::BitSet;
+pub use case_insensitive::CaseInsensitive;
+#[cfg(feature = "ipnet")]
+pub use cidr::CidrKey;
+#[cfg(feature = "icu")]
+pub use collated::Collated;
+pub use codec::{KeyCodec, LexCodec, ValueCodec};
+pub use compare::cmp_value_to_key;
+pub use components::{
+    common_prefix_len, compare_encoded, component_slices, decode_prefix, first_divergent_component, ComponentOrdering,
+};
+pub use config::{from_bytes_with_config, to_bytes_with_config, LexConfig};
 pub use de::{from_bytes, Deserializer};
+pub use descending::Descending;
+pub use encode::{decode, encode, max_encoded_len, Decode, Decoder, Encode, MaxEncodedLen};
 pub use error::{Error, Result};
 pub use fixed_bytes::FixedBytes;
-pub use ser::{to_bytes, Serializer};
+pub use fixed_string::FixedString;
+pub use flags::Flags;
+pub use index_key::IndexKey;
+pub use interleaved::{Interleave, Interleaved};
+pub use interval::Interval;
+pub use key::{EncodedKey, Key};
+pub use key_builder::KeyBuilder;
+pub use key_reader::KeyReader;
+pub use migrate::{reencode, FormatVersion};
+pub use money::Money;
+pub use namespace::Namespace;
+pub use numeric_string::NumericString;
+pub use option_last::OptionLast;
+pub use partition::{position, split_points};
+pub use path_key::PathKey;
+pub use pattern::KeyPattern;
+pub use project::{project, Project};
+pub use range::{midpoint, prefix_range, prefix_successor, range, range_contains, successor, truncate_for_bound, ByteRange};
+pub use reverse_timestamp::ReverseTimestamp;
+pub use reversed_domain::ReversedDomain;
+pub use scan::{Scan, ScanPlan};
+pub use schema::Schema;
+pub use ser::{to_bytes, NanPolicy, Serializer};
+pub use sharded::Sharded;
+pub use tail::{TailBytes, TailString};
+pub use tags::Tags;
+pub use time_bucket::{BucketTime, TimeBucket};
+pub use ulid::Ulid;
+pub use unescaped_str::UnescapedStr;
+pub use value::Value;
+pub use varint::const_encode_u64;
+#[cfg(feature = "semver")]
+pub use version_key::VersionKey;
+
+#[cfg(feature = "derive")]
+pub use lexcode_derive::{Decode, Encode, LexIndex, LexKey, LexTable, MaxEncodedLen};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+}
 
@@ -0,0 +1,64 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::fmt;
+
+use crate::fixed_bytes::{RawBytes, RawBytesSeed};
+
+/// An arbitrary-width unsigned integer, encoded length-graded: the number of
+/// big-endian magnitude bytes as a varint, then the bytes verbatim.
+///
+/// Leading zero bytes are stripped (zero has no bytes), so a longer
+/// encoding is always a larger number and equal values of any source type
+/// produce identical bytes.
+pub(crate) struct Magnitude(Vec<u8>);
+
+impl Magnitude {
+    pub(crate) fn from_be(bytes: &[u8]) -> Magnitude {
+        let skip = bytes.iter().take_while(|&&b| b == 0).count();
+        Magnitude(bytes[skip..].to_vec())
+    }
+
+    /// Big-endian bytes without leading zeros.
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Serialize for Magnitude {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&(self.0.len() as u64))?;
+        tuple.serialize_element(&RawBytes(&self.0))?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Magnitude {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MagnitudeVisitor;
+
+        impl<'de> Visitor<'de> for MagnitudeVisitor {
+            type Value = Magnitude;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a length-prefixed integer magnitude")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Magnitude, A::Error> {
+                let len: u64 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let len = usize::try_from(len).map_err(|_| de::Error::custom("integer magnitude too long"))?;
+                let bytes = seq
+                    .next_element_seed(RawBytesSeed(len))?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                if bytes.first() == Some(&0) {
+                    return Err(de::Error::custom("integer magnitude has a leading zero byte"));
+                }
+                Ok(Magnitude(bytes))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, MagnitudeVisitor)
+    }
+}
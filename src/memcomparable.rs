@@ -0,0 +1,609 @@
+//! The memcomparable key format of TiDB, for comparing lexcode keys with
+//! keys from TiDB (and MyRocks, whose string chunks are the same) or
+//! migrating them.
+//!
+//! Each value starts with a flag byte naming its type, followed by a
+//! fixed-width or self-delimiting body:
+//!
+//! | Rust type | Flag | Body |
+//! |---|---|---|
+//! | `Option::None` | `0x00` | none |
+//! | `str`, `String`, `char`, byte slices | `0x01` | the bytes in groups of 8, each followed by a marker byte (see [`encode_bytes`]) |
+//! | `i8`–`i64`, `bool` | `0x03` | 8 bytes big-endian, sign bit flipped |
+//! | `u8`–`u64`, unit enum variants | `0x04` | 8 bytes big-endian |
+//! | `f32`, `f64` | `0x05` | the `f64` bits, sign bit set if positive and all bits flipped if negative |
+//!
+//! `Some(v)` is `v`, and tuples, structs and newtype structs are their
+//! fields in order. Sequences, maps, enum variants with data and
+//! [`Descending`](crate::Descending) have no counterpart and fail to
+//! encode, as do 128-bit integers beyond 64 bits. As in TiDB, `-0.0`
+//! encodes as `0.0`.
+//!
+//! ```
+//! use lexcode::memcomparable;
+//!
+//! let tidb_key = memcomparable::to_bytes(&(42i64, "bob"))?;
+//! assert_eq!(tidb_key[..9], [0x03, 0x80, 0, 0, 0, 0, 0, 0, 42]);
+//! assert_eq!(tidb_key[9..], [0x01, b'b', b'o', b'b', 0, 0, 0, 0, 0, 0xFA]);
+//!
+//! let native = memcomparable::migrate::<(i64, String)>(&tidb_key)?;
+//! assert_eq!(native, lexcode::to_bytes(&(42i64, "bob"))?);
+//! # Ok::<(), lexcode::Error>(())
+//! ```
+
+use serde::de::{self, Deserialize, DeserializeSeed, IntoDeserializer, SeqAccess, Visitor};
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::error::{Error, Result};
+use crate::fixed_bytes::FIXED_BYTES_NAME;
+
+const NIL_FLAG: u8 = 0x00;
+const BYTES_FLAG: u8 = 0x01;
+const INT_FLAG: u8 = 0x03;
+const UINT_FLAG: u8 = 0x04;
+const FLOAT_FLAG: u8 = 0x05;
+
+const GROUP_SIZE: usize = 8;
+const MARKER: u8 = 0xFF;
+const SIGN_MASK: u64 = 1 << 63;
+
+/// Append the memcomparable encoding of `bytes`, without a flag: each
+/// group of 8 bytes, the last padded with `0x00`, followed by `0xFF` minus
+/// the number of padding bytes. A final group is always written, so input
+/// whose length is a multiple of 8 ends with a group of padding.
+///
+/// ```
+/// let mut out = Vec::new();
+/// lexcode::memcomparable::encode_bytes(&mut out, b"abc");
+/// assert_eq!(out, [b'a', b'b', b'c', 0, 0, 0, 0, 0, 0xFA]);
+/// ```
+pub fn encode_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.reserve((bytes.len() / GROUP_SIZE + 1) * (GROUP_SIZE + 1));
+    let mut chunks = bytes.chunks_exact(GROUP_SIZE);
+    for chunk in &mut chunks {
+        out.extend_from_slice(chunk);
+        out.push(MARKER);
+    }
+    let rest = chunks.remainder();
+    let pad = GROUP_SIZE - rest.len();
+    out.extend_from_slice(rest);
+    out.resize(out.len() + pad, 0x00);
+    out.push(MARKER - pad as u8);
+}
+
+/// Decode a memcomparable byte string at the start of `input`, returning
+/// it and the number of bytes read. Fails on a truncated group, a marker
+/// claiming more than 8 padding bytes, or padding that is not zero.
+pub fn decode_bytes(input: &[u8]) -> Result<(Vec<u8>, usize)> {
+    let mut bytes = Vec::new();
+    let mut read = 0;
+    loop {
+        let group = input.get(read..read + GROUP_SIZE + 1).ok_or(Error::Eof)?;
+        read += GROUP_SIZE + 1;
+        let pad = (MARKER - group[GROUP_SIZE]) as usize;
+        if pad > GROUP_SIZE {
+            return Err(Error::Message(format!("invalid memcomparable group marker {:#04x}", group[GROUP_SIZE])));
+        }
+        let (data, padding) = group[..GROUP_SIZE].split_at(GROUP_SIZE - pad);
+        if padding.iter().any(|&b| b != 0x00) {
+            return Err(Error::Message("memcomparable padding is not zero".into()));
+        }
+        bytes.extend_from_slice(data);
+        if pad > 0 {
+            return Ok((bytes, read));
+        }
+    }
+}
+
+/// Encode `value` in the memcomparable format.
+pub fn to_bytes<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    let mut serializer = Serializer { output: Vec::new(), fixed_bytes: false };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+/// Decode a `T` from memcomparable bytes. The input must contain exactly
+/// one encoded `T`.
+pub fn from_bytes<'a, T: Deserialize<'a>>(input: &'a [u8]) -> Result<T> {
+    let mut deserializer = Deserializer { input, fixed_bytes: false };
+    let value = T::deserialize(&mut deserializer)?;
+    if !deserializer.input.is_empty() {
+        return Err(Error::TrailingCharacters);
+    }
+    Ok(value)
+}
+
+/// Re-encode a memcomparable key as lexcode, by way of a `T`.
+pub fn migrate<'a, T>(input: &'a [u8]) -> Result<Vec<u8>>
+where
+    T: Deserialize<'a> + Serialize,
+{
+    crate::to_bytes(&from_bytes::<T>(input)?)
+}
+
+fn float_bits(value: f64) -> u64 {
+    let bits = value.to_bits();
+    if value >= 0.0 { bits | SIGN_MASK } else { !bits }
+}
+
+fn float_from_bits(bits: u64) -> f64 {
+    f64::from_bits(if bits & SIGN_MASK != 0 { bits & !SIGN_MASK } else { !bits })
+}
+
+struct Serializer {
+    output: Vec<u8>,
+    /// Set while the contents of a `FixedBytes` are written, which are
+    /// then their bytes rather than hex.
+    fixed_bytes: bool,
+}
+
+impl Serializer {
+    fn int(&mut self, value: i64) {
+        self.output.push(INT_FLAG);
+        self.output.extend_from_slice(&(value as u64 ^ SIGN_MASK).to_be_bytes());
+    }
+
+    fn uint(&mut self, value: u64) {
+        self.output.push(UINT_FLAG);
+        self.output.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn bytes(&mut self, value: &[u8]) {
+        self.output.push(BYTES_FLAG);
+        encode_bytes(&mut self.output, value);
+    }
+}
+
+fn unsupported(what: &str) -> Error {
+    Error::Message(format!("{what} cannot be encoded as a memcomparable key"))
+}
+
+impl ser::Serializer for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<()> {
+        self.int(v as i64);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<()> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<()> {
+        self.int(v);
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        let v = i64::try_from(v).map_err(|_| unsupported("an i128 outside the i64 range"))?;
+        self.serialize_i64(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<()> {
+        self.serialize_u64(v as u64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<()> {
+        self.uint(v);
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        let v = u64::try_from(v).map_err(|_| unsupported("a u128 outside the u64 range"))?;
+        self.serialize_u64(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<()> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<()> {
+        self.output.push(FLOAT_FLAG);
+        self.output.extend_from_slice(&float_bits(v).to_be_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<()> {
+        self.bytes(v.encode_utf8(&mut [0; 4]).as_bytes());
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<()> {
+        self.bytes(v.as_bytes());
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<()> {
+        self.bytes(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.output.push(NIL_FLAG);
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, variant_index: u32, _variant: &'static str) -> Result<()> {
+        self.serialize_u64(variant_index as u64)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, name: &'static str, value: &T) -> Result<()> {
+        if name == crate::descending::DESCENDING_NAME {
+            return Err(unsupported("a descending component"));
+        }
+        if name == FIXED_BYTES_NAME {
+            self.fixed_bytes = true;
+            let result = value.serialize(&mut *self);
+            self.fixed_bytes = false;
+            return result;
+        }
+        value.serialize(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        !self.fixed_bytes
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<()> {
+        Err(unsupported("an enum variant with data"))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(unsupported("a sequence"))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(unsupported("an enum variant with data"))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(unsupported("a map"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(unsupported("an enum variant with data"))
+    }
+}
+
+impl ser::SerializeTuple for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, _key: &'static str, value: &T) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct Deserializer<'de> {
+    input: &'de [u8],
+    /// As for [`Serializer`].
+    fixed_bytes: bool,
+}
+
+impl<'de> Deserializer<'de> {
+    fn take(&mut self, len: usize) -> Result<&'de [u8]> {
+        if self.input.len() < len {
+            return Err(Error::Eof);
+        }
+        let (head, rest) = self.input.split_at(len);
+        self.input = rest;
+        Ok(head)
+    }
+
+    fn flag(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn expect(&mut self, flag: u8, what: &str) -> Result<()> {
+        match self.flag()? {
+            found if found == flag => Ok(()),
+            found => Err(Error::Message(format!("expected {what}, found flag {found:#04x}"))),
+        }
+    }
+
+    fn word(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// An integer of either signedness.
+    fn integer(&mut self) -> Result<i128> {
+        match self.flag()? {
+            INT_FLAG => Ok((self.word()? ^ SIGN_MASK) as i64 as i128),
+            UINT_FLAG => Ok(self.word()? as i128),
+            found => Err(Error::Message(format!("expected an integer, found flag {found:#04x}"))),
+        }
+    }
+
+    fn bytes(&mut self) -> Result<Vec<u8>> {
+        self.expect(BYTES_FLAG, "a byte string")?;
+        let (bytes, read) = decode_bytes(self.input)?;
+        self.input = &self.input[read..];
+        Ok(bytes)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        String::from_utf8(self.bytes()?).map_err(|err| Error::Message(err.to_string()))
+    }
+}
+
+fn out_of_range<T>(value: i128) -> Error {
+    Error::Message(format!("{value} does not fit in {}", std::any::type_name::<T>()))
+}
+
+macro_rules! deserialize_integer {
+    ($($method:ident $t:ty => $visit:ident,)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            let value = self.integer()?;
+            visitor.$visit(<$t>::try_from(value).map_err(|_| out_of_range::<$t>(value))?)
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    /// The flags make the format self-describing for the types it has.
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.input.first() {
+            Some(&NIL_FLAG) => self.deserialize_option(visitor),
+            Some(&BYTES_FLAG) => self.deserialize_byte_buf(visitor),
+            Some(&INT_FLAG) => self.deserialize_i64(visitor),
+            Some(&UINT_FLAG) => self.deserialize_u64(visitor),
+            Some(&FLOAT_FLAG) => self.deserialize_f64(visitor),
+            Some(&flag) => Err(Error::Message(format!("unsupported memcomparable flag {flag:#04x}"))),
+            None => Err(Error::Eof),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.integer()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            other => Err(Error::Message(format!("{other} is not a boolean"))),
+        }
+    }
+
+    deserialize_integer! {
+        deserialize_i8 i8 => visit_i8,
+        deserialize_i16 i16 => visit_i16,
+        deserialize_i32 i32 => visit_i32,
+        deserialize_i64 i64 => visit_i64,
+        deserialize_i128 i128 => visit_i128,
+        deserialize_u8 u8 => visit_u8,
+        deserialize_u16 u16 => visit_u16,
+        deserialize_u32 u32 => visit_u32,
+        deserialize_u64 u64 => visit_u64,
+        deserialize_u128 u128 => visit_u128,
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.expect(FLOAT_FLAG, "a float")?;
+        visitor.visit_f32(float_from_bits(self.word()?) as f32)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.expect(FLOAT_FLAG, "a float")?;
+        visitor.visit_f64(float_from_bits(self.word()?))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let string = self.string()?;
+        let mut chars = string.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(Error::Message(format!("{string:?} is not a single character"))),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(self.string()?)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_string(self.string()?)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(self.bytes()?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_byte_buf(self.bytes()?)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if self.input.first() == Some(&NIL_FLAG) {
+            self.input = &self.input[1..];
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value> {
+        if name == crate::descending::DESCENDING_NAME {
+            return Err(unsupported("a descending component"));
+        }
+        if name == FIXED_BYTES_NAME {
+            self.fixed_bytes = true;
+            let result = visitor.visit_newtype_struct(&mut *self);
+            self.fixed_bytes = false;
+            return result;
+        }
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        !self.fixed_bytes
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(unsupported("a sequence"))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Fields { deserializer: self, remaining: len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(unsupported("a map"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        let index = self.integer()?;
+        let index = u32::try_from(index).map_err(|_| out_of_range::<u32>(index))?;
+        visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(index))
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_any(visitor)
+    }
+}
+
+struct Fields<'a, 'de> {
+    deserializer: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de> SeqAccess<'de> for Fields<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::de::Deserializer;
+use crate::error::Result;
+
+/// Revisions of the lexcode wire format.
+///
+/// Every format change bumps the version. Only [`V1`](FormatVersion::V1)
+/// exists, so nothing reads an older format yet: a deserializer for the
+/// previous format will ship together with the change that replaces it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum FormatVersion {
+  /// The initial format: varint integers and enum tags, sentinel-escaped
+  /// strings and byte slices, `0x01`/`0x00` framed sequences and maps.
+  V1,
+}
+
+impl FormatVersion {
+  /// The format written by [`to_bytes`](crate::to_bytes).
+  pub const CURRENT: FormatVersion = FormatVersion::V1;
+}
+
+impl Default for FormatVersion {
+  fn default() -> Self {
+    FormatVersion::CURRENT
+  }
+}
+
+/// Decode one `T` in the current format and encode it again.
+///
+/// This does not convert between format versions: with only
+/// [`FormatVersion::V1`] there is no other format to read. It rewrites a
+/// key whose type changed in a compatible way, such as a field widened
+/// from `u32` to `u64`, and checks that `input` is exactly one encoded `T`;
+/// trailing bytes are an error so that a wrong type parameter is not
+/// silently accepted.
+///
+/// ```
+/// let old = lexcode::to_bytes(&(7u32, "seven")).unwrap();
+/// let new = lexcode::reencode::<(u64, String)>(&old).unwrap();
+/// assert_eq!(new, lexcode::to_bytes(&(7u64, "seven")).unwrap());
+/// ```
+pub fn reencode<'a, T>(input: &'a [u8]) -> Result<Vec<u8>>
+where
+  T: Deserialize<'a> + Serialize,
+{
+  let mut deserializer = Deserializer::from_bytes(input);
+  let value = T::deserialize(&mut deserializer)?;
+  deserializer.end()?;
+  crate::to_bytes(&value)
+}
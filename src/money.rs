@@ -0,0 +1,102 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeTuple, Serializer};
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::fixed_bytes::FixedBytes;
+
+/// An amount of money in the minor units of an ISO 4217 currency.
+///
+/// Encoded as the three currency code bytes verbatim, then `minor_units` as
+/// a signed varint. Keys therefore group by currency and, within a
+/// currency, sort by amount; the derived `Ord` agrees.
+///
+/// The currency must be three ASCII uppercase letters. [`Money::new`] and
+/// deserialization check this, and serialization rejects a value whose
+/// public fields were set to anything else.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Money;
+///
+/// let small = Money::new("EUR", -500).unwrap();
+/// let large = Money::new("EUR", 12_000).unwrap();
+/// let other = Money::new("USD", -1_000_000).unwrap();
+/// let key = |m: &Money| lexcode::to_bytes(m).unwrap();
+/// assert!(key(&small) < key(&large) && key(&large) < key(&other));
+/// assert_eq!(lexcode::from_bytes::<Money>(&key(&small)).unwrap(), small);
+/// assert!(Money::new("eur", 1).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money {
+    pub currency: [u8; 3],
+    pub minor_units: i128,
+}
+
+impl Money {
+    pub fn new(currency: &str, minor_units: i128) -> Result<Money> {
+        let currency: [u8; 3] = currency
+            .as_bytes()
+            .try_into()
+            .map_err(|_| Error::Message(format!("currency code {currency:?} is not 3 letters")))?;
+        let money = Money { currency, minor_units };
+        money.validate()?;
+        Ok(money)
+    }
+
+    /// Check that the currency is three ASCII uppercase letters.
+    pub fn validate(&self) -> Result<()> {
+        if self.currency.iter().all(u8::is_ascii_uppercase) {
+            Ok(())
+        } else {
+            Err(Error::Message(format!(
+                "currency code {:?} is not 3 uppercase letters",
+                String::from_utf8_lossy(&self.currency)
+            )))
+        }
+    }
+
+    /// The currency code, e.g. `"EUR"`.
+    pub fn currency_code(&self) -> &str {
+        std::str::from_utf8(&self.currency).unwrap_or("???")
+    }
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        self.validate().map_err(ser::Error::custom)?;
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&FixedBytes(self.currency))?;
+        tuple.serialize_element(&self.minor_units)?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct MoneyVisitor;
+
+        impl<'de> Visitor<'de> for MoneyVisitor {
+            type Value = Money;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a currency code followed by an amount")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Money, A::Error> {
+                let FixedBytes(currency) = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let minor_units = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let money = Money { currency, minor_units };
+                money.validate().map_err(de::Error::custom)?;
+                Ok(money)
+            }
+        }
+
+        deserializer.deserialize_tuple(2, MoneyVisitor)
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::ops::Bound;
+
+use crate::de::from_bytes;
+use crate::error::{Error, Result};
+use crate::range::{prefix_successor, ByteRange};
+use crate::ser::to_bytes;
+
+/// A byte prefix that separates one part of a shared keyspace, such as a
+/// tenant or a table, from the rest.
+///
+/// [`encode`](Namespace::encode) writes the prefix before every key and
+/// [`decode`](Namespace::decode) checks and strips it again, so the
+/// prefix never leaks into the types being stored. The prefix is raw
+/// bytes; build it from an encoded value with
+/// [`of`](Namespace::of) so that namespaces sort like their ids and none
+/// is a prefix of another.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Namespace;
+///
+/// let tenant = Namespace::of(&"acme")?;
+/// let key = tenant.encode(&(7u64, "invoice"))?;
+/// assert!(key.starts_with(tenant.prefix()));
+///
+/// let (id, kind): (u64, String) = tenant.decode(&key)?;
+/// assert_eq!((id, kind.as_str()), (7, "invoice"));
+/// assert!(Namespace::of(&"other")?.decode::<(u64, String)>(&key).is_err());
+/// # Ok::<(), lexcode::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Namespace {
+    prefix: Vec<u8>,
+}
+
+impl Namespace {
+    /// A namespace with the given raw prefix.
+    pub fn new(prefix: impl Into<Vec<u8>>) -> Self {
+        Namespace { prefix: prefix.into() }
+    }
+
+    /// A namespace whose prefix is the encoding of `id`.
+    pub fn of<T>(id: &T) -> Result<Self>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(Namespace::new(to_bytes(&id)?))
+    }
+
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    /// The prefix followed by the encoding of `value`.
+    pub fn encode<T>(&self, value: &T) -> Result<Vec<u8>>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut key = self.prefix.clone();
+        key.extend(to_bytes(&value)?);
+        Ok(key)
+    }
+
+    /// Decode a key written by [`encode`](Namespace::encode). Fails if it
+    /// does not start with the prefix.
+    pub fn decode<'a, T>(&self, key: &'a [u8]) -> Result<T>
+    where
+        T: Deserialize<'a>,
+    {
+        let rest = self
+            .strip(key)
+            .ok_or_else(|| Error::Message("key is outside the namespace".into()))?;
+        from_bytes(rest)
+    }
+
+    /// `key` without the prefix, or `None` if it does not start with it.
+    pub fn strip<'a>(&self, key: &'a [u8]) -> Option<&'a [u8]> {
+        key.strip_prefix(self.prefix.as_slice())
+    }
+
+    /// The byte range holding every key in the namespace.
+    pub fn range(&self) -> ByteRange {
+        let end = prefix_successor(&self.prefix).map_or(Bound::Unbounded, Bound::Excluded);
+        (Bound::Included(self.prefix.clone()), end)
+    }
+}
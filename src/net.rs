@@ -0,0 +1,195 @@
+//! Compact order-preserving encodings for the `std::net` address types.
+//!
+//! lexcode reports itself as a human-readable format, so serde's own impls
+//! write addresses as text (`"10.0.0.2"`), which sorts `"10.0.0.10"` before
+//! `"10.0.0.2"`. Annotate fields with these modules to write them as raw
+//! big-endian bytes instead:
+//!
+//! | Module | Type | Encoding |
+//! |---|---|---|
+//! | `ipv4_addr` | `Ipv4Addr` | 4 raw bytes |
+//! | `ipv6_addr` | `Ipv6Addr` | 16 raw bytes |
+//! | `ip_addr` | `IpAddr` | `0x04` + 4 bytes, or `0x06` + 16 bytes |
+//! | `socket_addr` | `SocketAddr` | `ip_addr`, then the port as 2 bytes; V6 adds flow info and scope id (4 bytes each) |
+//!
+//! The byte order matches each type's `Ord`: every V4 address sorts before
+//! every V6 address, and socket addresses sort by address, then port.
+//!
+//! An IPv4-mapped IPv6 address such as `::ffff:192.0.2.1` is a V6 address
+//! and sorts among the V6 addresses, not next to `192.0.2.1`. Call
+//! [`IpAddr::to_canonical`] before encoding to store such addresses as V4.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use std::net::IpAddr;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Flow(#[serde(with = "lexcode::net::ip_addr")] IpAddr);
+//!
+//! let low = lexcode::to_bytes(&Flow("10.0.0.2".parse().unwrap())).unwrap();
+//! let high = lexcode::to_bytes(&Flow("10.0.0.10".parse().unwrap())).unwrap();
+//! assert!(low < high);
+//! assert_eq!(low, [0x04, 10, 0, 0, 2]);
+//! ```
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use crate::fixed_bytes::FixedBytes;
+
+const V4: u8 = 4;
+const V6: u8 = 6;
+
+/// An `IpAddr` in the tagged `ip_addr` encoding.
+pub(crate) struct Ip(pub IpAddr);
+
+impl Serialize for Ip {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        match self.0 {
+            IpAddr::V4(v4) => {
+                tuple.serialize_element(&V4)?;
+                tuple.serialize_element(&FixedBytes(v4.octets()))?;
+            }
+            IpAddr::V6(v6) => {
+                tuple.serialize_element(&V6)?;
+                tuple.serialize_element(&FixedBytes(v6.octets()))?;
+            }
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Ip {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IpVisitor;
+
+        impl<'de> Visitor<'de> for IpVisitor {
+            type Value = Ip;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an address family tag followed by an address")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Ip, A::Error> {
+                let tag: u8 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let ip = match tag {
+                    V4 => seq
+                        .next_element::<FixedBytes<4>>()?
+                        .map(|b| IpAddr::from(b.0)),
+                    V6 => seq
+                        .next_element::<FixedBytes<16>>()?
+                        .map(|b| IpAddr::from(b.0)),
+                    _ => return Err(de::Error::custom("invalid address family tag")),
+                };
+                ip.map(Ip).ok_or_else(|| de::Error::invalid_length(1, &self))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, IpVisitor)
+    }
+}
+
+/// `#[serde(with = "lexcode::net::ipv4_addr")]` for `Ipv4Addr`.
+pub mod ipv4_addr {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Ipv4Addr, serializer: S) -> Result<S::Ok, S::Error> {
+        FixedBytes(value.octets()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Ipv4Addr, D::Error> {
+        FixedBytes::<4>::deserialize(deserializer).map(|b| Ipv4Addr::from(b.0))
+    }
+}
+
+/// `#[serde(with = "lexcode::net::ipv6_addr")]` for `Ipv6Addr`.
+pub mod ipv6_addr {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Ipv6Addr, serializer: S) -> Result<S::Ok, S::Error> {
+        FixedBytes(value.octets()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Ipv6Addr, D::Error> {
+        FixedBytes::<16>::deserialize(deserializer).map(|b| Ipv6Addr::from(b.0))
+    }
+}
+
+/// `#[serde(with = "lexcode::net::ip_addr")]` for `IpAddr`.
+pub mod ip_addr {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &IpAddr, serializer: S) -> Result<S::Ok, S::Error> {
+        Ip(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<IpAddr, D::Error> {
+        Ip::deserialize(deserializer).map(|ip| ip.0)
+    }
+}
+
+/// `#[serde(with = "lexcode::net::socket_addr")]` for `SocketAddr`.
+pub mod socket_addr {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &SocketAddr, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            SocketAddr::V4(v4) => {
+                let mut tuple = serializer.serialize_tuple(2)?;
+                tuple.serialize_element(&Ip(IpAddr::V4(*v4.ip())))?;
+                tuple.serialize_element(&FixedBytes(v4.port().to_be_bytes()))?;
+                tuple.end()
+            }
+            SocketAddr::V6(v6) => {
+                let mut tuple = serializer.serialize_tuple(4)?;
+                tuple.serialize_element(&Ip(IpAddr::V6(*v6.ip())))?;
+                tuple.serialize_element(&FixedBytes(v6.port().to_be_bytes()))?;
+                tuple.serialize_element(&FixedBytes(v6.flowinfo().to_be_bytes()))?;
+                tuple.serialize_element(&FixedBytes(v6.scope_id().to_be_bytes()))?;
+                tuple.end()
+            }
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SocketAddr, D::Error> {
+        struct SocketAddrVisitor;
+
+        impl<'de> Visitor<'de> for SocketAddrVisitor {
+            type Value = SocketAddr;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an address followed by a port")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<SocketAddr, A::Error> {
+                let Ip(ip) = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let FixedBytes(port) = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let port = u16::from_be_bytes(port);
+                match ip {
+                    IpAddr::V4(ip) => Ok(SocketAddr::V4(SocketAddrV4::new(ip, port))),
+                    IpAddr::V6(ip) => {
+                        let FixedBytes(flowinfo) = seq
+                            .next_element()?
+                            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                        let FixedBytes(scope_id) = seq
+                            .next_element()?
+                            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                        let (flowinfo, scope_id) = (u32::from_be_bytes(flowinfo), u32::from_be_bytes(scope_id));
+                        Ok(SocketAddr::V6(SocketAddrV6::new(ip, port, flowinfo, scope_id)))
+                    }
+                }
+            }
+        }
+
+        deserializer.deserialize_tuple(4, SocketAddrVisitor)
+    }
+}
@@ -0,0 +1,126 @@
+//! Order-preserving encodings for [`num_bigint`](::num_bigint) integers of
+//! any size (feature `bigint`).
+//!
+//! Built-in integers stop at 128 bits. For wider values the magnitude is
+//! written length-graded: the number of big-endian magnitude bytes as a
+//! varint, then the bytes themselves verbatim. A longer magnitude is always
+//! larger, and equal lengths compare byte by byte.
+//!
+//! `BigInt` is prefixed with a sign byte (`0x00` negative, `0x01` zero,
+//! `0x02` positive); negative magnitudes are complemented like
+//! [`Descending`](crate::Descending) so that larger magnitudes sort first.
+//!
+//! ```
+//! use num_bigint::{BigInt, BigUint};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Balance {
+//!     #[serde(with = "lexcode::num_bigint::biguint")]
+//!     wei: BigUint,
+//!     #[serde(with = "lexcode::num_bigint::bigint")]
+//!     delta: BigInt,
+//! }
+//!
+//! let small = Balance { wei: BigUint::from(u128::MAX), delta: BigInt::from(-1) };
+//! let large = Balance { wei: BigUint::from(u128::MAX) + 1u8, delta: BigInt::from(-1) };
+//! assert!(lexcode::to_bytes(&small).unwrap() < lexcode::to_bytes(&large).unwrap());
+//! ```
+
+use ::num_bigint::{BigInt, BigUint, Sign};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::fmt;
+
+use crate::descending::Descending;
+use crate::magnitude::Magnitude;
+
+const NEGATIVE: u8 = 0;
+const ZERO: u8 = 1;
+const POSITIVE: u8 = 2;
+
+fn magnitude(value: &BigUint) -> Magnitude {
+    Magnitude::from_be(&value.to_bytes_be())
+}
+
+/// `#[serde(with = "lexcode::num_bigint::biguint")]` for `BigUint`.
+pub mod biguint {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &BigUint, serializer: S) -> Result<S::Ok, S::Error> {
+        magnitude(value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigUint, D::Error> {
+        let magnitude = Magnitude::deserialize(deserializer)?;
+        Ok(BigUint::from_bytes_be(magnitude.as_bytes()))
+    }
+}
+
+/// `#[serde(with = "lexcode::num_bigint::bigint")]` for `BigInt`.
+pub mod bigint {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+        let magnitude = magnitude(value.magnitude());
+        match value.sign() {
+            Sign::NoSign => {
+                let mut tuple = serializer.serialize_tuple(1)?;
+                tuple.serialize_element(&ZERO)?;
+                tuple.end()
+            }
+            Sign::Plus => {
+                let mut tuple = serializer.serialize_tuple(2)?;
+                tuple.serialize_element(&POSITIVE)?;
+                tuple.serialize_element(&magnitude)?;
+                tuple.end()
+            }
+            Sign::Minus => {
+                let mut tuple = serializer.serialize_tuple(2)?;
+                tuple.serialize_element(&NEGATIVE)?;
+                tuple.serialize_element(&Descending(magnitude))?;
+                tuple.end()
+            }
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigInt, D::Error> {
+        struct BigIntVisitor;
+
+        impl<'de> Visitor<'de> for BigIntVisitor {
+            type Value = BigInt;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sign tag followed by an integer magnitude")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BigInt, A::Error> {
+                let sign: u8 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let (sign, magnitude) = match sign {
+                    ZERO => return Ok(BigInt::ZERO),
+                    NEGATIVE => {
+                        let magnitude: Descending<Magnitude> = seq
+                            .next_element()?
+                            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                        (Sign::Minus, magnitude.0)
+                    }
+                    POSITIVE => {
+                        let magnitude: Magnitude = seq
+                            .next_element()?
+                            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                        (Sign::Plus, magnitude)
+                    }
+                    _ => return Err(de::Error::custom("invalid integer sign tag")),
+                };
+                if magnitude.as_bytes().is_empty() {
+                    return Err(de::Error::custom("signed integer has an empty magnitude"));
+                }
+                Ok(BigInt::from_bytes_be(sign, magnitude.as_bytes()))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, BigIntVisitor)
+    }
+}
@@ -0,0 +1,184 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::fixed_bytes::{RawBytes, RawBytesSeed};
+
+/// A string of ASCII digits that sorts by numeric value instead of
+/// lexicographically, so `"9" < "10" < "100"`.
+///
+/// The encoding is the number of significant digits as a varint, the
+/// significant digits verbatim, and finally the number of leading zeros as
+/// a varint. The original string round-trips exactly; strings with the
+/// same value but more leading zeros (`"7"`, `"07"`, `"007"`) sort one after
+/// the other. At most [`NumericString::MAX_DIGITS`] significant digits and
+/// [`NumericString::MAX_LEADING_ZEROS`] leading zeros are accepted, so a
+/// short key cannot decode into a huge string.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::NumericString;
+///
+/// let nine = lexcode::to_bytes(&NumericString::new("9").unwrap()).unwrap();
+/// let ten = lexcode::to_bytes(&NumericString::new("10").unwrap()).unwrap();
+/// assert!(nine < ten);
+///
+/// let decoded: NumericString = lexcode::from_bytes(&ten).unwrap();
+/// assert_eq!(decoded.as_str(), "10");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NumericString(String);
+
+impl NumericString {
+    /// The most significant digits a numeric string may have.
+    pub const MAX_DIGITS: usize = 1 << 16;
+
+    /// The most leading zeros a numeric string may have.
+    pub const MAX_LEADING_ZEROS: usize = 1024;
+
+    /// Wrap `s`, which must be a non-empty string of ASCII digits with at
+    /// most [`MAX_DIGITS`](Self::MAX_DIGITS) significant digits and
+    /// [`MAX_LEADING_ZEROS`](Self::MAX_LEADING_ZEROS) leading zeros.
+    pub fn new(s: impl Into<String>) -> Result<NumericString> {
+        let s = s.into();
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::Message(format!("{s:?} is not a non-empty string of ASCII digits")));
+        }
+        let numeric = NumericString(s);
+        if numeric.significant().len() > Self::MAX_DIGITS {
+            return Err(Error::Message(format!(
+                "numeric string has more than {} significant digits",
+                Self::MAX_DIGITS
+            )));
+        }
+        if numeric.leading_zeros() > Self::MAX_LEADING_ZEROS {
+            return Err(Error::Message(format!(
+                "numeric string has more than {} leading zeros",
+                Self::MAX_LEADING_ZEROS
+            )));
+        }
+        Ok(numeric)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    fn leading_zeros(&self) -> usize {
+        self.0.bytes().take_while(|&b| b == b'0').count()
+    }
+
+    fn significant(&self) -> &str {
+        &self.0[self.leading_zeros()..]
+    }
+}
+
+impl PartialOrd for NumericString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NumericString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (self.significant(), other.significant());
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.cmp(b))
+            .then_with(|| self.leading_zeros().cmp(&other.leading_zeros()))
+    }
+}
+
+impl fmt::Display for NumericString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for NumericString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for NumericString {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<NumericString> {
+        NumericString::new(s)
+    }
+}
+
+impl TryFrom<&str> for NumericString {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<NumericString> {
+        NumericString::new(s)
+    }
+}
+
+impl From<NumericString> for String {
+    fn from(s: NumericString) -> String {
+        s.0
+    }
+}
+
+impl Serialize for NumericString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let digits = self.significant();
+        let mut tuple = serializer.serialize_tuple(3)?;
+        tuple.serialize_element(&(digits.len() as u64))?;
+        tuple.serialize_element(&RawBytes(digits.as_bytes()))?;
+        tuple.serialize_element(&(self.leading_zeros() as u64))?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for NumericString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct NumericStringVisitor;
+
+        impl<'de> Visitor<'de> for NumericStringVisitor {
+            type Value = NumericString;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a digit count, digits and a leading zero count")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<NumericString, A::Error> {
+                let len: u64 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let len = usize::try_from(len)
+                    .ok()
+                    .filter(|&len| len <= NumericString::MAX_DIGITS)
+                    .ok_or_else(|| de::Error::custom("too many digits in numeric string"))?;
+                let digits = seq
+                    .next_element_seed(RawBytesSeed(len))?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let zeros: u64 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let zeros = usize::try_from(zeros)
+                    .ok()
+                    .filter(|&zeros| zeros <= NumericString::MAX_LEADING_ZEROS)
+                    .ok_or_else(|| de::Error::custom("too many leading zeros in numeric string"))?;
+                if !digits.iter().all(u8::is_ascii_digit) || digits.first() == Some(&b'0') {
+                    return Err(de::Error::custom("invalid digits in numeric string"));
+                }
+                let mut s = "0".repeat(zeros);
+                s.push_str(std::str::from_utf8(&digits).map_err(de::Error::custom)?);
+                NumericString::new(s).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_tuple(3, NumericStringVisitor)
+    }
+}
@@ -0,0 +1,111 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// An `Option` whose `None` sorts after every `Some`, like SQL's
+/// `NULLS LAST`.
+///
+/// A plain `Option<T>` encodes `None` as `0x00` and `Some(v)` as `0x01`
+/// followed by `v`. `OptionLast<T>` swaps the tags: `Some(v)` is `0x00`
+/// followed by `v`, and `None` is the single byte `0x01`.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::OptionLast;
+///
+/// let some = lexcode::to_bytes(&OptionLast(Some(u64::MAX))).unwrap();
+/// let none = lexcode::to_bytes(&OptionLast::<u64>(None)).unwrap();
+/// assert!(some < none);
+///
+/// let decoded: OptionLast<u64> = lexcode::from_bytes(&none).unwrap();
+/// assert_eq!(decoded, OptionLast(None));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct OptionLast<T>(pub Option<T>);
+
+impl<T: PartialOrd> PartialOrd for OptionLast<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => a.partial_cmp(b),
+            (Some(_), None) => Some(Ordering::Less),
+            (None, Some(_)) => Some(Ordering::Greater),
+            (None, None) => Some(Ordering::Equal),
+        }
+    }
+}
+
+impl<T: Ord> Ord for OptionLast<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for OptionLast<T> {
+    fn from(value: Option<T>) -> Self {
+        OptionLast(value)
+    }
+}
+
+impl<T> From<OptionLast<T>> for Option<T> {
+    fn from(value: OptionLast<T>) -> Self {
+        value.0
+    }
+}
+
+// The tag is written as a `bool` (`false` = `0x00` for `Some`, `true` =
+// `0x01` for `None`), followed by the payload when there is one.
+impl<T: Serialize> Serialize for OptionLast<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            Some(value) => {
+                let mut tuple = serializer.serialize_tuple(2)?;
+                tuple.serialize_element(&false)?;
+                tuple.serialize_element(value)?;
+                tuple.end()
+            }
+            None => {
+                let mut tuple = serializer.serialize_tuple(1)?;
+                tuple.serialize_element(&true)?;
+                tuple.end()
+            }
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for OptionLast<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OptionLastVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for OptionLastVisitor<T> {
+            type Value = OptionLast<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an option tag followed by an optional value")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<OptionLast<T>, A::Error> {
+                let is_none: bool = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                if is_none {
+                    return Ok(OptionLast(None));
+                }
+                let value = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                Ok(OptionLast(Some(value)))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, OptionLastVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,79 @@
+//! `#[serde(with = "lexcode::option_non_zero")]` for `Option<NonZeroU*>`.
+//!
+//! A plain `Option<NonZeroU64>` is a tag byte (`0x00` for `None`, `0x01`
+//! for `Some`) followed by the varint of the value. Since zero is never a
+//! valid `Some`, this helper writes the value alone and uses `0` for
+//! `None`, saving the tag byte. `None` still sorts before every `Some`, and
+//! `Some` values sort numerically, as with `Option`'s `Ord`.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use std::num::NonZeroU64;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Row {
+//!     #[serde(with = "lexcode::option_non_zero")]
+//!     parent: Option<NonZeroU64>,
+//! }
+//!
+//! assert_eq!(lexcode::to_bytes(&Row { parent: None }).unwrap(), [0x00]);
+//! assert_eq!(lexcode::to_bytes(&Row { parent: NonZeroU64::new(5) }).unwrap(), [0x05]);
+//! ```
+//!
+//! Only unsigned types are supported: a zero niche in a signed integer
+//! would sort `None` between the negative and positive values.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+
+/// Non-zero unsigned integers supported by this module.
+pub trait NonZero: Copy + sealed::Sealed {
+    /// The underlying integer type.
+    type Int: Serialize + for<'de> Deserialize<'de>;
+
+    /// The integer for `value`, with `0` for `None`.
+    fn to_int(value: Option<Self>) -> Self::Int;
+
+    /// The inverse of `to_int`.
+    fn from_int(int: Self::Int) -> Option<Self>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! non_zero {
+    ($($nz:ty => $int:ty),*) => {$(
+        impl sealed::Sealed for $nz {}
+
+        impl NonZero for $nz {
+            type Int = $int;
+
+            fn to_int(value: Option<Self>) -> $int {
+                value.map_or(0, <$nz>::get)
+            }
+
+            fn from_int(int: $int) -> Option<Self> {
+                <$nz>::new(int)
+            }
+        }
+    )*};
+}
+
+non_zero!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize
+);
+
+pub fn serialize<T: NonZero, S: Serializer>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+    T::to_int(*value).serialize(serializer)
+}
+
+pub fn deserialize<'de, T: NonZero, D: Deserializer<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+    T::Int::deserialize(deserializer).map(T::from_int)
+}
+
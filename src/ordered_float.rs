@@ -0,0 +1,99 @@
+//! Order-consistent encodings for the [`ordered_float`](::ordered_float)
+//! crate (feature `ordered-float`).
+//!
+//! `OrderedFloat<T>` and `NotNan<T>` serialize as their inner float, so they
+//! already round-trip through lexcode. Their `Ord` impls however treat every
+//! NaN as one value greater than `+inf`, and `-0.0` as equal to `+0.0`,
+//! while the plain float encoding keeps the raw bits (so a negative NaN
+//! sorts before `-inf`). Two ways make the encoding agree with `Ord`:
+//!
+//! - serialize with a [`Serializer`](crate::Serializer) configured with
+//!   `.nan_policy(NanPolicy::Canonicalize).normalize_zero(true)`, or
+//! - annotate fields with the helper modules below, which canonicalize
+//!   regardless of serializer settings:
+//!
+//! ```
+//! use ordered_float::{NotNan, OrderedFloat};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Key {
+//!     #[serde(with = "lexcode::ordered_float::ordered")]
+//!     score: OrderedFloat<f64>,
+//!     #[serde(with = "lexcode::ordered_float::not_nan")]
+//!     price: NotNan<f32>,
+//! }
+//!
+//! let nan = Key { score: OrderedFloat(-f64::NAN), price: NotNan::new(1.0).unwrap() };
+//! let inf = Key { score: OrderedFloat(f64::INFINITY), price: NotNan::new(1.0).unwrap() };
+//! assert!(lexcode::to_bytes(&inf).unwrap() < lexcode::to_bytes(&nan).unwrap());
+//! ```
+//!
+//! With either approach, the encoded order is exactly the `Ord` order and
+//! values equal under `Ord` produce identical bytes.
+
+use ::ordered_float::{FloatCore, NotNan, OrderedFloat};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Floats supported by the helper modules (`f32` and `f64`).
+pub trait KeyFloat: FloatCore + Serialize + for<'de> Deserialize<'de> + sealed::Sealed {
+    /// The positive quiet NaN for NaNs, `+0.0` for zeros, otherwise `self`.
+    fn canonical(self) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+impl KeyFloat for f32 {
+    fn canonical(self) -> f32 {
+        if self.is_nan() {
+            f32::from_bits(0x7FC0_0000)
+        } else if self == 0.0 {
+            0.0
+        } else {
+            self
+        }
+    }
+}
+
+impl KeyFloat for f64 {
+    fn canonical(self) -> f64 {
+        if self.is_nan() {
+            f64::from_bits(0x7FF8_0000_0000_0000)
+        } else if self == 0.0 {
+            0.0
+        } else {
+            self
+        }
+    }
+}
+
+/// `#[serde(with = "lexcode::ordered_float::ordered")]` for `OrderedFloat<T>`.
+pub mod ordered {
+    use super::*;
+
+    pub fn serialize<T: KeyFloat, S: Serializer>(value: &OrderedFloat<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.0.canonical().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: KeyFloat, D: Deserializer<'de>>(deserializer: D) -> Result<OrderedFloat<T>, D::Error> {
+        T::deserialize(deserializer).map(OrderedFloat)
+    }
+}
+
+/// `#[serde(with = "lexcode::ordered_float::not_nan")]` for `NotNan<T>`.
+pub mod not_nan {
+    use super::*;
+
+    pub fn serialize<T: KeyFloat, S: Serializer>(value: &NotNan<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        value.into_inner().canonical().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T: KeyFloat, D: Deserializer<'de>>(deserializer: D) -> Result<NotNan<T>, D::Error> {
+        let value = T::deserialize(deserializer)?;
+        NotNan::new(value).map_err(|_| de::Error::custom("NaN is not a valid NotNan"))
+    }
+}
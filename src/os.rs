@@ -0,0 +1,215 @@
+//! Order-preserving encodings for [`OsString`] and [`PathBuf`], including
+//! names that are not valid UTF-8.
+//!
+//! serde's impls are no good for keys: `PathBuf` serializes as a string and
+//! fails on non-UTF-8 paths, `OsString` serializes as a platform-tagged
+//! enum of code units, and `CString` goes through `serialize_bytes`, whose
+//! `0x7F` terminator does not preserve order. With these helper modules:
+//!
+//! - `os_string` and `c_string` write the bytes exactly like a `str` with
+//!   those bytes (`0x00` escaped, `0x00 0x00` terminator), which sorts like
+//!   `OsStr`'s and `CStr`'s `Ord`;
+//! - `path_buf` writes the path's components as a sequence (root, `.`,
+//!   `..`, or a name encoded like `os_string`), which sorts like `Path`'s
+//!   component-wise `Ord`. Redundant separators are not kept, matching
+//!   `Path` equality: `a//b/` decodes as `a/b`.
+//!
+//! On Unix every `OsStr` has a byte representation. Elsewhere only Unicode
+//! names are supported and others fail to serialize.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use std::path::PathBuf;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct File(#[serde(with = "lexcode::os::path_buf")] PathBuf);
+//!
+//! let child = lexcode::to_bytes(&File("/a/b/c".into())).unwrap();
+//! let sibling = lexcode::to_bytes(&File("/a/b-c".into())).unwrap();
+//! assert!(child < sibling);
+//! ```
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeSeq, Serializer};
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::path::{Component, PathBuf};
+
+use crate::bytes::Terminated;
+
+#[cfg(unix)]
+fn to_bytes(s: &OsStr) -> Option<Cow<'_, [u8]>> {
+    use std::os::unix::ffi::OsStrExt;
+    Some(Cow::Borrowed(s.as_bytes()))
+}
+
+#[cfg(not(unix))]
+fn to_bytes(s: &OsStr) -> Option<Cow<'_, [u8]>> {
+    s.to_str().map(|s| Cow::Borrowed(s.as_bytes()))
+}
+
+#[cfg(unix)]
+fn from_bytes(bytes: Vec<u8>) -> Option<OsString> {
+    use std::os::unix::ffi::OsStringExt;
+    Some(OsString::from_vec(bytes))
+}
+
+#[cfg(not(unix))]
+fn from_bytes(bytes: Vec<u8>) -> Option<OsString> {
+    String::from_utf8(bytes).ok().map(OsString::from)
+}
+
+fn serialize_os<S: Serializer>(s: &OsStr, serializer: S) -> Result<S::Ok, S::Error> {
+    let bytes = to_bytes(s).ok_or_else(|| ser::Error::custom("name is not valid Unicode"))?;
+    Terminated(bytes.into_owned()).serialize(serializer)
+}
+
+fn deserialize_os<'de, D: Deserializer<'de>>(deserializer: D) -> Result<OsString, D::Error> {
+    let Terminated(bytes) = Terminated::deserialize(deserializer)?;
+    from_bytes(bytes).ok_or_else(|| de::Error::custom("name is not valid Unicode"))
+}
+
+/// `#[serde(with = "lexcode::os::os_string")]` for `OsString`.
+pub mod os_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &OsStr, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_os(value, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<OsString, D::Error> {
+        deserialize_os(deserializer)
+    }
+}
+
+/// `#[serde(with = "lexcode::os::c_string")]` for `CString`.
+pub mod c_string {
+    use super::*;
+    use std::ffi::{CStr, CString};
+
+    pub fn serialize<S: Serializer>(value: &CStr, serializer: S) -> Result<S::Ok, S::Error> {
+        Terminated(value.to_bytes().to_vec()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CString, D::Error> {
+        let Terminated(bytes) = Terminated::deserialize(deserializer)?;
+        CString::new(bytes).map_err(de::Error::custom)
+    }
+}
+
+/// One path component, tagged in the order of `std::path::Component`.
+enum Part {
+    Prefix(OsString),
+    RootDir,
+    CurDir,
+    ParentDir,
+    Normal(OsString),
+}
+
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Name<'a>(&'a OsStr);
+
+        impl Serialize for Name<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_os(self.0, serializer)
+            }
+        }
+
+        match self {
+            Part::Prefix(name) => serializer.serialize_newtype_variant("Part", 0, "Prefix", &Name(name)),
+            Part::RootDir => serializer.serialize_unit_variant("Part", 1, "RootDir"),
+            Part::CurDir => serializer.serialize_unit_variant("Part", 2, "CurDir"),
+            Part::ParentDir => serializer.serialize_unit_variant("Part", 3, "ParentDir"),
+            Part::Normal(name) => serializer.serialize_newtype_variant("Part", 4, "Normal", &Name(name)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Name(OsString);
+
+        impl<'de> Deserialize<'de> for Name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserialize_os(deserializer).map(Name)
+            }
+        }
+
+        struct PartVisitor;
+
+        impl<'de> Visitor<'de> for PartVisitor {
+            type Value = Part;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a path component")
+            }
+
+            fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Part, A::Error> {
+                use de::VariantAccess;
+                let (index, variant): (u32, _) = data.variant()?;
+                match index {
+                    0 => variant.newtype_variant::<Name>().map(|n| Part::Prefix(n.0)),
+                    1 => variant.unit_variant().map(|()| Part::RootDir),
+                    2 => variant.unit_variant().map(|()| Part::CurDir),
+                    3 => variant.unit_variant().map(|()| Part::ParentDir),
+                    4 => variant.newtype_variant::<Name>().map(|n| Part::Normal(n.0)),
+                    _ => Err(de::Error::custom("invalid path component tag")),
+                }
+            }
+        }
+
+        const VARIANTS: &[&str] = &["Prefix", "RootDir", "CurDir", "ParentDir", "Normal"];
+        deserializer.deserialize_enum("Part", VARIANTS, PartVisitor)
+    }
+}
+
+/// `#[serde(with = "lexcode::os::path_buf")]` for `PathBuf`.
+pub mod path_buf {
+    use super::*;
+    use std::path::Path;
+
+    pub fn serialize<S: Serializer>(value: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for component in value.components() {
+            let part = match component {
+                Component::Prefix(prefix) => Part::Prefix(prefix.as_os_str().to_owned()),
+                Component::RootDir => Part::RootDir,
+                Component::CurDir => Part::CurDir,
+                Component::ParentDir => Part::ParentDir,
+                Component::Normal(name) => Part::Normal(name.to_owned()),
+            };
+            seq.serialize_element(&part)?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+        struct PathVisitor;
+
+        impl<'de> Visitor<'de> for PathVisitor {
+            type Value = PathBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence of path components")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PathBuf, A::Error> {
+                let mut path = PathBuf::new();
+                while let Some(part) = seq.next_element()? {
+                    match part {
+                        Part::Prefix(prefix) => path.push(prefix),
+                        Part::RootDir => path.push(Component::RootDir),
+                        Part::CurDir => path.push(Component::CurDir),
+                        Part::ParentDir => path.push(Component::ParentDir),
+                        Part::Normal(name) => path.push(name),
+                    }
+                }
+                Ok(path)
+            }
+        }
+
+        deserializer.deserialize_seq(PathVisitor)
+    }
+}
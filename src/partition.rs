@@ -0,0 +1,146 @@
+//! Dividing a key range into pieces, and locating keys within one, by
+//! interpolating over its bytes.
+
+use serde::Serialize;
+
+use crate::components::common_prefix_len;
+use crate::error::{Error, Result};
+use crate::ser::to_bytes;
+
+/// `n - 1` encoded keys splitting the range from `low` to `high` into `n`
+/// pieces, for handing out scan work across `n` workers.
+///
+/// The encodings are read as big-endian fractions and the points spaced
+/// evenly between them, so the pieces are even in byte space; how many
+/// keys each holds depends on how the real keys are spread. The points
+/// ascend strictly, lie strictly between the two encodings, and need not
+/// decode as a `T`. Fails if either endpoint cannot be encoded, if `low`
+/// does not encode below `high`, or if `high`'s encoding is `low`'s followed
+/// only by zero bytes, leaving no room to split.
+///
+/// ```
+/// let points = lexcode::split_points(&0u8, &100u8, 4).unwrap();
+/// assert_eq!(points, [vec![25], vec![50], vec![75]]);
+///
+/// let (low, high) = ((7u64, "a"), (7u64, "z"));
+/// let points = lexcode::split_points(&low, &high, 8).unwrap();
+/// assert_eq!(points.len(), 7);
+/// assert!(lexcode::to_bytes(&low).unwrap() < points[0]);
+/// assert!(points[6] < lexcode::to_bytes(&high).unwrap());
+/// ```
+pub fn split_points<T>(low: &T, high: &T, n: usize) -> Result<Vec<Vec<u8>>>
+where
+    T: Serialize,
+{
+    let (mut low, mut high) = (to_bytes(low)?, to_bytes(high)?);
+    if low >= high {
+        return Err(Error::Message("split range is empty".into()));
+    }
+    let len = low.len().max(high.len());
+    low.resize(len, 0x00);
+    high.resize(len, 0x00);
+    let mut span = sub(&high, &low);
+    if span.iter().all(|&byte| byte == 0x00) {
+        return Err(Error::Message("split range is too narrow to split".into()));
+    }
+    if n < 2 {
+        return Ok(Vec::new());
+    }
+    // Add bytes of precision until the points are distinct.
+    while less_than(&span, n as u128) {
+        span.push(0x00);
+        low.push(0x00);
+    }
+    let (step, rest) = div_rem(&span, n as u128);
+    Ok((1..n as u128)
+        .map(|k| add(&low, &mul_add(&step, k, rest * k / n as u128)))
+        .collect())
+}
+
+/// An estimate of where `key` falls between `low` and `high`, from `0.0`
+/// at `low` to `1.0` at `high`, for progress reporting or balancing a long
+/// scan.
+///
+/// As in [`split_points`], the encodings are read as big-endian fractions
+/// and `key` is placed linearly between the bounds, using up to 16 bytes
+/// after the prefix the bounds share. Keys outside the range are clamped
+/// to `0.0` or `1.0`.
+///
+/// ```
+/// let (low, high) = (lexcode::to_bytes(&0u8).unwrap(), lexcode::to_bytes(&100u8).unwrap());
+/// let key = lexcode::to_bytes(&25u8).unwrap();
+/// assert_eq!(lexcode::position(&key, &low, &high), 0.25);
+/// ```
+pub fn position(key: &[u8], low: &[u8], high: &[u8]) -> f64 {
+    if key <= low || low >= high {
+        return 0.0;
+    }
+    if key >= high {
+        return 1.0;
+    }
+    // Everything between the bounds starts with their common prefix.
+    let start = common_prefix_len(low, high);
+    let window = |bytes: &[u8]| {
+        (start..start + 16).fold(0u128, |acc, i| (acc << 8) | *bytes.get(i).unwrap_or(&0) as u128)
+    };
+    let (key, low, high) = (window(key), window(low), window(high));
+    if high == low {
+        return 0.0;
+    }
+    ((key - low) as f64 / (high - low) as f64).clamp(0.0, 1.0)
+}
+
+/// `a - b` for big-endian numbers of equal length with `a >= b`.
+fn sub(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut out = vec![0; a.len()];
+    let mut borrow = 0;
+    for i in (0..a.len()).rev() {
+        let diff = a[i] as i16 - b[i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        out[i] = diff.rem_euclid(0x100) as u8;
+    }
+    out
+}
+
+/// `a + b` for big-endian numbers of equal length whose sum fits.
+fn add(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut out = vec![0; a.len()];
+    let mut carry = 0;
+    for i in (0..a.len()).rev() {
+        let sum = a[i] as u16 + b[i] as u16 + carry;
+        out[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    out
+}
+
+/// `x * k + extra`, in the length of `x`; the caller ensures it fits.
+fn mul_add(x: &[u8], k: u128, extra: u128) -> Vec<u8> {
+    let mut out = vec![0; x.len()];
+    let mut carry = extra;
+    for i in (0..x.len()).rev() {
+        let product = x[i] as u128 * k + carry;
+        out[i] = product as u8;
+        carry = product >> 8;
+    }
+    out
+}
+
+fn div_rem(x: &[u8], n: u128) -> (Vec<u8>, u128) {
+    let mut rem = 0;
+    let quotient = x
+        .iter()
+        .map(|&byte| {
+            let acc = (rem << 8) | byte as u128;
+            rem = acc % n;
+            (acc / n) as u8
+        })
+        .collect();
+    (quotient, rem)
+}
+
+fn less_than(x: &[u8], n: u128) -> bool {
+    let split = x.len().saturating_sub(16);
+    x[..split].iter().all(|&byte| byte == 0x00)
+        && x[split..].iter().fold(0u128, |acc, &byte| (acc << 8) | byte as u128) < n
+}
@@ -0,0 +1,133 @@
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+use std::fmt;
+
+use crate::ser::to_bytes;
+
+/// A `/`-separated path that encodes as a sequence of components.
+///
+/// Encoded as a plain string, `/a/b/c` sorts after `/a/b-c` because `/` is
+/// greater than `-`, so the descendants of `/a/b` are not contiguous.
+/// `PathKey` encodes `["a", "b", "c"]` instead, which orders component by
+/// component: `/a/b` < `/a/b/c` < `/a/b-c` < `/a/c`. The key of every
+/// descendant of a path starts with its
+/// [`scan_prefix`](PathKey::scan_prefix).
+///
+/// Empty components are dropped, so `a//b/` is the same key as `/a/b`.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::PathKey;
+///
+/// let dir = PathKey::new("/a/b");
+/// let child = lexcode::to_bytes(&PathKey::new("/a/b/c")).unwrap();
+/// let sibling = lexcode::to_bytes(&PathKey::new("/a/b-c")).unwrap();
+/// assert!(child < sibling);
+/// assert!(child.starts_with(&dir.scan_prefix()));
+/// assert!(!sibling.starts_with(&dir.scan_prefix()));
+///
+/// let decoded: PathKey = lexcode::from_bytes(&child).unwrap();
+/// assert_eq!(decoded.as_str(), "/a/b/c");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathKey(String);
+
+impl PathKey {
+    pub fn new(path: impl AsRef<str>) -> PathKey {
+        PathKey::from_components(path.as_ref().split('/').filter(|c| !c.is_empty()))
+    }
+
+    fn from_components<'a>(components: impl IntoIterator<Item = &'a str>) -> PathKey {
+        let mut path = String::new();
+        for component in components {
+            path.push('/');
+            path.push_str(component);
+        }
+        if path.is_empty() {
+            path.push('/');
+        }
+        PathKey(path)
+    }
+
+    /// The normalized path, always starting with `/`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.0.split('/').filter(|c| !c.is_empty())
+    }
+
+    /// The encoding without its sequence terminator: the common prefix of
+    /// this path's key and the keys of all its descendants.
+    pub fn scan_prefix(&self) -> Vec<u8> {
+        let mut bytes = to_bytes(self).expect("serializing strings cannot fail");
+        bytes.pop();
+        bytes
+    }
+}
+
+impl fmt::Display for PathKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for PathKey {
+    fn from(path: &str) -> PathKey {
+        PathKey::new(path)
+    }
+}
+
+impl From<String> for PathKey {
+    fn from(path: String) -> PathKey {
+        PathKey::new(path)
+    }
+}
+
+impl PartialOrd for PathKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PathKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.components().cmp(other.components())
+    }
+}
+
+impl Serialize for PathKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for component in self.components() {
+            seq.serialize_element(component)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for PathKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PathKeyVisitor;
+
+        impl<'de> Visitor<'de> for PathKeyVisitor {
+            type Value = PathKey;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence of path components")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<PathKey, A::Error> {
+                let mut components: Vec<String> = Vec::new();
+                while let Some(component) = seq.next_element()? {
+                    components.push(component);
+                }
+                Ok(PathKey::from_components(components.iter().map(String::as_str)))
+            }
+        }
+
+        deserializer.deserialize_seq(PathKeyVisitor)
+    }
+}
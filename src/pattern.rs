@@ -0,0 +1,152 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::{Bound, RangeBounds};
+
+use crate::de::Deserializer;
+use crate::error::Result;
+use crate::range::ByteRange;
+use crate::ser::to_bytes;
+
+/// A filter on the leading components of encoded composite keys.
+///
+/// Each component is matched exactly, against a range, or not at all, in
+/// the order they were added. Matching works on the encoded bytes: an
+/// exact component is a byte comparison, and since encoding preserves
+/// order, a range is two byte comparisons against encoded bounds. No
+/// values are built, and matching stops at the first component that does
+/// not fit. Components after the last one in the pattern are ignored.
+///
+/// Ranges and wildcards take the component's type, needed to find where it
+/// ends in the key.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::KeyPattern;
+///
+/// // (42, _, "a".."m")
+/// let pattern = KeyPattern::new()
+///     .exact(&42u64)?
+///     .any::<i64>()
+///     .range("a".to_string().."m".to_string())?;
+///
+/// assert!(pattern.matches(&lexcode::to_bytes(&(42u64, -1i64, "dave"))?));
+/// assert!(!pattern.matches(&lexcode::to_bytes(&(42u64, -1i64, "zoe"))?));
+/// assert!(!pattern.matches(&lexcode::to_bytes(&(41u64, -1i64, "dave"))?));
+/// # Ok::<(), lexcode::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KeyPattern {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone)]
+enum Part {
+    Exact(Vec<u8>),
+    Any(Skip),
+    Range(ByteRange, Skip),
+}
+
+/// Length of the encoding of some type at the start of the input.
+type Skip = fn(&[u8]) -> Result<usize>;
+
+fn skip<T: DeserializeOwned>(input: &[u8]) -> Result<usize> {
+    let mut deserializer = Deserializer::from_bytes(input);
+    T::deserialize(&mut deserializer)?;
+    Ok(input.len() - deserializer.remaining().len())
+}
+
+impl KeyPattern {
+    pub fn new() -> Self {
+        KeyPattern::default()
+    }
+
+    /// Match a component equal to `value`.
+    pub fn exact<T>(mut self, value: &T) -> Result<Self>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.parts.push(Part::Exact(to_bytes(&value)?));
+        Ok(self)
+    }
+
+    /// Match any component of type `T`.
+    pub fn any<T: DeserializeOwned>(mut self) -> Self {
+        self.parts.push(Part::Any(skip::<T>));
+        self
+    }
+
+    /// Match a component of type `T` within `range`.
+    pub fn range<T, R>(mut self, range: R) -> Result<Self>
+    where
+        T: Serialize + DeserializeOwned,
+        R: RangeBounds<T>,
+    {
+        self.parts.push(Part::Range(crate::range::range(range)?, skip::<T>));
+        Ok(self)
+    }
+
+    /// The bytes every matching key starts with: the leading exact
+    /// components. Scanning [`prefix_range`](crate::prefix_range)-style
+    /// bounds around it and filtering with [`matches`](KeyPattern::matches)
+    /// visits only candidate keys.
+    pub fn prefix(&self) -> Vec<u8> {
+        self.parts
+            .iter()
+            .map_while(|part| match part {
+                Part::Exact(bytes) => Some(bytes.as_slice()),
+                _ => None,
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Whether `key` matches every component of the pattern. A key too
+    /// short or malformed to hold them does not match.
+    pub fn matches(&self, key: &[u8]) -> bool {
+        let mut rest = key;
+        for part in &self.parts {
+            let len = match part {
+                Part::Exact(bytes) => {
+                    // Encodings are prefix-free, so a component starting
+                    // with `bytes` is exactly `bytes`.
+                    if !rest.starts_with(bytes) {
+                        return false;
+                    }
+                    bytes.len()
+                }
+                Part::Any(skip) => match skip(rest) {
+                    Ok(len) => len,
+                    Err(_) => return false,
+                },
+                Part::Range((start, end), skip) => {
+                    let Ok(len) = skip(rest) else { return false };
+                    let component = &rest[..len];
+                    if !(after(component, start) && before(component, end)) {
+                        return false;
+                    }
+                    len
+                }
+            };
+            rest = &rest[len..];
+        }
+        true
+    }
+}
+
+fn after(component: &[u8], start: &Bound<Vec<u8>>) -> bool {
+    match start {
+        Bound::Included(start) => component >= start.as_slice(),
+        Bound::Excluded(start) => component > start.as_slice(),
+        Bound::Unbounded => true,
+    }
+}
+
+fn before(component: &[u8], end: &Bound<Vec<u8>>) -> bool {
+    match end {
+        Bound::Included(end) => component <= end.as_slice(),
+        Bound::Excluded(end) => component < end.as_slice(),
+        Bound::Unbounded => true,
+    }
+}
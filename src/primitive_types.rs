@@ -0,0 +1,53 @@
+//! Compact order-preserving encodings for the fixed-width unsigned
+//! integers of [`primitive_types`](::primitive_types) (feature
+//! `primitive-types`).
+//!
+//! Each value is written length-graded: the count of significant
+//! big-endian bytes as a varint, then those bytes verbatim. Small balances
+//! stay small (zero is one byte, `2^64` is ten) and byte order matches
+//! numeric order. The layout is the same as the `bigint` feature's, so a
+//! `U256` and a `BigUint` holding the same number encode identically.
+//!
+//! ```
+//! use primitive_types::U256;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Balance(#[serde(with = "lexcode::primitive_types::u256")] U256);
+//!
+//! let small = lexcode::to_bytes(&Balance(U256::from(255u8))).unwrap();
+//! let large = lexcode::to_bytes(&Balance(U256::MAX)).unwrap();
+//! assert_eq!(small.len(), 2);
+//! assert!(small < large);
+//! ```
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use crate::magnitude::Magnitude;
+
+macro_rules! uint_module {
+    ($module:ident, $ty:ident, $bytes:literal) => {
+        #[doc = concat!("`#[serde(with = \"lexcode::primitive_types::", stringify!($module), "\")]` for `", stringify!($ty), "`.")]
+        pub mod $module {
+            use super::*;
+            use ::primitive_types::$ty;
+
+            pub fn serialize<S: Serializer>(value: &$ty, serializer: S) -> Result<S::Ok, S::Error> {
+                Magnitude::from_be(&value.to_big_endian()).serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<$ty, D::Error> {
+                let magnitude = Magnitude::deserialize(deserializer)?;
+                if magnitude.as_bytes().len() > $bytes {
+                    return Err(de::Error::custom(concat!("integer does not fit in ", stringify!($ty))));
+                }
+                Ok($ty::from_big_endian(magnitude.as_bytes()))
+            }
+        }
+    };
+}
+
+uint_module!(u128, U128, 16);
+uint_module!(u256, U256, 32);
+uint_module!(u512, U512, 64);
@@ -0,0 +1,69 @@
+use serde::Deserialize;
+
+use crate::components::decode_prefix;
+use crate::error::Result;
+
+/// Access to field `N` of an encoded tuple or struct.
+///
+/// Implemented for tuples of up to eight elements, and for structs deriving
+/// `LexKey` with `#[lex(project)]` (with the `derive` feature). See
+/// [`project`].
+pub trait Project<const N: usize> {
+    /// The type of field `N`.
+    type Field;
+
+    /// Fields `0..=N` as they are encoded, as a type whose encoding is
+    /// those fields back to back.
+    type Prefix;
+
+    /// Take field `N` out of the decoded prefix.
+    fn pick(prefix: Self::Prefix) -> Self::Field;
+}
+
+/// Decode only field `N` of the `T` encoded in `key`.
+///
+/// Nothing after field `N` is read. The fields before it still have to be
+/// walked with their own types, since the encoding does not record where
+/// they end, but their values are dropped as soon as they are read.
+///
+/// # Example
+///
+/// ```
+/// let key = lexcode::to_bytes(&(7u64, "eu-west", vec![0u8; 64])).unwrap();
+/// let region: String = lexcode::project::<(u64, String, Vec<u8>), 1>(&key).unwrap();
+/// assert_eq!(region, "eu-west");
+/// ```
+pub fn project<'a, T, const N: usize>(key: &'a [u8]) -> Result<T::Field>
+where
+    T: Project<N>,
+    T::Prefix: Deserialize<'a>,
+{
+    decode_prefix::<T::Prefix>(key).map(T::pick)
+}
+
+// Implements `Project<N>` for the tuple `($all,)` and each listed `N`,
+// carrying the element types before it along as `$done`.
+macro_rules! project_tuple {
+    (($($all:ident)+) [$($done:ident)*] $n:tt $f:ident $(, $rest_n:tt $rest_f:ident)*) => {
+        impl<$($all),+> Project<$n> for ($($all,)+) {
+            type Field = $f;
+            type Prefix = ($($done,)* $f,);
+
+            fn pick(prefix: Self::Prefix) -> $f {
+                prefix.$n
+            }
+        }
+
+        project_tuple!(($($all)+) [$($done)* $f] $($rest_n $rest_f),*);
+    };
+    (($($all:ident)+) [$($done:ident)*]) => {};
+}
+
+project_tuple!((A) [] 0 A);
+project_tuple!((A B) [] 0 A, 1 B);
+project_tuple!((A B C) [] 0 A, 1 B, 2 C);
+project_tuple!((A B C D) [] 0 A, 1 B, 2 C, 3 D);
+project_tuple!((A B C D E) [] 0 A, 1 B, 2 C, 3 D, 4 E);
+project_tuple!((A B C D E F) [] 0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+project_tuple!((A B C D E F G) [] 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+project_tuple!((A B C D E F G H) [] 0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
@@ -0,0 +1,221 @@
+//! Byte bounds for range and prefix scans over encoded keys.
+
+use serde::Serialize;
+use std::ops::{Bound, RangeBounds};
+
+use crate::components::common_prefix_len;
+use crate::error::{Error, Result};
+use crate::ser::to_bytes;
+
+/// The smallest byte string strictly greater than `key`: `key` followed by
+/// `0x00`.
+///
+/// Use it as the exclusive upper bound of a scan that should include `key`
+/// itself but nothing after it, or as the exclusive lower bound's
+/// replacement when a store only offers inclusive starts.
+///
+/// ```
+/// assert_eq!(lexcode::successor(b"ab"), b"ab\0");
+/// assert_eq!(lexcode::successor(&[0xFF]), [0xFF, 0x00]);
+/// ```
+pub fn successor(key: &[u8]) -> Vec<u8> {
+    let mut next = Vec::with_capacity(key.len() + 1);
+    next.extend_from_slice(key);
+    next.push(0x00);
+    next
+}
+
+/// The smallest byte string greater than every string starting with
+/// `prefix`, or `None` if there is none (the prefix is empty or all
+/// `0xFF`), in which case the scan is unbounded above.
+///
+/// Trailing `0xFF` bytes are dropped and the last remaining byte is
+/// incremented, so `[prefix, prefix_successor(prefix))` covers exactly the
+/// keys starting with `prefix`.
+///
+/// ```
+/// assert_eq!(lexcode::prefix_successor(b"ab"), Some(b"ac".to_vec()));
+/// assert_eq!(lexcode::prefix_successor(&[0x01, 0xFF, 0xFF]), Some(vec![0x02]));
+/// assert_eq!(lexcode::prefix_successor(&[0xFF]), None);
+/// ```
+pub fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let end = prefix.iter().rposition(|&b| b != 0xFF)?;
+    let mut next = prefix[..=end].to_vec();
+    next[end] += 1;
+    Some(next)
+}
+
+/// The byte range `[start, end)` covering every key whose encoding begins
+/// with the encoding of `prefix`, such as a tuple prefix `(user_id,)` of a
+/// `(user_id, timestamp)` key.
+///
+/// `start` is the encoded prefix and `end` its [`prefix_successor`]. Fails
+/// if `prefix` cannot be encoded, or if its encoding is empty or all `0xFF`
+/// (possible with [`Descending`](crate::Descending)) and so has no upper
+/// bound; scan from `start` to the end of the keyspace in that case.
+///
+/// ```
+/// let (start, end) = lexcode::prefix_range(&(7u64,)).unwrap();
+/// let key = lexcode::to_bytes(&(7u64, "2024-01-01")).unwrap();
+/// assert!(start <= key && key < end);
+///
+/// let other = lexcode::to_bytes(&(8u64, "")).unwrap();
+/// assert!(other >= end);
+/// ```
+pub fn prefix_range<T>(prefix: &T) -> Result<(Vec<u8>, Vec<u8>)>
+where
+    T: Serialize,
+{
+    let start = to_bytes(prefix)?;
+    let end = prefix_successor(&start).ok_or_else(|| Error::Message("encoded prefix has no upper bound".into()))?;
+    Ok((start, end))
+}
+
+/// Bounds of at most `max_len` bytes around `key`: its first `max_len`
+/// bytes as the inclusive lower bound, and their [`prefix_successor`] as the
+/// exclusive upper bound, or `None` if the scan is unbounded above.
+///
+/// The range covers every key sharing the truncated prefix, so it never
+/// excludes `key` or anything that compares the same as it within
+/// `max_len` bytes. Use it for separator keys or fences in stores that cap
+/// key lengths.
+///
+/// ```
+/// let key = lexcode::to_bytes(&(7u64, "a long component")).unwrap();
+/// let (low, high) = lexcode::truncate_for_bound(&key, 4);
+/// let high = high.unwrap();
+/// assert!(low.len() <= 4 && high.len() <= 4);
+/// assert!(low <= key && key < high);
+/// ```
+pub fn truncate_for_bound(key: &[u8], max_len: usize) -> (Vec<u8>, Option<Vec<u8>>) {
+    let prefix = &key[..key.len().min(max_len)];
+    (prefix.to_vec(), prefix_successor(prefix))
+}
+
+/// A pair of byte bounds, as returned by [`range`]. It implements
+/// `RangeBounds<Vec<u8>>`.
+pub type ByteRange = (Bound<Vec<u8>>, Bound<Vec<u8>>);
+
+/// The byte bounds selecting the encodings of the values in `range`.
+///
+/// Encoding preserves order, so each bound maps to the encoded bound of
+/// the same kind. The result implements `RangeBounds<Vec<u8>>` and can be
+/// passed straight to `BTreeMap::range` or a store's range scan. Fails if
+/// either endpoint cannot be encoded.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// let map: BTreeMap<Vec<u8>, u64> =
+///     (0u64..100).map(|n| (lexcode::to_bytes(&n).unwrap(), n)).collect();
+///
+/// let hits: Vec<u64> = map.range(lexcode::range(10u64..=20).unwrap()).map(|(_, &n)| n).collect();
+/// assert_eq!(hits, (10..=20).collect::<Vec<_>>());
+/// ```
+pub fn range<T, R>(range: R) -> Result<ByteRange>
+where
+    T: Serialize,
+    R: RangeBounds<T>,
+{
+    Ok((encode_bound(range.start_bound())?, encode_bound(range.end_bound())?))
+}
+
+/// Whether `key` lies within `range`.
+///
+/// The same as `RangeBounds::contains`, but with the element type fixed to
+/// bytes, so it needs no annotations whatever other `PartialOrd` impls for
+/// `Vec<u8>` are in scope.
+///
+/// ```
+/// let range = lexcode::range(10u64..20).unwrap();
+/// assert!(lexcode::range_contains(&range, &lexcode::to_bytes(&15u64).unwrap()));
+/// assert!(!lexcode::range_contains(&range, &lexcode::to_bytes(&20u64).unwrap()));
+/// ```
+pub fn range_contains(range: &ByteRange, key: &[u8]) -> bool {
+    let above_start = match &range.0 {
+        Bound::Included(start) => start.as_slice() <= key,
+        Bound::Excluded(start) => start.as_slice() < key,
+        Bound::Unbounded => true,
+    };
+    let below_end = match &range.1 {
+        Bound::Included(end) => key <= end.as_slice(),
+        Bound::Excluded(end) => key < end.as_slice(),
+        Bound::Unbounded => true,
+    };
+    above_start && below_end
+}
+
+fn encode_bound<T: Serialize>(bound: Bound<&T>) -> Result<Bound<Vec<u8>>> {
+    Ok(match bound {
+        Bound::Included(value) => Bound::Included(to_bytes(value)?),
+        Bound::Excluded(value) => Bound::Excluded(to_bytes(value)?),
+        Bound::Unbounded => Bound::Unbounded,
+    })
+}
+
+/// A byte string strictly between `a` and `b`, roughly halfway, or `None`
+/// if there is none: when `a >= b`, or when `b` is `a` followed by `0x00`
+/// (see [`successor`]).
+///
+/// Useful for splitting a key range in two, or for placing an item
+/// between two neighbours in a user-ordered list. The result may be longer
+/// than either input when they are close.
+///
+/// ```
+/// assert_eq!(lexcode::midpoint(b"a", b"c"), Some(b"b".to_vec()));
+/// let mid = lexcode::midpoint(b"a", b"b").unwrap();
+/// assert!(b"a".as_slice() < mid.as_slice() && mid.as_slice() < b"b".as_slice());
+/// assert_eq!(lexcode::midpoint(b"a", b"a\0"), None);
+/// ```
+pub fn midpoint(a: &[u8], b: &[u8]) -> Option<Vec<u8>> {
+    if a >= b {
+        return None;
+    }
+    let common = common_prefix_len(a, b);
+    let mut mid = a[..common].to_vec();
+    let (a, b) = (&a[common..], &b[common..]);
+    match a.first() {
+        // `a` is a prefix of `b`: extend it with something below the rest
+        // of `b`.
+        None => mid.extend(below(b)?),
+        Some(&x) if b[0] - x >= 2 => mid.push(x + (b[0] - x) / 2),
+        // Adjacent bytes: keep `a`'s and go above the rest of `a`.
+        Some(&x) => {
+            mid.push(x);
+            mid.extend(above(&a[1..]));
+        }
+    }
+    Some(mid)
+}
+
+/// A non-empty byte string below `b`, if there is one.
+fn below(b: &[u8]) -> Option<Vec<u8>> {
+    let zeros = b.iter().take_while(|&&byte| byte == 0x00).count();
+    match b.get(zeros) {
+        Some(&byte) => {
+            let mut out = vec![0x00; zeros];
+            out.push(byte / 2);
+            Some(out)
+        }
+        // All zeros: drop one, unless that leaves nothing.
+        None if zeros >= 2 => Some(vec![0x00; zeros - 1]),
+        None => None,
+    }
+}
+
+/// A byte string above `a`, halfway between it and the end of the
+/// keyspace at its first byte below `0xFF`.
+fn above(a: &[u8]) -> Vec<u8> {
+    match a.iter().position(|&byte| byte != 0xFF) {
+        Some(i) => {
+            let mut out = a[..i].to_vec();
+            out.push(((a[i] as u16 + 0x100) / 2) as u8);
+            out
+        }
+        None => {
+            let mut out = a.to_vec();
+            out.push(0x80);
+            out
+        }
+    }
+}
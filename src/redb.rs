@@ -0,0 +1,100 @@
+//! lexcode-encoded keys and values for [redb](::redb) tables (feature
+//! `redb`).
+//!
+//! redb tables are typed by their key and value types, which say how to
+//! turn the stored bytes into values and, for keys, how to compare them.
+//! [`Lex<T>`] stores any `T` as its lexcode encoding and compares keys as
+//! bytes, so a table iterates in `T`'s order and range queries take typed
+//! bounds.
+//!
+//! ```
+//! use lexcode::redb::Lex;
+//! use redb::{Database, ReadableDatabase, TableDefinition};
+//!
+//! const EVENTS: TableDefinition<Lex<(u64, i64)>, Lex<String>> = TableDefinition::new("events");
+//!
+//! let db = Database::builder().create_with_backend(redb::backends::InMemoryBackend::new())?;
+//! let tx = db.begin_write()?;
+//! {
+//!     let mut table = tx.open_table(EVENTS)?;
+//!     table.insert((7, -5), "logout".to_string())?;
+//!     table.insert((7, 1700), "login".to_string())?;
+//!     table.insert((8, -9), "login".to_string())?;
+//! }
+//! tx.commit()?;
+//!
+//! let table = db.begin_read()?.open_table(EVENTS)?;
+//! let user_7: Vec<_> = table.range((7, i64::MIN)..(8, i64::MIN))?.map(|row| row.unwrap().0.value()).collect();
+//! assert_eq!(user_7, [(7, -5), (7, 1700)]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::redb::{Key, TypeName, Value};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use crate::de::from_bytes;
+use crate::ser::to_bytes;
+
+/// A redb key or value type storing a `T` as its lexcode encoding.
+///
+/// Reads and writes go through `T` itself: `insert` takes a `T` (or a
+/// reference to one) and `value()` returns an owned `T`. As redb's traits
+/// cannot fail, a `T` that does not encode, or stored bytes that do not
+/// decode, panic.
+///
+/// The redb type name is `lexcode::Lex<` followed by
+/// [`std::any::type_name`] of `T`, which redb checks when opening a table;
+/// renaming or moving `T` therefore needs the table reopened under the old
+/// name or migrated.
+#[derive(Debug)]
+pub struct Lex<T>(PhantomData<T>);
+
+impl<T> Value for Lex<T>
+where
+    T: Debug + Serialize + DeserializeOwned,
+{
+    type SelfType<'a>
+        = T
+    where
+        Self: 'a;
+    type AsBytes<'a>
+        = Vec<u8>
+    where
+        Self: 'a;
+
+    fn fixed_width() -> Option<usize> {
+        None
+    }
+
+    fn from_bytes<'a>(data: &'a [u8]) -> T
+    where
+        Self: 'a,
+    {
+        from_bytes(data).unwrap_or_else(|err| panic!("stored bytes are not a valid {}: {err}", std::any::type_name::<T>()))
+    }
+
+    fn as_bytes<'a, 'b: 'a>(value: &'a T) -> Vec<u8>
+    where
+        Self: 'b,
+    {
+        to_bytes(value).unwrap_or_else(|err| panic!("cannot encode {}: {err}", std::any::type_name::<T>()))
+    }
+
+    fn type_name() -> TypeName {
+        TypeName::new(&format!("lexcode::Lex<{}>", std::any::type_name::<T>()))
+    }
+}
+
+impl<T> Key for Lex<T>
+where
+    T: Debug + Serialize + DeserializeOwned,
+{
+    /// Encodings compare as the values they encode.
+    fn compare(data1: &[u8], data2: &[u8]) -> Ordering {
+        data1.cmp(data2)
+    }
+}
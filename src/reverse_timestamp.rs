@@ -0,0 +1,93 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::fixed_bytes::FixedBytes;
+
+/// A millisecond Unix timestamp that sorts latest first.
+///
+/// `ReverseTimestamp(t)` encodes as the 8 big-endian bytes of
+/// `u64::MAX - t`, so a forward scan over keys visits the newest entries
+/// first. Unlike `Descending<u64>`, the encoding is fixed-width, which keeps
+/// every key of a table the same length. Its `Ord` impl is reversed to
+/// match.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::ReverseTimestamp;
+///
+/// let older = lexcode::to_bytes(&ReverseTimestamp(1_700_000_000_000)).unwrap();
+/// let newer = lexcode::to_bytes(&ReverseTimestamp(1_700_000_000_001)).unwrap();
+/// assert!(newer < older);
+/// assert_eq!(newer.len(), 8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ReverseTimestamp(pub u64);
+
+impl ReverseTimestamp {
+    /// The current time.
+    pub fn now() -> ReverseTimestamp {
+        SystemTime::now().into()
+    }
+
+    pub fn from_millis(millis: u64) -> ReverseTimestamp {
+        ReverseTimestamp(millis)
+    }
+
+    /// Milliseconds since the Unix epoch.
+    pub fn as_millis(&self) -> u64 {
+        self.0
+    }
+}
+
+impl PartialOrd for ReverseTimestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ReverseTimestamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl From<u64> for ReverseTimestamp {
+    fn from(millis: u64) -> ReverseTimestamp {
+        ReverseTimestamp(millis)
+    }
+}
+
+impl From<ReverseTimestamp> for u64 {
+    fn from(value: ReverseTimestamp) -> u64 {
+        value.0
+    }
+}
+
+/// Truncates to whole milliseconds; times before the epoch become `0` and
+/// times past `u64::MAX` milliseconds saturate.
+impl From<SystemTime> for ReverseTimestamp {
+    fn from(time: SystemTime) -> ReverseTimestamp {
+        let millis = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis());
+        ReverseTimestamp(u64::try_from(millis).unwrap_or(u64::MAX))
+    }
+}
+
+impl From<ReverseTimestamp> for SystemTime {
+    fn from(value: ReverseTimestamp) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(value.0)
+    }
+}
+
+impl Serialize for ReverseTimestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        FixedBytes((u64::MAX - self.0).to_be_bytes()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ReverseTimestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        FixedBytes::<8>::deserialize(deserializer).map(|b| ReverseTimestamp(u64::MAX - u64::from_be_bytes(b.0)))
+    }
+}
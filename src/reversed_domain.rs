@@ -0,0 +1,125 @@
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+use std::fmt;
+
+use crate::ser::to_bytes;
+
+/// A domain name that encodes label by label from the top-level domain
+/// down, so all hosts under a domain are contiguous in the keyspace.
+///
+/// `"www.example.com"` encodes as the sequence `["com", "example", "www"]`.
+/// Sequence elements are prefix-free, so every subdomain of `example.com`
+/// (and `example.com` itself) starts with the bytes returned by
+/// [`scan_prefix`](ReversedDomain::scan_prefix), and nothing else does:
+/// `example.community` and `myexample.com` fall outside.
+///
+/// Names are compared case-insensitively in DNS, so ASCII letters are
+/// lowercased on construction and a trailing root dot is dropped.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::ReversedDomain;
+///
+/// let domain = ReversedDomain::new("Example.com.");
+/// let host = lexcode::to_bytes(&ReversedDomain::new("www.example.com")).unwrap();
+/// let other = lexcode::to_bytes(&ReversedDomain::new("www.example.community")).unwrap();
+/// assert!(host.starts_with(&domain.scan_prefix()));
+/// assert!(!other.starts_with(&domain.scan_prefix()));
+///
+/// let decoded: ReversedDomain = lexcode::from_bytes(&host).unwrap();
+/// assert_eq!(decoded.as_str(), "www.example.com");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReversedDomain(String);
+
+impl ReversedDomain {
+    pub fn new(domain: impl AsRef<str>) -> ReversedDomain {
+        let domain = domain.as_ref();
+        let domain = domain.strip_suffix('.').unwrap_or(domain);
+        ReversedDomain(domain.to_ascii_lowercase())
+    }
+
+    /// The domain in its usual dotted form.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Labels from the top-level domain down.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.0.rsplit('.').filter(|_| !self.0.is_empty())
+    }
+
+    /// The encoding without its sequence terminator: the common prefix of
+    /// this domain's key and the keys of all its subdomains.
+    pub fn scan_prefix(&self) -> Vec<u8> {
+        let mut bytes = to_bytes(self).expect("serializing strings cannot fail");
+        bytes.pop();
+        bytes
+    }
+}
+
+impl fmt::Display for ReversedDomain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for ReversedDomain {
+    fn from(domain: &str) -> ReversedDomain {
+        ReversedDomain::new(domain)
+    }
+}
+
+impl From<String> for ReversedDomain {
+    fn from(domain: String) -> ReversedDomain {
+        ReversedDomain::new(domain)
+    }
+}
+
+impl PartialOrd for ReversedDomain {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ReversedDomain {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.labels().cmp(other.labels())
+    }
+}
+
+impl Serialize for ReversedDomain {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for label in self.labels() {
+            seq.serialize_element(label)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ReversedDomain {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ReversedDomainVisitor;
+
+        impl<'de> Visitor<'de> for ReversedDomainVisitor {
+            type Value = ReversedDomain;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence of domain labels")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ReversedDomain, A::Error> {
+                let mut labels: Vec<String> = Vec::new();
+                while let Some(label) = seq.next_element()? {
+                    labels.push(label);
+                }
+                labels.reverse();
+                Ok(ReversedDomain(labels.join(".")))
+            }
+        }
+
+        deserializer.deserialize_seq(ReversedDomainVisitor)
+    }
+}
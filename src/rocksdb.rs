@@ -0,0 +1,141 @@
+//! Typed keys and values over [RocksDB](::rocksdb) (feature `rocksdb`).
+//!
+//! RocksDB sees keys and values as byte strings. These helpers put lexcode
+//! on both sides: [`read_options`] turns typed bounds into the iterator
+//! bounds of a `ReadOptions`, [`Iter`] decodes the pairs a RocksDB
+//! iterator yields, and [`scan`] combines the two. [`prefix_extractor`]
+//! cuts keys at a component boundary so that prefix bloom filters and
+//! prefix seeks work per leading component.
+//!
+//! ```no_run
+//! use rocksdb::{Options, DB};
+//!
+//! let mut options = Options::default();
+//! options.create_if_missing(true);
+//! // Keys are (user_id, timestamp); bloom filters cover the user id.
+//! options.set_prefix_extractor(lexcode::rocksdb::prefix_extractor::<u64>("user_id"));
+//! let db = DB::open(&options, "/tmp/events")?;
+//!
+//! db.put(lexcode::to_bytes(&(7u64, 1700i64))?, lexcode::to_bytes(&"login")?)?;
+//! for row in lexcode::rocksdb::scan::<(u64, i64), String, _>(&db, (7u64, 0i64)..(8, 0))? {
+//!     let ((user, ts), event) = row?;
+//!     println!("{user} {ts} {event}");
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::rocksdb::{DBIterator, IteratorMode, ReadOptions, SliceTransform, DB};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+
+use crate::de::{from_bytes, Deserializer};
+use crate::error::{Error, Result};
+use crate::range::successor;
+
+/// A prefix extractor cutting each key after the encoding of its leading
+/// `P`, such as the first field of a tuple key or a tuple of its first
+/// fields.
+///
+/// Keys that do not start with a `P` are outside the extractor's domain
+/// and skip the prefix bloom filter. RocksDB records `name` with the data
+/// and ignores the filters if it changes, so change `name` whenever `P`
+/// does.
+pub fn prefix_extractor<P: DeserializeOwned>(name: &str) -> SliceTransform {
+    SliceTransform::create(name, extract::<P>, Some(in_domain::<P>))
+}
+
+fn prefix_len<P: DeserializeOwned>(key: &[u8]) -> Option<usize> {
+    let mut deserializer = Deserializer::from_bytes(key);
+    P::deserialize(&mut deserializer).ok()?;
+    Some(key.len() - deserializer.remaining().len())
+}
+
+fn extract<P: DeserializeOwned>(key: &[u8]) -> &[u8] {
+    // Only called on keys in the domain.
+    &key[..prefix_len::<P>(key).unwrap_or(key.len())]
+}
+
+fn in_domain<P: DeserializeOwned>(key: &[u8]) -> bool {
+    prefix_len::<P>(key).is_some()
+}
+
+/// Read options bounding iteration to the encodings of the keys in
+/// `range`.
+///
+/// RocksDB's lower bound is inclusive and its upper bound exclusive, so an
+/// excluded start or an included end is moved to its [`successor`]. Fails
+/// if either endpoint cannot be encoded.
+pub fn read_options<K, R>(range: R) -> Result<ReadOptions>
+where
+    K: Serialize,
+    R: RangeBounds<K>,
+{
+    let (start, end) = crate::range::range(range)?;
+    let mut options = ReadOptions::default();
+    match start {
+        Bound::Included(start) => options.set_iterate_lower_bound(start),
+        Bound::Excluded(start) => options.set_iterate_lower_bound(successor(&start)),
+        Bound::Unbounded => {}
+    }
+    match end {
+        Bound::Included(end) => options.set_iterate_upper_bound(successor(&end)),
+        Bound::Excluded(end) => options.set_iterate_upper_bound(end),
+        Bound::Unbounded => {}
+    }
+    Ok(options)
+}
+
+/// The `(key, value)` pairs of `db` whose keys lie in `range`, decoded, in
+/// ascending key order.
+pub fn scan<K, V, R>(db: &DB, range: R) -> Result<Iter<K, V, DBIterator<'_>>>
+where
+    K: Serialize + DeserializeOwned,
+    V: DeserializeOwned,
+    R: RangeBounds<K>,
+{
+    Ok(Iter::new(db.iterator_opt(IteratorMode::Start, read_options(range)?)))
+}
+
+/// An iterator adapter decoding the raw pairs of a RocksDB iterator into
+/// `(K, V)`.
+///
+/// RocksDB errors and pairs that fail to decode are yielded as errors, and
+/// iteration carries on after them.
+pub struct Iter<K, V, I> {
+    inner: I,
+    marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V, I> Iter<K, V, I> {
+    pub fn new(inner: I) -> Self {
+        Iter { inner, marker: PhantomData }
+    }
+
+    /// The underlying RocksDB iterator.
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<K, V, I> Iterator for Iter<K, V, I>
+where
+    K: DeserializeOwned,
+    V: DeserializeOwned,
+    I: Iterator<Item = std::result::Result<(Box<[u8]>, Box<[u8]>), ::rocksdb::Error>>,
+{
+    type Item = Result<(K, V)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pair = match self.inner.next()? {
+            Ok(pair) => pair,
+            Err(err) => return Some(Err(Error::Message(err.to_string()))),
+        };
+        Some(decode_pair(&pair))
+    }
+}
+
+fn decode_pair<K: DeserializeOwned, V: DeserializeOwned>((key, value): &(Box<[u8]>, Box<[u8]>)) -> Result<(K, V)> {
+    Ok((from_bytes(key)?, from_bytes(value)?))
+}
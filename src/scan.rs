@@ -0,0 +1,304 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::marker::PhantomData;
+use std::ops::Bound;
+
+use crate::components::component_slices;
+use crate::error::Result;
+use crate::range::{prefix_successor, range_contains, ByteRange};
+use crate::ser::to_bytes;
+
+/// Predicates on the components of a composite key `K`, compiled into the
+/// byte ranges to scan and a filter for what the ranges cannot express.
+///
+/// Components are numbered from 0 in encoding order. Equalities on the
+/// leading components become key prefixes, and `one_of` multiplies them
+/// into one range per combination; bounds on the first component after
+/// those narrow each range. Predicates on later components cannot be
+/// expressed as contiguous ranges and are left to
+/// [`ScanPlan::matches`].
+///
+/// Values are compared as encodings, so they must encode like the
+/// component they constrain: the same type, or one encoding identically
+/// such as `&str` for `String`. On a [`Descending`](crate::Descending)
+/// component, pass `Descending` values; the bounds then apply in the
+/// component's sort order.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Scan;
+///
+/// let plan = Scan::<(u64, String, i64)>::on()
+///     .eq(0, &42u64)?
+///     .ge(1, "a")?
+///     .lt(1, "m")?
+///     .gt(2, &0i64)?
+///     .plan();
+/// assert_eq!(plan.ranges().len(), 1);
+///
+/// let key = lexcode::to_bytes(&(42u64, "dave", 7i64))?;
+/// assert!(plan.contains(&key) && plan.matches(&key));
+///
+/// let key = lexcode::to_bytes(&(42u64, "dave", -7i64))?;
+/// assert!(!plan.matches(&key));
+/// # Ok::<(), lexcode::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scan<K> {
+    constraints: BTreeMap<usize, Constraint>,
+    marker: PhantomData<fn() -> K>,
+}
+
+/// Everything known about one component, as encodings.
+#[derive(Debug, Clone)]
+struct Constraint {
+    /// The allowed values, if restricted to a set.
+    values: Option<BTreeSet<Vec<u8>>>,
+    start: Bound<Vec<u8>>,
+    end: Bound<Vec<u8>>,
+}
+
+impl Default for Constraint {
+    fn default() -> Self {
+        Constraint { values: None, start: Bound::Unbounded, end: Bound::Unbounded }
+    }
+}
+
+impl<K> Scan<K> {
+    /// A scan over every key.
+    pub fn on() -> Self {
+        Scan { constraints: BTreeMap::new(), marker: PhantomData }
+    }
+
+    /// Require component `field` to equal `value`.
+    pub fn eq<T>(self, field: usize, value: &T) -> Result<Self>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.one_of(field, [value])
+    }
+
+    /// Require component `field` to equal one of `values`.
+    pub fn one_of<'v, T, I>(mut self, field: usize, values: I) -> Result<Self>
+    where
+        T: ?Sized + Serialize + 'v,
+        I: IntoIterator<Item = &'v T>,
+    {
+        let values = values.into_iter().map(|value| to_bytes(&value)).collect::<Result<BTreeSet<_>>>()?;
+        let constraint = self.constraints.entry(field).or_default();
+        constraint.values = Some(match constraint.values.take() {
+            Some(old) => old.intersection(&values).cloned().collect(),
+            None => values,
+        });
+        Ok(self)
+    }
+
+    /// Require component `field` to be greater than `value`.
+    pub fn gt<T>(self, field: usize, value: &T) -> Result<Self>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(self.start(field, Bound::Excluded(to_bytes(&value)?)))
+    }
+
+    /// Require component `field` to be at least `value`.
+    pub fn ge<T>(self, field: usize, value: &T) -> Result<Self>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(self.start(field, Bound::Included(to_bytes(&value)?)))
+    }
+
+    /// Require component `field` to be less than `value`.
+    pub fn lt<T>(self, field: usize, value: &T) -> Result<Self>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(self.end(field, Bound::Excluded(to_bytes(&value)?)))
+    }
+
+    /// Require component `field` to be at most `value`.
+    pub fn le<T>(self, field: usize, value: &T) -> Result<Self>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(self.end(field, Bound::Included(to_bytes(&value)?)))
+    }
+
+    fn start(mut self, field: usize, bound: Bound<Vec<u8>>) -> Self {
+        let constraint = self.constraints.entry(field).or_default();
+        if !tighter(&constraint.start, &bound, true) {
+            constraint.start = bound;
+        }
+        self
+    }
+
+    fn end(mut self, field: usize, bound: Bound<Vec<u8>>) -> Self {
+        let constraint = self.constraints.entry(field).or_default();
+        if !tighter(&constraint.end, &bound, false) {
+            constraint.end = bound;
+        }
+        self
+    }
+
+    /// Compile the predicates into ranges and a residual filter.
+    ///
+    /// The number of ranges is the product of the sizes of the leading
+    /// `one_of` sets. Contradictory predicates give no ranges.
+    pub fn plan(&self) -> ScanPlan<K> {
+        let mut constraints = BTreeMap::new();
+        for (&field, constraint) in &self.constraints {
+            match constraint.normalize() {
+                Some(constraint) => constraints.insert(field, constraint),
+                None => return ScanPlan { ranges: Vec::new(), residual: BTreeMap::new(), marker: PhantomData },
+            };
+        }
+
+        // Extend the prefixes through the leading components with a fixed
+        // set of values.
+        let mut prefixes = vec![Vec::new()];
+        let mut field = 0;
+        while let Some(values) = constraints.get(&field).and_then(|c| c.values.as_ref()) {
+            prefixes = prefixes
+                .iter()
+                .flat_map(|prefix| values.iter().map(move |value| [prefix.as_slice(), value].concat()))
+                .collect();
+            field += 1;
+        }
+        let (start, end) = match constraints.get(&field) {
+            Some(constraint) => {
+                field += 1;
+                (constraint.start.clone(), constraint.end.clone())
+            }
+            None => (Bound::Unbounded, Bound::Unbounded),
+        };
+        let ranges = prefixes.iter().filter_map(|prefix| bounded(prefix, &start, &end)).collect();
+        let residual = constraints.split_off(&field);
+        ScanPlan { ranges, residual, marker: PhantomData }
+    }
+}
+
+impl<K> Default for Scan<K> {
+    fn default() -> Self {
+        Scan::on()
+    }
+}
+
+/// Byte ranges to scan for a [`Scan`], and the predicates left to check on
+/// the keys they return.
+#[derive(Debug, Clone)]
+pub struct ScanPlan<K> {
+    ranges: Vec<ByteRange>,
+    residual: BTreeMap<usize, Constraint>,
+    marker: PhantomData<fn() -> K>,
+}
+
+impl<K> ScanPlan<K> {
+    /// The ranges to scan, ascending and disjoint. Every matching key lies
+    /// in one of them.
+    pub fn ranges(&self) -> &[ByteRange] {
+        &self.ranges
+    }
+
+    /// Whether `key` lies in one of the [`ranges`](ScanPlan::ranges).
+    pub fn contains(&self, key: &[u8]) -> bool {
+        self.ranges.iter().any(|range| range_contains(range, key))
+    }
+
+    /// Whether the ranges alone select exactly the matching keys, so
+    /// [`matches`](ScanPlan::matches) need not be called.
+    pub fn is_exact(&self) -> bool {
+        self.residual.is_empty()
+    }
+}
+
+impl<K: DeserializeOwned> ScanPlan<K> {
+    /// Whether a key from one of the [`ranges`](ScanPlan::ranges) meets the
+    /// predicates the ranges could not express. Keys that do not decode as
+    /// a `K` do not match.
+    pub fn matches(&self, key: &[u8]) -> bool {
+        if self.residual.is_empty() {
+            return true;
+        }
+        let Ok(components) = component_slices::<K>(key) else { return false };
+        self.residual.iter().all(|(&field, constraint)| {
+            components.get(field).is_some_and(|component| constraint.contains(component))
+        })
+    }
+}
+
+impl Constraint {
+    /// Fold the bounds into the value set, or `None` if nothing satisfies
+    /// the constraint.
+    fn normalize(&self) -> Option<Constraint> {
+        match &self.values {
+            Some(values) => {
+                let values: BTreeSet<_> = values.iter().filter(|v| self.contains(v)).cloned().collect();
+                (!values.is_empty()).then(|| Constraint { values: Some(values), ..Constraint::default() })
+            }
+            None => {
+                let empty = match (&self.start, &self.end) {
+                    (Bound::Included(s), Bound::Included(e)) => s > e,
+                    (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) => s >= e,
+                    _ => false,
+                };
+                (!empty).then(|| self.clone())
+            }
+        }
+    }
+
+    fn contains(&self, component: &[u8]) -> bool {
+        let after = match &self.start {
+            Bound::Included(s) => component >= s.as_slice(),
+            Bound::Excluded(s) => component > s.as_slice(),
+            Bound::Unbounded => true,
+        };
+        let before = match &self.end {
+            Bound::Included(e) => component <= e.as_slice(),
+            Bound::Excluded(e) => component < e.as_slice(),
+            Bound::Unbounded => true,
+        };
+        after && before && self.values.as_ref().is_none_or(|values| values.contains(component))
+    }
+}
+
+/// Whether bound `a` already excludes everything `b` does, for lower
+/// bounds if `lower` and upper bounds otherwise.
+fn tighter(a: &Bound<Vec<u8>>, b: &Bound<Vec<u8>>, lower: bool) -> bool {
+    let (a_value, a_excl) = match a {
+        Bound::Included(v) => (v, false),
+        Bound::Excluded(v) => (v, true),
+        Bound::Unbounded => return matches!(b, Bound::Unbounded),
+    };
+    let (b_value, b_excl) = match b {
+        Bound::Included(v) => (v, false),
+        Bound::Excluded(v) => (v, true),
+        Bound::Unbounded => return true,
+    };
+    match a_value.cmp(b_value) {
+        std::cmp::Ordering::Equal => a_excl || !b_excl,
+        std::cmp::Ordering::Greater => lower,
+        std::cmp::Ordering::Less => !lower,
+    }
+}
+
+/// The range of keys starting with `prefix` whose next component lies
+/// between `start` and `end`, or `None` if there are none.
+fn bounded(prefix: &[u8], start: &Bound<Vec<u8>>, end: &Bound<Vec<u8>>) -> Option<ByteRange> {
+    // Encodings are prefix-free, so a component equal to `v` spans exactly
+    // the keys starting with `prefix + v`.
+    let start = match start {
+        Bound::Unbounded if prefix.is_empty() => Bound::Unbounded,
+        Bound::Unbounded => Bound::Included(prefix.to_vec()),
+        Bound::Included(v) => Bound::Included([prefix, v].concat()),
+        Bound::Excluded(v) => Bound::Included(prefix_successor(&[prefix, v].concat())?),
+    };
+    let end = match end {
+        Bound::Unbounded => prefix_successor(prefix).map_or(Bound::Unbounded, Bound::Excluded),
+        Bound::Excluded(v) => Bound::Excluded([prefix, v].concat()),
+        Bound::Included(v) => prefix_successor(&[prefix, v].concat()).map_or(Bound::Unbounded, Bound::Excluded),
+    };
+    Some((start, end))
+}
@@ -0,0 +1,479 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
+
+use crate::de::Deserializer;
+use crate::error::{Error, Result};
+use crate::fixed_bytes::FIXED_BYTES_NAME;
+use crate::value::Value;
+
+/// The shape of an encoded value, used to decode bytes into a [`Value`]
+/// without a Rust type.
+///
+/// Schemas have a compact textual form, parsed with [`str::parse`]:
+///
+/// | Syntax | Meaning |
+/// |---|---|
+/// | `unit`, `bool`, `char`, `str`, `bytes` | the corresponding scalar |
+/// | `u8` … `u128`, `i8` … `i128`, `f32`, `f64` | numbers |
+/// | `fixed<N>` | `FixedBytes<N>` |
+/// | `option<T>` | `Option<T>` |
+/// | `[T]` | a sequence of `T` |
+/// | `(A, B, …)` | a tuple or struct with the given fields |
+/// | `{K: V}` | a map |
+/// | `enum<A, B, …>` | an enum whose variant *i* carries payload *i* |
+///
+/// ```
+/// use lexcode::{Schema, Value};
+///
+/// let schema: Schema = "(u64, str, option<i32>)".parse().unwrap();
+/// let bytes = lexcode::to_bytes(&(7u64, "seven", Some(-7i32))).unwrap();
+/// assert_eq!(
+///     schema.decode(&bytes).unwrap(),
+///     Value::Tuple(vec![
+///         Value::UInt(7),
+///         Value::String("seven".into()),
+///         Value::Option(Some(Box::new(Value::Int(-7)))),
+///     ]),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Schema {
+    Unit,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    F32,
+    F64,
+    Char,
+    Str,
+    Bytes,
+    Fixed(usize),
+    Option(Box<Schema>),
+    Seq(Box<Schema>),
+    Tuple(Vec<Schema>),
+    Map(Box<Schema>, Box<Schema>),
+    Enum(Vec<Schema>),
+}
+
+impl Schema {
+    /// Decode exactly one value of this shape from `bytes`.
+    pub fn decode(&self, bytes: &[u8]) -> Result<Value> {
+        let mut deserializer = Deserializer::from_bytes(bytes);
+        let value = self.deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(value)
+    }
+
+    /// Reject an `option<unit>` or `option<option<T>>` anywhere in this
+    /// schema.
+    ///
+    /// Formats with a single null, such as JSON or Python's `None`, cannot
+    /// tell `None` from `Some(())` or `Some(None)` apart, so front ends that
+    /// convert from them call this before encoding or decoding.
+    ///
+    /// ```
+    /// use lexcode::Schema;
+    ///
+    /// let schema: Schema = "[option<option<u8>>]".parse().unwrap();
+    /// assert!(schema.check_unambiguous().is_err());
+    /// let schema: Schema = "option<(unit, u8)>".parse().unwrap();
+    /// assert!(schema.check_unambiguous().is_ok());
+    /// ```
+    pub fn check_unambiguous(&self) -> Result<()> {
+        match self {
+            Schema::Option(inner) if matches!(**inner, Schema::Unit | Schema::Option(_)) => {
+                Err(Error::Message(format!("{self} is ambiguous: its None and Some would both be null")))
+            }
+            Schema::Option(inner) | Schema::Seq(inner) => inner.check_unambiguous(),
+            Schema::Tuple(items) | Schema::Enum(items) => items.iter().try_for_each(Schema::check_unambiguous),
+            Schema::Map(key, value) => {
+                key.check_unambiguous()?;
+                value.check_unambiguous()
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// ── Decoding ───────────────────────────────────────────────────────────
+
+impl<'de> DeserializeSeed<'de> for &Schema {
+    type Value = Value;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> std::result::Result<Value, D::Error> {
+        let visitor = ValueVisitor { schema: self };
+        match self {
+            Schema::Unit => deserializer.deserialize_unit(visitor),
+            Schema::Bool => deserializer.deserialize_bool(visitor),
+            Schema::U8 => deserializer.deserialize_u8(visitor),
+            Schema::U16 => deserializer.deserialize_u16(visitor),
+            Schema::U32 => deserializer.deserialize_u32(visitor),
+            Schema::U64 => deserializer.deserialize_u64(visitor),
+            Schema::U128 => deserializer.deserialize_u128(visitor),
+            Schema::I8 => deserializer.deserialize_i8(visitor),
+            Schema::I16 => deserializer.deserialize_i16(visitor),
+            Schema::I32 => deserializer.deserialize_i32(visitor),
+            Schema::I64 => deserializer.deserialize_i64(visitor),
+            Schema::I128 => deserializer.deserialize_i128(visitor),
+            Schema::F32 => deserializer.deserialize_f32(visitor),
+            Schema::F64 => deserializer.deserialize_f64(visitor),
+            Schema::Char => deserializer.deserialize_char(visitor),
+            Schema::Str => deserializer.deserialize_string(visitor),
+            Schema::Bytes => deserializer.deserialize_byte_buf(visitor),
+            Schema::Fixed(n) => deserializer.deserialize_tuple_struct(FIXED_BYTES_NAME, *n, visitor),
+            Schema::Option(_) => deserializer.deserialize_option(visitor),
+            Schema::Seq(_) => deserializer.deserialize_seq(visitor),
+            Schema::Tuple(fields) => deserializer.deserialize_tuple(fields.len(), visitor),
+            Schema::Map(_, _) => deserializer.deserialize_map(visitor),
+            Schema::Enum(_) => deserializer.deserialize_enum("Value", &[], visitor),
+        }
+    }
+}
+
+struct ValueVisitor<'s> {
+    schema: &'s Schema,
+}
+
+impl<'de> Visitor<'de> for ValueVisitor<'_> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a value of shape {}", self.schema)
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> std::result::Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<Value, E> {
+        Ok(Value::UInt(v as u128))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> std::result::Result<Value, E> {
+        Ok(Value::UInt(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> std::result::Result<Value, E> {
+        Ok(Value::Int(v as i128))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> std::result::Result<Value, E> {
+        Ok(Value::Int(v))
+    }
+
+    fn visit_f32<E: de::Error>(self, v: f32) -> std::result::Result<Value, E> {
+        Ok(Value::F32(v))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> std::result::Result<Value, E> {
+        Ok(Value::F64(v))
+    }
+
+    fn visit_char<E: de::Error>(self, v: char) -> std::result::Result<Value, E> {
+        Ok(Value::Char(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Value, E> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> std::result::Result<Value, E> {
+        match self.schema {
+            Schema::Fixed(n) if v.len() == *n => Ok(Value::Fixed(v.to_vec())),
+            Schema::Fixed(_) => Err(E::invalid_length(v.len(), &self)),
+            _ => Ok(Value::Bytes(v.to_vec())),
+        }
+    }
+
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<Value, E> {
+        Ok(Value::Unit)
+    }
+
+    fn visit_none<E: de::Error>(self) -> std::result::Result<Value, E> {
+        Ok(Value::Option(None))
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> std::result::Result<Value, D::Error> {
+        let Schema::Option(inner) = self.schema else {
+            return Err(de::Error::invalid_type(de::Unexpected::Option, &self));
+        };
+        let value = (&**inner).deserialize(deserializer)?;
+        Ok(Value::Option(Some(Box::new(value))))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Value, A::Error> {
+        match self.schema {
+            Schema::Fixed(n) => {
+                let mut bytes = Vec::with_capacity(*n);
+                for i in 0..*n {
+                    let byte = seq.next_element::<u8>()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                    bytes.push(byte);
+                }
+                Ok(Value::Fixed(bytes))
+            }
+            Schema::Bytes => {
+                let mut bytes = Vec::new();
+                while let Some(byte) = seq.next_element::<u8>()? {
+                    bytes.push(byte);
+                }
+                Ok(Value::Bytes(bytes))
+            }
+            Schema::Seq(inner) => {
+                let mut items = Vec::new();
+                while let Some(item) = seq.next_element_seed(&**inner)? {
+                    items.push(item);
+                }
+                Ok(Value::Seq(items))
+            }
+            Schema::Tuple(fields) => {
+                let mut items = Vec::with_capacity(fields.len());
+                for (i, field) in fields.iter().enumerate() {
+                    let item = seq.next_element_seed(field)?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+                    items.push(item);
+                }
+                Ok(Value::Tuple(items))
+            }
+            _ => Err(de::Error::invalid_type(de::Unexpected::Seq, &self)),
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<Value, A::Error> {
+        let Schema::Map(key, value) = self.schema else {
+            return Err(de::Error::invalid_type(de::Unexpected::Map, &self));
+        };
+        let mut entries = Vec::new();
+        while let Some(k) = map.next_key_seed(&**key)? {
+            let v = map.next_value_seed(&**value)?;
+            entries.push((k, v));
+        }
+        Ok(Value::Map(entries))
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> std::result::Result<Value, A::Error> {
+        let Schema::Enum(variants) = self.schema else {
+            return Err(de::Error::invalid_type(de::Unexpected::Enum, &self));
+        };
+        let (index, variant) = data.variant_seed(VariantIndex)?;
+        let payload = variants.get(index as usize).ok_or_else(|| {
+            de::Error::invalid_value(de::Unexpected::Unsigned(index as u64), &self)
+        })?;
+        let value = variant.newtype_variant_seed(payload)?;
+        Ok(Value::Variant(index, Box::new(value)))
+    }
+}
+
+/// An enum variant index, also accepted as a string of digits, the form
+/// it takes as a map key in formats such as JSON and CBOR.
+struct VariantIndex;
+
+impl<'de> DeserializeSeed<'de> for VariantIndex {
+    type Value = u32;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> std::result::Result<u32, D::Error> {
+        deserializer.deserialize_u32(self)
+    }
+}
+
+impl<'de> Visitor<'de> for VariantIndex {
+    type Value = u32;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a variant index")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> std::result::Result<u32, E> {
+        u32::try_from(v).map_err(|_| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<u32, E> {
+        v.parse().map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
+
+// ── Text form ──────────────────────────────────────────────────────────
+
+impl Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn list(f: &mut fmt::Formatter, items: &[Schema]) -> fmt::Result {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{item}")?;
+            }
+            Ok(())
+        }
+
+        match self {
+            Schema::Unit => f.write_str("unit"),
+            Schema::Bool => f.write_str("bool"),
+            Schema::U8 => f.write_str("u8"),
+            Schema::U16 => f.write_str("u16"),
+            Schema::U32 => f.write_str("u32"),
+            Schema::U64 => f.write_str("u64"),
+            Schema::U128 => f.write_str("u128"),
+            Schema::I8 => f.write_str("i8"),
+            Schema::I16 => f.write_str("i16"),
+            Schema::I32 => f.write_str("i32"),
+            Schema::I64 => f.write_str("i64"),
+            Schema::I128 => f.write_str("i128"),
+            Schema::F32 => f.write_str("f32"),
+            Schema::F64 => f.write_str("f64"),
+            Schema::Char => f.write_str("char"),
+            Schema::Str => f.write_str("str"),
+            Schema::Bytes => f.write_str("bytes"),
+            Schema::Fixed(n) => write!(f, "fixed<{n}>"),
+            Schema::Option(inner) => write!(f, "option<{inner}>"),
+            Schema::Seq(inner) => write!(f, "[{inner}]"),
+            Schema::Tuple(fields) => {
+                f.write_str("(")?;
+                list(f, fields)?;
+                f.write_str(")")
+            }
+            Schema::Map(k, v) => write!(f, "{{{k}: {v}}}"),
+            Schema::Enum(variants) => {
+                f.write_str("enum<")?;
+                list(f, variants)?;
+                f.write_str(">")
+            }
+        }
+    }
+}
+
+impl FromStr for Schema {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Schema> {
+        let mut parser = Parser { input: s, pos: 0 };
+        let schema = parser.schema()?;
+        parser.skip_ws();
+        if parser.pos != s.len() {
+            return Err(parser.error("trailing input"));
+        }
+        Ok(schema)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, what: &str) -> Error {
+        Error::Message(format!("invalid schema at offset {}: {what}", self.pos))
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_ws();
+        self.input[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) { Ok(()) } else { Err(self.error(&format!("expected `{c}`"))) }
+    }
+
+    fn word(&mut self) -> &'a str {
+        self.skip_ws();
+        let rest: &'a str = &self.input[self.pos..];
+        let len = rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Comma-separated schemas up to (and including) `close`.
+    fn list(&mut self, close: char) -> Result<Vec<Schema>> {
+        let mut items = Vec::new();
+        while !self.eat(close) {
+            items.push(self.schema()?);
+            if !self.eat(',') {
+                self.expect(close)?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn schema(&mut self) -> Result<Schema> {
+        if self.eat('[') {
+            let inner = self.schema()?;
+            self.expect(']')?;
+            return Ok(Schema::Seq(Box::new(inner)));
+        }
+        if self.eat('(') {
+            return Ok(Schema::Tuple(self.list(')')?));
+        }
+        if self.eat('{') {
+            let key = self.schema()?;
+            self.expect(':')?;
+            let value = self.schema()?;
+            self.expect('}')?;
+            return Ok(Schema::Map(Box::new(key), Box::new(value)));
+        }
+        let start = self.pos;
+        let schema = match self.word() {
+            "unit" => Schema::Unit,
+            "bool" => Schema::Bool,
+            "u8" => Schema::U8,
+            "u16" => Schema::U16,
+            "u32" => Schema::U32,
+            "u64" => Schema::U64,
+            "u128" => Schema::U128,
+            "i8" => Schema::I8,
+            "i16" => Schema::I16,
+            "i32" => Schema::I32,
+            "i64" => Schema::I64,
+            "i128" => Schema::I128,
+            "f32" => Schema::F32,
+            "f64" => Schema::F64,
+            "char" => Schema::Char,
+            "str" | "string" => Schema::Str,
+            "bytes" => Schema::Bytes,
+            "fixed" => {
+                self.expect('<')?;
+                let n = self.word().parse().map_err(|_| self.error("expected a length"))?;
+                self.expect('>')?;
+                Schema::Fixed(n)
+            }
+            "option" => {
+                self.expect('<')?;
+                let inner = self.schema()?;
+                self.expect('>')?;
+                Schema::Option(Box::new(inner))
+            }
+            "enum" => {
+                self.expect('<')?;
+                Schema::Enum(self.list('>')?)
+            }
+            _ => {
+                self.pos = start;
+                return Err(self.error("unknown type"));
+            }
+        };
+        Ok(schema)
+    }
+}
@@ -1,23 +1,194 @@
 use serde::{ser, Serialize};
 
+use crate::config::LexConfig;
 use crate::error::{Error, Result};
-use crate::varint;
+use crate::varint::{self, VarintFormat};
 
 pub struct Serializer {
     output: Vec<u8>,
     raw_byte_mode: bool,
+    /// Set while an element of a tuple or array is written with
+    /// `raw_byte_arrays` on, until the element's first call.
+    array_element: bool,
+    config: LexConfig,
+    /// For each map being written when `sort_maps` or `canonical` is on,
+    /// the start of each entry and the end of its key.
+    map_entries: Vec<Vec<(usize, usize)>>,
+    /// Byte range of the most recently written string, sequence or map
+    /// terminator, if it has not been complemented since.
+    terminator: Option<(usize, usize)>,
+    /// Set while the contents of a `Descending` are written.
+    descending: bool,
+}
+
+/// How the serializer treats NaN floats.
+///
+/// IEEE 754 has many NaN bit patterns, and by default each is encoded as
+/// is, so two NaNs may produce different keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NanPolicy {
+    /// Encode the raw bits of the NaN.
+    #[default]
+    Raw,
+    /// Encode every NaN as the positive quiet NaN (`0x7FC00000` /
+    /// `0x7FF8000000000000`), which sorts after `+inf`.
+    Canonicalize,
+    /// Fail with an error.
+    Reject,
+}
+
+impl Serializer {
+  pub fn new() -> Self {
+    Serializer::with_config(LexConfig::default())
+  }
+
+  /// Create a serializer with all the options in `config`.
+  pub fn with_config(config: LexConfig) -> Self {
+    Serializer {
+      output: Vec::new(),
+      raw_byte_mode: false,
+      array_element: false,
+      config,
+      map_entries: Vec::new(),
+      terminator: None,
+      descending: false,
+    }
+  }
+
+  /// Set how NaN floats are encoded.
+  pub fn nan_policy(mut self, policy: NanPolicy) -> Self {
+    self.config.nan_policy = policy;
+    self
+  }
+
+  /// Encode `-0.0` as `+0.0`, so that floats which compare equal also
+  /// encode identically. Off by default.
+  pub fn normalize_zero(mut self, normalize: bool) -> Self {
+    self.config.normalize_zero = normalize;
+    self
+  }
+
+  /// Reject NaN and infinite floats with an error instead of encoding them.
+  /// Takes precedence over the NaN policy. Off by default.
+  pub fn finite_only(mut self, finite_only: bool) -> Self {
+    self.config.finite_only = finite_only;
+    self
+  }
+
+  /// Apply Unicode NFC normalization to every string before encoding it,
+  /// so canonically equivalent text (such as a precomposed `"é"` and `"e"`
+  /// followed by a combining acute accent) produces identical keys. Decoding
+  /// yields the normalized text. Off by default; requires the `nfc` feature.
+  #[cfg(feature = "nfc")]
+  pub fn normalize_nfc(mut self, normalize: bool) -> Self {
+    self.config.normalize_nfc = normalize;
+    self
+  }
+
+  /// Drop the terminator of a string, sequence or map that ends the
+  /// output, saving up to two bytes per key. Byte strings keep theirs, as
+  /// their `0x7F` sentinel sorts above other bytes and dropping it would
+  /// change their order. Keys still sort the same, but
+  /// must be decoded by a `Deserializer` with the matching
+  /// [`omit_trailing_terminator`](crate::Deserializer::omit_trailing_terminator)
+  /// option. A terminator inside `Descending` is always kept. Off by
+  /// default.
+  pub fn omit_trailing_terminator(mut self, omit: bool) -> Self {
+    self.config.omit_trailing_terminator = omit;
+    self
+  }
+
+  /// Write map entries sorted by their encoded keys instead of in
+  /// iteration order, so a `HashMap` produces the same canonical bytes as
+  /// a `BTreeMap` with the same entries. Entries are buffered in the output
+  /// and reordered when the map ends. Off by default.
+  pub fn sort_maps(mut self, sort: bool) -> Self {
+    self.config.sort_maps = sort;
+    self
+  }
+
+  /// Fail on values whose encoding is not deterministic instead of writing
+  /// them: any NaN float (whatever the NaN policy), and maps whose entries
+  /// do not arrive in strictly ascending key order, such as most
+  /// `HashMap`s. Meant for CI runs that guard a production keyspace.
+  /// Combined with `sort_maps`, maps are sorted rather than rejected. Off
+  /// by default.
+  pub fn canonical(mut self, canonical: bool) -> Self {
+    self.config.canonical = canonical;
+    self
+  }
+
+  /// Encode `None` as `0x01` and `Some(v)` as `0x00` followed by `v`, so
+  /// that every `Option` sorts its `None` last, like SQL's `NULLS LAST` and
+  /// [`OptionLast`](crate::OptionLast). Keys must be decoded with the same
+  /// option. Off by default.
+  pub fn none_last(mut self, none_last: bool) -> Self {
+    self.config.none_last = none_last;
+    self
+  }
+
+  /// Write a `u8` that is directly an element of a tuple or array, such as
+  /// each byte of a `[u8; N]`, as one raw byte instead of a varint, so a
+  /// 32-byte hash takes 32 bytes without a [`FixedBytes`](crate::FixedBytes)
+  /// wrapper. Bytes in tuple structs, structs, sequences and `Option`s
+  /// are unaffected. Keys must be decoded with the same option. Off by
+  /// default.
+  pub fn raw_byte_arrays(mut self, raw: bool) -> Self {
+    self.config.raw_byte_arrays = raw;
+    self
+  }
+
+  /// Write unsigned integers (`u8` to `u128`) as SQLite4 varints instead
+  /// of lexcode's, to share a keyspace laid out for SQLite4; see
+  /// [`VarintFormat`]. Keys must be decoded with the same format.
+  /// [`VarintFormat::Lexcode`] by default.
+  pub fn varint_format(mut self, format: VarintFormat) -> Self {
+    self.config.varint_format = format;
+    self
+  }
+
+  /// Write an unsigned integer value in the configured varint format.
+  fn write_unsigned(&mut self, v: u64) {
+    match self.config.varint_format {
+      VarintFormat::Lexcode => varint::encode_u64(v, &mut self.output),
+      VarintFormat::Sqlite4 => varint::encode_sqlite4(v, &mut self.output),
+    }
+  }
+
+  /// Write an enum variant index. Always a lexcode varint, whatever the
+  /// [`VarintFormat`], as the deserializer reads it as one.
+  fn write_variant_index(&mut self, index: u32) {
+    varint::encode_u32(index, &mut self.output);
+  }
+
+  /// The bytes written so far.
+  pub fn into_bytes(mut self) -> Vec<u8> {
+    match self.terminator {
+      Some((start, end)) if self.config.omit_trailing_terminator && end == self.output.len() => {
+        self.output.truncate(start);
+      }
+      _ => {}
+    }
+    self.output
+  }
+}
+
+impl Default for Serializer {
+  fn default() -> Self {
+    Serializer::new()
+  }
 }
 
 pub fn to_bytes<T>(value: &T) -> Result<Vec<u8>>
 where
     T: Serialize,
 {
-    let mut serializer = Serializer { output: Vec::new(), raw_byte_mode: false };
+    let mut serializer = Serializer::new();
     value.serialize(&mut serializer)?;
     Ok(serializer.output)
 }
 
-impl<'a> ser::Serializer for &'a mut Serializer {
+impl ser::Serializer for &mut Serializer {
   type Ok = ();
   type Error = Error;
 
@@ -35,22 +206,22 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   }
 
   fn serialize_i8(self, v: i8) -> Result<()> {
-    varint::encode_sint(v as i128, &mut self.output);
+    varint::encode_i64(v as i64, &mut self.output);
     Ok(())
   }
 
   fn serialize_i16(self, v: i16) -> Result<()> {
-    varint::encode_sint(v as i128, &mut self.output);
+    varint::encode_i64(v as i64, &mut self.output);
     Ok(())
   }
 
   fn serialize_i32(self, v: i32) -> Result<()> {
-    varint::encode_sint(v as i128, &mut self.output);
+    varint::encode_i64(v as i64, &mut self.output);
     Ok(())
   }
 
   fn serialize_i64(self, v: i64) -> Result<()> {
-    varint::encode_sint(v as i128, &mut self.output);
+    varint::encode_i64(v, &mut self.output);
     Ok(())
   }
 
@@ -60,36 +231,49 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   }
 
   fn serialize_u8(self, v: u8) -> Result<()> {
-    if self.raw_byte_mode {
+    if self.raw_byte_mode || std::mem::take(&mut self.array_element) {
       self.output.push(v);
     } else {
-      varint::encode_uint(v as u128, &mut self.output);
+      self.write_unsigned(v as u64);
     }
     Ok(())
   }
 
   fn serialize_u16(self, v: u16) -> Result<()> {
-    varint::encode_uint(v as u128, &mut self.output);
+    self.write_unsigned(v as u64);
     Ok(())
   }
 
   fn serialize_u32(self, v: u32) -> Result<()> {
-    varint::encode_uint(v as u128, &mut self.output);
+    self.write_unsigned(v as u64);
     Ok(())
   }
 
   fn serialize_u64(self, v: u64) -> Result<()> {
-    varint::encode_uint(v as u128, &mut self.output);
+    self.write_unsigned(v);
     Ok(())
   }
 
   fn serialize_u128(self, v: u128) -> std::result::Result<Self::Ok, Self::Error> {
-    varint::encode_uint(v, &mut self.output);
+    match self.config.varint_format {
+      VarintFormat::Lexcode => varint::encode_uint(v, &mut self.output),
+      VarintFormat::Sqlite4 => {
+        let v = u64::try_from(v).map_err(|_| Error::Message("integer overflow: a SQLite4 varint holds at most u64::MAX".into()))?;
+        varint::encode_sqlite4(v, &mut self.output);
+      }
+    }
     Ok(())
   }
 
   fn serialize_f32(self, v: f32) -> Result<()> {
-    let v = v.to_bits();
+    if self.config.finite_only && !v.is_finite() {
+      return Err(Error::Message(format!("non-finite float {v} cannot be encoded in finite-only mode")));
+    }
+    if self.config.canonical && v.is_nan() {
+      return Err(Error::Message("NaN cannot be encoded in canonical mode".into()));
+    }
+    let v = if self.config.normalize_zero && v == 0.0 { 0.0 } else { v };
+    let v = if v.is_nan() { self.nan_bits(v.to_bits(), 0x7FC0_0000)? } else { v.to_bits() };
     const SIGN_MASK: u32 = 1 << 31;
     if (v & SIGN_MASK) != 0 {
         // Negative number: flip all bits
@@ -104,7 +288,14 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   }
 
   fn serialize_f64(self, v: f64) -> Result<()> {
-    let v = v.to_bits();
+    if self.config.finite_only && !v.is_finite() {
+      return Err(Error::Message(format!("non-finite float {v} cannot be encoded in finite-only mode")));
+    }
+    if self.config.canonical && v.is_nan() {
+      return Err(Error::Message("NaN cannot be encoded in canonical mode".into()));
+    }
+    let v = if self.config.normalize_zero && v == 0.0 { 0.0 } else { v };
+    let v = if v.is_nan() { self.nan_bits(v.to_bits(), 0x7FF8_0000_0000_0000)? } else { v.to_bits() };
     const SIGN_MASK: u64 = 1 << 63;
     if (v & SIGN_MASK) != 0 {
         // Negative number: flip all bits
@@ -119,11 +310,17 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   }
 
   fn serialize_char(self, c: char) -> Result<()> {
-    varint::encode_uint(c as u128, &mut self.output);
+    varint::encode_u64(c as u64, &mut self.output);
     Ok(())
   }
 
   fn serialize_str(self, v: &str) -> Result<()> {
+    #[cfg(feature = "nfc")]
+    if self.config.normalize_nfc && !unicode_normalization::is_nfc(v) {
+      use unicode_normalization::UnicodeNormalization;
+      let normalized: String = v.nfc().collect();
+      return self.serialize_with_sentinel(normalized.as_bytes(), 0x00);
+    }
     self.serialize_with_sentinel(v.as_bytes(), 0x00)
   }
 
@@ -132,7 +329,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   }
 
   fn serialize_none(self) -> Result<()> {
-    self.output.push(0x00);
+    self.output.push(if self.config.none_last { 0x01 } else { 0x00 });
     Ok(())
   }
 
@@ -140,7 +337,8 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   where
       T: ?Sized + Serialize,
   {
-    self.output.push(0x01);
+    self.array_element = false;
+    self.output.push(if self.config.none_last { 0x00 } else { 0x01 });
     value.serialize(self)
   }
 
@@ -154,18 +352,48 @@ impl<'a> ser::Serializer for &'a mut Serializer {
       variant_index: u32,
       _variant: &'static str,
   ) -> Result<()> {
-    self.serialize_u32(variant_index)
+    self.write_variant_index(variant_index);
+    Ok(())
   }
 
   fn serialize_newtype_struct<T>(
       self,
-      _name: &'static str,
+      name: &'static str,
       value: &T,
   ) -> Result<()>
   where
       T: ?Sized + Serialize,
   {
-    value.serialize(self)
+    self.array_element = false;
+    if name == crate::descending::DESCENDING_NAME {
+      let start = self.output.len();
+      let outer = std::mem::replace(&mut self.descending, true);
+      let result = value.serialize(&mut *self);
+      self.descending = outer;
+      result?;
+      for b in &mut self.output[start..] {
+        *b = !*b;
+      }
+      self.terminator = None;
+      Ok(())
+    } else if name == crate::tail::TAIL_NAME && self.descending {
+      // Complementing a prefix does not reverse its order against its
+      // extensions, and tail bytes have no terminator to tell them apart.
+      Err(Error::Message("TailBytes and TailString cannot be inside Descending".into()))
+    } else if name == crate::fixed_bytes::FIXED_BYTES_NAME {
+      self.raw_byte_mode = true;
+      let result = value.serialize(&mut *self);
+      self.raw_byte_mode = false;
+      result
+    } else {
+      value.serialize(self)
+    }
+  }
+
+  /// Human-readable, so that types such as `IpAddr` keep their serde
+  /// text form, except inside `FixedBytes`, whose contents are raw.
+  fn is_human_readable(&self) -> bool {
+    !self.raw_byte_mode
   }
 
   fn serialize_newtype_variant<T>(
@@ -178,15 +406,18 @@ impl<'a> ser::Serializer for &'a mut Serializer {
   where
       T: ?Sized + Serialize,
   {
-    self.serialize_u32(variant_index)?;
+    self.array_element = false;
+    self.write_variant_index(variant_index);
     value.serialize(self)
   }
 
   fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+    self.array_element = false;
     Ok(self)
   }
 
   fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+    self.array_element = false;
     Ok(self)
   }
 
@@ -195,6 +426,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
       name: &'static str,
       _len: usize,
   ) -> Result<Self::SerializeTupleStruct> {
+    self.array_element = false;
     if name == crate::fixed_bytes::FIXED_BYTES_NAME {
       self.raw_byte_mode = true;
     }
@@ -208,11 +440,16 @@ impl<'a> ser::Serializer for &'a mut Serializer {
       _variant: &'static str,
       _len: usize,
   ) -> Result<Self::SerializeTupleVariant> {
-    self.serialize_u32(variant_index)?;
+    self.array_element = false;
+    self.write_variant_index(variant_index);
     Ok(self)
   }
 
-  fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+  fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+    self.array_element = false;
+    if self.config.sort_maps || self.config.canonical {
+      self.map_entries.push(Vec::with_capacity(len.unwrap_or(0)));
+    }
     Ok(self)
   }
 
@@ -221,6 +458,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
       _name: &'static str,
       _len: usize,
   ) -> Result<Self::SerializeStruct> {
+    self.array_element = false;
     Ok(self)
   }
 
@@ -231,7 +469,8 @@ impl<'a> ser::Serializer for &'a mut Serializer {
       _variant: &'static str,
       _len: usize,
   ) -> Result<Self::SerializeStructVariant> {
-    self.serialize_u32(variant_index)?;
+    self.array_element = false;
+    self.write_variant_index(variant_index);
     Ok(self)
   }
 
@@ -241,6 +480,55 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 }
 
 impl Serializer {
+  /// Apply the NaN policy to the bits of a NaN.
+  fn nan_bits<B>(&self, raw: B, canonical: B) -> Result<B> {
+    match self.config.nan_policy {
+      NanPolicy::Raw => Ok(raw),
+      NanPolicy::Canonicalize => Ok(canonical),
+      NanPolicy::Reject => Err(Error::Message("NaN cannot be encoded under NanPolicy::Reject".into())),
+    }
+  }
+
+  /// Reorder the map entries written at `entries` (each an entry start and
+  /// key end), which run to the end of the output, by their key bytes.
+  fn sort_entries(&mut self, entries: &[(usize, usize)]) {
+    let Some(&(region_start, _)) = entries.first() else {
+      return;
+    };
+    let region_end = self.output.len();
+    let mut spans: Vec<(usize, usize, usize)> = entries
+      .iter()
+      .enumerate()
+      .map(|(i, &(start, key_end))| {
+        let end = entries.get(i + 1).map_or(region_end, |next| next.0);
+        (start, key_end, end)
+      })
+      .collect();
+    spans.sort_by(|a, b| self.output[a.0..a.1].cmp(&self.output[b.0..b.1]));
+    let mut sorted = Vec::with_capacity(region_end - region_start);
+    for (start, _, end) in spans {
+      sorted.extend_from_slice(&self.output[start..end]);
+    }
+    self.output[region_start..].copy_from_slice(&sorted);
+  }
+
+  /// Fail unless the keys of the map entries at `entries` are strictly
+  /// ascending.
+  fn check_entries_sorted(&self, entries: &[(usize, usize)]) -> Result<()> {
+    let key = |&(start, key_end): &(usize, usize)| &self.output[start..key_end];
+    if entries.windows(2).all(|pair| key(&pair[0]) < key(&pair[1])) {
+      Ok(())
+    } else {
+      Err(Error::Message("map entries are not in key order in canonical mode; use a BTreeMap or sort_maps".into()))
+    }
+  }
+
+  /// Record that the last `len` bytes written are a terminator.
+  fn mark_terminator(&mut self, len: usize) {
+    let end = self.output.len();
+    self.terminator = Some((end - len, end));
+  }
+
   fn serialize_with_sentinel(&mut self, data: &[u8], sentinel: u8) -> Result<()> {
     for byte in data {
       self.output.push(*byte);
@@ -250,11 +538,15 @@ impl Serializer {
     }
     self.output.push(sentinel);
     self.output.push(0x00);
+    // Only a 0x00 sentinel sorts below every byte that could follow it.
+    if sentinel == 0x00 {
+      self.mark_terminator(2);
+    }
     Ok(())
   }
 }
 
-impl<'a> ser::SerializeSeq for &'a mut Serializer {
+impl ser::SerializeSeq for &mut Serializer {
   type Ok = ();
   type Error = Error;
 
@@ -268,11 +560,12 @@ impl<'a> ser::SerializeSeq for &'a mut Serializer {
 
   fn end(self) -> Result<()> {
     self.output.push(0x00); // End of sequence
+    self.mark_terminator(1);
     Ok(())
   }
 }
 
-impl<'a> ser::SerializeTuple for &'a mut Serializer {
+impl ser::SerializeTuple for &mut Serializer {
   type Ok = ();
   type Error = Error;
 
@@ -280,7 +573,10 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
   where
       T: ?Sized + Serialize,
   {
-    value.serialize(&mut **self)
+    self.array_element = self.config.raw_byte_arrays;
+    let result = value.serialize(&mut **self);
+    self.array_element = false;
+    result
   }
 
   fn end(self) -> Result<()> {
@@ -288,7 +584,7 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
   }
 }
 
-impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
+impl ser::SerializeTupleStruct for &mut Serializer {
   type Ok = ();
   type Error = Error;
 
@@ -305,7 +601,7 @@ impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
   }
 }
 
-impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
+impl ser::SerializeTupleVariant for &mut Serializer {
   type Ok = ();
   type Error = Error;
 
@@ -321,7 +617,7 @@ impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
   }
 }
 
-impl<'a> ser::SerializeMap for &'a mut Serializer {
+impl ser::SerializeMap for &mut Serializer {
   type Ok = ();
   type Error = Error;
 
@@ -329,8 +625,16 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
   where
       T: ?Sized + Serialize,
   {
+    let start = self.output.len();
     self.output.push(0x01); // Key separator
-    key.serialize(&mut **self)
+    key.serialize(&mut **self)?;
+    if self.config.sort_maps || self.config.canonical {
+      let key_end = self.output.len();
+      if let Some(entries) = self.map_entries.last_mut() {
+        entries.push((start, key_end));
+      }
+    }
+    Ok(())
   }
 
   fn serialize_value<T>(&mut self, value: &T) -> Result<()>
@@ -341,12 +645,21 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
   }
 
   fn end(self) -> Result<()> {
+    if self.config.sort_maps || self.config.canonical {
+      let entries = self.map_entries.pop().unwrap_or_default();
+      if self.config.sort_maps {
+        self.sort_entries(&entries);
+      } else {
+        self.check_entries_sorted(&entries)?;
+      }
+    }
     self.output.push(0x00); // End of map
+    self.mark_terminator(1);
     Ok(())
   }
 }
 
-impl<'a> ser::SerializeStruct for &'a mut Serializer {
+impl ser::SerializeStruct for &mut Serializer {
   type Ok = ();
   type Error = Error;
 
@@ -362,7 +675,7 @@ impl<'a> ser::SerializeStruct for &'a mut Serializer {
   }
 }
 
-impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
+impl ser::SerializeStructVariant for &mut Serializer {
   type Ok = ();
   type Error = Error;
 
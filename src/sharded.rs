@@ -0,0 +1,132 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeTuple, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::error::Result;
+use crate::range::prefix_range;
+use crate::ser::to_bytes;
+
+/// A key prefixed by a shard number derived from its hash, to spread
+/// sequential keys over `N` shards.
+///
+/// `Sharded<N, T>` encodes as the tuple `(shard, value)`, where `shard` is
+/// a 64-bit FNV-1a hash of the value's encoding modulo `N`, written as a
+/// varint (one byte for `N <= 128`). Keys that would all land at the end
+/// of the keyspace, such as timestamps or counters, are spread across `N`
+/// ranges instead, and within each shard they keep their order. The hash
+/// is part of the format and does not change between versions or
+/// platforms.
+///
+/// A range scan by the value's leading fields must visit every shard:
+/// [`prefix_ranges`](Sharded::prefix_ranges) gives the range for each.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Sharded;
+///
+/// type EventKey = Sharded<8, (u64, i64)>;
+///
+/// let key = lexcode::to_bytes(&EventKey::new((7, 1_000)))?;
+/// let shard = EventKey::new((7, 1_000)).shard()?;
+/// assert!(shard < 8);
+/// assert_eq!(key[0], shard as u8);
+///
+/// // All events of user 7, one range per shard.
+/// let ranges = EventKey::prefix_ranges(&(7u64,))?;
+/// assert_eq!(ranges.len(), 8);
+/// assert!(ranges.iter().any(|(start, end)| *start <= key && key < *end));
+///
+/// let decoded: EventKey = lexcode::from_bytes(&key)?;
+/// assert_eq!(*decoded.get(), (7, 1_000));
+/// # Ok::<(), lexcode::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sharded<const N: u16, T>(T);
+
+impl<const N: u16, T> Sharded<N, T> {
+    pub fn new(value: T) -> Self {
+        const { assert!(N > 0, "Sharded needs at least one shard") };
+        Sharded(value)
+    }
+
+    pub fn get(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// The `[start, end)` byte range of each shard, in shard order,
+    /// covering the keys whose value's encoding begins with that of
+    /// `prefix` (see [`prefix_range`](crate::prefix_range)). Pass `&()` for
+    /// the whole of each shard. Fails if `prefix` cannot be encoded.
+    pub fn prefix_ranges<P>(prefix: &P) -> Result<Vec<(Vec<u8>, Vec<u8>)>>
+    where
+        P: ?Sized + Serialize,
+    {
+        (0..N).map(|shard| prefix_range(&(shard, prefix))).collect()
+    }
+}
+
+impl<const N: u16, T: Serialize> Sharded<N, T> {
+    /// The shard the value belongs to. Fails if it cannot be encoded.
+    pub fn shard(&self) -> Result<u16> {
+        Ok(shard_of(&to_bytes(&self.0)?, N))
+    }
+}
+
+impl<const N: u16, T> From<T> for Sharded<N, T> {
+    fn from(value: T) -> Self {
+        Sharded::new(value)
+    }
+}
+
+fn shard_of(encoded: &[u8], n: u16) -> u16 {
+    let hash = encoded.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    (hash % n as u64) as u16
+}
+
+impl<const N: u16, T: Serialize> Serialize for Sharded<N, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let shard = self.shard().map_err(ser::Error::custom)?;
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&shard)?;
+        tuple.serialize_element(&self.0)?;
+        tuple.end()
+    }
+}
+
+impl<'de, const N: u16, T: Serialize + Deserialize<'de>> Deserialize<'de> for Sharded<N, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct ShardedVisitor<const N: u16, T>(PhantomData<T>);
+
+        impl<'de, const N: u16, T: Serialize + Deserialize<'de>> Visitor<'de> for ShardedVisitor<N, T> {
+            type Value = Sharded<N, T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a shard number followed by a value")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Sharded<N, T>, A::Error> {
+                let shard: u16 = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let value: T = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let value = Sharded::new(value);
+                if value.shard().map_err(de::Error::custom)? != shard {
+                    return Err(de::Error::custom("value does not belong to its encoded shard"));
+                }
+                Ok(value)
+            }
+        }
+
+        deserializer.deserialize_tuple(2, ShardedVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,74 @@
+//! lexcode keys in SQLite through [rusqlite](::rusqlite) (feature
+//! `sqlite`).
+//!
+//! SQLite compares BLOBs with `memcmp`, shorter first on a tie, which is
+//! exactly the byte order lexcode preserves; collations never apply to
+//! BLOBs. Keys stored as BLOBs therefore need nothing more for `ORDER BY`,
+//! `MIN`, indexes and range conditions to follow the Rust ordering.
+//! [`Lex<T>`] binds a value as such a BLOB and reads it back. Bound as
+//! TEXT or compared with values of other types, keys would sort by
+//! SQLite's type precedence instead.
+//!
+//! Plain strings kept in TEXT columns can follow lexcode's string order,
+//! that of their UTF-8 bytes, with the collation that
+//! [`create_collation`] registers. SQLite's built-in `BINARY` collation
+//! agrees with it only in UTF-8 databases.
+//!
+//! ```
+//! use lexcode::sqlite::Lex;
+//! use rusqlite::Connection;
+//!
+//! let conn = Connection::open_in_memory()?;
+//! conn.execute("CREATE TABLE events (key BLOB PRIMARY KEY, event TEXT)", [])?;
+//! for (key, event) in [((7u64, 1700i64), "login"), ((7, -5), "logout"), ((3, 0), "login")] {
+//!     conn.execute("INSERT INTO events VALUES (?1, ?2)", (Lex(key), event))?;
+//! }
+//!
+//! let mut stmt = conn.prepare("SELECT key FROM events WHERE key >= ?1 ORDER BY key")?;
+//! let keys: Vec<(u64, i64)> =
+//!     stmt.query_map([Lex((7u64, i64::MIN))], |row| Ok(row.get::<_, Lex<_>>(0)?.0))?.collect::<Result<_, _>>()?;
+//! assert_eq!(keys, [(7, -5), (7, 1700)]);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use ::rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, Value, ValueRef};
+use ::rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::de::from_bytes;
+use crate::ser::to_bytes;
+
+/// The name [`create_collation`] registers the collation under.
+pub const COLLATION: &str = "LEXCODE";
+
+/// Register the [`COLLATION`] collation on `conn`, ordering TEXT values
+/// as lexcode orders strings: by their UTF-8 bytes, whatever the encoding
+/// of the database.
+///
+/// Use it as `ORDER BY name COLLATE LEXCODE`, or declare a column
+/// `TEXT COLLATE LEXCODE` so its indexes use it. The collation has to be
+/// registered on every connection that reads or writes such an index.
+pub fn create_collation(conn: &Connection) -> ::rusqlite::Result<()> {
+    conn.create_collation(COLLATION, |a: &str, b: &str| a.cmp(b))
+}
+
+/// A `T` bound to and read from SQLite as its lexcode encoding, a BLOB.
+///
+/// Reading fails on values that are not BLOBs or do not decode as a `T`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Lex<T>(pub T);
+
+impl<T: Serialize> ToSql for Lex<T> {
+    fn to_sql(&self) -> ::rusqlite::Result<ToSqlOutput<'_>> {
+        let bytes = to_bytes(&self.0).map_err(|err| ::rusqlite::Error::ToSqlConversionFailure(Box::new(err)))?;
+        Ok(ToSqlOutput::Owned(Value::Blob(bytes)))
+    }
+}
+
+impl<T: DeserializeOwned> FromSql for Lex<T> {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let bytes = value.as_blob()?;
+        from_bytes(bytes).map(Lex).map_err(|err| FromSqlError::Other(Box::new(err)))
+    }
+}
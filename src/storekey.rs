@@ -0,0 +1,56 @@
+//! Reading keys written by `storekey` and `bytekey`, for moving a store
+//! over to lexcode (feature `storekey`).
+//!
+//! This is the serde-based format of `storekey` up to 0.5, the fork of
+//! `bytekey` that keeps its layout: fixed-width big-endian integers with
+//! the sign bit flipped, NUL-terminated strings, byte slices after a
+//! 64-bit length, and `u32` enum variant indices. Later `storekey`
+//! releases use a different format and are not covered.
+//!
+//! Like lexcode, the format is not self-describing, so reading takes the
+//! Rust type the keys were written from. A store can be converted online
+//! by writing every key through [`migrate`] into a new keyspace, such as a
+//! fresh table or a [`Namespace`](crate::Namespace), while readers still
+//! use the old one, then switching over. Converting in place would mix
+//! the two formats in one ordering: `storekey` sorts byte slices by length
+//! first and lexcode by content, so keys can change places.
+//!
+//! ```
+//! let old = storekey::serialize(&(7u32, "seven")).unwrap();
+//! let new = lexcode::storekey::migrate::<(u32, String)>(&old)?;
+//! assert_eq!(new, lexcode::to_bytes(&(7u32, "seven"))?);
+//! # Ok::<(), lexcode::Error>(())
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::ser::to_bytes;
+
+/// Decode a key in the `storekey` format.
+///
+/// The input must be exactly one encoded `T`: it is written back in the
+/// old format and compared, so trailing bytes or a wrong type parameter
+/// are an error rather than a misread key.
+pub fn from_bytes<'a, T>(input: &'a [u8]) -> Result<T>
+where
+    T: Deserialize<'a> + Serialize,
+{
+    let value: T = ::storekey::deserialize(input).map_err(|err| Error::Message(err.to_string()))?;
+    let written = ::storekey::serialize(&value).map_err(|err| Error::Message(err.to_string()))?;
+    if written != input {
+        return Err(Error::Message("input is not exactly one key of the given type".into()));
+    }
+    Ok(value)
+}
+
+/// Re-encode a key in the `storekey` format as lexcode.
+///
+/// Fails if `input` is not exactly one encoded `T` (see [`from_bytes`]),
+/// or if the value uses something lexcode cannot encode.
+pub fn migrate<'a, T>(input: &'a [u8]) -> Result<Vec<u8>>
+where
+    T: Deserialize<'a> + Serialize,
+{
+    to_bytes(&from_bytes::<T>(input)?)
+}
@@ -0,0 +1,60 @@
+//! `#[serde(with = "lexcode::system_time")]` for
+//! [`SystemTime`](std::time::SystemTime).
+//!
+//! serde's own `SystemTime` impl writes the time since the Unix epoch as a
+//! `(secs: u64, nanos: u32)` struct. Since lexcode concatenates fields and
+//! both varints preserve order, that layout already sorts chronologically,
+//! but it fails to serialize any time before 1970. This module writes the
+//! seconds as a signed varint instead (flooring towards the past, so the
+//! nanoseconds are always in `0..1_000_000_000`), covering the full range.
+//! The layout matches the `chrono` and `time` features' timestamps.
+//!
+//! `Duration` needs no helper: its serde `(secs, nanos)` struct encodes as
+//! two varints and sorts like `Duration`'s `Ord`.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use std::time::{Duration, SystemTime, UNIX_EPOCH};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Stamp(#[serde(with = "lexcode::system_time")] SystemTime);
+//!
+//! let before = lexcode::to_bytes(&Stamp(UNIX_EPOCH - Duration::from_secs(1))).unwrap();
+//! let after = lexcode::to_bytes(&Stamp(UNIX_EPOCH + Duration::from_secs(1))).unwrap();
+//! assert!(before < after);
+//! ```
+
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{self, Serialize, Serializer};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub fn serialize<S: Serializer>(value: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+    let parts = match value.duration_since(UNIX_EPOCH) {
+        Ok(after) => i64::try_from(after.as_secs()).map(|secs| (secs, after.subsec_nanos())),
+        Err(err) => {
+            let before = err.duration();
+            i64::try_from(before.as_secs()).map(|secs| match before.subsec_nanos() {
+                0 => (-secs, 0),
+                nanos => (-secs - 1, 1_000_000_000 - nanos),
+            })
+        }
+    };
+    parts
+        .map_err(|_| <S::Error as ser::Error>::custom("system time out of range"))?
+        .serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+    let (secs, nanos): (i64, u32) = Deserialize::deserialize(deserializer)?;
+    if nanos >= 1_000_000_000 {
+        return Err(de::Error::custom("nanoseconds out of range"));
+    }
+    let time = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+    } else {
+        UNIX_EPOCH
+            .checked_sub(Duration::from_secs(secs.unsigned_abs()))
+            .and_then(|t| t.checked_add(Duration::from_nanos(u64::from(nanos))))
+    };
+    time.ok_or_else(|| de::Error::custom("system time out of range"))
+}
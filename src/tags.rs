@@ -0,0 +1,121 @@
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::collections::BTreeMap;
+
+use crate::ser::to_bytes;
+
+/// A set of `key=value` string tags, such as the tags of a time series,
+/// encoded in key order so that equal sets always encode identically.
+///
+/// The encoding is that of a map: `0x01`, the key and the value for each
+/// tag in ascending key order, then `0x00`. Sets compare tag by tag, and a
+/// set sorts before every set extending it with later keys, so a metric
+/// keyspace `(metric, tags, timestamp)` groups each series' points and
+/// [`prefix_bytes`](Tags::prefix_bytes) can scan every series whose
+/// leading tags are given. Decoding rejects keys out of order or repeated,
+/// which no `Tags` encodes.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Tags;
+///
+/// let a: Tags = [("region", "eu"), ("host", "a1")].into_iter().collect();
+/// let b: Tags = [("host", "a1"), ("region", "eu")].into_iter().collect();
+/// assert_eq!(lexcode::to_bytes(&a).unwrap(), lexcode::to_bytes(&b).unwrap());
+///
+/// let decoded: Tags = lexcode::from_bytes(&lexcode::to_bytes(&a).unwrap()).unwrap();
+/// assert_eq!(decoded.get("host"), Some("a1"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Tags {
+    tags: BTreeMap<String, String>,
+}
+
+impl Tags {
+    pub fn new() -> Tags {
+        Tags::default()
+    }
+
+    /// Set tag `key` to `value`, returning its previous value.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        self.tags.insert(key.into(), value.into())
+    }
+
+    /// Remove tag `key`, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.tags.remove(key)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tags.get(key).map(String::as_str)
+    }
+
+    /// The tags in ascending key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.tags.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// The encoding without its terminator: the bytes every encoded tag set
+    /// starts with if it holds these tags and, beyond them, only keys that
+    /// sort after all of theirs.
+    ///
+    /// Appended to the encoding of the key components before the tags, it
+    /// gives a prefix for [`prefix_successor`](crate::prefix_successor)
+    /// bounds. To select series by one tag this way, that tag's key must
+    /// sort first among the series' keys.
+    ///
+    /// ```
+    /// use lexcode::Tags;
+    ///
+    /// let series: Tags = [("dc", "fra"), ("host", "a1")].into_iter().collect();
+    /// let key = lexcode::to_bytes(&("cpu", &series, 1700i64)).unwrap();
+    ///
+    /// let filter: Tags = [("dc", "fra")].into_iter().collect();
+    /// let mut start = lexcode::to_bytes(&"cpu").unwrap();
+    /// start.extend(filter.prefix_bytes());
+    /// let end = lexcode::prefix_successor(&start).unwrap();
+    /// assert!(start <= key && key < end);
+    /// ```
+    pub fn prefix_bytes(&self) -> Vec<u8> {
+        let mut bytes = to_bytes(self).expect("serializing strings cannot fail");
+        bytes.pop();
+        bytes
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Tags {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Tags {
+        let mut tags = Tags::new();
+        for (key, value) in iter {
+            tags.insert(key, value);
+        }
+        tags
+    }
+}
+
+impl Serialize for Tags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.tags.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Read the pairs as they are so a non-canonical order is caught
+        // rather than silently sorted.
+        let pairs = Vec::<(String, String)>::deserialize(deserializer)?;
+        if pairs.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(de::Error::custom("tags are not in strictly ascending key order"));
+        }
+        Ok(Tags { tags: pairs.into_iter().collect() })
+    }
+}
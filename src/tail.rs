@@ -0,0 +1,110 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+use crate::fixed_bytes::RawBytes;
+
+/// Reading a tuple struct with this name yields every remaining input byte
+/// in raw byte mode, whatever length the visitor asked for.
+pub(crate) const TAIL_NAME: &str = "lexcode::Tail";
+
+/// Bytes written raw, with no escaping or terminator, that decode as
+/// everything left in the input.
+///
+/// Only valid as the **last** component of a key: a `TailBytes` followed by
+/// another field cannot be decoded, and wrapping it in `Descending` is an
+/// error, since complemented tail bytes would sort a prefix after its
+/// extensions. In that position the encoding sorts exactly like the
+/// bytes, a prefix before its extensions, and saves the escape pass and the
+/// two terminator bytes of a `str`, which matters for large trailing blobs.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::TailBytes;
+///
+/// let key = lexcode::to_bytes(&(7u8, TailBytes(vec![0x00, 0xFF]))).unwrap();
+/// assert_eq!(key, [7, 0x00, 0xFF]);
+///
+/// let (id, tail): (u8, TailBytes) = lexcode::from_bytes(&key).unwrap();
+/// assert_eq!((id, tail.0), (7, vec![0x00, 0xFF]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TailBytes(pub Vec<u8>);
+
+/// A string written raw, with no escaping or terminator, that decodes as
+/// everything left in the input.
+///
+/// The string counterpart of [`TailBytes`], with the same restriction to
+/// the last component of a key.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::TailString;
+///
+/// let key = lexcode::to_bytes(&(7u8, TailString("notes".into()))).unwrap();
+/// assert_eq!(key, b"\x07notes");
+///
+/// let (_, tail): (u8, TailString) = lexcode::from_bytes(&key).unwrap();
+/// assert_eq!(tail.0, "notes");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TailString(pub String);
+
+impl From<Vec<u8>> for TailBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        TailBytes(bytes)
+    }
+}
+
+impl From<String> for TailString {
+    fn from(s: String) -> Self {
+        TailString(s)
+    }
+}
+
+impl Serialize for TailBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(TAIL_NAME, &RawBytes(&self.0))
+    }
+}
+
+impl Serialize for TailString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(TAIL_NAME, &RawBytes(self.0.as_bytes()))
+    }
+}
+
+struct TailVisitor;
+
+impl<'de> Visitor<'de> for TailVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("the remaining bytes of the input")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+impl<'de> Deserialize<'de> for TailBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple_struct(TAIL_NAME, 0, TailVisitor).map(TailBytes)
+    }
+}
+
+impl<'de> Deserialize<'de> for TailString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserializer.deserialize_tuple_struct(TAIL_NAME, 0, TailVisitor)?;
+        String::from_utf8(bytes)
+            .map(TailString)
+            .map_err(|_| de::Error::custom("tail string is not valid UTF-8"))
+    }
+}
@@ -0,0 +1,96 @@
+//! Compact order-preserving encodings for the [`time`](::time) crate's
+//! dates and date-times (feature `time`).
+//!
+//! The layouts match the `chrono` feature's, so keys written by either
+//! crate are interchangeable:
+//!
+//! - `Date`: days since 0001-01-01 as a signed varint;
+//! - `PrimitiveDateTime` and `OffsetDateTime`: seconds since the Unix epoch
+//!   as a signed varint, then the nanosecond of the second as an unsigned
+//!   varint. A `PrimitiveDateTime` is taken to be in UTC.
+//!
+//! An `OffsetDateTime` is normalized to UTC before encoding, so the same
+//! instant always produces the same bytes regardless of its offset, and it
+//! decodes with a UTC offset.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use time::{Date, Month, OffsetDateTime, UtcOffset};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(with = "lexcode::time::offset_date_time")]
+//!     at: OffsetDateTime,
+//!     #[serde(with = "lexcode::time::date")]
+//!     day: Date,
+//! }
+//!
+//! let utc = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+//! let local = utc.to_offset(UtcOffset::from_hms(5, 30, 0).unwrap());
+//! let day = Date::from_calendar_date(2023, Month::November, 14).unwrap();
+//! assert_eq!(
+//!     lexcode::to_bytes(&Event { at: utc, day }).unwrap(),
+//!     lexcode::to_bytes(&Event { at: local, day }).unwrap(),
+//! );
+//! ```
+
+use ::time::{Date, OffsetDateTime, PrimitiveDateTime};
+use serde::de::{self, Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+/// Julian day number of 0001-01-01, which is day 1 in the encoding.
+const JULIAN_DAY_OFFSET: i32 = 1_721_425;
+
+fn timestamp_parts(value: OffsetDateTime) -> (i64, u32) {
+    (value.unix_timestamp(), value.nanosecond())
+}
+
+fn from_timestamp_parts<E: de::Error>((secs, nanos): (i64, u32)) -> Result<OffsetDateTime, E> {
+    OffsetDateTime::from_unix_timestamp(secs)
+        .and_then(|t| t.replace_nanosecond(nanos))
+        .map_err(E::custom)
+}
+
+/// `#[serde(with = "lexcode::time::date")]` for `Date`.
+pub mod date {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Date, serializer: S) -> Result<S::Ok, S::Error> {
+        (value.to_julian_day() - JULIAN_DAY_OFFSET).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Date, D::Error> {
+        let days = i32::deserialize(deserializer)?;
+        days.checked_add(JULIAN_DAY_OFFSET)
+            .ok_or_else(|| de::Error::custom("date out of range"))
+            .and_then(|julian| Date::from_julian_day(julian).map_err(de::Error::custom))
+    }
+}
+
+/// `#[serde(with = "lexcode::time::primitive_date_time")]` for
+/// `PrimitiveDateTime`, read as UTC.
+pub mod primitive_date_time {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &PrimitiveDateTime, serializer: S) -> Result<S::Ok, S::Error> {
+        timestamp_parts(value.assume_utc()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PrimitiveDateTime, D::Error> {
+        from_timestamp_parts(Deserialize::deserialize(deserializer)?).map(|t| PrimitiveDateTime::new(t.date(), t.time()))
+    }
+}
+
+/// `#[serde(with = "lexcode::time::offset_date_time")]` for
+/// `OffsetDateTime`, normalized to UTC.
+pub mod offset_date_time {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &OffsetDateTime, serializer: S) -> Result<S::Ok, S::Error> {
+        timestamp_parts(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<OffsetDateTime, D::Error> {
+        from_timestamp_parts(Deserialize::deserialize(deserializer)?)
+    }
+}
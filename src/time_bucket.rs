@@ -0,0 +1,152 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A timestamp prefixed by the start of its `SECS`-second bucket.
+///
+/// `TimeBucket<SECS, T>` encodes as the tuple `(bucket_start, value)`, so
+/// every key in the same hour (`SECS = 3600`) or day (`SECS = 86400`) shares
+/// a common prefix and a range scan over one bucket is a prefix scan. The
+/// full value follows, so ordering within a bucket and round-tripping are
+/// unaffected.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::TimeBucket;
+///
+/// type Hourly = TimeBucket<3600, u64>;
+///
+/// let a = lexcode::to_bytes(&(Hourly::new(7_200), 1u8)).unwrap();
+/// let b = lexcode::to_bytes(&(Hourly::new(10_799), 2u8)).unwrap();
+/// let bucket = lexcode::to_bytes(&7_200u64).unwrap();
+/// assert!(a.starts_with(&bucket) && b.starts_with(&bucket));
+///
+/// let decoded: (Hourly, u8) = lexcode::from_bytes(&b).unwrap();
+/// assert_eq!(*decoded.0.get(), 10_799);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeBucket<const SECS: u64, T>(T);
+
+/// Timestamps that [`TimeBucket`] can truncate.
+///
+/// Integers are taken as seconds; negative values round towards the past.
+/// A bucket starting before the earliest representable value saturates:
+/// to `i64::MIN` for `i64`, and to the value itself for a `SystemTime`.
+pub trait BucketTime: Sized {
+    /// The latest bucket boundary not after `self`, for buckets of `secs`
+    /// seconds.
+    fn truncate(&self, secs: u64) -> Self;
+}
+
+impl BucketTime for u32 {
+    fn truncate(&self, secs: u64) -> u32 {
+        match u32::try_from(secs) {
+            Ok(secs) => self - self % secs,
+            Err(_) => 0,
+        }
+    }
+}
+
+impl BucketTime for u64 {
+    fn truncate(&self, secs: u64) -> u64 {
+        self - self % secs
+    }
+}
+
+impl BucketTime for i64 {
+    fn truncate(&self, secs: u64) -> i64 {
+        match i64::try_from(secs) {
+            Ok(secs) => self.checked_sub(self.rem_euclid(secs)).unwrap_or(i64::MIN),
+            Err(_) if *self >= 0 => 0,
+            Err(_) => i64::MIN,
+        }
+    }
+}
+
+impl BucketTime for Duration {
+    fn truncate(&self, secs: u64) -> Duration {
+        Duration::from_secs(self.as_secs().truncate(secs))
+    }
+}
+
+impl BucketTime for SystemTime {
+    fn truncate(&self, secs: u64) -> SystemTime {
+        match self.duration_since(UNIX_EPOCH) {
+            Ok(after) => UNIX_EPOCH + after.truncate(secs),
+            Err(err) => {
+                let before = err.duration();
+                let whole = before.as_secs().saturating_add(u64::from(before.subsec_nanos() > 0));
+                let rounded = whole.div_ceil(secs).saturating_mul(secs);
+                UNIX_EPOCH.checked_sub(Duration::from_secs(rounded)).unwrap_or(*self)
+            }
+        }
+    }
+}
+
+impl<const SECS: u64, T: BucketTime> TimeBucket<SECS, T> {
+    pub fn new(value: T) -> Self {
+        const { assert!(SECS > 0, "TimeBucket needs a non-zero bucket width") };
+        TimeBucket(value)
+    }
+
+    /// The start of the bucket containing the value.
+    pub fn bucket(&self) -> T {
+        self.0.truncate(SECS)
+    }
+
+    pub fn get(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<const SECS: u64, T: BucketTime> From<T> for TimeBucket<SECS, T> {
+    fn from(value: T) -> Self {
+        TimeBucket::new(value)
+    }
+}
+
+impl<const SECS: u64, T: BucketTime + Serialize> Serialize for TimeBucket<SECS, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.bucket())?;
+        tuple.serialize_element(&self.0)?;
+        tuple.end()
+    }
+}
+
+impl<'de, const SECS: u64, T: BucketTime + PartialEq + Deserialize<'de>> Deserialize<'de> for TimeBucket<SECS, T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TimeBucketVisitor<const SECS: u64, T>(PhantomData<T>);
+
+        impl<'de, const SECS: u64, T: BucketTime + PartialEq + Deserialize<'de>> Visitor<'de> for TimeBucketVisitor<SECS, T> {
+            type Value = TimeBucket<SECS, T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a bucket start followed by a timestamp")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<TimeBucket<SECS, T>, A::Error> {
+                let bucket: T = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let value: T = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let value = TimeBucket::new(value);
+                if value.bucket() != bucket {
+                    return Err(de::Error::custom("timestamp does not belong to its encoded bucket"));
+                }
+                Ok(value)
+            }
+        }
+
+        deserializer.deserialize_tuple(2, TimeBucketVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,163 @@
+//! Converting JSON, CBOR and other self-describing input to lexcode keys
+//! without Rust types (feature `transcode`).
+//!
+//! [`Serializer`] accepts everything `serde_transcode` produces, including
+//! sequences and maps of unknown length, so any self-describing
+//! deserializer can be transcoded directly:
+//!
+//! ```
+//! let mut json = serde_json::Deserializer::from_str(r#"{"b": [1, -2], "a": null}"#);
+//! let mut serializer = lexcode::Serializer::new().sort_maps(true);
+//! serde_transcode::transcode(&mut json, &mut serializer)?;
+//!
+//! let mut expected = std::collections::BTreeMap::new();
+//! expected.insert("a", lexcode::Value::Unit);
+//! expected.insert("b", lexcode::Value::Seq(vec![lexcode::Value::UInt(1), lexcode::Value::Int(-2)]));
+//! assert_eq!(serializer.into_bytes(), lexcode::to_bytes(&expected)?);
+//! # Ok::<(), lexcode::Error>(())
+//! ```
+//!
+//! Each value keeps the type the input format gives it, which is rarely
+//! the type the keys were written with: JSON arrays become sequences
+//! rather than tuples, and non-negative integers unsigned even where the
+//! key has a signed field, whose encoding differs. [`transcode_with_schema`]
+//! reads the input in the shape of a [`Schema`] instead, converting
+//! numbers to the integer or float types it names, so the result matches
+//! what [`to_bytes`](crate::to_bytes) gives for the corresponding Rust
+//! value. In that mode tuples and structs are arrays of their fields, maps
+//! are objects (or whatever the input format has), `bytes` and
+//! `fixed<N>` are arrays of numbers or native byte strings, a `char` is a
+//! one-character string, and an enum variant is an object with the
+//! variant index as its only key.
+//!
+//! ```
+//! use lexcode::transcode::transcode_json_to_lex;
+//!
+//! let schema = "(u64, str, i32, option<f64>)".parse()?;
+//! let key = transcode_json_to_lex(r#"[7, "seven", 3, 1]"#, Some(&schema))?;
+//! assert_eq!(key, lexcode::to_bytes(&(7u64, "seven", 3i32, Some(1.0f64)))?);
+//! # Ok::<(), lexcode::Error>(())
+//! ```
+
+use serde::de::{self, DeserializeSeed};
+
+use crate::error::{Error, Result};
+use crate::schema::Schema;
+use crate::ser::{to_bytes, Serializer};
+use crate::value::Value;
+
+/// Transcode one value from a self-describing `deserializer`, each part
+/// encoded with the type the input gives it.
+pub fn transcode<'de, D: de::Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>> {
+    let mut serializer = Serializer::new();
+    serde_transcode::transcode(deserializer, &mut serializer)?;
+    Ok(serializer.into_bytes())
+}
+
+/// Transcode one value of shape `schema` from `deserializer`, encoding it
+/// exactly as the Rust type the schema describes.
+///
+/// Fails if the input does not have that shape, or holds a number out of
+/// the range of its field's type.
+pub fn transcode_with_schema<'de, D: de::Deserializer<'de>>(deserializer: D, schema: &Schema) -> Result<Vec<u8>> {
+    let value = schema.deserialize(deserializer).map_err(|err| Error::Message(err.to_string()))?;
+    to_bytes(&coerce(schema, value)?)
+}
+
+/// Transcode a JSON document, with [`transcode_with_schema`] if a schema
+/// is given and [`transcode`] otherwise.
+///
+/// Fails on anything after the document other than whitespace.
+///
+/// Floats are parsed by serde_json, which may be off by one in the last
+/// place unless its `float_roundtrip` feature is enabled.
+pub fn transcode_json_to_lex(json: &str, schema_hint: Option<&Schema>) -> Result<Vec<u8>> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let bytes = match schema_hint {
+        Some(schema) => transcode_with_schema(&mut deserializer, schema)?,
+        None => transcode(&mut deserializer)?,
+    };
+    deserializer.end().map_err(|err| Error::Message(err.to_string()))?;
+    Ok(bytes)
+}
+
+/// Convert `value`, read in the shape of `schema` but with the scalar
+/// types of the input, to the types `schema` names.
+fn coerce(schema: &Schema, value: Value) -> Result<Value> {
+    let mismatch = |value: &Value| Error::Message(format!("expected {schema}, found {value:?}"));
+    Ok(match (schema, value) {
+        (Schema::F32, Value::F32(v)) => Value::F32(v),
+        (Schema::F32, Value::F64(v)) => Value::F32(v as f32),
+        (Schema::F32, Value::UInt(v)) => Value::F32(v as f32),
+        (Schema::F32, Value::Int(v)) => Value::F32(v as f32),
+        (Schema::F64, Value::F32(v)) => Value::F64(v as f64),
+        (Schema::F64, Value::F64(v)) => Value::F64(v),
+        (Schema::F64, Value::UInt(v)) => Value::F64(v as f64),
+        (Schema::F64, Value::Int(v)) => Value::F64(v as f64),
+        (_, value @ (Value::UInt(_) | Value::Int(_))) => {
+            let (min, max) = int_range(schema).ok_or_else(|| mismatch(&value))?;
+            match value {
+                Value::UInt(v) if v <= max && min == 0 => Value::UInt(v),
+                Value::UInt(v) if v <= max => Value::Int(v as i128),
+                Value::Int(v) if v >= min && (v < 0 || v as u128 <= max) && min == 0 => Value::UInt(v as u128),
+                Value::Int(v) if v >= min && (v < 0 || v as u128 <= max) => Value::Int(v),
+                value => return Err(mismatch(&value)),
+            }
+        }
+        (Schema::Char, Value::String(s)) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Value::Char(c),
+                _ => return Err(mismatch(&Value::String(s))),
+            }
+        }
+        (Schema::Option(inner), Value::Option(Some(value))) => Value::Option(Some(Box::new(coerce(inner, *value)?))),
+        (Schema::Seq(inner), Value::Seq(items)) => {
+            Value::Seq(items.into_iter().map(|item| coerce(inner, item)).collect::<Result<_>>()?)
+        }
+        (Schema::Tuple(fields), Value::Tuple(items)) => {
+            Value::Tuple(fields.iter().zip(items).map(|(field, item)| coerce(field, item)).collect::<Result<_>>()?)
+        }
+        (Schema::Map(key, value), Value::Map(entries)) => Value::Map(
+            entries.into_iter().map(|(k, v)| Ok((coerce(key, k)?, coerce(value, v)?))).collect::<Result<_>>()?,
+        ),
+        (Schema::Enum(variants), Value::Variant(index, payload)) => {
+            let payload = coerce(&variants[index as usize], *payload)?;
+            Value::Variant(index, Box::new(payload))
+        }
+        (_, value) if same_kind(schema, &value) => value,
+        (_, value) => return Err(mismatch(&value)),
+    })
+}
+
+/// The range of an integer schema.
+fn int_range(schema: &Schema) -> Option<(i128, u128)> {
+    Some(match schema {
+        Schema::U8 => (0, u8::MAX as u128),
+        Schema::U16 => (0, u16::MAX as u128),
+        Schema::U32 => (0, u32::MAX as u128),
+        Schema::U64 => (0, u64::MAX as u128),
+        Schema::U128 => (0, u128::MAX),
+        Schema::I8 => (i8::MIN as i128, i8::MAX as u128),
+        Schema::I16 => (i16::MIN as i128, i16::MAX as u128),
+        Schema::I32 => (i32::MIN as i128, i32::MAX as u128),
+        Schema::I64 => (i64::MIN as i128, i64::MAX as u128),
+        Schema::I128 => (i128::MIN, i128::MAX as u128),
+        _ => return None,
+    })
+}
+
+/// Whether a value read for a scalar or `option` schema already has its
+/// type.
+fn same_kind(schema: &Schema, value: &Value) -> bool {
+    matches!(
+        (schema, value),
+        (Schema::Unit, Value::Unit)
+            | (Schema::Bool, Value::Bool(_))
+            | (Schema::Char, Value::Char(_))
+            | (Schema::Str, Value::String(_))
+            | (Schema::Bytes, Value::Bytes(_))
+            | (Schema::Fixed(_), Value::Fixed(_))
+            | (Schema::Option(_), Value::Option(None))
+    )
+}
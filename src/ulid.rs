@@ -0,0 +1,170 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result};
+use crate::fixed_bytes::FixedBytes;
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const RANDOM_BITS: u32 = 80;
+const RANDOM_MASK: u128 = (1 << RANDOM_BITS) - 1;
+
+/// A Universally Unique Lexicographically Sortable Identifier.
+///
+/// A ULID is 128 bits: a 48-bit millisecond Unix timestamp followed by 80
+/// random bits. It encodes as exactly 16 raw big-endian bytes (like
+/// `FixedBytes<16>`), so keys sort by creation time, and displays as the
+/// usual 26-character Crockford base32 string, which sorts the same way.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::Ulid;
+///
+/// let early = Ulid::from_parts(1_700_000_000_000, 42);
+/// let late = Ulid::from_parts(1_700_000_000_001, 0);
+/// assert!(lexcode::to_bytes(&early).unwrap() < lexcode::to_bytes(&late).unwrap());
+/// assert_eq!(lexcode::to_bytes(&early).unwrap().len(), 16);
+///
+/// let text = early.to_string();
+/// assert_eq!(text, "01HF7YAT00000000000000001A");
+/// assert_eq!(text.parse::<Ulid>().unwrap(), early);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Ulid(u128);
+
+impl Ulid {
+    /// Generate a ULID for the current time.
+    ///
+    /// With the `random` feature, the random bits come from the operating
+    /// system's random number generator. Without it they are not random:
+    /// they hash a process-wide counter and the clock with the standard
+    /// library's randomly seeded hasher, which keeps them distinct but
+    /// makes them unfit where the ULID must be unguessable.
+    ///
+    /// # Panics
+    ///
+    /// With the `random` feature, if the generator is unavailable or fails.
+    pub fn new() -> Ulid {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        #[cfg(feature = "random")]
+        let random = random_bits();
+        #[cfg(not(feature = "random"))]
+        let random = hashed_bits(now.subsec_nanos());
+        Ulid::from_parts(now.as_millis() as u64, random)
+    }
+
+    /// Build a ULID from a millisecond timestamp (truncated to 48 bits) and
+    /// random bits (truncated to 80 bits).
+    pub fn from_parts(timestamp_ms: u64, random: u128) -> Ulid {
+        let timestamp = u128::from(timestamp_ms & ((1 << 48) - 1));
+        Ulid(timestamp << RANDOM_BITS | (random & RANDOM_MASK))
+    }
+
+    /// Milliseconds since the Unix epoch.
+    pub fn timestamp_ms(&self) -> u64 {
+        (self.0 >> RANDOM_BITS) as u64
+    }
+
+    /// The 80 random bits.
+    pub fn random(&self) -> u128 {
+        self.0 & RANDOM_MASK
+    }
+
+    pub fn from_bytes(bytes: [u8; 16]) -> Ulid {
+        Ulid(u128::from_be_bytes(bytes))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+}
+
+#[cfg(feature = "random")]
+fn random_bits() -> u128 {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).expect("operating system random number generator failed");
+    u128::from_be_bytes(bytes)
+}
+
+#[cfg(not(feature = "random"))]
+fn hashed_bits(nanos: u32) -> u128 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let mut words = [0u64; 2];
+    for (i, word) in words.iter_mut().enumerate() {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(count);
+        hasher.write_u32(nanos);
+        hasher.write_usize(i);
+        *word = hasher.finish();
+    }
+    u128::from(words[0]) << 64 | u128::from(words[1])
+}
+
+impl From<u128> for Ulid {
+    fn from(value: u128) -> Ulid {
+        Ulid(value)
+    }
+}
+
+impl From<Ulid> for u128 {
+    fn from(value: Ulid) -> u128 {
+        value.0
+    }
+}
+
+impl fmt::Display for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out = [0u8; 26];
+        for (i, c) in out.iter_mut().enumerate() {
+            let shift = 5 * (25 - i);
+            *c = CROCKFORD[(self.0 >> shift) as usize & 0x1F];
+        }
+        f.write_str(std::str::from_utf8(&out).expect("Crockford alphabet is ASCII"))
+    }
+}
+
+impl FromStr for Ulid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Ulid> {
+        if s.len() != 26 {
+            return Err(Error::Message(format!("ULID must be 26 characters, got {}", s.len())));
+        }
+        let mut value: u128 = 0;
+        for (i, c) in s.bytes().enumerate() {
+            let digit = match c.to_ascii_uppercase() {
+                b'O' => 0,
+                b'I' | b'L' => 1,
+                c => CROCKFORD
+                    .iter()
+                    .position(|&d| d == c)
+                    .ok_or_else(|| Error::Message(format!("invalid ULID character {:?}", c as char)))?
+                    as u128,
+            };
+            if i == 0 && digit > 7 {
+                return Err(Error::Message("ULID overflows 128 bits".into()));
+            }
+            value = value << 5 | digit;
+        }
+        Ok(Ulid(value))
+    }
+}
+
+impl Serialize for Ulid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        FixedBytes(self.to_bytes()).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ulid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        FixedBytes::<16>::deserialize(deserializer).map(|bytes| Ulid::from_bytes(bytes.0))
+    }
+}
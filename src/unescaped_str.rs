@@ -0,0 +1,105 @@
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTupleStruct, Serializer};
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::fixed_bytes::{FIXED_BYTES_NAME, TERMINATED_BYTES_NAME};
+
+/// A string known to contain no `'\0'`, encoded without escaping.
+///
+/// A plain `str` escapes every `0x00` and ends with the two-byte terminator
+/// `0x00 0x00`. An `UnescapedStr` is checked once on construction and then
+/// written verbatim followed by a single `0x00`, like a C string, so
+/// encoding skips the escape pass and saves a byte. Keys still sort like
+/// the strings (`strcmp` order): the terminator is below every data byte,
+/// so a string sorts before its extensions.
+///
+/// The encoding differs from a `str`'s, so the two are not interchangeable
+/// within one keyspace.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::UnescapedStr;
+///
+/// let key = lexcode::to_bytes(&UnescapedStr::new("abc").unwrap()).unwrap();
+/// assert_eq!(key, b"abc\0");
+/// assert!(UnescapedStr::new("a\0b").is_err());
+///
+/// let decoded: UnescapedStr = lexcode::from_bytes(&key).unwrap();
+/// assert_eq!(decoded.as_str(), "abc");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct UnescapedStr(String);
+
+impl UnescapedStr {
+    /// Wrap `s`, which must not contain `'\0'`.
+    pub fn new(s: impl Into<String>) -> Result<UnescapedStr> {
+        let s = s.into();
+        if s.contains('\0') {
+            return Err(Error::Message(format!("{s:?} contains a NUL character")));
+        }
+        Ok(UnescapedStr(s))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for UnescapedStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for UnescapedStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Serialize for UnescapedStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut ts = serializer.serialize_tuple_struct(FIXED_BYTES_NAME, self.0.len() + 1)?;
+        for byte in self.0.as_bytes() {
+            ts.serialize_field(byte)?;
+        }
+        ts.serialize_field(&0x00u8)?;
+        ts.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for UnescapedStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct UnescapedStrVisitor;
+
+        impl<'de> Visitor<'de> for UnescapedStrVisitor {
+            type Value = UnescapedStr;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a NUL-terminated string")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<UnescapedStr, A::Error> {
+                let mut bytes = Vec::new();
+                loop {
+                    match seq.next_element::<u8>()? {
+                        Some(0x00) => break,
+                        Some(byte) => bytes.push(byte),
+                        None => return Err(de::Error::custom("unterminated string")),
+                    }
+                }
+                String::from_utf8(bytes)
+                    .map(UnescapedStr)
+                    .map_err(|_| de::Error::custom("string is not valid UTF-8"))
+            }
+        }
+
+        deserializer.deserialize_tuple_struct(TERMINATED_BYTES_NAME, usize::MAX, UnescapedStrVisitor)
+    }
+}
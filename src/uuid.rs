@@ -0,0 +1,33 @@
+//! `#[serde(with = "lexcode::uuid")]` for [`uuid::Uuid`](::uuid::Uuid)
+//! (feature `uuid`).
+//!
+//! lexcode reports itself as a human-readable format, so `Uuid` serializes
+//! itself as a 36-character hyphenated string. This helper writes the 16
+//! bytes verbatim instead, like `FixedBytes<16>`, so
+//! the key is always exactly 16 bytes and sorts like `Uuid`'s `Ord`.
+//! Time-ordered versions (v6, v7) therefore sort by creation time.
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use uuid::Uuid;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Row(#[serde(with = "lexcode::uuid")] Uuid);
+//!
+//! let id = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+//! let bytes = lexcode::to_bytes(&Row(id)).unwrap();
+//! assert_eq!(bytes, id.as_bytes());
+//! ```
+
+use ::uuid::Uuid;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::fixed_bytes::FixedBytes;
+
+pub fn serialize<S: Serializer>(value: &Uuid, serializer: S) -> Result<S::Ok, S::Error> {
+    FixedBytes(*value.as_bytes()).serialize(serializer)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Uuid, D::Error> {
+    FixedBytes::<16>::deserialize(deserializer).map(|bytes| Uuid::from_bytes(bytes.0))
+}
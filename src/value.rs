@@ -0,0 +1,79 @@
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, SerializeTuple};
+
+use crate::fixed_bytes::RawBytes;
+
+/// A dynamically-typed lexcode value.
+///
+/// `Value` serializes to exactly the bytes the corresponding Rust type would
+/// produce, so it can stand in for types that are only known at runtime
+/// (command-line tooling, bindings to other languages). Because the format is
+/// not self-describing, decoding a `Value` requires a [`Schema`](crate::Schema).
+///
+/// Integers are stored widened to 128 bits; since lexcode's varint encoding
+/// is width-independent this does not change the encoded bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    UInt(u128),
+    Int(i128),
+    F32(f32),
+    F64(f64),
+    Char(char),
+    String(String),
+    /// A sentinel-escaped byte string (`&[u8]`, `serde_bytes`).
+    Bytes(Vec<u8>),
+    /// Raw bytes encoded like [`FixedBytes`](crate::FixedBytes).
+    Fixed(Vec<u8>),
+    Option(Option<Box<Value>>),
+    Seq(Vec<Value>),
+    Tuple(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    /// An enum variant: its index followed by its payload (`Unit` for unit
+    /// variants, `Tuple` for tuple and struct variants).
+    Variant(u32, Box<Value>),
+}
+
+impl Serialize for Value {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Unit => serializer.serialize_unit(),
+            Value::Bool(v) => serializer.serialize_bool(*v),
+            Value::UInt(v) => serializer.serialize_u128(*v),
+            Value::Int(v) => serializer.serialize_i128(*v),
+            Value::F32(v) => serializer.serialize_f32(*v),
+            Value::F64(v) => serializer.serialize_f64(*v),
+            Value::Char(v) => serializer.serialize_char(*v),
+            Value::String(v) => serializer.serialize_str(v),
+            Value::Bytes(v) => serializer.serialize_bytes(v),
+            Value::Fixed(v) => RawBytes(v).serialize(serializer),
+            Value::Option(None) => serializer.serialize_none(),
+            Value::Option(Some(v)) => serializer.serialize_some(v),
+            Value::Seq(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            }
+            Value::Tuple(items) => {
+                let mut tuple = serializer.serialize_tuple(items.len())?;
+                for item in items {
+                    tuple.serialize_element(item)?;
+                }
+                tuple.end()
+            }
+            Value::Map(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (k, v) in entries {
+                    map.serialize_entry(k, v)?;
+                }
+                map.end()
+            }
+            Value::Variant(index, payload) => match **payload {
+                Value::Unit => serializer.serialize_unit_variant("Value", *index, ""),
+                ref payload => serializer.serialize_newtype_variant("Value", *index, "", payload),
+            },
+        }
+    }
+}
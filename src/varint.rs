@@ -1,20 +1,61 @@
-/// Variable-length integer encoding that preserves lexicographic ordering.
-///
-/// # Unsigned encoding
-///
-/// Uses a unary-prefix scheme: the number of leading 1-bits across header
-/// byte(s) determines how many extra data bytes follow. Smaller values use
-/// fewer bytes; larger values grow up to 18 bytes for u128.
-///
-/// # Signed encoding
-///
-/// Bit 7 of the first byte is the sign bit (1 = non-negative, 0 = negative).
-/// The remaining 7 bits begin the same unary-prefix scheme for the magnitude.
-/// Negative values encode (|v| - 1) and then complement all bits (except
-/// leaving the sign bit as 0), so more-negative values produce smaller bytes.
+//! Variable-length integer encoding that preserves lexicographic ordering.
+//!
+//! These are the integer encodings lexcode writes for `u8`–`u128` and
+//! `i8`–`i128`, exposed for storage engines that want to build keys or
+//! lengths by hand without going through serde. Comparing two encodings as
+//! bytes gives the same result as comparing the integers, and an encoding
+//! is never a prefix of another, so they can be concatenated freely.
+//!
+//! ```
+//! use lexcode::varint;
+//!
+//! let mut key = Vec::new();
+//! varint::encode_uint(300, &mut key);
+//! varint::encode_sint(-5, &mut key);
+//! let (a, len) = varint::decode_uint(&key).unwrap();
+//! let (b, _) = varint::decode_sint(&key[len..]).unwrap();
+//! assert_eq!((a, b), (300, -5));
+//! assert_eq!(key, lexcode::to_bytes(&(300u64, -5i32)).unwrap());
+//! ```
+//!
+//! # Stability
+//!
+//! The bytes written by these functions are part of lexcode's wire format
+//! ([`FormatVersion::V1`](crate::FormatVersion::V1)) and will not change
+//! within it. A value encodes to the same bytes whichever integer type it
+//! came from, so a `u32` written here can be read back by `from_bytes` as
+//! a `u64`, and the other way round.
+//!
+//! # SQLite4 varints
+//!
+//! [`encode_sqlite4`] and [`decode_sqlite4`] implement the order-preserving
+//! varint of SQLite4 instead, for keyspaces designed around it. It covers
+//! `u64` only, in 1 to 9 bytes: values up to 240 take one byte, up to 2287
+//! two and up to 67823 three. Set [`VarintFormat::Sqlite4`] on a
+//! [`LexConfig`](crate::LexConfig) to use it for the unsigned integers of
+//! serialized values.
+//!
+//! # Unsigned encoding
+//!
+//! Uses a unary-prefix scheme: the number of leading 1-bits across header
+//! byte(s) determines how many extra data bytes follow. Smaller values use
+//! fewer bytes; larger values grow up to 18 bytes for u128.
+//!
+//! # Signed encoding
+//!
+//! Bit 7 of the first byte is the sign bit (1 = non-negative, 0 = negative).
+//! The remaining 7 bits begin the same unary-prefix scheme for the magnitude.
+//! Negative values encode (|v| - 1) and then complement all bits (except
+//! leaving the sign bit as 0), so more-negative values produce smaller bytes.
+
+use std::io::{self, Read};
 
 use crate::error::{Error, Result};
 
+/// The longest encoding produced by either scheme (a `u128` at level 16,
+/// or an `i128` at level 15).
+pub const MAX_LEN: usize = 18;
+
 // ── Level tables ───────────────────────────────────────────────────────
 
 /// For unsigned encoding (8-bit first header):
@@ -75,21 +116,57 @@ const fn compute_signed_offsets() -> [u128; 16] {
     offsets
 }
 
-fn find_level(v: u128, offsets: &[u128]) -> usize {
-    let last = offsets.len() - 1;
-    for level in 0..last {
-        if v < offsets[level + 1] {
-            return level;
+/// For each bit length 0..=128, the lowest level whose data bits hold a
+/// value that long. Since consecutive levels differ by at least 7 data
+/// bits, the value's level is that one or the one below it.
+const UNSIGNED_LEVEL_BY_BITS: [u8; 129] = level_by_bits(&UNSIGNED_DATA_BITS);
+const SIGNED_LEVEL_BY_BITS: [u8; 129] = level_by_bits(&SIGNED_DATA_BITS);
+
+const fn level_by_bits(data_bits: &[u32]) -> [u8; 129] {
+    let mut table = [0u8; 129];
+    let mut bits = 0;
+    let mut level = 0;
+    while bits <= 128 {
+        while level < data_bits.len() - 1 && data_bits[level] < bits {
+            level += 1;
         }
+        table[bits as usize] = level as u8;
+        bits += 1;
     }
-    last
+    table
+}
+
+/// The level of `v`, without a loop: look up the candidate for its bit
+/// length and step down one if it lies below that level's offset.
+const fn find_level(v: u128, offsets: &[u128], by_bits: &[u8; 129]) -> usize {
+    let level = by_bits[(128 - v.leading_zeros()) as usize] as usize;
+    level - (v < offsets[level]) as usize
 }
 
 // ── Unsigned encoding ──────────────────────────────────────────────────
 
-/// Encode an unsigned integer into variable-length bytes.
+/// Append the encoding of an unsigned integer to `out`.
+///
+/// Takes between 1 and [`MAX_LEN`] bytes: values below 128 take one byte,
+/// below 16,512 two, and so on.
 pub fn encode_uint(v: u128, out: &mut Vec<u8>) {
-    let level = find_level(v, &UNSIGNED_OFFSETS);
+    let (bytes, len) = encode_uint_to_array(v);
+    out.extend_from_slice(&bytes[..len]);
+}
+
+/// Encode an unsigned integer into a stack buffer, without allocating.
+///
+/// Returns the buffer and the length of the encoding, which is
+/// `&bytes[..len]`; the bytes after it are zero. Usable in constants; see
+/// also [`const_encode_u64`].
+///
+/// ```
+/// let (bytes, len) = lexcode::varint::encode_uint_to_array(300);
+/// assert_eq!(&bytes[..len], lexcode::to_bytes(&300u32).unwrap());
+/// ```
+pub const fn encode_uint_to_array(v: u128) -> ([u8; MAX_LEN], usize) {
+    let mut out = Buf::new();
+    let level = find_level(v, &UNSIGNED_OFFSETS, &UNSIGNED_LEVEL_BY_BITS);
     let data = v - UNSIGNED_OFFSETS[level];
 
     if level <= 7 {
@@ -112,11 +189,57 @@ pub fn encode_uint(v: u128, out: &mut Vec<u8>) {
     }
 
     // Write `level` extra data bytes in big-endian
-    write_be_tail(data, level, out);
+    write_be_tail(data, level, &mut out);
+    (out.bytes, out.len)
+}
+
+/// Encode a `u64` in a constant, such as a table prefix baked into the
+/// binary.
+///
+/// `N` must be the length of the encoding, [`encoded_len_uint`] of `v`;
+/// anything else fails to compile when evaluated in a constant, and panics
+/// otherwise.
+///
+/// ```
+/// use lexcode::varint::encoded_len_uint;
+///
+/// const TABLE: u64 = 300;
+/// const PREFIX: [u8; encoded_len_uint(TABLE as u128)] = lexcode::const_encode_u64(TABLE);
+/// assert_eq!(PREFIX.to_vec(), lexcode::to_bytes(&TABLE).unwrap());
+/// ```
+///
+/// ```compile_fail
+/// const PREFIX: [u8; 1] = lexcode::const_encode_u64(300);
+/// ```
+pub const fn const_encode_u64<const N: usize>(v: u64) -> [u8; N] {
+    let (bytes, len) = encode_uint_to_array(v as u128);
+    assert!(len == N, "const_encode_u64: N must be encoded_len_uint(v)");
+    let mut out = [0; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = bytes[i];
+        i += 1;
+    }
+    out
 }
 
-/// Decode a variable-length unsigned integer.
-/// Returns (value, bytes_consumed).
+/// The number of bytes [`encode_uint`] writes for `v`, without encoding it.
+///
+/// ```
+/// assert_eq!(lexcode::varint::encoded_len_uint(127), 1);
+/// assert_eq!(lexcode::varint::encoded_len_uint(128), 2);
+/// assert_eq!(lexcode::varint::encoded_len_uint(u128::MAX), lexcode::varint::MAX_LEN);
+/// ```
+pub const fn encoded_len_uint(v: u128) -> usize {
+    let level = find_level(v, &UNSIGNED_OFFSETS, &UNSIGNED_LEVEL_BY_BITS);
+    // One header byte up to level 7, two after.
+    level + if level <= 7 { 1 } else { 2 }
+}
+
+/// Decode an unsigned integer from the start of `input`.
+///
+/// Returns the value and the number of bytes it took; any bytes after it
+/// are left alone. Fails with [`Error::Eof`] if `input` ends early.
 pub fn decode_uint(input: &[u8]) -> Result<(u128, usize)> {
     if input.is_empty() {
         return Err(Error::Eof);
@@ -149,30 +272,78 @@ pub fn decode_uint(input: &[u8]) -> Result<(u128, usize)> {
     }
 
     let data = assemble_be(header_data, &input[header_len..total]);
-    Ok((data + UNSIGNED_OFFSETS[level], total))
+    let v = data.checked_add(UNSIGNED_OFFSETS[level]).ok_or_else(overflow)?;
+    Ok((v, total))
+}
+
+/// Decode an unsigned integer from a stream, reading exactly its bytes.
+///
+/// The header byte(s) are read first to learn the length, then the rest in
+/// one `read_exact`, so nothing after the integer is consumed. A stream
+/// that ends early fails with [`io::ErrorKind::UnexpectedEof`], and an
+/// encoding that does not fit in a `u128` with [`io::ErrorKind::InvalidData`].
+pub fn decode_uint_from_reader<R: Read>(mut reader: R) -> io::Result<u128> {
+    let mut buf = [0; MAX_LEN];
+    reader.read_exact(&mut buf[..1])?;
+    let (header_len, total) = if buf[0] != 0xFF {
+        (1, 1 + buf[0].leading_ones() as usize)
+    } else {
+        reader.read_exact(&mut buf[1..2])?;
+        (2, 2 + 8 + buf[1].leading_ones() as usize)
+    };
+    reader.read_exact(&mut buf[header_len..total])?;
+    decode_uint(&buf[..total]).map(|(v, _)| v).map_err(invalid_data)
 }
 
 // ── Signed encoding ────────────────────────────────────────────────────
 
-/// Encode a signed integer into variable-length bytes.
+/// Append the encoding of a signed integer to `out`.
+///
+/// Takes between 1 and [`MAX_LEN`] bytes: values from -64 to 63 take one
+/// byte, and negative values sort before non-negative ones.
 pub fn encode_sint(v: i128, out: &mut Vec<u8>) {
+    let (bytes, len) = encode_sint_to_array(v);
+    out.extend_from_slice(&bytes[..len]);
+}
+
+/// Encode a signed integer into a stack buffer, without allocating.
+///
+/// Returns the buffer and the length of the encoding, which is
+/// `&bytes[..len]`; the bytes after it are zero. Usable in constants.
+pub const fn encode_sint_to_array(v: i128) -> ([u8; MAX_LEN], usize) {
+    let mut buf = Buf::new();
     if v >= 0 {
-        let start = out.len();
-        encode_sint_magnitude(v as u128, out);
-        out[start] |= 0x80; // set sign bit = 1 (non-negative)
+        encode_sint_magnitude(v as u128, &mut buf);
+        buf.bytes[0] |= 0x80; // set sign bit = 1 (non-negative)
     } else {
         let magnitude = (-(v + 1)) as u128;
-        let start = out.len();
-        encode_sint_magnitude(magnitude, out);
-        out[start] |= 0x80; // temporarily set sign = 1 so complement makes it 0
-        for b in &mut out[start..] {
-            *b = !*b;
+        encode_sint_magnitude(magnitude, &mut buf);
+        buf.bytes[0] |= 0x80; // temporarily set sign = 1 so complement makes it 0
+        let mut i = 0;
+        while i < buf.len {
+            buf.bytes[i] = !buf.bytes[i];
+            i += 1;
         }
     }
+    (buf.bytes, buf.len)
 }
 
-/// Decode a variable-length signed integer.
-/// Returns (value, bytes_consumed).
+/// The number of bytes [`encode_sint`] writes for `v`, without encoding it.
+pub const fn encoded_len_sint(v: i128) -> usize {
+    let magnitude = if v >= 0 { v as u128 } else { (-(v + 1)) as u128 };
+    let level = find_level(magnitude, &SIGNED_OFFSETS, &SIGNED_LEVEL_BY_BITS);
+    // One header byte up to level 6, two up to level 14, three at level 15.
+    level + match level {
+        0..=6 => 1,
+        7..=14 => 2,
+        _ => 3,
+    }
+}
+
+/// Decode a signed integer from the start of `input`.
+///
+/// Returns the value and the number of bytes it took; any bytes after it
+/// are left alone. Fails with [`Error::Eof`] if `input` ends early.
 pub fn decode_sint(input: &[u8]) -> Result<(i128, usize)> {
     if input.is_empty() {
         return Err(Error::Eof);
@@ -183,7 +354,7 @@ pub fn decode_sint(input: &[u8]) -> Result<(i128, usize)> {
     if positive {
         let first_sub = input[0] & 0x7F;
         let (mag, consumed) = decode_sint_magnitude(first_sub, &input[1..])?;
-        Ok((mag as i128, consumed))
+        Ok((signed_magnitude(mag)?, consumed))
     } else {
         // Determine length, complement, decode
         let first_complemented = !input[0];
@@ -192,21 +363,40 @@ pub fn decode_sint(input: &[u8]) -> Result<(i128, usize)> {
         if input.len() < total_len {
             return Err(Error::Eof);
         }
-        let mut buf = Vec::with_capacity(total_len);
-        for i in 0..total_len {
-            buf.push(!input[i]);
-        }
+        let buf: Vec<u8> = input[..total_len].iter().map(|b| !b).collect();
         let sub = buf[0] & 0x7F;
         let (mag, consumed) = decode_sint_magnitude(sub, &buf[1..])?;
         debug_assert_eq!(consumed, total_len);
-        Ok((-(mag as i128) - 1, total_len))
+        Ok((-signed_magnitude(mag)? - 1, total_len))
     }
 }
 
+/// Decode a signed integer from a stream, reading exactly its bytes.
+///
+/// Like [`decode_uint_from_reader`], nothing after the integer is consumed.
+pub fn decode_sint_from_reader<R: Read>(mut reader: R) -> io::Result<i128> {
+    let mut buf = [0; MAX_LEN];
+    reader.read_exact(&mut buf[..1])?;
+    // Negative values are complemented; undo that to read the header.
+    let flip = if buf[0] & 0x80 == 0 { 0xFF } else { 0 };
+    let sub = (buf[0] ^ flip) & 0x7F;
+    let (header_len, total) = if sub != 0x7F {
+        (1, 1 + leading_ones_in_7bits(sub))
+    } else {
+        reader.read_exact(&mut buf[1..2])?;
+        match buf[1] ^ flip {
+            0xFF => (2, MAX_LEN), // level 15: a third header byte and 15 data bytes
+            second => (2, 2 + 7 + second.leading_ones() as usize),
+        }
+    };
+    reader.read_exact(&mut buf[header_len..total])?;
+    decode_sint(&buf[..total]).map(|(v, _)| v).map_err(invalid_data)
+}
+
 /// Encode magnitude using the 7-bit sub-header scheme.
 /// Bit 7 of first byte is left as 0 (caller sets sign bit).
-fn encode_sint_magnitude(v: u128, out: &mut Vec<u8>) {
-    let level = find_level(v, &SIGNED_OFFSETS);
+const fn encode_sint_magnitude(v: u128, out: &mut Buf) {
+    let level = find_level(v, &SIGNED_OFFSETS, &SIGNED_LEVEL_BY_BITS);
     let data = v - SIGNED_OFFSETS[level];
 
     if level <= 6 {
@@ -291,20 +481,267 @@ fn sint_total_len(sub: u8, rest: &[u8], complemented: bool) -> Result<usize> {
     }
 }
 
+// ── 64-bit fast paths ──────────────────────────────────────────────────
+
+/// `UNSIGNED_OFFSETS` up to level 8, the highest a `u64` reaches.
+const U64_OFFSETS: [u64; 9] = narrow_offsets(&UNSIGNED_OFFSETS);
+
+/// `SIGNED_OFFSETS` up to level 7, the highest an `i64` magnitude reaches.
+const I64_OFFSETS: [u64; 8] = narrow_offsets(&SIGNED_OFFSETS);
+
+const fn narrow_offsets<const N: usize>(offsets: &[u128]) -> [u64; N] {
+    let mut narrow = [0u64; N];
+    let mut i = 0;
+    while i < N {
+        narrow[i] = offsets[i] as u64;
+        i += 1;
+    }
+    narrow
+}
+
+/// [`find_level`] with the 64-bit offset tables.
+fn find_level_u64(v: u64, offsets: &[u64], by_bits: &[u8; 129]) -> usize {
+    let level = by_bits[(64 - v.leading_zeros()) as usize] as usize;
+    level - (v < offsets[level]) as usize
+}
+
+/// Append the encoding of a `u64` to `out`.
+///
+/// Writes the same bytes as [`encode_uint`], with 64-bit arithmetic only.
+pub fn encode_u64(v: u64, out: &mut Vec<u8>) {
+    let level = find_level_u64(v, &U64_OFFSETS, &UNSIGNED_LEVEL_BY_BITS);
+    let data = v - U64_OFFSETS[level];
+    if level <= 7 {
+        // The header's prefix bits sit right above the data bits.
+        let word = (leading_ones_byte(level) as u64) << (8 * level) | data;
+        out.extend_from_slice(&word.to_be_bytes()[7 - level..]);
+    } else {
+        // Level 8: a second header with no prefix bits, and seven data
+        // bits that are all zero for a u64.
+        out.extend_from_slice(&[0xFF, 0x00]);
+        out.extend_from_slice(&data.to_be_bytes());
+    }
+}
+
+/// Decode a `u64` from the start of `input`.
+///
+/// Reads what [`decode_uint`] reads, with 64-bit arithmetic only, and fails
+/// if the value does not fit in a `u64`.
+pub fn decode_u64(input: &[u8]) -> Result<(u64, usize)> {
+    let first = *input.first().ok_or(Error::Eof)?;
+    if first != 0xFF {
+        let level = first.leading_ones() as usize;
+        let tail = input.get(1..1 + level).ok_or(Error::Eof)?;
+        let data = assemble_be_u64((first & low_mask_u8(7 - level)) as u64, tail);
+        return Ok((data + U64_OFFSETS[level], 1 + level));
+    }
+    if input.get(1) == Some(&0x00) {
+        let tail = input.get(2..10).ok_or(Error::Eof)?;
+        if let Some(v) = assemble_be_u64(0, tail).checked_add(U64_OFFSETS[8]) {
+            return Ok((v, 10));
+        }
+    }
+    // Too large for a u64, or truncated: let the general decoder report it.
+    let (v, len) = decode_uint(input)?;
+    let v = u64::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in u64".into()))?;
+    Ok((v, len))
+}
+
+/// Append the encoding of a `u32` to `out`, like [`encode_u64`].
+pub fn encode_u32(v: u32, out: &mut Vec<u8>) {
+    encode_u64(v as u64, out);
+}
+
+/// Decode a `u32` from the start of `input`, like [`decode_u64`].
+pub fn decode_u32(input: &[u8]) -> Result<(u32, usize)> {
+    let (v, len) = decode_u64(input)?;
+    let v = u32::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in u32".into()))?;
+    Ok((v, len))
+}
+
+/// Append the encoding of an `i64` to `out`.
+///
+/// Writes the same bytes as [`encode_sint`], with 64-bit arithmetic only.
+pub fn encode_i64(v: i64, out: &mut Vec<u8>) {
+    // A negative value encodes the magnitude `-(v + 1)`, which is `!v`,
+    // complemented.
+    let flip = if v < 0 { u64::MAX } else { 0 };
+    let magnitude = v as u64 ^ flip;
+    let level = find_level_u64(magnitude, &I64_OFFSETS, &SIGNED_LEVEL_BY_BITS);
+    let data = magnitude - I64_OFFSETS[level];
+    if level <= 6 {
+        let word = ((0x80 | leading_ones_7bit(level)) as u64) << (8 * level) | data;
+        out.extend_from_slice(&(word ^ flip).to_be_bytes()[7 - level..]);
+    } else {
+        // Level 7: the sub-header is exhausted, and the second header has
+        // no prefix bits, so it is the top byte of the 63 data bits.
+        out.push(0xFF ^ flip as u8);
+        out.extend_from_slice(&(data ^ flip).to_be_bytes());
+    }
+}
+
+/// Decode an `i64` from the start of `input`.
+///
+/// Reads what [`decode_sint`] reads, with 64-bit arithmetic only, and fails
+/// if the value does not fit in an `i64`.
+pub fn decode_i64(input: &[u8]) -> Result<(i64, usize)> {
+    let first = *input.first().ok_or(Error::Eof)?;
+    let flip = if first & 0x80 == 0 { u64::MAX } else { 0 };
+    let sub = (first ^ flip as u8) & 0x7F;
+    if sub != 0x7F {
+        let level = leading_ones_in_7bits(sub);
+        let tail = input.get(1..1 + level).ok_or(Error::Eof)?;
+        let data_bits = 6 + 7 * level;
+        let data = (assemble_be_u64(first as u64, tail) ^ flip) & ((1 << data_bits) - 1);
+        let magnitude = data + I64_OFFSETS[level];
+        return Ok(((magnitude ^ flip) as i64, 1 + level));
+    }
+    if input.get(1).is_some_and(|&second| (second ^ flip as u8) & 0x80 == 0) {
+        let tail = input.get(1..9).ok_or(Error::Eof)?;
+        let magnitude = (assemble_be_u64(0, tail) ^ flip).checked_add(I64_OFFSETS[7]);
+        if let Some(magnitude) = magnitude.filter(|&m| m <= i64::MAX as u64) {
+            return Ok(((magnitude ^ flip) as i64, 9));
+        }
+    }
+    // Too large for an i64, or truncated: let the general decoder report it.
+    let (v, len) = decode_sint(input)?;
+    let v = i64::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in i64".into()))?;
+    Ok((v, len))
+}
+
+/// Append the encoding of an `i32` to `out`, like [`encode_i64`].
+pub fn encode_i32(v: i32, out: &mut Vec<u8>) {
+    encode_i64(v as i64, out);
+}
+
+/// Decode an `i32` from the start of `input`, like [`decode_i64`].
+pub fn decode_i32(input: &[u8]) -> Result<(i32, usize)> {
+    let (v, len) = decode_i64(input)?;
+    let v = i32::try_from(v).map_err(|_| Error::Message("integer overflow: value does not fit in i32".into()))?;
+    Ok((v, len))
+}
+
+// ── SQLite4 varints ────────────────────────────────────────────────────
+
+/// The varint format a [`Serializer`](crate::Serializer) writes unsigned
+/// integers in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VarintFormat {
+    /// lexcode's own varint, [`encode_uint`].
+    #[default]
+    Lexcode,
+    /// The SQLite4 varint, [`encode_sqlite4`]. A `u128` above `u64::MAX`
+    /// cannot be written. Signed integers, `char`s, enum variant indexes
+    /// and lengths keep lexcode's varints, as SQLite4 has none for them.
+    Sqlite4,
+}
+
+/// Append the SQLite4 varint encoding of `v` to `out`.
+///
+/// ```
+/// let mut out = Vec::new();
+/// lexcode::varint::encode_sqlite4(2288, &mut out);
+/// assert_eq!(out, [249, 0, 0]);
+/// ```
+pub fn encode_sqlite4(v: u64, out: &mut Vec<u8>) {
+    match v {
+        0..=240 => out.push(v as u8),
+        241..=2287 => {
+            let v = v - 240;
+            out.extend_from_slice(&[(v >> 8) as u8 + 241, v as u8]);
+        }
+        2288..=67823 => {
+            let v = v - 2288;
+            out.extend_from_slice(&[249, (v >> 8) as u8, v as u8]);
+        }
+        _ => {
+            // 250 to 255: a header, then 3 to 8 big-endian bytes.
+            let len = (8 - v.leading_zeros() as usize / 8).max(3);
+            out.push(247 + len as u8);
+            out.extend_from_slice(&v.to_be_bytes()[8 - len..]);
+        }
+    }
+}
+
+/// Decode a SQLite4 varint from the start of `input`.
+///
+/// Returns the value and the number of bytes it took. Fails with
+/// [`Error::Eof`] if `input` ends early, and on an encoding that is longer
+/// than needed for its value, which would not sort in its place.
+pub fn decode_sqlite4(input: &[u8]) -> Result<(u64, usize)> {
+    let first = *input.first().ok_or(Error::Eof)?;
+    let (v, len) = match first {
+        0..=240 => return Ok((first as u64, 1)),
+        241..=248 => {
+            let second = *input.get(1).ok_or(Error::Eof)?;
+            return Ok((240 + 256 * (first as u64 - 241) + second as u64, 2));
+        }
+        249 => {
+            let tail = input.get(1..3).ok_or(Error::Eof)?;
+            return Ok((2288 + assemble_be_u64(0, tail), 3));
+        }
+        _ => {
+            let len = first as usize - 247;
+            let tail = input.get(1..1 + len).ok_or(Error::Eof)?;
+            (assemble_be_u64(0, tail), 1 + len)
+        }
+    };
+    // The shortest form for 250 is 67824 and up; for the others, a value
+    // that needs all their bytes.
+    let min = if first == 250 { 67824 } else { 1 << (8 * (len - 2)) };
+    if v < min {
+        return Err(Error::Message(format!("non-canonical SQLite4 varint for {v}")));
+    }
+    Ok((v, len))
+}
+
 // ── Helpers ────────────────────────────────────────────────────────────
 
+/// A fixed-size output buffer, large enough for any encoding.
+struct Buf {
+    bytes: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl Buf {
+    const fn new() -> Self {
+        Buf { bytes: [0; MAX_LEN], len: 0 }
+    }
+
+    const fn push(&mut self, b: u8) {
+        self.bytes[self.len] = b;
+        self.len += 1;
+    }
+}
+
+/// The error for an encoding whose value does not fit in 128 bits, which
+/// the encoders never produce.
+fn overflow() -> Error {
+    Error::Message("integer overflow: varint does not fit in 128 bits".into())
+}
+
+/// A decoding error reported through a reader.
+fn invalid_data(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// A decoded signed magnitude as an `i128`, if it fits.
+fn signed_magnitude(mag: u128) -> Result<i128> {
+    i128::try_from(mag).map_err(|_| overflow())
+}
+
 /// Count leading 1-bits in a 7-bit field (stored in bits 6..0 of a u8).
 fn leading_ones_in_7bits(v: u8) -> usize {
     (v << 1).leading_ones() as usize
 }
 
 /// A byte with `n` leading 1-bits followed by 0s.
-fn leading_ones_byte(n: usize) -> u8 {
+const fn leading_ones_byte(n: usize) -> u8 {
     if n == 0 { 0 } else if n >= 8 { 0xFF } else { !0u8 << (8 - n) }
 }
 
 /// Byte with bits 6..0 having `n` leading 1-bits (bit 7 always 0).
-fn leading_ones_7bit(n: usize) -> u8 {
+const fn leading_ones_7bit(n: usize) -> u8 {
     if n == 0 { 0 } else { leading_ones_byte(n) >> 1 }
 }
 
@@ -315,7 +752,7 @@ fn low_mask_u8(bits: usize) -> u8 {
 
 /// Extract the top `want` data bits from a value that has `extra_bytes*8` bits
 /// in the tail. The header data bits are above the tail, so we shift right.
-fn extract_top_bits(data: u128, extra_bytes: usize, want: usize) -> u128 {
+const fn extract_top_bits(data: u128, extra_bytes: usize, want: usize) -> u128 {
     if want == 0 {
         return 0;
     }
@@ -326,13 +763,15 @@ fn extract_top_bits(data: u128, extra_bytes: usize, want: usize) -> u128 {
     (data >> shift) & low_mask_128(want)
 }
 
-fn low_mask_128(bits: usize) -> u128 {
+const fn low_mask_128(bits: usize) -> u128 {
     if bits == 0 { 0 } else if bits >= 128 { u128::MAX } else { (1u128 << bits) - 1 }
 }
 
 /// Write the bottom `n` bytes of `data` in big-endian.
-fn write_be_tail(data: u128, n: usize, out: &mut Vec<u8>) {
-    for i in (0..n).rev() {
+const fn write_be_tail(data: u128, n: usize, out: &mut Buf) {
+    let mut i = n;
+    while i > 0 {
+        i -= 1;
         let shift = i * 8;
         out.push(if shift >= 128 { 0 } else { (data >> shift) as u8 });
     }
@@ -347,6 +786,15 @@ fn assemble_be(prefix: u128, bytes: &[u8]) -> u128 {
     v
 }
 
+/// [`assemble_be`] for at most eight bytes in all.
+fn assemble_be_u64(prefix: u64, bytes: &[u8]) -> u64 {
+    let mut v = prefix;
+    for &b in bytes {
+        v = (v << 8) | b as u64;
+    }
+    v
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,6 +808,33 @@ mod tests {
         assert_eq!(leading_ones_in_7bits(0b0111_1111), 7);
     }
 
+    /// The level a linear scan over the offsets finds.
+    fn scan_level(v: u128, offsets: &[u128]) -> usize {
+        offsets.iter().rposition(|&offset| v >= offset).unwrap()
+    }
+
+    #[test]
+    fn test_find_level_at_boundaries() {
+        for (offsets, by_bits) in [
+            (&UNSIGNED_OFFSETS[..], &UNSIGNED_LEVEL_BY_BITS),
+            (&SIGNED_OFFSETS[..], &SIGNED_LEVEL_BY_BITS),
+        ] {
+            let mut values = vec![0, u128::MAX >> 1];
+            for &offset in offsets {
+                values.extend([offset.saturating_sub(1), offset, offset.saturating_add(1)]);
+            }
+            for shift in 0..128 {
+                values.extend([(1u128 << shift) - 1, 1u128 << shift]);
+            }
+            if offsets.len() == UNSIGNED_OFFSETS.len() {
+                values.push(u128::MAX);
+            }
+            for v in values {
+                assert_eq!(find_level(v, offsets, by_bits), scan_level(v, offsets), "level of {v}");
+            }
+        }
+    }
+
     #[test]
     fn test_uint_roundtrip_small() {
         for v in 0u128..=300 {
@@ -0,0 +1,180 @@
+use semver::{BuildMetadata, Prerelease, Version};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::numeric_string::NumericString;
+use crate::option_last::OptionLast;
+
+/// A [`semver::Version`] that sorts by semver precedence (feature
+/// `semver`).
+///
+/// The encoding is:
+///
+/// - `major`, `minor` and `patch` as unsigned varints;
+/// - the pre-release as an [`OptionLast`] of its identifiers, so a release
+///   sorts after all of its pre-releases. Identifiers form a sequence, each
+///   one tagged `0x00` + a [`NumericString`] for numeric identifiers or
+///   `0x01` + a string otherwise, so numeric identifiers compare
+///   numerically and before alphanumeric ones, and a shorter list of
+///   identifiers sorts before a longer one it is a prefix of;
+/// - the build metadata as a string. It does not take part in semver
+///   precedence, and is only there to round-trip and to tell apart versions
+///   that differ in nothing else.
+///
+/// # Example
+///
+/// ```
+/// use lexcode::VersionKey;
+///
+/// let key = |v: &str| lexcode::to_bytes(&VersionKey(v.parse().unwrap())).unwrap();
+/// assert!(key("1.0.0-alpha") < key("1.0.0-alpha.1"));
+/// assert!(key("1.0.0-alpha.2") < key("1.0.0-alpha.10"));
+/// assert!(key("1.0.0-rc.1") < key("1.0.0"));
+/// assert!(key("1.9.0") < key("1.10.0"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VersionKey(pub Version);
+
+impl VersionKey {
+    pub fn new(version: Version) -> VersionKey {
+        VersionKey(version)
+    }
+
+    pub fn into_inner(self) -> Version {
+        self.0
+    }
+}
+
+/// A pre-release identifier in precedence order.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Ident<'a> {
+    Numeric(NumericString),
+    Alphanumeric(&'a str),
+}
+
+impl<'a> Ident<'a> {
+    fn parse(s: &'a str) -> Ident<'a> {
+        match NumericString::new(s) {
+            Ok(n) => Ident::Numeric(n),
+            Err(_) => Ident::Alphanumeric(s),
+        }
+    }
+}
+
+impl Serialize for Ident<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Ident::Numeric(n) => serializer.serialize_newtype_variant("Ident", 0, "Numeric", n),
+            Ident::Alphanumeric(s) => serializer.serialize_newtype_variant("Ident", 1, "Alphanumeric", s),
+        }
+    }
+}
+
+/// An owned pre-release identifier, as decoded.
+#[derive(serde::Deserialize)]
+enum OwnedIdent {
+    Numeric(NumericString),
+    Alphanumeric(String),
+}
+
+fn idents(pre: &Prerelease) -> OptionLast<Vec<Ident<'_>>> {
+    if pre.is_empty() {
+        OptionLast(None)
+    } else {
+        OptionLast(Some(pre.as_str().split('.').map(Ident::parse).collect()))
+    }
+}
+
+impl PartialOrd for VersionKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for VersionKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (&self.0, &other.0);
+        (a.major, a.minor, a.patch)
+            .cmp(&(b.major, b.minor, b.patch))
+            .then_with(|| idents(&a.pre).cmp(&idents(&b.pre)))
+            .then_with(|| a.build.as_str().cmp(b.build.as_str()))
+    }
+}
+
+impl From<Version> for VersionKey {
+    fn from(version: Version) -> VersionKey {
+        VersionKey(version)
+    }
+}
+
+impl From<VersionKey> for Version {
+    fn from(key: VersionKey) -> Version {
+        key.0
+    }
+}
+
+impl fmt::Display for VersionKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Serialize for VersionKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let v = &self.0;
+        let mut tuple = serializer.serialize_tuple(5)?;
+        tuple.serialize_element(&v.major)?;
+        tuple.serialize_element(&v.minor)?;
+        tuple.serialize_element(&v.patch)?;
+        tuple.serialize_element(&idents(&v.pre))?;
+        tuple.serialize_element(v.build.as_str())?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VersionKeyVisitor;
+
+        impl<'de> Visitor<'de> for VersionKeyVisitor {
+            type Value = VersionKey;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a semver version")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<VersionKey, A::Error> {
+                let mut next_u64 = |i| -> Result<u64, A::Error> {
+                    seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))
+                };
+                let (major, minor, patch) = (next_u64(0)?, next_u64(1)?, next_u64(2)?);
+                let pre: OptionLast<Vec<OwnedIdent>> = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+                let build: String = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(4, &self))?;
+
+                let pre = match pre.0 {
+                    None => Prerelease::EMPTY,
+                    Some(idents) => {
+                        let idents: Vec<String> = idents
+                            .into_iter()
+                            .map(|ident| match ident {
+                                OwnedIdent::Numeric(n) => n.into_string(),
+                                OwnedIdent::Alphanumeric(s) => s,
+                            })
+                            .collect();
+                        Prerelease::new(&idents.join(".")).map_err(de::Error::custom)?
+                    }
+                };
+                let build = BuildMetadata::new(&build).map_err(de::Error::custom)?;
+                Ok(VersionKey(Version { major, minor, patch, pre, build }))
+            }
+        }
+
+        deserializer.deserialize_tuple(5, VersionKeyVisitor)
+    }
+}
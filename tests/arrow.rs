@@ -0,0 +1,109 @@
+#![cfg(feature = "arrow")]
+
+use arrow_array::{
+    BinaryArray, BooleanArray, FixedSizeBinaryArray, Float64Array, Int32Array, LargeStringArray,
+    ListArray, StringViewArray, TimestampMillisecondArray, UInt64Array,
+};
+use arrow_array::types::Int32Type;
+use lexcode::arrow::{encode_columns, KeyColumn};
+use lexcode::{Descending, FixedBytes};
+use proptest::prelude::*;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Blob(#[serde(with = "lexcode::as_bytes")] Vec<u8>);
+
+type Input = (u64, Option<String>, Vec<u8>, Option<f64>);
+type Row = (u64, Descending<Option<String>>, Blob, Option<f64>);
+
+fn rows() -> impl Strategy<Value = Vec<Input>> {
+    prop::collection::vec(
+        (0u64..4, prop::option::of("[a\0]{0,3}"), prop::collection::vec(0u8..3, 0..3), prop::option::of(-2.0f64..2.0)),
+        0..20,
+    )
+}
+
+fn encode(rows: &[Input]) -> lexcode::Result<lexcode::arrow::EncodedKeys> {
+    let ids = UInt64Array::from_iter_values(rows.iter().map(|row| row.0));
+    let names = LargeStringArray::from_iter(rows.iter().map(|row| row.1.clone()));
+    let blobs = BinaryArray::from_iter_values(rows.iter().map(|row| row.2.clone()));
+    let scores = Float64Array::from_iter(rows.iter().map(|row| row.3));
+    encode_columns(&[
+        KeyColumn::new(&ids),
+        KeyColumn::new(&names).nullable(true).descending(true),
+        KeyColumn::new(&blobs),
+        KeyColumn::new(&scores).nullable(true),
+    ])
+}
+
+proptest! {
+    #[test]
+    fn keys_match_to_bytes_of_the_row(rows in rows()) {
+        let keys = encode(&rows)?;
+        prop_assert_eq!(keys.len(), rows.len());
+        prop_assert_eq!(keys.offsets().len(), rows.len() + 1);
+        for (key, (id, name, blob, score)) in keys.iter().zip(rows) {
+            let row: Row = (id, Descending(name), Blob(blob), score);
+            prop_assert_eq!(key, &lexcode::to_bytes(&row)?[..]);
+        }
+    }
+
+    #[test]
+    fn keys_concatenate_into_data(rows in rows()) {
+        let keys = encode(&rows)?;
+        prop_assert_eq!(keys.iter().flatten().copied().collect::<Vec<u8>>(), keys.data());
+    }
+}
+
+#[test]
+fn other_types_match_their_rust_equivalents() {
+    let flags = BooleanArray::from(vec![true, false]);
+    let days = Int32Array::from(vec![-3, 19000]);
+    let times = TimestampMillisecondArray::from(vec![Some(5), None]).with_timezone("UTC");
+    let names = StringViewArray::from(vec!["x", "a longer string than twelve bytes"]);
+    let hashes = FixedSizeBinaryArray::try_from_iter([[1u8, 0], [0, 2]].into_iter()).unwrap();
+    let keys = encode_columns(&[
+        KeyColumn::new(&flags).descending(true),
+        KeyColumn::new(&days),
+        KeyColumn::new(&times).nullable(true),
+        KeyColumn::new(&names),
+        KeyColumn::new(&hashes),
+    ])
+    .unwrap();
+
+    let expected = [
+        (Descending(true), -3i32, Some(5i64), "x", FixedBytes([1u8, 0])),
+        (Descending(false), 19000, None, "a longer string than twelve bytes", FixedBytes([0, 2])),
+    ];
+    assert_eq!(keys.len(), 2);
+    for (key, row) in keys.iter().zip(expected) {
+        assert_eq!(key, lexcode::to_bytes(&row).unwrap());
+    }
+}
+
+#[test]
+fn no_columns_give_no_keys() {
+    let keys = encode_columns(&[]).unwrap();
+    assert!(keys.is_empty());
+    assert_eq!(keys.offsets(), [0]);
+}
+
+#[test]
+fn null_in_a_non_nullable_column_is_an_error() {
+    let values = Int32Array::from(vec![Some(1), None]);
+    let err = encode_columns(&[KeyColumn::new(&values)]).unwrap_err();
+    assert!(err.to_string().contains("row 1"), "{err}");
+}
+
+#[test]
+fn columns_of_different_lengths_are_an_error() {
+    let a = Int32Array::from(vec![1, 2]);
+    let b = Int32Array::from(vec![1]);
+    assert!(encode_columns(&[KeyColumn::new(&a), KeyColumn::new(&b)]).is_err());
+}
+
+#[test]
+fn unsupported_types_are_an_error() {
+    let lists = ListArray::from_iter_primitive::<Int32Type, _, _>([Some([Some(1)])]);
+    assert!(encode_columns(&[KeyColumn::new(&lists)]).is_err());
+}
@@ -0,0 +1,32 @@
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Key {
+    #[serde(with = "lexcode::as_bytes")]
+    blob: Vec<u8>,
+    #[serde(with = "lexcode::as_fixed")]
+    hash: [u8; 4],
+    tail: u8,
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in (prop::collection::vec(any::<u8>(), 0..6), any::<[u8; 4]>(), any::<u8>()),
+                           b in (prop::collection::vec(any::<u8>(), 0..6), any::<[u8; 4]>(), any::<u8>())) {
+        let a = Key { blob: a.0, hash: a.1, tail: a.2 };
+        let b = Key { blob: b.0, hash: b.1, tail: b.2 };
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Key>(&bytes_a)?, a);
+    }
+}
+
+#[test]
+fn more_compact_than_serde_defaults() {
+    let key = Key { blob: vec![0xFF; 10], hash: [0xFF; 4], tail: 0 };
+    assert_eq!(lexcode::to_bytes(&key).unwrap().len(), 10 + 2 + 4 + 1);
+    let plain = lexcode::to_bytes(&(vec![0xFFu8; 10], [0xFFu8; 4], 0u8)).unwrap();
+    assert!(plain.len() > 30);
+}
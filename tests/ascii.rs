@@ -0,0 +1,46 @@
+use proptest::prelude::*;
+
+fn bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(prop_oneof![Just(0u8), Just(0xFF), any::<u8>()], 0..12)
+}
+
+proptest! {
+    #[test]
+    fn roundtrip(bytes in bytes()) {
+        let armored = lexcode::encode_ascii(&bytes);
+        prop_assert!(armored.bytes().all(|c| c.is_ascii_digit() || (b'a'..=b'v').contains(&c)));
+        prop_assert_eq!(lexcode::decode_ascii(&armored)?, bytes.clone());
+        prop_assert_eq!(lexcode::decode_ascii(&armored.to_ascii_uppercase())?, bytes);
+    }
+
+    #[test]
+    fn order_is_preserved(a in bytes(), b in bytes()) {
+        prop_assert_eq!(lexcode::encode_ascii(&a).cmp(&lexcode::encode_ascii(&b)), a.cmp(&b));
+    }
+
+    #[test]
+    fn keys_roundtrip_and_sort_like_their_bytes(a in any::<(i64, String)>(), b in any::<(i64, String)>()) {
+        let (ka, kb) = (lexcode::to_ascii_key(&a)?, lexcode::to_ascii_key(&b)?);
+        prop_assert_eq!(ka.cmp(&kb), lexcode::to_bytes(&a)?.cmp(&lexcode::to_bytes(&b)?));
+        prop_assert_eq!(lexcode::from_ascii_key::<(i64, String)>(&ka)?, a);
+    }
+}
+
+#[test]
+fn known_encoding() {
+    assert_eq!(lexcode::encode_ascii(b""), "");
+    assert_eq!(lexcode::encode_ascii(b"f"), "co");
+    assert_eq!(lexcode::encode_ascii(b"foobar"), "cpnmuoj1e8");
+}
+
+#[test]
+fn malformed_keys_are_an_error() {
+    // Characters outside the alphabet.
+    assert!(lexcode::decode_ascii("w0").is_err());
+    assert!(lexcode::decode_ascii("c-").is_err());
+    // A length no byte string encodes to.
+    assert!(lexcode::decode_ascii("c").is_err());
+    assert!(lexcode::decode_ascii("cpn").is_err());
+    // Non-zero padding bits.
+    assert!(lexcode::decode_ascii("cp").is_err());
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "bigint")]
+
+use ::num_bigint::{BigInt, BigUint, Sign};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct U(#[serde(with = "lexcode::num_bigint::biguint")] BigUint);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct I(#[serde(with = "lexcode::num_bigint::bigint")] BigInt);
+
+fn biguint() -> impl Strategy<Value = BigUint> {
+    prop::collection::vec(any::<u8>(), 0..40).prop_map(|bytes| BigUint::from_bytes_be(&bytes))
+}
+
+fn bigint() -> impl Strategy<Value = BigInt> {
+    (any::<bool>(), biguint()).prop_map(|(neg, m)| BigInt::from_biguint(if neg { Sign::Minus } else { Sign::Plus }, m))
+}
+
+proptest! {
+    #[test]
+    fn biguint_order_and_roundtrip(a in biguint(), b in biguint()) {
+        let bytes_a = lexcode::to_bytes(&U(a.clone()))?;
+        let bytes_b = lexcode::to_bytes(&U(b.clone()))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<U>(&bytes_a)?.0, a);
+    }
+
+    #[test]
+    fn bigint_order_and_roundtrip(a in bigint(), b in bigint()) {
+        let bytes_a = lexcode::to_bytes(&I(a.clone()))?;
+        let bytes_b = lexcode::to_bytes(&I(b.clone()))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<I>(&bytes_a)?.0, a);
+    }
+}
+
+#[test]
+fn zero_is_compact() {
+    assert_eq!(lexcode::to_bytes(&U(BigUint::ZERO)).unwrap(), [0x00]);
+    assert_eq!(lexcode::to_bytes(&I(BigInt::ZERO)).unwrap(), [0x01]);
+}
+
+#[test]
+fn leading_zero_byte_is_rejected() {
+    assert!(lexcode::from_bytes::<U>(&[0x02, 0x00, 0x01]).is_err());
+}
+
+#[test]
+fn huge_lengths_are_an_error() {
+    let bytes = lexcode::to_bytes(&(u64::MAX, 1u8)).unwrap();
+    assert!(lexcode::from_bytes::<U>(&bytes).is_err());
+    let bytes = lexcode::memcomparable::to_bytes(&(u64::MAX, 1u8)).unwrap();
+    assert!(lexcode::memcomparable::from_bytes::<U>(&bytes).is_err());
+}
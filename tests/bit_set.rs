@@ -0,0 +1,40 @@
+use lexcode::BitSet;
+use proptest::prelude::*;
+use std::collections::BTreeSet;
+
+/// The integer whose set bits are `set`, for sets within `u128`.
+fn mask(set: &BitSet) -> u128 {
+    set.iter().map(|i| 1u128 << i).sum()
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in prop::collection::btree_set(0usize..128, 0..6),
+                           b in prop::collection::btree_set(0usize..128, 0..6)) {
+        let (a, b): (BitSet, BitSet) = (a.into_iter().collect(), b.into_iter().collect());
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(mask(&a).cmp(&mask(&b)), a.cmp(&b));
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<BitSet>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn set_get_iter(indices in prop::collection::btree_set(0usize..200, 0..10), removed in 0usize..200) {
+        let mut set: BitSet = indices.iter().copied().collect();
+        set.set(removed, false);
+        let mut expected: BTreeSet<usize> = indices;
+        expected.remove(&removed);
+        prop_assert_eq!(set.iter().collect::<Vec<_>>(), expected.iter().copied().collect::<Vec<_>>());
+        prop_assert_eq!(set.len(), expected.len());
+        prop_assert!(!set.get(removed));
+        prop_assert_eq!(set, expected.into_iter().collect::<BitSet>());
+    }
+}
+
+#[test]
+fn encoding_is_the_integer() {
+    assert_eq!(lexcode::to_bytes(&BitSet::new()).unwrap(), [0]);
+    let set: BitSet = [0, 9].into_iter().collect();
+    assert_eq!(lexcode::to_bytes(&set).unwrap(), [2, 0x02, 0x01]);
+}
@@ -0,0 +1,40 @@
+use lexcode::{NanPolicy, Serializer};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+fn encode<T: Serialize>(value: &T, serializer: Serializer) -> lexcode::Result<Vec<u8>> {
+    let mut serializer = serializer;
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_bytes())
+}
+
+fn canonical() -> Serializer {
+    Serializer::new().canonical(true)
+}
+
+#[test]
+fn deterministic_values_encode_as_usual() {
+    let btree = BTreeMap::from([(1u8, "a"), (2, "b")]);
+    let value = (1.5f64, btree, vec![1u8, 2]);
+    assert_eq!(encode(&value, canonical()).unwrap(), lexcode::to_bytes(&value).unwrap());
+}
+
+#[test]
+fn nan_is_rejected_under_any_policy() {
+    for policy in [NanPolicy::Raw, NanPolicy::Canonicalize] {
+        assert!(encode(&f64::NAN, canonical().nan_policy(policy)).is_err());
+        assert!(encode(&f32::NAN, canonical().nan_policy(policy)).is_err());
+    }
+    assert!(encode(&f64::INFINITY, canonical()).is_ok());
+}
+
+#[test]
+fn unsorted_map_is_rejected() {
+    let hash: HashMap<u32, u32> = (0..64).map(|i| (i, i)).collect();
+    assert!(encode(&hash, canonical()).is_err());
+    assert!(encode(&BTreeMap::from([(1u8, BTreeMap::from([(2u8, 3u8)]))]), canonical()).is_ok());
+
+    let sorted = encode(&hash, canonical().sort_maps(true)).unwrap();
+    let btree: BTreeMap<u32, u32> = hash.into_iter().collect();
+    assert_eq!(sorted, lexcode::to_bytes(&btree).unwrap());
+}
@@ -0,0 +1,37 @@
+use lexcode::CaseInsensitive;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in "[a-cA-CßÄä]{0,5}", b in "[a-cA-CßÄä]{0,5}") {
+        let (a, b) = (CaseInsensitive(a), CaseInsensitive(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<CaseInsensitive>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn casings_share_prefix(s in "[a-zA-Z]{0,8}") {
+        let lower = CaseInsensitive(s.to_lowercase());
+        let upper = lexcode::to_bytes(&CaseInsensitive(s.to_uppercase()))?;
+        prop_assert!(upper.starts_with(&lower.fold_prefix()));
+    }
+}
+
+#[test]
+fn groups_casings_together() {
+    let mut keys: Vec<Vec<u8>> = ["bob", "ALICE", "Bob", "alice", "Carol"]
+        .iter()
+        .map(|s| lexcode::to_bytes(&CaseInsensitive(*s)).unwrap())
+        .collect();
+    keys.sort();
+    let decoded: Vec<String> = keys.iter().map(|k| lexcode::from_bytes::<CaseInsensitive>(k).unwrap().0).collect();
+    assert_eq!(decoded, ["ALICE", "alice", "Bob", "bob", "Carol"]);
+}
+
+#[test]
+fn mismatched_fold_is_rejected() {
+    let bytes = lexcode::to_bytes(&("alice", "Bob")).unwrap();
+    assert!(lexcode::from_bytes::<CaseInsensitive>(&bytes).is_err());
+}
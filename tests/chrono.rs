@@ -0,0 +1,53 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Date(#[serde(with = "lexcode::chrono::naive_date")] NaiveDate);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Naive(#[serde(with = "lexcode::chrono::naive_date_time")] NaiveDateTime);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct UtcTime(#[serde(with = "lexcode::chrono::date_time")] DateTime<Utc>);
+
+fn date() -> impl Strategy<Value = NaiveDate> {
+    (NaiveDate::MIN.num_days_from_ce()..=NaiveDate::MAX.num_days_from_ce())
+        .prop_map(|days| NaiveDate::from_num_days_from_ce_opt(days).unwrap())
+}
+
+fn date_time() -> impl Strategy<Value = DateTime<Utc>> {
+    (-100_000_000_000i64..100_000_000_000, 0u32..2_000_000_000)
+        .prop_filter_map("out of range", |(secs, nanos)| DateTime::from_timestamp(secs, nanos))
+}
+
+proptest! {
+    #[test]
+    fn date_order_and_roundtrip(a in date(), b in date()) {
+        let bytes_a = lexcode::to_bytes(&Date(a))?;
+        let bytes_b = lexcode::to_bytes(&Date(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Date>(&bytes_a)?.0, a);
+    }
+
+    #[test]
+    fn date_time_order_and_roundtrip(a in date_time(), b in date_time()) {
+        let bytes_a = lexcode::to_bytes(&UtcTime(a))?;
+        let bytes_b = lexcode::to_bytes(&UtcTime(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<UtcTime>(&bytes_a)?.0, a);
+
+        let naive = lexcode::to_bytes(&Naive(a.naive_utc()))?;
+        prop_assert_eq!(&naive, &bytes_a);
+        prop_assert_eq!(lexcode::from_bytes::<Naive>(&naive)?.0, a.naive_utc());
+    }
+}
+
+#[test]
+fn present_day_sizes() {
+    let at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    assert_eq!(lexcode::to_bytes(&Date(at.date_naive())).unwrap().len(), 3);
+    assert_eq!(lexcode::to_bytes(&UtcTime(at)).unwrap().len(), 6);
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "ipnet")]
+
+use ipnet::IpNet;
+use lexcode::CidrKey;
+use proptest::prelude::*;
+use std::net::IpAddr;
+
+fn ip_net() -> impl Strategy<Value = IpNet> {
+    prop_oneof![
+        (any::<[u8; 4]>(), 0u8..=32).prop_map(|(a, len)| IpNet::new(IpAddr::from(a), len).unwrap()),
+        (any::<[u8; 16]>(), 0u8..=128).prop_map(|(a, len)| IpNet::new(IpAddr::from(a), len).unwrap()),
+    ]
+}
+
+fn longest_match(table: &[IpNet], addr: IpAddr) -> Option<IpNet> {
+    let range = CidrKey::scan_range(addr);
+    let lo = lexcode::to_bytes(range.start()).unwrap();
+    let hi = lexcode::to_bytes(range.end()).unwrap();
+    let mut keys: Vec<Vec<u8>> = table.iter().map(|net| lexcode::to_bytes(&CidrKey::new(*net)).unwrap()).collect();
+    keys.sort();
+    keys.iter()
+        .rev()
+        .filter(|key| lo <= **key && **key <= hi)
+        .map(|key| lexcode::from_bytes::<CidrKey>(key).unwrap())
+        .find(|key| key.contains(addr))
+        .map(|key| key.net())
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in ip_net(), b in ip_net()) {
+        let (a, b) = (CidrKey::new(a), CidrKey::new(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<CidrKey>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn range_scan_finds_longest_prefix(
+        bases in prop::collection::vec(any::<[u8; 4]>(), 1..20),
+        lens in prop::collection::vec(0u8..=32, 20),
+        addr in any::<[u8; 4]>(),
+    ) {
+        let addr = IpAddr::from(addr);
+        let table: Vec<IpNet> = bases
+            .iter()
+            .zip(&lens)
+            .map(|(base, len)| IpNet::new(IpAddr::from(*base), *len).unwrap().trunc())
+            .chain(std::iter::once(IpNet::new(addr, lens[0]).unwrap().trunc()))
+            .collect();
+        let expected = table.iter().filter(|net| net.contains(&addr)).max_by_key(|net| net.prefix_len()).copied();
+        prop_assert_eq!(longest_match(&table, addr), expected);
+    }
+}
+
+#[test]
+fn host_bits_are_cleared() {
+    let key = CidrKey::new("10.1.2.3/8".parse().unwrap());
+    assert_eq!(key.to_string(), "10.0.0.0/8");
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+fn lexcode(args: &[&str]) -> Result<String, String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_lexcode")).args(args).output().unwrap();
+    let text = |bytes: Vec<u8>| String::from_utf8(bytes).unwrap().trim_end().to_string();
+    if output.status.success() {
+        Ok(text(output.stdout))
+    } else {
+        Err(text(output.stderr))
+    }
+}
+
+#[test]
+fn encode_decode_roundtrip() {
+    let cases = [
+        ("(u64, str)", r#"[5,"hi"]"#, "0568690000"),
+        ("option<i32>", "null", "00"),
+        ("[u8]", "[1,2]", "0101010200"),
+        ("enum<unit, u8>", r#"{"1":7}"#, "0107"),
+        ("enum<unit, u8>", "0", "00"),
+    ];
+    for (schema, json, hex) in cases {
+        assert_eq!(lexcode(&["encode", schema, json]).unwrap(), hex, "{schema} {json}");
+        assert_eq!(lexcode(&["decode", schema, hex]).unwrap(), json, "{schema} {hex}");
+    }
+}
+
+#[test]
+fn compare_reports_the_first_difference() {
+    assert_eq!(lexcode(&["compare", "0568690000", "0568700000"]).unwrap(), "less (first difference at byte 2)");
+    assert_eq!(lexcode(&["compare", "05", "05"]).unwrap(), "equal");
+    assert_eq!(lexcode(&["compare", "0600", "05"]).unwrap(), "greater (first difference at byte 0)");
+}
+
+#[test]
+fn bad_input_is_an_error() {
+    assert!(lexcode(&[]).unwrap_err().starts_with("usage:"));
+    assert!(lexcode(&["encode", "(u64", "1"]).is_err());
+    assert!(lexcode(&["encode", "u64", "{"]).unwrap_err().starts_with("invalid JSON"));
+    assert_eq!(lexcode(&["encode", "u8", "256"]).unwrap_err(), "256 is out of range for u8");
+    assert!(lexcode(&["decode", "u64", "0g"]).unwrap_err().starts_with("invalid hex"));
+    assert!(lexcode(&["decode", "u64", "056"]).unwrap_err().starts_with("odd number"));
+    assert!(lexcode(&["decode", "str", "6869"]).is_err());
+}
+
+#[test]
+fn out_of_range_variants_are_an_error() {
+    for index in ["2", "4294967296", "18446744073709551616"] {
+        let json = format!(r#"{{"{index}":null}}"#);
+        let err = lexcode(&["encode", "enum<unit, u8>", &json]).unwrap_err();
+        assert!(err.starts_with(&format!("variant {index} out of range")), "{err}");
+    }
+}
+
+#[test]
+fn map_entries_are_sorted_by_key() {
+    let sorted = lexcode(&["encode", "{u8: bool}", "[[2,true],[1,false]]"]).unwrap();
+    assert_eq!(sorted, lexcode(&["encode", "{u8: bool}", "[[1,false],[2,true]]"]).unwrap());
+    assert_eq!(sorted, lexcode(&["encode", "{u8: bool}", r#"{"2":true,"1":false}"#]).unwrap());
+    assert_eq!(lexcode(&["decode", "{u8: bool}", &sorted]).unwrap(), "[[1,false],[2,true]]");
+    let err = lexcode(&["encode", "{u8: bool}", "[[1,true],[1,false]]"]).unwrap_err();
+    assert_eq!(err, "duplicate map key 1");
+}
+
+#[test]
+fn ambiguous_null_is_rejected() {
+    for schema in ["option<unit>", "[option<option<u8>>]"] {
+        assert!(lexcode(&["encode", schema, "null"]).unwrap_err().contains("ambiguous"), "{schema}");
+        assert!(lexcode(&["decode", schema, "00"]).unwrap_err().contains("ambiguous"), "{schema}");
+    }
+    assert_eq!(lexcode(&["encode", "option<(unit, u8)>", "null"]).unwrap(), "00");
+}
@@ -0,0 +1,50 @@
+use lexcode::{Descending, KeyCodec, LexCodec, ValueCodec};
+use proptest::prelude::*;
+use std::ops::Bound;
+
+type K = (u16, Descending<String>);
+
+fn key() -> impl Strategy<Value = K> {
+    (0u16..4, "[ab]{0,2}").prop_map(|(n, s)| (n, Descending(s)))
+}
+
+fn contains(range: &(Bound<Vec<u8>>, Bound<Vec<u8>>), bytes: &[u8]) -> bool {
+    lexcode::range_contains(range, bytes)
+}
+
+proptest! {
+    #[test]
+    fn roundtrip(k in key()) {
+        let bytes = <LexCodec as ValueCodec<K>>::encode(&k)?;
+        prop_assert_eq!(bytes.clone(), lexcode::to_bytes(&k)?);
+        prop_assert_eq!(<LexCodec as ValueCodec<K>>::decode(&bytes)?, k);
+    }
+
+    #[test]
+    fn range_selects_the_keys_in_it(lo in key(), hi in key(), k in key()) {
+        let bytes = lexcode::to_bytes(&k)?;
+        let range = <LexCodec as KeyCodec<K>>::range(lo.clone()..=hi.clone())?;
+        prop_assert_eq!(contains(&range, &bytes), (lo..=hi).contains(&k));
+    }
+
+    #[test]
+    fn prefix_range_selects_the_keys_sharing_it(n in 0u16..4, k in key()) {
+        let bytes = lexcode::to_bytes(&k)?;
+        let range = <LexCodec as KeyCodec<K>>::prefix_range(&n)?;
+        prop_assert_eq!(contains(&range, &bytes), k.0 == n);
+    }
+}
+
+#[test]
+fn prefix_range_without_a_successor_is_unbounded_above() {
+    let range = <LexCodec as KeyCodec<Descending<u8>>>::prefix_range(&Descending(0u8)).unwrap();
+    assert_eq!(range, (Bound::Included(vec![0xFF]), Bound::Unbounded));
+    let range = <LexCodec as KeyCodec<(u8, u8)>>::prefix_range(&()).unwrap();
+    assert_eq!(range, (Bound::Included(vec![]), Bound::Unbounded));
+}
+
+#[test]
+fn decode_rejects_malformed_bytes() {
+    assert!(<LexCodec as ValueCodec<bool>>::decode(&[2]).is_err());
+    assert!(<LexCodec as ValueCodec<String>>::decode(b"ab").is_err());
+}
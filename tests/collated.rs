@@ -0,0 +1,62 @@
+#![cfg(feature = "icu")]
+
+use icu_collator::options::{CollatorOptions, Strength};
+use icu_collator::{Collator, CollatorBorrowed};
+use icu_locale_core::Locale;
+use lexcode::Collated;
+use proptest::prelude::*;
+
+fn collator(locale: &str, strength: Option<Strength>) -> CollatorBorrowed<'static> {
+    let locale: Locale = locale.parse().unwrap();
+    let mut options = CollatorOptions::default();
+    options.strength = strength;
+    Collator::try_new(locale.into(), options).unwrap()
+}
+
+fn sorted(locale: &str, words: &[&str]) -> Vec<String> {
+    let collator = collator(locale, None);
+    let mut keys: Vec<Vec<u8>> = words
+        .iter()
+        .map(|w| lexcode::to_bytes(&Collated::new(&collator, *w)).unwrap())
+        .collect();
+    keys.sort();
+    keys.iter()
+        .map(|k| lexcode::from_bytes::<Collated>(k).unwrap().into_inner())
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in "[a-cA-CåäöÅÄÖ]{0,5}", b in "[a-cA-CåäöÅÄÖ]{0,5}") {
+        let collator = collator("de", None);
+        let (a, b) = (Collated::new(&collator, a), Collated::new(&collator, b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Collated>(&bytes_a)?, a);
+    }
+}
+
+#[test]
+fn swedish_sorts_letters_after_z() {
+    assert_eq!(
+        sorted("sv", &["ö", "z", "å", "ä", "a", "o"]),
+        ["a", "o", "z", "å", "ä", "ö"]
+    );
+}
+
+#[test]
+fn german_sorts_umlauts_with_base_letter() {
+    assert_eq!(
+        sorted("de", &["zebra", "Öl", "Ofen", "Apfel", "Äpfel"]),
+        ["Apfel", "Äpfel", "Ofen", "Öl", "zebra"]
+    );
+}
+
+#[test]
+fn equal_strings_share_key_prefix() {
+    let collator = collator("de", Some(Strength::Primary));
+    let upper = Collated::new(&collator, "STRASSE");
+    let lower = lexcode::to_bytes(&Collated::new(&collator, "straße")).unwrap();
+    assert!(lower.starts_with(&upper.key_prefix()));
+}
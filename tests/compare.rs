@@ -0,0 +1,59 @@
+use lexcode::{Descending, FixedBytes};
+use proptest::prelude::*;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, proptest_derive::Arbitrary)]
+enum Event {
+    Created,
+    Renamed(String),
+    Moved { from: u32, to: i64 },
+}
+
+#[derive(Debug, Clone, Serialize, proptest_derive::Arbitrary)]
+struct Row {
+    id: u64,
+    #[proptest(strategy = "any::<(i32, f64)>().prop_map(Descending)")]
+    score: Descending<(i32, f64)>,
+    #[proptest(strategy = "any::<[u8; 3]>().prop_map(FixedBytes)")]
+    hash: FixedBytes<3>,
+    tags: Vec<Option<String>>,
+    attrs: BTreeMap<u8, bool>,
+    event: Event,
+    payload: serde_bytes_like::Bytes,
+}
+
+mod serde_bytes_like {
+    use serde::{Serialize, Serializer};
+
+    #[derive(Debug, Clone, proptest_derive::Arbitrary)]
+    pub struct Bytes(pub Vec<u8>);
+
+    impl Serialize for Bytes {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn matches_encoded_comparison(a in any::<Row>(), b in any::<Row>()) {
+        let key = lexcode::to_bytes(&b).unwrap();
+        prop_assert_eq!(lexcode::cmp_value_to_key(&a, &key).unwrap(), lexcode::to_bytes(&a).unwrap().cmp(&key));
+        prop_assert_eq!(lexcode::cmp_value_to_key(&b, &key).unwrap(), Ordering::Equal);
+        // Share a long prefix with the key so the late fields decide.
+        let mixed = Row { event: a.event.clone(), payload: a.payload.clone(), ..b.clone() };
+        prop_assert_eq!(lexcode::cmp_value_to_key(&mixed, &key).unwrap(), lexcode::to_bytes(&mixed).unwrap().cmp(&key));
+    }
+
+    #[test]
+    fn handles_truncated_and_extended_keys(a in any::<(u16, String, i8)>().prop_map(|(x, y, z)| (x, y, Descending(z))), cut in any::<prop::sample::Index>(), extra in any::<u8>()) {
+        let key = lexcode::to_bytes(&a).unwrap();
+        let short = &key[..cut.index(key.len())];
+        prop_assert_eq!(lexcode::cmp_value_to_key(&a, short).unwrap(), Ordering::Greater);
+        let long = [&key[..], &[extra]].concat();
+        prop_assert_eq!(lexcode::cmp_value_to_key(&a, &long).unwrap(), Ordering::Less);
+    }
+}
@@ -0,0 +1,132 @@
+use lexcode::{component_slices, Descending, Schema};
+use proptest::prelude::*;
+use std::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Row {
+    user: u64,
+    tags: Vec<String>,
+    score: Option<(i16, f64)>,
+}
+
+proptest! {
+    #[test]
+    fn slices_are_field_encodings(a in any::<u64>(), b in ".*", c in any::<i32>()) {
+        let key = lexcode::to_bytes(&(a, &b, Descending(c))).unwrap();
+        let parts = component_slices::<(u64, String, Descending<i32>)>(&key).unwrap();
+        prop_assert_eq!(parts.len(), 3);
+        prop_assert_eq!(parts[0], &lexcode::to_bytes(&a).unwrap()[..]);
+        prop_assert_eq!(parts[1], &lexcode::to_bytes(&b).unwrap()[..]);
+        prop_assert_eq!(parts[2], &lexcode::to_bytes(&Descending(c)).unwrap()[..]);
+    }
+
+    #[test]
+    fn struct_fields(user in any::<u64>(), tags in prop::collection::vec(".*", 0..3), score in any::<Option<(i16, f64)>>()) {
+        let row = Row { user, tags: tags.clone(), score };
+        let key = lexcode::to_bytes(&row).unwrap();
+        let parts = component_slices::<Row>(&key).unwrap();
+        prop_assert_eq!(parts.len(), 3);
+        prop_assert_eq!(parts[1], &lexcode::to_bytes(&tags).unwrap()[..]);
+        prop_assert_eq!(parts.concat(), key);
+    }
+}
+
+#[test]
+fn nested_tuples_are_one_component() {
+    let key = lexcode::to_bytes(&((1u8, 2u8), 3u8)).unwrap();
+    let parts = component_slices::<((u8, u8), u8)>(&key).unwrap();
+    assert_eq!(parts, [&[1u8, 2][..], &[3][..]]);
+}
+
+#[test]
+fn non_tuples_are_a_single_component() {
+    let key = lexcode::to_bytes(&Some((1u8, 2u8))).unwrap();
+    assert_eq!(component_slices::<Option<(u8, u8)>>(&key).unwrap(), [&key[..]]);
+    let key = lexcode::to_bytes(&"abc").unwrap();
+    assert_eq!(component_slices::<String>(&key).unwrap(), [&key[..]]);
+
+    #[derive(Deserialize)]
+    struct Empty {}
+    assert!(component_slices::<Empty>(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn rejects_incomplete_or_trailing_input() {
+    let key = lexcode::to_bytes(&(1u8, "abc")).unwrap();
+    assert!(component_slices::<(u8, String)>(&key[..key.len() - 1]).is_err());
+    assert!(component_slices::<(u8,)>(&key).is_err());
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct RowPrefix {
+    user: u64,
+    tags: Vec<String>,
+}
+
+proptest! {
+    #[test]
+    fn decode_prefix_reads_leading_fields(a in any::<u64>(), b in ".*", c in any::<Vec<i32>>()) {
+        let key = lexcode::to_bytes(&(a, &b, &c)).unwrap();
+        prop_assert_eq!(lexcode::decode_prefix::<(u64, String)>(&key).unwrap(), (a, b.clone()));
+        prop_assert_eq!(lexcode::decode_prefix::<(u64,)>(&key).unwrap(), (a,));
+        prop_assert_eq!(lexcode::decode_prefix::<(u64, String, Vec<i32>)>(&key).unwrap(), (a, b, c));
+    }
+
+    #[test]
+    fn decode_prefix_of_struct(user in any::<u64>(), tags in prop::collection::vec(".*", 0..3), score in any::<Option<(i16, f64)>>()) {
+        let key = lexcode::to_bytes(&Row { user, tags: tags.clone(), score }).unwrap();
+        prop_assert_eq!(lexcode::decode_prefix::<RowPrefix>(&key).unwrap(), RowPrefix { user, tags });
+    }
+}
+
+#[test]
+fn decode_prefix_needs_the_leading_fields() {
+    let key = lexcode::to_bytes(&(1u8,)).unwrap();
+    assert!(lexcode::decode_prefix::<(u8, u8)>(&key).is_err());
+}
+
+proptest! {
+    #[test]
+    fn common_prefix_len_is_longest(a in prop::collection::vec(0u8..3, 0..6), b in prop::collection::vec(0u8..3, 0..6)) {
+        let n = lexcode::common_prefix_len(&a, &b);
+        prop_assert_eq!(&a[..n], &b[..n]);
+        prop_assert!(n == a.len() || n == b.len() || a[n] != b[n]);
+    }
+
+    #[test]
+    fn first_divergent_component_matches_values(a in any::<(u8, String, Option<i16>)>(), b in any::<(u8, String, Option<i16>)>()) {
+        let ka = lexcode::to_bytes(&a).unwrap();
+        let kb = lexcode::to_bytes(&b).unwrap();
+        let expected = if a.0 != b.0 {
+            Some(0)
+        } else if a.1 != b.1 {
+            Some(1)
+        } else if a.2 != b.2 {
+            Some(2)
+        } else {
+            None
+        };
+        prop_assert_eq!(lexcode::first_divergent_component::<(u8, String, Option<i16>)>(&ka, &kb).unwrap(), expected);
+
+        let shape: Schema = "(u8, str, option<i16>)".parse().unwrap();
+        let result = lexcode::compare_encoded(&ka, &kb, &shape);
+        prop_assert_eq!(result.ordering, a.cmp(&b));
+        prop_assert_eq!(result.component, expected);
+    }
+}
+
+#[test]
+fn compare_encoded_accepts_partial_keys() {
+    let shape: Schema = "(u64, str, i32)".parse().unwrap();
+    let key = lexcode::to_bytes(&(7u64, "eu-west", 1i32)).unwrap();
+    let prefix = lexcode::to_bytes(&(7u64, "eu")).unwrap();
+    let result = lexcode::compare_encoded(&key[..3], &key, &shape);
+    assert_eq!((result.ordering, result.component), (Ordering::Less, Some(1)));
+    let result = lexcode::compare_encoded(&prefix, &key, &shape);
+    assert_eq!((result.ordering, result.component), (Ordering::Less, Some(1)));
+    let result = lexcode::compare_encoded(&key, &[&key[..], &[0x01]].concat(), &shape);
+    assert_eq!((result.ordering, result.component), (Ordering::Less, Some(3)));
+    let scalar = lexcode::compare_encoded(&[0x05], &[0x04], &Schema::U8);
+    assert_eq!((scalar.ordering, scalar.component), (Ordering::Greater, Some(0)));
+}
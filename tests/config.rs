@@ -0,0 +1,60 @@
+use lexcode::{Deserializer, LexConfig, NanPolicy, Serializer};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+proptest! {
+    #[test]
+    fn none_last_order_and_roundtrip(a in any::<Option<(u8, Option<i16>)>>(), b in any::<Option<(u8, Option<i16>)>>()) {
+        let config = LexConfig::new().none_last(true);
+        let bytes_a = lexcode::to_bytes_with_config(&a, &config)?;
+        let bytes_b = lexcode::to_bytes_with_config(&b, &config)?;
+        let last = |o: &Option<(u8, Option<i16>)>| lexcode::OptionLast(o.map(|(x, y)| (x, lexcode::OptionLast(y))));
+        prop_assert_eq!(last(&a).cmp(&last(&b)), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes_with_config::<Option<(u8, Option<i16>)>>(&bytes_a, &config)?, a);
+    }
+}
+
+#[test]
+fn default_config_matches_to_bytes() {
+    let value = (1u8, "a", Some(2.5f64), vec![None, Some(3u8)]);
+    let config = LexConfig::default();
+    let bytes = lexcode::to_bytes_with_config(&value, &config).unwrap();
+    assert_eq!(bytes, lexcode::to_bytes(&value).unwrap());
+    let decoded: (u8, String, Option<f64>, Vec<Option<u8>>) = lexcode::from_bytes_with_config(&bytes, &config).unwrap();
+    assert_eq!(decoded, (1, "a".into(), Some(2.5), vec![None, Some(3)]));
+}
+
+#[test]
+fn config_matches_serializer_builder() {
+    let config = LexConfig::new().nan_policy(NanPolicy::Canonicalize).normalize_zero(true).sort_maps(true);
+    let value = (-0.0f64, f64::from_bits(0xFFF8_0000_0000_0001), HashMap::from([(2u8, 0u8), (1, 0)]));
+    let mut serializer = Serializer::new().nan_policy(NanPolicy::Canonicalize).normalize_zero(true).sort_maps(true);
+    value.serialize(&mut serializer).unwrap();
+    assert_eq!(lexcode::to_bytes_with_config(&value, &config).unwrap(), serializer.into_bytes());
+    assert!(lexcode::to_bytes_with_config(&f64::NAN, &LexConfig::new().canonical(true)).is_err());
+}
+
+#[test]
+fn omitted_terminator_roundtrips() {
+    let config = LexConfig::new().omit_trailing_terminator(true);
+    let bytes = lexcode::to_bytes_with_config(&(1u8, "key"), &config).unwrap();
+    assert_eq!(bytes, b"\x01key");
+    let decoded: (u8, String) = lexcode::from_bytes_with_config(&bytes, &config).unwrap();
+    assert_eq!(decoded, (1, "key".into()));
+}
+
+#[test]
+fn max_len_is_enforced_both_ways() {
+    let config = LexConfig::new().max_len(4);
+    assert!(lexcode::to_bytes_with_config(&"abc", &config).is_err());
+    assert!(lexcode::to_bytes_with_config(&"ab", &config).is_ok());
+    let long = lexcode::to_bytes(&"abc").unwrap();
+    assert!(lexcode::from_bytes_with_config::<String>(&long, &config).is_err());
+}
+
+#[test]
+fn deserializer_with_config_reads_none_last() {
+    let mut deserializer = Deserializer::with_config(&[0x01], LexConfig::new().none_last(true));
+    assert_eq!(Option::<u8>::deserialize(&mut deserializer).unwrap(), None);
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "decimal")]
+
+use proptest::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct D(#[serde(with = "lexcode::decimal")] Decimal);
+
+fn decimal() -> impl Strategy<Value = Decimal> {
+    (any::<i64>(), 0u32..=28).prop_map(|(m, s)| Decimal::new(m, s))
+}
+
+fn short_decimal() -> impl Strategy<Value = Decimal> {
+    (-10_000i64..10_000, 0u32..6).prop_map(|(m, s)| Decimal::new(m, s))
+}
+
+fn wide_decimal() -> impl Strategy<Value = Decimal> {
+    (any::<i128>(), 0u32..=28).prop_filter_map("out of range", |(m, s)| {
+        Decimal::try_from_i128_with_scale(m >> 32, s).ok()
+    })
+}
+
+proptest! {
+    #[test]
+    fn order_matches_numeric_order(a in decimal(), b in decimal()) {
+        let bytes_a = lexcode::to_bytes(&D(a))?;
+        let bytes_b = lexcode::to_bytes(&D(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+    }
+
+    #[test]
+    fn shared_digit_prefixes_order(a in short_decimal(), b in short_decimal()) {
+        let bytes_a = lexcode::to_bytes(&D(a))?;
+        let bytes_b = lexcode::to_bytes(&D(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<D>(&bytes_a)?.0, a);
+    }
+
+    #[test]
+    fn wide_values_roundtrip(a in wide_decimal(), b in wide_decimal()) {
+        let bytes_a = lexcode::to_bytes(&D(a))?;
+        let bytes_b = lexcode::to_bytes(&D(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<D>(&bytes_a)?.0, a);
+    }
+}
+
+#[test]
+fn trailing_zeros_encode_identically() {
+    let a = lexcode::to_bytes(&D(Decimal::new(15, 1))).unwrap();
+    let b = lexcode::to_bytes(&D(Decimal::new(1500, 3))).unwrap();
+    assert_eq!(a, b);
+    assert_eq!(lexcode::from_bytes::<D>(&a).unwrap().0.to_string(), "1.5");
+}
+
+#[test]
+fn longer_digits_sort_after_their_prefix() {
+    let short = lexcode::to_bytes(&D(Decimal::new(12, 1))).unwrap();
+    let long = lexcode::to_bytes(&D(Decimal::new(123, 2))).unwrap();
+    assert!(short < long);
+}
+
+#[test]
+fn extremes_roundtrip() {
+    for value in [Decimal::MAX, Decimal::MIN, Decimal::ZERO, Decimal::new(1, 28), Decimal::new(-1, 28)] {
+        let bytes = lexcode::to_bytes(&D(value)).unwrap();
+        assert_eq!(lexcode::from_bytes::<D>(&bytes).unwrap().0, value);
+    }
+    let zero = lexcode::to_bytes(&D(Decimal::ZERO)).unwrap();
+    assert!(lexcode::to_bytes(&D(Decimal::new(-1, 28))).unwrap() < zero);
+    assert!(zero < lexcode::to_bytes(&D(Decimal::new(1, 28))).unwrap());
+}
@@ -0,0 +1,79 @@
+use lexcode::{Descending, FixedBytes};
+use proptest::prelude::*;
+
+macro_rules! descending_test {
+    ($name:ident, $ty:ty) => {
+        proptest! {
+            #[test]
+            fn $name(a in any::<$ty>(), b in any::<$ty>()) {
+                let bytes_a = lexcode::to_bytes(&Descending(a.clone()))?;
+                let bytes_b = lexcode::to_bytes(&Descending(b.clone()))?;
+                prop_assert_eq!(a.cmp(&b).reverse(), bytes_a.cmp(&bytes_b));
+                let decoded: Descending<$ty> = lexcode::from_bytes(&bytes_a)?;
+                prop_assert_eq!(decoded.0, a);
+            }
+        }
+    };
+}
+
+descending_test!(desc_u64, u64);
+descending_test!(desc_i128, i128);
+descending_test!(desc_char, char);
+descending_test!(desc_string, String);
+descending_test!(desc_option_i32, Option<i32>);
+descending_test!(desc_vec_string, Vec<String>);
+descending_test!(desc_tuple, (u8, String, i64));
+descending_test!(desc_bytes, [u8; 8]);
+
+proptest! {
+    #[test]
+    fn desc_f64(a in any::<f64>(), b in any::<f64>()) {
+        let bytes_a = lexcode::to_bytes(&Descending(a))?;
+        let bytes_b = lexcode::to_bytes(&Descending(b))?;
+        if a < b {
+            prop_assert!(bytes_a > bytes_b);
+        } else if a > b {
+            prop_assert!(bytes_a < bytes_b);
+        }
+        let decoded: Descending<f64> = lexcode::from_bytes(&bytes_a)?;
+        prop_assert_eq!(decoded.0.to_bits(), a.to_bits());
+    }
+
+    #[test]
+    fn desc_in_composite_key(
+        a in any::<(u32, String, u8)>(),
+        b in any::<(u32, String, u8)>(),
+    ) {
+        // (ASC, DESC, ASC)
+        let key = |(x, y, z): (u32, String, u8)| (x, Descending(y), z);
+        let bytes_a = lexcode::to_bytes(&key(a.clone()))?;
+        let bytes_b = lexcode::to_bytes(&key(b.clone()))?;
+        let expected = a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2));
+        prop_assert_eq!(expected, bytes_a.cmp(&bytes_b));
+        let decoded: (u32, Descending<String>, u8) = lexcode::from_bytes(&bytes_a)?;
+        prop_assert_eq!(decoded, key(a));
+    }
+
+    #[test]
+    fn desc_fixed_bytes(a in any::<[u8; 16]>(), b in any::<[u8; 16]>()) {
+        let bytes_a = lexcode::to_bytes(&Descending(FixedBytes(a)))?;
+        let bytes_b = lexcode::to_bytes(&Descending(FixedBytes(b)))?;
+        prop_assert_eq!(bytes_a.len(), 16);
+        prop_assert_eq!(a.cmp(&b).reverse(), bytes_a.cmp(&bytes_b));
+        let decoded: Descending<FixedBytes<16>> = lexcode::from_bytes(&bytes_a)?;
+        prop_assert_eq!(decoded.0, FixedBytes(a));
+    }
+}
+
+#[test]
+fn nested_descending_restores_ascending_order() {
+    let a = lexcode::to_bytes(&Descending(Descending(1u32))).unwrap();
+    assert_eq!(a, lexcode::to_bytes(&1u32).unwrap());
+    let decoded: Descending<Descending<u32>> = lexcode::from_bytes(&a).unwrap();
+    assert_eq!(decoded.0.0, 1);
+}
+
+#[test]
+fn descending_ord_is_reversed() {
+    assert!(Descending(1) > Descending(2));
+}
@@ -0,0 +1,84 @@
+use lexcode::{Decode, Descending, FixedBytes};
+use proptest::prelude::*;
+use serde::Serialize;
+use std::fmt::Debug;
+
+type Key = (Descending<(u8, String, Vec<i32>)>, FixedBytes<4>, u64);
+
+/// `encode` writes what `to_bytes` writes, and `decode` reads it back.
+fn check<T>(value: &T) -> Result<(), TestCaseError>
+where
+    T: lexcode::Encode + Decode + Serialize + PartialEq + Debug,
+{
+    let bytes = lexcode::encode(value);
+    prop_assert_eq!(&bytes, &lexcode::to_bytes(value)?);
+    prop_assert_eq!(&lexcode::decode::<T>(&bytes)?, value);
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn integers(v in any::<(u8, u16, u32, u64, u128, usize)>(), w in any::<(i8, i16, i32, i64, i128, isize)>()) {
+        check(&v)?;
+        check(&w)?;
+    }
+
+    #[test]
+    fn scalars(v in any::<(bool, char, String, ())>()) {
+        check(&v)?;
+    }
+
+    #[test]
+    fn floats(a in any::<f32>(), b in any::<f64>()) {
+        let bytes = lexcode::encode(&(a, b));
+        prop_assert_eq!(&bytes, &lexcode::to_bytes(&(a, b))?);
+        let (x, y) = lexcode::decode::<(f32, f64)>(&bytes)?;
+        prop_assert_eq!((x.to_bits(), y.to_bits()), (a.to_bits(), b.to_bits()));
+    }
+
+    #[test]
+    fn containers(v in any::<(Vec<Option<String>>, Option<Vec<u8>>, [i16; 3], Box<u32>)>()) {
+        check(&v)?;
+    }
+
+    #[test]
+    fn strings_with_nul_bytes(s in "[a\0\u{7f}é]{0,6}") {
+        check(&s)?;
+    }
+
+    #[test]
+    fn descending_and_fixed_bytes(v in any::<(u8, String, Vec<i32>, [u8; 4])>(), tail in any::<u64>()) {
+        let value = (Descending((v.0, v.1, v.2)), FixedBytes(v.3), tail);
+        let bytes = lexcode::encode(&value);
+        prop_assert_eq!(&bytes, &lexcode::to_bytes(&value)?);
+        prop_assert_eq!(lexcode::decode::<Key>(&bytes)?, value);
+    }
+
+    #[test]
+    fn long_tuples(a in any::<(u8, i16, u32, bool, char, String)>(), b in any::<(i64, u8, Option<u8>, f32, (), u128)>()) {
+        let value = (a.0, a.1, a.2, a.3, a.4, a.5, b.0, b.1, b.2, b.3.to_bits(), b.4, b.5);
+        check(&value)?;
+    }
+}
+
+#[test]
+fn malformed_input_is_an_error() {
+    assert!(lexcode::decode::<bool>(&[2]).is_err());
+    assert!(lexcode::decode::<u8>(&lexcode::encode(&256u16)).is_err());
+    assert!(lexcode::decode::<Option<u8>>(&[2]).is_err());
+    assert!(lexcode::decode::<Vec<u8>>(&[1, 1, 2]).is_err());
+    assert!(lexcode::decode::<String>(b"ab").is_err());
+    assert!(lexcode::decode::<String>(&[0xFF, 0, 0]).is_err());
+    assert!(lexcode::decode::<f64>(&[0x80; 7]).is_err());
+}
+
+#[test]
+fn decoder_tracks_the_rest_of_the_input() {
+    let bytes = lexcode::encode(&(1u8, "a"));
+    let mut decoder = lexcode::Decoder::new(&bytes);
+    assert_eq!(u8::decode(&mut decoder).unwrap(), 1);
+    assert_eq!(decoder.remaining(), b"a\0\0");
+    assert!(decoder.end().is_err());
+    assert_eq!(String::decode(&mut decoder).unwrap(), "a");
+    assert!(decoder.end().is_ok());
+}
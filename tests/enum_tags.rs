@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Kind {
+    User,
+    Order(u8),
+    Item { id: u8 },
+}
+
+#[test]
+fn small_enums_use_one_tag_byte() {
+    assert_eq!(lexcode::to_bytes(&Kind::User).unwrap(), [0]);
+    assert_eq!(lexcode::to_bytes(&Kind::Order(5)).unwrap(), [1, 5]);
+    assert_eq!(lexcode::to_bytes(&Kind::Item { id: 5 }).unwrap(), [2, 5]);
+    assert_eq!(lexcode::from_bytes::<Kind>(&[2, 5]).unwrap(), Kind::Item { id: 5 });
+}
+
+#[test]
+fn tags_match_u32_varints() {
+    let mut serializer = lexcode::Serializer::new();
+    for index in [0u32, 127, 128, 255, 16_511, 16_512, u32::MAX] {
+        serde::Serializer::serialize_unit_variant(&mut serializer, "E", index, "V").unwrap();
+        assert_eq!(std::mem::take(&mut serializer).into_bytes(), lexcode::to_bytes(&index).unwrap());
+    }
+    assert_eq!(lexcode::to_bytes(&127u32).unwrap().len(), 1);
+    assert_eq!(lexcode::to_bytes(&255u32).unwrap().len(), 2);
+}
+
+#[test]
+fn variant_seed_reads_varint_tags() {
+    // A two-byte varint tag for index 128 is read as one index, not as two
+    // bytes of a fixed-width integer.
+    let bytes = lexcode::to_bytes(&128u32).unwrap();
+    assert_eq!(bytes.len(), 2);
+    let err = lexcode::from_bytes::<Kind>(&bytes).unwrap_err();
+    assert!(err.to_string().contains("128"), "{err}");
+
+    let too_wide = lexcode::to_bytes(&(u64::from(u32::MAX) + 1)).unwrap();
+    assert!(lexcode::from_bytes::<Kind>(&too_wide).is_err());
+}
@@ -0,0 +1,96 @@
+use lexcode::FixedBytes;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn lexcode_writes_the_bytes_verbatim(bytes in any::<[u8; 8]>()) {
+        let fixed = FixedBytes(bytes);
+        prop_assert_eq!(lexcode::to_bytes(&fixed)?, bytes.to_vec());
+        prop_assert_eq!(lexcode::from_bytes::<FixedBytes<8>>(&bytes)?, fixed);
+    }
+
+    #[test]
+    fn descending_bytes_are_complemented(bytes in any::<[u8; 8]>(), tail in any::<u16>()) {
+        let key = (lexcode::Descending(FixedBytes(bytes)), tail);
+        let encoded = lexcode::to_bytes(&key)?;
+        prop_assert_eq!(&encoded[..8], &bytes.map(|b| !b)[..]);
+        prop_assert_eq!(lexcode::from_bytes::<(lexcode::Descending<FixedBytes<8>>, u16)>(&encoded)?, key);
+    }
+
+    #[test]
+    fn human_readable_formats_get_hex(bytes in any::<[u8; 8]>()) {
+        let fixed = FixedBytes(bytes);
+        let json = serde_json::to_string(&fixed).unwrap();
+        prop_assert_eq!(&json, &format!("\"{fixed}\""));
+        prop_assert_eq!(serde_json::from_str::<FixedBytes<8>>(&json).unwrap(), fixed);
+    }
+
+    #[test]
+    fn display_roundtrips_through_from_str(bytes in any::<[u8; 5]>()) {
+        let fixed = FixedBytes(bytes);
+        let hex = fixed.to_string();
+        prop_assert_eq!(hex.len(), 10);
+        prop_assert_eq!(hex.parse::<FixedBytes<5>>()?, fixed);
+        prop_assert_eq!(hex.to_uppercase().parse::<FixedBytes<5>>()?, fixed);
+    }
+
+    #[test]
+    fn binary_formats_keep_their_encoding(bytes in any::<[u8; 4]>()) {
+        let fixed = FixedBytes(bytes);
+        let key = lexcode::memcomparable::to_bytes(&fixed)?;
+        prop_assert_eq!(&key, &lexcode::memcomparable::to_bytes(&(bytes[0], bytes[1], bytes[2], bytes[3]))?);
+        prop_assert_eq!(lexcode::memcomparable::from_bytes::<FixedBytes<4>>(&key)?, fixed);
+    }
+}
+
+#[test]
+fn known_hex() {
+    let fixed = FixedBytes([0xde, 0xad, 0x00, 0x0f]);
+    assert_eq!(fixed.to_string(), "dead000f");
+    assert_eq!("DEAD000f".parse::<FixedBytes<4>>().unwrap(), fixed);
+    assert_eq!(serde_json::to_string(&(1, fixed)).unwrap(), r#"[1,"dead000f"]"#);
+}
+
+#[test]
+fn malformed_hex_is_an_error() {
+    assert!("dead00".parse::<FixedBytes<4>>().is_err());
+    assert!("dead000f00".parse::<FixedBytes<4>>().is_err());
+    assert!("dead000g".parse::<FixedBytes<4>>().is_err());
+    assert!("deé00f".parse::<FixedBytes<3>>().is_err());
+    assert!(serde_json::from_str::<FixedBytes<4>>(r#""dead""#).is_err());
+}
+
+#[test]
+fn truncated_input_is_an_error() {
+    assert!(lexcode::from_bytes::<FixedBytes<4>>(&[1, 2, 3]).is_err());
+    assert!(lexcode::from_bytes::<(u8, FixedBytes<2>)>(&[1, 2]).is_err());
+}
+
+#[test]
+fn huge_decoded_lengths_are_an_error() {
+    // A digit count of `u64::MAX` is read as raw bytes that are not there.
+    let bytes = lexcode::to_bytes(&(u64::MAX, 0u64)).unwrap();
+    assert!(lexcode::from_bytes::<lexcode::NumericString>(&bytes).is_err());
+}
+
+#[test]
+fn slices_of_the_right_length_convert() {
+    let slice: &[u8] = &[1, 2, 3];
+    assert_eq!(FixedBytes::<3>::try_from(slice).unwrap(), FixedBytes([1, 2, 3]));
+    assert!(FixedBytes::<4>::try_from(slice).is_err());
+}
+
+#[test]
+fn derefs_to_the_array() {
+    let fixed = FixedBytes([1u8, 2, 3]);
+    assert_eq!(fixed.len(), 3);
+    assert_eq!(fixed[1], 2);
+    assert_eq!(*fixed, [1, 2, 3]);
+}
+
+#[cfg(feature = "random")]
+#[test]
+fn random_values_differ() {
+    let (a, b) = (FixedBytes::<16>::random(), FixedBytes::<16>::random());
+    assert_ne!(a, b);
+}
@@ -0,0 +1,34 @@
+use lexcode::FixedString;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in "[a-c\u{1}é]{0,4}", b in "[a-c\u{1}é]{0,4}") {
+        let (a, b) = (FixedString::<8>::new(a).unwrap(), FixedString::<8>::new(b).unwrap());
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(bytes_a.len(), 8);
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<FixedString<8>>(&bytes_a)?, a);
+    }
+}
+
+#[test]
+fn full_width_has_no_padding() {
+    let bytes = lexcode::to_bytes(&FixedString::<3>::new("abc").unwrap()).unwrap();
+    assert_eq!(bytes, b"abc");
+    assert_eq!(lexcode::from_bytes::<FixedString<3>>(&bytes).unwrap().as_str(), "abc");
+}
+
+#[test]
+fn rejects_overflow_and_nul() {
+    assert!(FixedString::<3>::new("abcd").is_err());
+    assert!(FixedString::<3>::new("é\u{e9}").is_err());
+    assert!(FixedString::<3>::new("a\0").is_err());
+}
+
+#[test]
+fn rejects_data_after_padding() {
+    assert!(lexcode::from_bytes::<FixedString<3>>(b"a\0b").is_err());
+    assert!(lexcode::from_bytes::<FixedString<3>>(&[0xFF, 0, 0]).is_err());
+}
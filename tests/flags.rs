@@ -0,0 +1,35 @@
+use lexcode::Flags;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in any::<[bool; 11]>(), b in any::<[bool; 11]>()) {
+        let (a, b) = (Flags(a), Flags(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(bytes_a.len(), 2);
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Flags<11>>(&bytes_a)?, a);
+    }
+}
+
+#[test]
+fn whole_bytes_and_empty() {
+    let flags = Flags([true; 8]);
+    assert_eq!(lexcode::to_bytes(&flags).unwrap(), [0xFF]);
+    assert_eq!(lexcode::from_bytes::<Flags<8>>(&[0xFF]).unwrap(), flags);
+    assert!(lexcode::to_bytes(&Flags::<0>::default()).unwrap().is_empty());
+}
+
+#[test]
+fn get_and_set() {
+    let mut flags = Flags::<3>::default();
+    flags.set(2, true);
+    assert!(!flags.get(0) && flags.get(2));
+    assert_eq!(lexcode::to_bytes(&flags).unwrap(), [0b0010_0000]);
+}
+
+#[test]
+fn padding_bits_are_rejected() {
+    assert!(lexcode::from_bytes::<Flags<3>>(&[0b0001_0000]).is_err());
+}
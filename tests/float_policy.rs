@@ -0,0 +1,74 @@
+use lexcode::{NanPolicy, Serializer};
+use serde::Serialize;
+
+fn encode<T: Serialize>(value: &T, serializer: Serializer) -> lexcode::Result<Vec<u8>> {
+    let mut serializer = serializer;
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_bytes())
+}
+
+const NANS_F64: [u64; 4] = [0x7FF8_0000_0000_0000, 0x7FF0_0000_0000_0001, 0xFFF8_0000_0000_0000, 0xFFFF_FFFF_FFFF_FFFF];
+const NANS_F32: [u32; 3] = [0x7FC0_0000, 0x7F80_0001, 0xFFC0_0000];
+
+#[test]
+fn raw_nan_policy_is_the_default() {
+    for bits in NANS_F64 {
+        let nan = f64::from_bits(bits);
+        assert_eq!(encode(&nan, Serializer::new()).unwrap(), lexcode::to_bytes(&nan).unwrap());
+        let decoded: f64 = lexcode::from_bytes(&lexcode::to_bytes(&nan).unwrap()).unwrap();
+        assert_eq!(decoded.to_bits(), bits);
+    }
+}
+
+#[test]
+fn canonical_nan_policy_collapses_nans() {
+    let canonical = |v| encode(&v, Serializer::new().nan_policy(NanPolicy::Canonicalize)).unwrap();
+    let expected = canonical(f64::NAN);
+    for bits in NANS_F64 {
+        assert_eq!(canonical(f64::from_bits(bits)), expected);
+    }
+    assert!(canonical(f64::INFINITY) < expected);
+    let decoded: f64 = lexcode::from_bytes(&expected).unwrap();
+    assert_eq!(decoded.to_bits(), 0x7FF8_0000_0000_0000);
+
+    let canonical32 = |v| encode(&v, Serializer::new().nan_policy(NanPolicy::Canonicalize)).unwrap();
+    let expected32 = canonical32(f32::NAN);
+    for bits in NANS_F32 {
+        assert_eq!(canonical32(f32::from_bits(bits)), expected32);
+    }
+    // Non-NaN values are untouched.
+    assert_eq!(canonical(-1.5f64), lexcode::to_bytes(&-1.5f64).unwrap());
+}
+
+#[test]
+fn reject_nan_policy_errors() {
+    let reject = || Serializer::new().nan_policy(NanPolicy::Reject);
+    assert!(encode(&f64::NAN, reject()).is_err());
+    assert!(encode(&(1u8, vec![f32::NAN]), reject()).is_err());
+    assert!(encode(&f64::INFINITY, reject()).is_ok());
+}
+
+#[test]
+fn zero_normalization() {
+    assert_ne!(lexcode::to_bytes(&-0.0f64).unwrap(), lexcode::to_bytes(&0.0f64).unwrap());
+    let normalized = |v| encode(&v, Serializer::new().normalize_zero(true)).unwrap();
+    assert_eq!(normalized(-0.0f64), normalized(0.0f64));
+    assert_eq!(normalized(-0.0f64), lexcode::to_bytes(&0.0f64).unwrap());
+    let normalized32 = |v| encode(&v, Serializer::new().normalize_zero(true)).unwrap();
+    assert_eq!(normalized32(-0.0f32), normalized32(0.0f32));
+    assert!(normalized(-f64::MIN_POSITIVE) < normalized(-0.0));
+    assert!(normalized(-0.0) < normalized(f64::MIN_POSITIVE));
+}
+
+#[test]
+fn finite_only_mode() {
+    let strict = || Serializer::new().finite_only(true);
+    for v in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(encode(&v, strict()).is_err());
+        assert!(encode(&(v as f32), strict()).is_err());
+    }
+    // Even a canonicalizing NaN policy does not let NaN through.
+    assert!(encode(&f64::NAN, strict().nan_policy(NanPolicy::Canonicalize)).is_err());
+    assert_eq!(encode(&f64::MAX, strict()).unwrap(), lexcode::to_bytes(&f64::MAX).unwrap());
+    assert!(encode(&("price", Some(-0.25f32)), strict()).is_ok());
+}
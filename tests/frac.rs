@@ -0,0 +1,87 @@
+use lexcode::frac;
+use proptest::prelude::*;
+
+fn valid_key() -> impl Strategy<Value = String> {
+    "[0-9A-Za-z]{0,4}[1-9A-Za-z]"
+}
+
+proptest! {
+    #[test]
+    fn between_is_strictly_between(a in valid_key(), b in valid_key()) {
+        match frac::between(&a, &b) {
+            Ok(mid) => {
+                prop_assert!(a < mid && mid < b);
+                prop_assert!(mid.len() <= a.len().max(b.len()) + 1);
+            }
+            Err(_) => prop_assert!(a >= b),
+        }
+    }
+
+    #[test]
+    fn before_and_after_are_neighbours(key in valid_key()) {
+        let (low, high) = (frac::before(&key).unwrap(), frac::after(&key).unwrap());
+        prop_assert!(low < key && key < high);
+        prop_assert!(frac::between(&low, &key).is_ok());
+        prop_assert!(frac::between(&key, &high).is_ok());
+    }
+
+    #[test]
+    fn spread_between_is_sorted_and_bounded(a in valid_key(), b in valid_key(), n in 0usize..40) {
+        prop_assume!(a < b);
+        let keys = frac::spread_between(&a, &b, n).unwrap();
+        prop_assert_eq!(keys.len(), n);
+        prop_assert!(keys.windows(2).all(|w| w[0] < w[1]));
+        prop_assert!(keys.iter().all(|k| a < *k && *k < b));
+    }
+
+    #[test]
+    fn keys_sort_like_their_encoding(a in valid_key(), b in valid_key()) {
+        let (x, y) = (lexcode::to_bytes(&a).unwrap(), lexcode::to_bytes(&b).unwrap());
+        prop_assert_eq!(a.cmp(&b), x.cmp(&y));
+    }
+}
+
+#[test]
+fn spread_uses_minimal_length() {
+    assert_eq!(frac::spread(0), Vec::<String>::new());
+    assert_eq!(frac::spread(1), ["V"]);
+    for n in [61, 62, 1000, 3844] {
+        let keys = frac::spread(n);
+        assert_eq!(keys.len(), n);
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+        let len = if n < 62 { 1 } else if n < 3844 { 2 } else { 3 };
+        assert!(keys.iter().all(|k| k.len() <= len && !k.ends_with('0')));
+    }
+}
+
+#[test]
+fn growth_stays_bounded() {
+    let mut last = "V".to_string();
+    for _ in 0..1000 {
+        last = frac::after(&last).unwrap();
+    }
+    assert!(last.len() <= 20);
+
+    let mut first = "V".to_string();
+    for _ in 0..1000 {
+        first = frac::before(&first).unwrap();
+    }
+    assert!(first.len() <= 20);
+
+    // Always inserting right after the same item halves the same gap.
+    let (low, mut high) = ("V".to_string(), "W".to_string());
+    for _ in 0..60 {
+        high = frac::between(&low, &high).unwrap();
+    }
+    assert!(high.len() <= 13);
+}
+
+#[test]
+fn rejects_invalid_keys() {
+    assert!(frac::between("", "V").is_err());
+    assert!(frac::between("V0", "W").is_err());
+    assert!(frac::after("a-b").is_err());
+    assert!(frac::before("").is_err());
+    assert!(frac::between("W", "V").is_err());
+    assert!(frac::spread_between("V", "V", 1).is_err());
+}
@@ -0,0 +1,85 @@
+use lexcode::geo::{self, GeoPoint};
+use proptest::prelude::*;
+
+fn point() -> impl Strategy<Value = GeoPoint> {
+    (-90.0..=90.0f64, -180.0..=180.0f64).prop_map(|(lat, lon)| GeoPoint::new(lat, lon).unwrap())
+}
+
+fn covered(ranges: &[lexcode::ByteRange], key: &[u8]) -> bool {
+    ranges.iter().any(|range| lexcode::range_contains(range, key))
+}
+
+proptest! {
+    #[test]
+    fn roundtrip_and_accuracy(lat in -90.0..=90.0f64, lon in -180.0..=180.0f64) {
+        let point = GeoPoint::new(lat, lon).unwrap();
+        let bytes = lexcode::to_bytes(&point)?;
+        prop_assert_eq!(bytes.len(), 8);
+        prop_assert_eq!(lexcode::from_bytes::<GeoPoint>(&bytes)?, point);
+        prop_assert_eq!(GeoPoint::from_z_order(point.z_order()), point);
+        prop_assert!((point.lat() - lat).abs() < 1e-7);
+        prop_assert!((point.lon() - lon).abs() < 1e-7);
+    }
+
+    #[test]
+    fn covering_ranges_contain_every_point_in_the_box(
+        a in point(),
+        b in point(),
+        inside in (0.0..=1.0f64, 0.0..=1.0f64),
+        outside in point(),
+        max_ranges in 1usize..40,
+    ) {
+        let sw = GeoPoint::new(a.lat().min(b.lat()), a.lon().min(b.lon())).unwrap();
+        let ne = GeoPoint::new(a.lat().max(b.lat()), a.lon().max(b.lon())).unwrap();
+        let ranges = geo::covering_ranges(sw, ne, max_ranges).unwrap();
+        prop_assert!(!ranges.is_empty() && ranges.len() <= max_ranges);
+
+        let lat = sw.lat() + inside.0 * (ne.lat() - sw.lat());
+        let lon = sw.lon() + inside.1 * (ne.lon() - sw.lon());
+        let point = GeoPoint::new(lat, lon).unwrap();
+        prop_assert!(point.is_within(sw, ne));
+        prop_assert!(covered(&ranges, &lexcode::to_bytes(&(point, 5u32))?));
+        if outside.is_within(sw, ne) {
+            prop_assert!(covered(&ranges, &lexcode::to_bytes(&outside)?));
+        }
+    }
+}
+
+#[test]
+fn ranges_are_ascending_and_disjoint() {
+    let sw = GeoPoint::new(40.0, -75.0).unwrap();
+    let ne = GeoPoint::new(41.5, -73.0).unwrap();
+    let ranges = geo::covering_ranges(sw, ne, 32).unwrap();
+    for pair in ranges.windows(2) {
+        let (std::ops::Bound::Excluded(end), std::ops::Bound::Included(next)) = (&pair[0].1, &pair[1].0) else {
+            panic!("unexpected bounds {pair:?}");
+        };
+        assert!(end < next);
+    }
+}
+
+#[test]
+fn more_ranges_fit_tighter() {
+    let sw = GeoPoint::new(48.0, 2.0).unwrap();
+    let ne = GeoPoint::new(49.0, 3.0).unwrap();
+    let far = lexcode::to_bytes(&GeoPoint::new(47.0, 2.5).unwrap()).unwrap();
+    assert!(!covered(&geo::covering_ranges(sw, ne, 64).unwrap(), &far));
+}
+
+#[test]
+fn rejects_bad_input() {
+    assert!(GeoPoint::new(90.5, 0.0).is_err());
+    assert!(GeoPoint::new(0.0, f64::NAN).is_err());
+    let (sw, ne) = (GeoPoint::new(1.0, 1.0).unwrap(), GeoPoint::new(0.0, 2.0).unwrap());
+    assert!(geo::covering_ranges(sw, ne, 4).is_err());
+    assert!(geo::covering_ranges(ne, ne, 0).is_err());
+}
+
+#[test]
+fn single_point_box_is_one_exact_range() {
+    let point = GeoPoint::new(-33.8688, 151.2093).unwrap();
+    let ranges = geo::covering_ranges(point, point, 1000).unwrap();
+    let key = lexcode::to_bytes(&point).unwrap();
+    let next = lexcode::to_bytes(&GeoPoint::from_z_order(point.z_order() + 1)).unwrap();
+    assert_eq!(ranges, [(std::ops::Bound::Included(key), std::ops::Bound::Excluded(next))]);
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "half")]
+
+use half::{bf16, f16};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct H(#[serde(with = "lexcode::half::f16")] f16);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct B(#[serde(with = "lexcode::half::bf16")] bf16);
+
+proptest! {
+    #[test]
+    fn f16_order_and_roundtrip(a in any::<u16>(), b in any::<u16>()) {
+        let (a, b) = (f16::from_bits(a), f16::from_bits(b));
+        let bytes_a = lexcode::to_bytes(&H(a))?;
+        let bytes_b = lexcode::to_bytes(&H(b))?;
+        prop_assert_eq!(bytes_a.len(), 2);
+        if a < b {
+            prop_assert!(bytes_a < bytes_b);
+        } else if a > b {
+            prop_assert!(bytes_a > bytes_b);
+        }
+        prop_assert_eq!(lexcode::from_bytes::<H>(&bytes_a)?.0.to_bits(), a.to_bits());
+    }
+
+    #[test]
+    fn bf16_order_and_roundtrip(a in any::<u16>(), b in any::<u16>()) {
+        let (a, b) = (bf16::from_bits(a), bf16::from_bits(b));
+        let bytes_a = lexcode::to_bytes(&B(a))?;
+        let bytes_b = lexcode::to_bytes(&B(b))?;
+        if a < b {
+            prop_assert!(bytes_a < bytes_b);
+        } else if a > b {
+            prop_assert!(bytes_a > bytes_b);
+        }
+        prop_assert_eq!(lexcode::from_bytes::<B>(&bytes_a)?.0.to_bits(), a.to_bits());
+    }
+}
+
+#[test]
+fn f16_matches_f32_order_of_widened_values() {
+    let values = [f16::NEG_INFINITY, f16::MIN, f16::from_f32(-1.0), f16::ZERO, f16::MIN_POSITIVE, f16::MAX, f16::INFINITY];
+    let encoded: Vec<_> = values.iter().map(|v| lexcode::to_bytes(&H(*v)).unwrap()).collect();
+    assert!(encoded.windows(2).all(|w| w[0] < w[1]));
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "heed")]
+
+use heed::{Database, EnvOpenOptions};
+use lexcode::heed::Lex;
+use lexcode::Descending;
+use proptest::prelude::*;
+
+type K = (String, Descending<i32>);
+
+fn env(name: &str) -> (heed::Env, std::path::PathBuf) {
+    let path = std::env::temp_dir().join(format!("lexcode-heed-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&path).unwrap();
+    let env = unsafe { EnvOpenOptions::new().open(&path) }.unwrap();
+    (env, path)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn databases_iterate_in_key_order(keys in prop::collection::btree_set(("[ab]{0,2}", any::<i32>()), 0..8)) {
+        let (env, path) = env("order");
+        let mut wtxn = env.write_txn().unwrap();
+        let db: Database<Lex<K>, Lex<i32>> = env.create_database(&mut wtxn, None).unwrap();
+        db.clear(&mut wtxn).unwrap();
+        for (s, n) in &keys {
+            db.put(&mut wtxn, &(s.clone(), Descending(*n)), n).unwrap();
+        }
+        wtxn.commit().unwrap();
+
+        let rtxn = env.read_txn().unwrap();
+        let stored: Vec<(K, i32)> = db.iter(&rtxn).unwrap().map(Result::unwrap).collect();
+        let mut expected: Vec<(K, i32)> = keys.iter().map(|(s, n)| ((s.clone(), Descending(*n)), *n)).collect();
+        expected.sort();
+        drop(rtxn);
+        drop(env);
+        std::fs::remove_dir_all(path).unwrap();
+        prop_assert_eq!(stored, expected);
+    }
+}
+
+#[test]
+fn ranges_take_typed_bounds() {
+    let (env, path) = env("range");
+    let mut wtxn = env.write_txn().unwrap();
+    let db: Database<Lex<K>, Lex<()>> = env.create_database(&mut wtxn, None).unwrap();
+    for (s, n) in [("a", 1), ("b", 3), ("b", 2), ("b", -1), ("c", 0)] {
+        db.put(&mut wtxn, &(s.to_string(), Descending(n)), &()).unwrap();
+    }
+    wtxn.commit().unwrap();
+
+    let rtxn = env.read_txn().unwrap();
+    let range = ("b".to_string(), Descending(2))..=("b".to_string(), Descending(i32::MIN));
+    let hits: Vec<i32> = db.range(&rtxn, &range).unwrap().map(|row| row.unwrap().0 .1 .0).collect();
+    assert_eq!(hits, [2, -1]);
+
+    drop(rtxn);
+    drop(env);
+    std::fs::remove_dir_all(path).unwrap();
+}
+
+#[test]
+fn undecodable_keys_are_errors() {
+    let (env, path) = env("errors");
+    let mut wtxn = env.write_txn().unwrap();
+    let db: Database<Lex<K>, Lex<()>> = env.create_database(&mut wtxn, None).unwrap();
+    db.remap_key_type::<heed::types::Bytes>().put(&mut wtxn, b"\xFF", &()).unwrap();
+    wtxn.commit().unwrap();
+
+    let rtxn = env.read_txn().unwrap();
+    assert!(db.first(&rtxn).is_err());
+    drop(rtxn);
+    drop(env);
+    std::fs::remove_dir_all(path).unwrap();
+}
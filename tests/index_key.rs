@@ -0,0 +1,49 @@
+use lexcode::{Descending, IndexKey};
+use proptest::prelude::*;
+use std::collections::BTreeMap;
+use std::ops::{Bound, RangeBounds};
+
+type ByScore = IndexKey<i32, u64>;
+
+fn bound() -> impl Strategy<Value = Bound<i32>> {
+    prop_oneof![
+        (-3i32..3).prop_map(Bound::Included),
+        (-3i32..3).prop_map(Bound::Excluded),
+        Just(Bound::Unbounded),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn range_selects_by_key_whatever_the_id(entries in prop::collection::vec((-3i32..3, any::<u64>()), 0..20), start in bound(), end in bound()) {
+        prop_assume!(match (start, end) {
+            (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b)) => a < b,
+            _ => true,
+        });
+        let index: BTreeMap<Vec<u8>, (i32, u64)> = entries
+            .iter()
+            .map(|&(score, id)| (lexcode::to_bytes(&ByScore::new(score, id)).unwrap(), (score, id)))
+            .collect();
+        let mut hits: Vec<_> = index.range(ByScore::range((start, end)).unwrap()).map(|(_, &e)| e).collect();
+        let mut expected: Vec<_> = entries.into_iter().filter(|(score, _)| (start, end).contains(score)).collect();
+        hits.sort();
+        expected.sort();
+        expected.dedup();
+        prop_assert_eq!(hits, expected);
+    }
+
+    #[test]
+    fn decode_key_ignores_id(score in any::<i32>(), id in any::<(u64, String)>()) {
+        let bytes = lexcode::to_bytes(&IndexKey::new(score, id.clone())).unwrap();
+        prop_assert_eq!(IndexKey::<i32, (u64, String)>::decode_key(&bytes).unwrap(), score);
+        let (start, end) = IndexKey::<i32, (u64, String)>::prefix(&score).unwrap();
+        prop_assert!(start <= bytes && bytes < end);
+    }
+}
+
+#[test]
+fn all_ff_keys() {
+    type Desc = IndexKey<Descending<()>, u8>;
+    assert!(Desc::range(..=Descending(())).is_ok());
+    assert!(Desc::range((Bound::Excluded(Descending(())), Bound::Unbounded)).is_err());
+}
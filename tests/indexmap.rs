@@ -0,0 +1,46 @@
+#![cfg(feature = "indexmap")]
+
+use indexmap::{IndexMap, IndexSet};
+use lexcode::Serializer;
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Sorted {
+    #[serde(with = "lexcode::indexmap::sorted")]
+    map: IndexMap<i32, String>,
+    #[serde(with = "lexcode::indexmap::sorted_set")]
+    set: IndexSet<String>,
+}
+
+proptest! {
+    #[test]
+    fn sorted_matches_btree(entries in prop::collection::vec((any::<i32>(), "[a-c]{0,3}"), 0..8)) {
+        let value = Sorted {
+            map: entries.iter().cloned().collect(),
+            set: entries.iter().map(|(_, s)| s.clone()).collect(),
+        };
+        let btree: BTreeMap<i32, String> = entries.iter().cloned().collect();
+        let btree_set: BTreeSet<String> = entries.into_iter().map(|(_, s)| s).collect();
+        let bytes = lexcode::to_bytes(&value)?;
+        prop_assert_eq!(&bytes, &lexcode::to_bytes(&(&btree, &btree_set))?);
+
+        let decoded: Sorted = lexcode::from_bytes(&bytes)?;
+        prop_assert!(decoded.map.keys().eq(btree.keys()));
+        prop_assert_eq!(decoded, value);
+    }
+}
+
+#[test]
+fn plain_index_map_keeps_insertion_order() {
+    let map: IndexMap<u8, u8> = IndexMap::from([(2, 0), (1, 0)]);
+    let bytes = lexcode::to_bytes(&map).unwrap();
+    assert_eq!(bytes, [1, 2, 0, 1, 1, 0, 0]);
+    let decoded: IndexMap<u8, u8> = lexcode::from_bytes(&bytes).unwrap();
+    assert!(decoded.keys().eq([2, 1].iter()));
+
+    let mut serializer = Serializer::new().sort_maps(true);
+    map.serialize(&mut serializer).unwrap();
+    assert_eq!(serializer.into_bytes(), [1, 1, 0, 1, 2, 0, 0]);
+}
@@ -0,0 +1,42 @@
+use lexcode::Interleaved;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn roundtrip_and_order(a in any::<(u8, u32, u16)>(), b in any::<(u8, u32, u16)>()) {
+        let (a, b) = (Interleaved(a), Interleaved(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(bytes_a.len(), 7);
+        prop_assert_eq!(lexcode::from_bytes::<Interleaved<(u8, u32, u16)>>(&bytes_a)?, a);
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+    }
+
+    #[test]
+    fn matches_reference_morton_code(x in any::<u32>(), y in any::<u32>()) {
+        let z = (0..32).fold(0u64, |z, i| z | (((x >> i) & 1) as u64) << (2 * i + 1) | (((y >> i) & 1) as u64) << (2 * i));
+        prop_assert_eq!(lexcode::to_bytes(&Interleaved((x, y)))?, z.to_be_bytes());
+    }
+
+    #[test]
+    fn aligned_boxes_are_contiguous(x in any::<u16>(), y in any::<u16>(), other in any::<(u16, u16)>(), level in 0u32..16) {
+        // The box of side 2^level holding (x, y) covers one run of keys.
+        let mask = !0u16 << level;
+        let first = Interleaved((x & mask, y & mask));
+        let last = Interleaved((x | !mask, y | !mask));
+        let inside = (other.0 & mask, other.1 & mask) == (x & mask, y & mask);
+        let other = Interleaved(other);
+        prop_assert_eq!(first <= other && other <= last, inside);
+    }
+}
+
+#[test]
+fn length_is_the_total_width() {
+    assert_eq!(Interleaved::<(u8, u8, u8)>::encoded_len(), 3);
+    assert_eq!(Interleaved::<(u128, u64, u8)>::encoded_len(), 25);
+    let key = Interleaved((1u8, 2u16, 3u64));
+    assert_eq!(lexcode::to_bytes(&key).unwrap(), key.to_bytes());
+    assert_eq!(Interleaved::from_bytes(&key.to_bytes()), Some(key));
+    assert_eq!(Interleaved::<(u8, u16, u64)>::from_bytes(&[0; 10]), None);
+    assert!(lexcode::from_bytes::<Interleaved<(u8, u16, u64)>>(&[0; 10]).is_err());
+}
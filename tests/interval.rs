@@ -0,0 +1,78 @@
+use lexcode::Interval;
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ops::Bound;
+
+fn bound() -> impl Strategy<Value = Bound<i16>> {
+    prop_oneof![
+        Just(Bound::Unbounded),
+        any::<i16>().prop_map(Bound::Included),
+        any::<i16>().prop_map(Bound::Excluded),
+    ]
+}
+
+fn interval() -> impl Strategy<Value = Interval<i16>> {
+    (bound(), bound()).prop_map(|(start, end)| Interval::new(start, end))
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Bounds {
+    #[serde(with = "lexcode::bound::start")]
+    start: Bound<String>,
+    #[serde(with = "lexcode::bound::end")]
+    end: Bound<String>,
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in interval(), b in interval()) {
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Interval<i16>>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn contains_matches_range_bounds(a in interval(), x in any::<i16>()) {
+        let expected = match a.start {
+            Bound::Included(s) => s <= x,
+            Bound::Excluded(s) => s < x,
+            Bound::Unbounded => true,
+        } && match a.end {
+            Bound::Included(e) => x <= e,
+            Bound::Excluded(e) => x < e,
+            Bound::Unbounded => true,
+        };
+        prop_assert_eq!(a.contains(&x), expected);
+    }
+
+    #[test]
+    fn bound_modules_roundtrip(start in bound(), end in bound()) {
+        let value = Bounds { start: start.map(|v| v.to_string()), end: end.map(|v| v.to_string()) };
+        prop_assert_eq!(lexcode::from_bytes::<Bounds>(&lexcode::to_bytes(&value)?)?, value);
+    }
+}
+
+#[test]
+fn starts_and_ends_sort_as_bounds() {
+    let starts = [Bound::Unbounded, Bound::Included(1), Bound::Excluded(1), Bound::Included(2)];
+    let ends = [Bound::Excluded(1), Bound::Included(1), Bound::Included(2), Bound::Unbounded];
+    let keys: Vec<_> = starts.iter().map(|&s| lexcode::to_bytes(&Interval::new(s, Bound::Unbounded)).unwrap()).collect();
+    assert!(keys.is_sorted());
+    let keys: Vec<_> = ends.iter().map(|&e| lexcode::to_bytes(&Interval::new(Bound::Unbounded, e)).unwrap()).collect();
+    assert!(keys.is_sorted());
+}
+
+#[test]
+fn scan_of_intervals_starting_at_or_before_a_point() {
+    let limit = lexcode::to_bytes(&Interval::new(Bound::Excluded(5), Bound::Unbounded)).unwrap();
+    for (interval, expected) in [
+        (Interval::from(..), true),
+        (Interval::from(5..), true),
+        (Interval::from(5..=5), true),
+        (Interval::new(Bound::Excluded(5), Bound::Unbounded), false),
+        (Interval::from(6..7), false),
+    ] {
+        assert_eq!(lexcode::to_bytes(&interval).unwrap() < limit, expected, "{interval:?}");
+    }
+}
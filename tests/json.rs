@@ -0,0 +1,141 @@
+#![cfg(feature = "json")]
+
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Number, Value};
+use std::cmp::Ordering;
+
+fn number() -> impl Strategy<Value = Number> {
+    prop_oneof![
+        any::<u64>().prop_map(Number::from),
+        any::<i64>().prop_map(Number::from),
+        (-8i64..8).prop_map(Number::from),
+        (-16i32..16).prop_map(|v| Number::from_f64(v as f64 / 2.0).unwrap()),
+        any::<f64>().prop_filter_map("finite", Number::from_f64),
+        Just(Number::from(u64::MAX)),
+        Just(Number::from_f64(u64::MAX as f64).unwrap()),
+        Just(Number::from_f64(-0.0).unwrap()),
+    ]
+}
+
+fn value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        number().prop_map(Value::Number),
+        "[ab\0é]{0,3}".prop_map(Value::String),
+    ];
+    leaf.prop_recursive(3, 16, 3, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..3).prop_map(Value::Array),
+            prop::collection::btree_map("[ab]{0,2}", inner, 0..3).prop_map(|map| Value::Object(map.into_iter().collect())),
+        ]
+    })
+}
+
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::Null => 0,
+        Value::Bool(false) => 1,
+        Value::Bool(true) => 2,
+        Value::Number(_) => 3,
+        Value::String(_) => 4,
+        Value::Array(_) => 5,
+        Value::Object(_) => 6,
+    }
+}
+
+fn integer(number: &Number) -> Option<i128> {
+    number.as_u64().map(i128::from).or_else(|| number.as_i64().map(i128::from))
+}
+
+/// Numeric order, a float before the equal integer.
+fn cmp_numbers(a: &Number, b: &Number) -> Ordering {
+    match (integer(a), integer(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (None, None) => a.as_f64().unwrap().total_cmp(&b.as_f64().unwrap()),
+        (Some(n), None) => cmp_integer_float(n, b.as_f64().unwrap()),
+        (None, Some(n)) => cmp_integer_float(n, a.as_f64().unwrap()).reverse(),
+    }
+}
+
+fn cmp_integer_float(n: i128, x: f64) -> Ordering {
+    match (n as f64).partial_cmp(&x).unwrap() {
+        Ordering::Equal => n.cmp(&(x as i128)).then(Ordering::Greater),
+        order => order,
+    }
+}
+
+/// The documented collation.
+fn cmp_values(a: &Value, b: &Value) -> Ordering {
+    rank(a).cmp(&rank(b)).then_with(|| match (a, b) {
+        (Value::Number(a), Value::Number(b)) => cmp_numbers(a, b),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Array(a), Value::Array(b)) => cmp_seqs(a.iter(), b.iter(), |x, y| cmp_values(x, y)),
+        (Value::Object(a), Value::Object(b)) => {
+            let mut a: Vec<_> = a.iter().collect();
+            let mut b: Vec<_> = b.iter().collect();
+            a.sort_by(|x, y| x.0.cmp(y.0));
+            b.sort_by(|x, y| x.0.cmp(y.0));
+            cmp_seqs(a.into_iter(), b.into_iter(), |x, y| x.0.cmp(y.0).then_with(|| cmp_values(x.1, y.1)))
+        }
+        _ => Ordering::Equal,
+    })
+}
+
+fn cmp_seqs<T>(mut a: impl Iterator<Item = T>, mut b: impl Iterator<Item = T>, cmp: impl Fn(&T, &T) -> Ordering) -> Ordering {
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match cmp(&x, &y) {
+                Ordering::Equal => continue,
+                order => return order,
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Doc {
+    id: u32,
+    #[serde(with = "lexcode::json")]
+    body: Value,
+}
+
+proptest! {
+    #[test]
+    fn roundtrip(v in value()) {
+        let bytes = lexcode::json::to_bytes(&v)?;
+        prop_assert_eq!(lexcode::json::from_bytes(&bytes)?, v);
+    }
+
+    #[test]
+    fn order_is_the_collation(a in value(), b in value()) {
+        let (ka, kb) = (lexcode::json::to_bytes(&a)?, lexcode::json::to_bytes(&b)?);
+        prop_assert_eq!(ka.cmp(&kb), cmp_values(&a, &b));
+    }
+
+    #[test]
+    fn with_helper_roundtrips_in_a_struct(id in any::<u32>(), body in value()) {
+        let doc = Doc { id, body };
+        let bytes = lexcode::to_bytes(&doc)?;
+        prop_assert_eq!(lexcode::from_bytes::<Doc>(&bytes)?, doc);
+    }
+}
+
+#[test]
+fn large_integers_keep_their_order_and_value() {
+    let values = [json!(9007199254740992u64), json!(9007199254740993u64), json!(u64::MAX - 1), json!(u64::MAX)];
+    let keys: Vec<_> = values.iter().map(|v| lexcode::json::to_bytes(v).unwrap()).collect();
+    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    for (key, value) in keys.iter().zip(&values) {
+        assert_eq!(&lexcode::json::from_bytes(key).unwrap(), value);
+    }
+}
+
+#[test]
+fn invalid_tags_are_an_error() {
+    assert!(lexcode::json::from_bytes(&[7]).is_err());
+}
@@ -0,0 +1,52 @@
+use std::collections::{BTreeMap, HashSet};
+
+use lexcode::Key;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn order_matches_values(a in any::<(u32, String)>(), b in any::<(u32, String)>()) {
+        let ka = Key::encode(&a).unwrap();
+        let kb = Key::encode(&b).unwrap();
+        prop_assert_eq!(ka.cmp(&kb), a.cmp(&b));
+        prop_assert_eq!(ka.into_bytes(), lexcode::to_bytes(&a).unwrap());
+    }
+}
+
+#[test]
+fn compares_and_hashes_as_bytes() {
+    let keys: HashSet<Key<u64>> = [1u64, 2, 1].iter().map(|n| Key::encode(n).unwrap()).collect();
+    assert_eq!(keys.len(), 2);
+    let key = Key::<u64>::from_bytes(vec![0x05]);
+    assert_eq!(Vec::from(key.clone()), vec![0x05]);
+    assert_eq!(key, Key::encode(&5u64).unwrap());
+}
+
+fn scan<R: std::ops::RangeBounds<i32>>(map: &BTreeMap<Vec<u8>, i32>, r: R) -> Vec<i32> {
+    map.range(lexcode::range(r).unwrap()).map(|(_, &v)| v).collect()
+}
+
+#[test]
+fn range_maps_every_bound_kind() {
+    let map: BTreeMap<Vec<u8>, i32> = (-50..50).map(|n| (lexcode::to_bytes(&n).unwrap(), n)).collect();
+    assert_eq!(scan(&map, -3..3), (-3..3).collect::<Vec<_>>());
+    assert_eq!(scan(&map, -3..=3), (-3..=3).collect::<Vec<_>>());
+    assert_eq!(scan(&map, 45..), (45..50).collect::<Vec<_>>());
+    assert_eq!(scan(&map, ..-45), (-50..-45).collect::<Vec<_>>());
+    assert_eq!(scan(&map, ..), (-50..50).collect::<Vec<_>>());
+    use std::ops::Bound::{Excluded, Included};
+    assert_eq!(scan(&map, (Excluded(0), Included(2))), vec![1, 2]);
+}
+
+#[test]
+fn decodes_and_borrows_as_bytes() {
+    let key: lexcode::EncodedKey<(u64, String)> = Key::encode(&(3, "x".to_string())).unwrap();
+    assert_eq!(key.decode().unwrap(), (3, "x".to_string()));
+    assert_eq!(key.as_ref(), key.as_bytes());
+
+    let stored = lexcode::to_bytes(&(3u64, "x")).unwrap();
+    let map: BTreeMap<Key<(u64, String)>, &str> = [(key, "row")].into_iter().collect();
+    assert_eq!(map.get(stored.as_slice()), Some(&"row"));
+    let set: HashSet<Key<(u64, String)>> = map.into_keys().collect();
+    assert!(set.contains(stored.as_slice()));
+}
@@ -0,0 +1,35 @@
+use lexcode::{Descending, KeyBuilder, LexConfig};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn matches_tuple_encoding(a in any::<u32>(), b in ".*", c in any::<i64>()) {
+        let mut key = KeyBuilder::new();
+        key.push(&a).unwrap().push(&b).unwrap().push(&Descending(c)).unwrap();
+        prop_assert_eq!(key.finish(), lexcode::to_bytes(&(a, &b, Descending(c))).unwrap());
+    }
+
+    #[test]
+    fn partial_key_is_a_prefix(a in any::<u32>(), b in ".*") {
+        let mut prefix = KeyBuilder::new();
+        prefix.push(&a).unwrap();
+        let prefix = prefix.finish();
+        let full = lexcode::to_bytes(&(a, &b)).unwrap();
+        prop_assert!(full.starts_with(&prefix));
+        let (start, end) = lexcode::prefix_range(&a).unwrap();
+        prop_assert!(start == prefix && full < end);
+    }
+}
+
+#[test]
+fn uses_config() {
+    let config = LexConfig::new().none_last(true);
+    let mut key = KeyBuilder::with_config(config);
+    key.push(&None::<u8>).unwrap();
+    assert_eq!(key.finish(), lexcode::to_bytes_with_config(&None::<u8>, &config).unwrap());
+}
+
+#[test]
+fn empty_builder_is_empty_key() {
+    assert!(KeyBuilder::new().finish().is_empty());
+}
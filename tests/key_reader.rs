@@ -0,0 +1,35 @@
+use lexcode::{Descending, KeyBuilder, KeyReader, LexConfig};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn reads_back_builder_components(a in any::<u32>(), b in ".*", c in any::<i64>()) {
+        let mut builder = KeyBuilder::new();
+        builder.push(&a).unwrap().push(&b).unwrap().push(&Descending(c)).unwrap();
+        let key = builder.finish();
+
+        let mut reader = KeyReader::new(&key);
+        prop_assert_eq!(reader.next::<u32>().unwrap(), a);
+        prop_assert_eq!(reader.remaining(), &lexcode::to_bytes(&(&b, Descending(c))).unwrap()[..]);
+        prop_assert_eq!(reader.next::<String>().unwrap(), b);
+        prop_assert_eq!(reader.next::<Descending<i64>>().unwrap().0, c);
+        prop_assert!(reader.is_empty());
+    }
+}
+
+#[test]
+fn errors_past_the_end() {
+    let key = lexcode::to_bytes(&1u8).unwrap();
+    let mut reader = KeyReader::new(&key);
+    assert_eq!(reader.next::<u8>().unwrap(), 1);
+    assert!(reader.next::<u8>().is_err());
+}
+
+#[test]
+fn uses_config() {
+    let config = LexConfig::new().none_last(true);
+    let key = lexcode::to_bytes_with_config(&(None::<u8>, 5u8), &config).unwrap();
+    let mut reader = KeyReader::with_config(&key, config);
+    assert_eq!(reader.next::<Option<u8>>().unwrap(), None);
+    assert_eq!(reader.next::<u8>().unwrap(), 5);
+}
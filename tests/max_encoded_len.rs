@@ -0,0 +1,47 @@
+use lexcode::{max_encoded_len, Descending, FixedBytes, MaxEncodedLen};
+use proptest::prelude::*;
+
+/// The encoding of `value`, checked against the bound of its type.
+fn within_bound<T: lexcode::Encode + MaxEncodedLen>(value: &T) -> Result<usize, TestCaseError> {
+    let len = lexcode::encode(value).len();
+    prop_assert!(len <= T::MAX_ENCODED_LEN, "{} > {}", len, T::MAX_ENCODED_LEN);
+    Ok(len)
+}
+
+proptest! {
+    #[test]
+    fn encodings_are_within_the_bound(
+        ints in any::<(u8, u16, u32, u64, u128, usize)>(),
+        signed in any::<(i8, i16, i32, i64, i128, isize)>(),
+        other in any::<(bool, char, f32, f64, Option<i64>, [u16; 3])>(),
+    ) {
+        within_bound(&ints)?;
+        within_bound(&signed)?;
+        within_bound(&other)?;
+        within_bound(&Descending(other))?;
+    }
+}
+
+#[test]
+fn extremes_reach_the_bound() {
+    assert_eq!(lexcode::encode(&u8::MAX).len(), max_encoded_len::<u8>());
+    assert_eq!(lexcode::encode(&u16::MAX).len(), max_encoded_len::<u16>());
+    assert_eq!(lexcode::encode(&u32::MAX).len(), max_encoded_len::<u32>());
+    assert_eq!(lexcode::encode(&u64::MAX).len(), max_encoded_len::<u64>());
+    assert_eq!(lexcode::encode(&u128::MAX).len(), max_encoded_len::<u128>());
+    assert_eq!(lexcode::encode(&i8::MIN).len(), max_encoded_len::<i8>());
+    assert_eq!(lexcode::encode(&i16::MIN).len(), max_encoded_len::<i16>());
+    assert_eq!(lexcode::encode(&i32::MIN).len(), max_encoded_len::<i32>());
+    assert_eq!(lexcode::encode(&i64::MIN).len(), max_encoded_len::<i64>());
+    assert_eq!(lexcode::encode(&i128::MIN).len(), max_encoded_len::<i128>());
+    assert_eq!(lexcode::encode(&char::MAX).len(), max_encoded_len::<char>());
+    assert_eq!(max_encoded_len::<(u64, Option<f64>, FixedBytes<16>)>(), 10 + 9 + 16);
+    assert_eq!(max_encoded_len::<()>(), 0);
+}
+
+#[test]
+fn the_bound_is_a_constant() {
+    const LEN: usize = max_encoded_len::<(u32, i64)>();
+    let buf = [0u8; LEN];
+    assert_eq!(buf.len(), 5 + 9);
+}
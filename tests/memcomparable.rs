@@ -0,0 +1,80 @@
+use lexcode::memcomparable::{self, decode_bytes, encode_bytes};
+use lexcode::Descending;
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+enum Status {
+    Active,
+    Suspended,
+}
+
+type K = (i64, String, Option<u32>, f64, Status);
+
+fn key() -> impl Strategy<Value = K> {
+    (
+        any::<i64>(),
+        "[a\0\u{FF}]{0,10}",
+        any::<Option<u32>>(),
+        any::<f64>().prop_filter("not NaN or zero", |f| !f.is_nan() && *f != 0.0),
+        prop_oneof![Just(Status::Active), Just(Status::Suspended)],
+    )
+}
+
+proptest! {
+    #[test]
+    fn bytes_roundtrip_and_keep_their_order(a in prop::collection::vec(any::<u8>(), 0..30), b in prop::collection::vec(any::<u8>(), 0..30)) {
+        let mut encoded_a = Vec::new();
+        encode_bytes(&mut encoded_a, &a);
+        let mut encoded_b = Vec::new();
+        encode_bytes(&mut encoded_b, &b);
+        prop_assert_eq!(encoded_a.len() % 9, 0);
+        prop_assert_eq!(decode_bytes(&encoded_a)?, (a.clone(), encoded_a.len()));
+        prop_assert_eq!(encoded_a.cmp(&encoded_b), a.cmp(&b));
+    }
+
+    #[test]
+    fn keys_roundtrip_and_keep_their_order(a in key(), b in key()) {
+        let (encoded_a, encoded_b) = (memcomparable::to_bytes(&a)?, memcomparable::to_bytes(&b)?);
+        prop_assert_eq!(encoded_a.cmp(&encoded_b), a.partial_cmp(&b).unwrap());
+        prop_assert_eq!(memcomparable::from_bytes::<K>(&encoded_a)?, a.clone());
+        prop_assert_eq!(memcomparable::migrate::<K>(&encoded_a)?, lexcode::to_bytes(&a)?);
+    }
+}
+
+#[test]
+fn matches_tidb_encodings() {
+    let encode = |bytes: &[u8]| {
+        let mut out = Vec::new();
+        encode_bytes(&mut out, bytes);
+        out
+    };
+    assert_eq!(encode(&[]), [0, 0, 0, 0, 0, 0, 0, 0, 0xF7]);
+    assert_eq!(encode(&[1, 2, 3]), [1, 2, 3, 0, 0, 0, 0, 0, 0xFA]);
+    assert_eq!(encode(&[1, 2, 3, 4, 5, 6, 7, 8]), [1, 2, 3, 4, 5, 6, 7, 8, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0, 0xF7]);
+
+    assert_eq!(memcomparable::to_bytes(&-1i64).unwrap(), [0x03, 0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_eq!(memcomparable::to_bytes(&1u8).unwrap(), [0x04, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(memcomparable::to_bytes(&1.0f64).unwrap(), [0x05, 0xBF, 0xF0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(memcomparable::to_bytes(&None::<i64>).unwrap(), [0x00]);
+    assert_eq!(memcomparable::to_bytes(&-0.0f64).unwrap(), memcomparable::to_bytes(&0.0f64).unwrap());
+}
+
+#[test]
+fn integers_read_across_signedness_when_they_fit() {
+    let unsigned = memcomparable::to_bytes(&7u64).unwrap();
+    assert_eq!(memcomparable::from_bytes::<i32>(&unsigned).unwrap(), 7);
+    let negative = memcomparable::to_bytes(&-7i64).unwrap();
+    assert!(memcomparable::from_bytes::<u64>(&negative).is_err());
+}
+
+#[test]
+fn malformed_input_and_unsupported_types_are_errors() {
+    assert!(memcomparable::from_bytes::<String>(&[0x01, b'a', 0, 0, 0, 0, 0, 0, 0, 0xF0]).is_err());
+    assert!(memcomparable::from_bytes::<String>(&[0x01, b'a', 1, 0, 0, 0, 0, 0, 0, 0xF8]).is_err());
+    assert!(memcomparable::from_bytes::<String>(&[0x01, b'a', 0, 0]).is_err());
+    assert!(memcomparable::from_bytes::<u8>(&[0x04, 0, 0, 0, 0, 0, 0, 0, 1, 0]).is_err());
+    assert!(memcomparable::to_bytes(&vec![1u8]).is_err());
+    assert!(memcomparable::to_bytes(&Descending(1u8)).is_err());
+    assert!(memcomparable::to_bytes(&u128::MAX).is_err());
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Kind {
+  A,
+  B(u64),
+}
+
+#[test]
+fn reencode_same_type_is_identity() {
+  let value = (Kind::B(300), "key".to_string(), -5i32);
+  let bytes = lexcode::to_bytes(&value).unwrap();
+  let reencoded = lexcode::reencode::<(Kind, String, i32)>(&bytes).unwrap();
+  assert_eq!(bytes, reencoded);
+}
+
+#[test]
+fn reencode_widens_fields() {
+  let bytes = lexcode::to_bytes(&(Kind::A, 7u8)).unwrap();
+  let reencoded = lexcode::reencode::<(Kind, u64)>(&bytes).unwrap();
+  assert_eq!(reencoded, lexcode::to_bytes(&(Kind::A, 7u64)).unwrap());
+}
+
+#[test]
+fn reencode_rejects_trailing_bytes() {
+  let bytes = lexcode::to_bytes(&(1u8, 2u8)).unwrap();
+  assert!(lexcode::reencode::<u8>(&bytes).is_err());
+}
@@ -0,0 +1,34 @@
+use lexcode::Money;
+use proptest::prelude::*;
+
+fn money() -> impl Strategy<Value = Money> {
+    ("[A-C]{3}", any::<i128>()).prop_map(|(code, amount)| Money::new(&code, amount).unwrap())
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in money(), b in money()) {
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Money>(&bytes_a)?, a);
+    }
+}
+
+#[test]
+fn validation() {
+    assert_eq!(Money::new("JPY", 100).unwrap().currency_code(), "JPY");
+    assert!(Money::new("JP", 100).is_err());
+    assert!(Money::new("JPYY", 100).is_err());
+    assert!(Money::new("jpy", 100).is_err());
+
+    let invalid = Money { currency: *b"A1C", minor_units: 0 };
+    assert!(lexcode::to_bytes(&invalid).is_err());
+    assert!(lexcode::from_bytes::<Money>(&[b'A', b'1', b'C', 0x80]).is_err());
+}
+
+#[test]
+fn encoding_layout() {
+    let bytes = lexcode::to_bytes(&Money::new("USD", 0).unwrap()).unwrap();
+    assert_eq!(bytes, [b'U', b'S', b'D', 0x80]);
+}
@@ -0,0 +1,33 @@
+use lexcode::Namespace;
+use proptest::prelude::*;
+use std::collections::BTreeMap;
+
+proptest! {
+    #[test]
+    fn namespaces_keep_keys_apart_and_ordered(a in "[a-c]{0,2}", b in "[a-c]{0,2}", x in any::<(u32, String)>(), y in any::<(u32, String)>()) {
+        let (na, nb) = (Namespace::of(&a).unwrap(), Namespace::of(&b).unwrap());
+        let (ka, kb) = (na.encode(&x).unwrap(), nb.encode(&y).unwrap());
+        prop_assert_eq!(na.decode::<(u32, String)>(&ka).unwrap(), x.clone());
+        prop_assert_eq!(nb.decode::<(u32, String)>(&ka).is_ok(), a == b);
+        prop_assert_eq!(ka.cmp(&kb), (&a, &x).cmp(&(&b, &y)));
+    }
+}
+
+#[test]
+fn range_covers_exactly_the_namespace() {
+    let mut map = BTreeMap::new();
+    for tenant in ["a", "ab", "b"] {
+        let ns = Namespace::of(tenant).unwrap();
+        for id in 0u8..3 {
+            map.insert(ns.encode(&id).unwrap(), (tenant, id));
+        }
+    }
+    let ns = Namespace::of("a").unwrap();
+    let hits: Vec<_> = map.range(ns.range()).map(|(_, v)| *v).collect();
+    assert_eq!(hits, [("a", 0), ("a", 1), ("a", 2)]);
+
+    let raw = Namespace::new(vec![0xFF]);
+    assert_eq!(raw.range().1, std::ops::Bound::Unbounded);
+    assert_eq!(raw.strip(&[0xFF, 0x01]), Some(&[0x01][..]));
+    assert_eq!(raw.strip(&[0xFE]), None);
+}
@@ -0,0 +1,73 @@
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct V4(#[serde(with = "lexcode::net::ipv4_addr")] Ipv4Addr);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct V6(#[serde(with = "lexcode::net::ipv6_addr")] Ipv6Addr);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Ip(#[serde(with = "lexcode::net::ip_addr")] IpAddr);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Socket(#[serde(with = "lexcode::net::socket_addr")] SocketAddr);
+
+fn ip_addr() -> impl Strategy<Value = IpAddr> {
+    prop_oneof![
+        any::<[u8; 4]>().prop_map(IpAddr::from),
+        any::<[u8; 16]>().prop_map(IpAddr::from),
+    ]
+}
+
+fn socket_addr() -> impl Strategy<Value = SocketAddr> {
+    prop_oneof![
+        (any::<[u8; 4]>(), any::<u16>()).prop_map(|(ip, port)| SocketAddr::V4(SocketAddrV4::new(ip.into(), port))),
+        (any::<[u8; 16]>(), any::<u16>(), any::<u32>(), any::<u32>())
+            .prop_map(|(ip, port, flow, scope)| SocketAddr::V6(SocketAddrV6::new(ip.into(), port, flow, scope))),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn ipv4_is_raw(a in any::<[u8; 4]>(), b in any::<[u8; 4]>()) {
+        let (a, b) = (Ipv4Addr::from(a), Ipv4Addr::from(b));
+        let bytes_a = lexcode::to_bytes(&V4(a))?;
+        prop_assert_eq!(&bytes_a, &a.octets());
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&lexcode::to_bytes(&V4(b))?));
+        prop_assert_eq!(lexcode::from_bytes::<V4>(&bytes_a)?.0, a);
+    }
+
+    #[test]
+    fn ipv6_is_raw(a in any::<[u8; 16]>()) {
+        let a = Ipv6Addr::from(a);
+        let bytes = lexcode::to_bytes(&V6(a))?;
+        prop_assert_eq!(&bytes, &a.octets());
+        prop_assert_eq!(lexcode::from_bytes::<V6>(&bytes)?.0, a);
+    }
+
+    #[test]
+    fn ip_addr_order_and_roundtrip(a in ip_addr(), b in ip_addr()) {
+        let bytes_a = lexcode::to_bytes(&Ip(a))?;
+        let bytes_b = lexcode::to_bytes(&Ip(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Ip>(&bytes_a)?.0, a);
+    }
+
+    #[test]
+    fn socket_addr_order_and_roundtrip(a in socket_addr(), b in socket_addr()) {
+        let bytes_a = lexcode::to_bytes(&Socket(a))?;
+        let bytes_b = lexcode::to_bytes(&Socket(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Socket>(&bytes_a)?.0, a);
+    }
+}
+
+#[test]
+fn v4_mapped_stays_v6() {
+    let mapped: IpAddr = "::ffff:192.0.2.1".parse().unwrap();
+    let v4: IpAddr = "192.0.2.1".parse().unwrap();
+    assert_ne!(lexcode::to_bytes(&Ip(mapped)).unwrap(), lexcode::to_bytes(&Ip(v4)).unwrap());
+    assert_eq!(lexcode::to_bytes(&Ip(mapped.to_canonical())).unwrap(), lexcode::to_bytes(&Ip(v4)).unwrap());
+}
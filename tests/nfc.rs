@@ -0,0 +1,29 @@
+#![cfg(feature = "nfc")]
+
+use lexcode::Serializer;
+use serde::Serialize;
+
+fn encode<T: Serialize>(value: &T, serializer: Serializer) -> Vec<u8> {
+    let mut serializer = serializer;
+    value.serialize(&mut serializer).unwrap();
+    serializer.into_bytes()
+}
+
+const COMPOSED: &str = "caf\u{e9}";
+const DECOMPOSED: &str = "cafe\u{301}";
+
+#[test]
+fn equivalent_text_differs_by_default() {
+    assert_ne!(encode(&COMPOSED, Serializer::new()), encode(&DECOMPOSED, Serializer::new()));
+}
+
+#[test]
+fn normalized_text_produces_identical_keys() {
+    let nfc = || Serializer::new().normalize_nfc(true);
+    let composed = encode(&(COMPOSED, 1u8), nfc());
+    assert_eq!(composed, encode(&(DECOMPOSED, 1u8), nfc()));
+    assert_eq!(composed, lexcode::to_bytes(&(COMPOSED, 1u8)).unwrap());
+
+    let decoded: (String, u8) = lexcode::from_bytes(&encode(&(DECOMPOSED, 1u8), nfc())).unwrap();
+    assert_eq!(decoded.0, COMPOSED);
+}
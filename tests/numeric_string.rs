@@ -0,0 +1,65 @@
+use lexcode::NumericString;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in "[0-9]{1,30}", b in "[0-9]{1,30}") {
+        let (a, b) = (NumericString::new(a).unwrap(), NumericString::new(b).unwrap());
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<NumericString>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn matches_integer_order(a in any::<u64>(), b in any::<u64>()) {
+        let bytes_a = lexcode::to_bytes(&NumericString::new(a.to_string()).unwrap())?;
+        let bytes_b = lexcode::to_bytes(&NumericString::new(b.to_string()).unwrap())?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+    }
+}
+
+#[test]
+fn leading_zeros_roundtrip() {
+    for s in ["0", "00", "007", "7", "070"] {
+        let bytes = lexcode::to_bytes(&NumericString::new(s).unwrap()).unwrap();
+        assert_eq!(lexcode::from_bytes::<NumericString>(&bytes).unwrap().as_str(), s);
+    }
+}
+
+#[test]
+fn rejects_non_digits() {
+    assert!(NumericString::new("").is_err());
+    assert!(NumericString::new("12a").is_err());
+    assert!(NumericString::new("-1").is_err());
+}
+
+#[test]
+fn too_many_leading_zeros_are_an_error() {
+    let max = NumericString::MAX_LEADING_ZEROS;
+    let longest = NumericString::new("0".repeat(max) + "1").unwrap();
+    let bytes = lexcode::to_bytes(&longest).unwrap();
+    assert_eq!(lexcode::from_bytes::<NumericString>(&bytes).unwrap(), longest);
+    assert!(NumericString::new("0".repeat(max + 1)).is_err());
+
+    // No digits, then 2^40 leading zeros.
+    let huge = lexcode::to_bytes(&(0u64, 1u64 << 40)).unwrap();
+    assert!(lexcode::from_bytes::<NumericString>(&huge).is_err());
+    let over = lexcode::to_bytes(&(1u64, lexcode::FixedBytes([b'1']), max as u64 + 1)).unwrap();
+    assert!(lexcode::from_bytes::<NumericString>(&over).is_err());
+}
+
+#[test]
+fn too_many_digits_are_an_error() {
+    let max = NumericString::MAX_DIGITS;
+    let longest = NumericString::new("9".repeat(max)).unwrap();
+    let bytes = lexcode::to_bytes(&longest).unwrap();
+    assert_eq!(lexcode::from_bytes::<NumericString>(&bytes).unwrap(), longest);
+    assert!(NumericString::new("9".repeat(max + 1)).is_err());
+
+    for len in [max as u64 + 1, u64::MAX] {
+        let bytes = lexcode::to_bytes(&(len, 0u64)).unwrap();
+        let err = lexcode::from_bytes::<NumericString>(&bytes).unwrap_err();
+        assert!(err.to_string().contains("too many digits"), "{err}");
+    }
+}
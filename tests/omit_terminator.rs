@@ -0,0 +1,98 @@
+use lexcode::{Deserializer, Descending, Serializer};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut serializer = Serializer::new().omit_trailing_terminator(true);
+    value.serialize(&mut serializer).unwrap();
+    serializer.into_bytes()
+}
+
+fn decode<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> lexcode::Result<T> {
+    let mut deserializer = Deserializer::from_bytes(bytes).omit_trailing_terminator(true);
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Bytes(Vec<u8>);
+
+impl Serialize for Bytes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Bytes, D::Error> {
+        struct BytesVisitor;
+
+        impl serde::de::Visitor<'_> for BytesVisitor {
+            type Value = Bytes;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a byte string")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Bytes, E> {
+                Ok(Bytes(v.to_vec()))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+proptest! {
+    #[test]
+    fn byte_string_order_and_roundtrip(a in prop::collection::vec(prop_oneof![Just(0x10u8), Just(0x7F), Just(0xFF)], 0..5), b in prop::collection::vec(prop_oneof![Just(0x10u8), Just(0x7F), Just(0xFF)], 0..5)) {
+        let (a, b) = ((1u8, Bytes(a)), (1u8, Bytes(b)));
+        let (bytes_a, bytes_b) = (encode(&a), encode(&b));
+        prop_assert_eq!(&bytes_a, &lexcode::to_bytes(&a)?);
+        prop_assert_eq!(bytes_a.cmp(&bytes_b), lexcode::to_bytes(&a)?.cmp(&lexcode::to_bytes(&b)?));
+        prop_assert_eq!(decode::<(u8, Bytes)>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn string_order_and_roundtrip(a in "[a\u{0}\u{1}]{0,5}", b in "[a\u{0}\u{1}]{0,5}") {
+        let (bytes_a, bytes_b) = (encode(&a), encode(&b));
+        prop_assert_eq!(bytes_a.len() + 2, lexcode::to_bytes(&a)?.len());
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(decode::<String>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn nested_order_and_roundtrip(a in any::<(u8, Vec<Vec<u8>>)>(), b in any::<(u8, Vec<Vec<u8>>)>()) {
+        let (bytes_a, bytes_b) = (encode(&a), encode(&b));
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(decode::<(u8, Vec<Vec<u8>>)>(&bytes_a)?, a);
+    }
+}
+
+#[test]
+fn only_the_last_terminator_is_dropped() {
+    assert_eq!(encode(&("a", "b")), b"a\0\0b");
+    assert_eq!(encode(&vec![1u8, 2]), [1, 1, 1, 2]);
+    let map = BTreeMap::from([(1u8, 2u8)]);
+    assert_eq!(decode::<BTreeMap<u8, u8>>(&encode(&map)).unwrap(), map);
+}
+
+#[test]
+fn byte_string_terminator_is_kept() {
+    let empty = encode(&Bytes(vec![]));
+    assert_eq!(empty, [0x7F, 0x00]);
+    assert_eq!(decode::<Bytes>(&empty).unwrap(), Bytes(vec![]));
+}
+
+#[test]
+fn terminator_not_at_end_is_kept() {
+    assert_eq!(encode(&("a", 1u8)), lexcode::to_bytes(&("a", 1u8)).unwrap());
+    assert_eq!(encode(&Descending("a")), lexcode::to_bytes(&Descending("a")).unwrap());
+}
+
+#[test]
+fn default_deserializer_requires_terminator() {
+    assert!(lexcode::from_bytes::<String>(&encode(&"a")).is_err());
+}
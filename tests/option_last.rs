@@ -0,0 +1,36 @@
+use lexcode::{Descending, OptionLast};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn option_last_orders_none_after_some(a in any::<Option<i64>>(), b in any::<Option<i64>>()) {
+        let (a, b) = (OptionLast(a), OptionLast(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<OptionLast<i64>>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn option_last_in_composite_key(a in any::<(Option<String>, u8)>(), b in any::<(Option<String>, u8)>()) {
+        let key = |(x, y): (Option<String>, u8)| (OptionLast(x), y);
+        let (a, b) = (key(a), key(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<(OptionLast<String>, u8)>(&bytes_a)?, a);
+    }
+}
+
+#[test]
+fn option_last_encoding() {
+    assert_eq!(lexcode::to_bytes(&OptionLast::<u8>(None)).unwrap(), [0x01]);
+    assert_eq!(lexcode::to_bytes(&OptionLast(Some(5u8))).unwrap(), [0x00, 0x05]);
+}
+
+#[test]
+fn option_last_descending_is_nulls_first_descending() {
+    let none = lexcode::to_bytes(&Descending(OptionLast::<u8>(None))).unwrap();
+    let some = lexcode::to_bytes(&Descending(OptionLast(Some(9u8)))).unwrap();
+    assert!(none < some);
+}
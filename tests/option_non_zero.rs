@@ -0,0 +1,39 @@
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::num::{NonZeroU128, NonZeroU64, NonZeroU8};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Key {
+    #[serde(with = "lexcode::option_non_zero")]
+    parent: Option<NonZeroU64>,
+    #[serde(with = "lexcode::option_non_zero")]
+    wide: Option<NonZeroU128>,
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in any::<(Option<NonZeroU64>, Option<NonZeroU128>)>(),
+                           b in any::<(Option<NonZeroU64>, Option<NonZeroU128>)>()) {
+        let a = Key { parent: a.0, wide: a.1 };
+        let b = Key { parent: b.0, wide: b.1 };
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Key>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn one_byte_shorter_than_option(v in any::<NonZeroU64>()) {
+        let niche = lexcode::to_bytes(&Key { parent: Some(v), wide: None })?;
+        let tagged = lexcode::to_bytes(&(Some(v.get()), None::<u128>))?;
+        prop_assert_eq!(niche.len() + 1, tagged.len());
+    }
+}
+
+#[test]
+fn overflow_is_rejected() {
+    #[derive(Deserialize)]
+    struct Small(#[serde(with = "lexcode::option_non_zero")] Option<NonZeroU8>);
+    let bytes = lexcode::to_bytes(&256u64).unwrap();
+    assert!(lexcode::from_bytes::<Small>(&bytes).map(|small| small.0).is_err());
+}
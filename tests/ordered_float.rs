@@ -0,0 +1,72 @@
+#![cfg(feature = "ordered-float")]
+
+use lexcode::{NanPolicy, Serializer};
+use ordered_float::{NotNan, OrderedFloat};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Ordered(#[serde(with = "lexcode::ordered_float::ordered")] OrderedFloat<f64>);
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Finite(#[serde(with = "lexcode::ordered_float::not_nan")] NotNan<f32>);
+
+fn interesting() -> impl Strategy<Value = f64> {
+    prop_oneof![
+        any::<f64>(),
+        Just(f64::NAN),
+        Just(-f64::NAN),
+        Just(f64::from_bits(0x7FF0_0000_0000_0001)),
+        Just(0.0),
+        Just(-0.0),
+        Just(f64::INFINITY),
+        Just(f64::NEG_INFINITY),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn ordered_helper_matches_ord(a in interesting(), b in interesting()) {
+        let (a, b) = (Ordered(OrderedFloat(a)), Ordered(OrderedFloat(b)));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        let decoded: Ordered = lexcode::from_bytes(&bytes_a)?;
+        prop_assert_eq!(decoded, a);
+    }
+
+    #[test]
+    fn configured_serializer_matches_ord(a in interesting(), b in interesting()) {
+        let encode = |v: OrderedFloat<f64>| {
+            let mut ser = Serializer::new().nan_policy(NanPolicy::Canonicalize).normalize_zero(true);
+            v.serialize(&mut ser).unwrap();
+            ser.into_bytes()
+        };
+        let (a, b) = (OrderedFloat(a), OrderedFloat(b));
+        prop_assert_eq!(a.cmp(&b), encode(a).cmp(&encode(b)));
+        prop_assert_eq!(lexcode::from_bytes::<OrderedFloat<f64>>(&encode(a))?, a);
+    }
+
+    #[test]
+    fn not_nan_helper_matches_ord(a in any::<f32>(), b in any::<f32>()) {
+        prop_assume!(!a.is_nan() && !b.is_nan());
+        let (a, b) = (Finite(NotNan::new(a).unwrap()), Finite(NotNan::new(b).unwrap()));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Finite>(&bytes_a)?, a);
+    }
+}
+
+#[test]
+fn not_nan_rejects_encoded_nan() {
+    let bytes = lexcode::to_bytes(&f32::NAN).unwrap();
+    assert!(lexcode::from_bytes::<Finite>(&bytes).is_err());
+}
+
+#[test]
+fn plain_ordered_float_roundtrips() {
+    let value = (OrderedFloat(1.5f32), NotNan::new(-2.0f64).unwrap());
+    let bytes = lexcode::to_bytes(&value).unwrap();
+    assert_eq!(lexcode::from_bytes::<(OrderedFloat<f32>, NotNan<f64>)>(&bytes).unwrap(), value);
+}
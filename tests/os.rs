@@ -0,0 +1,64 @@
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ffi::{CString, OsString};
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Name(#[serde(with = "lexcode::os::os_string")] OsString);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct C(#[serde(with = "lexcode::os::c_string")] CString);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct File(#[serde(with = "lexcode::os::path_buf")] PathBuf);
+
+fn path() -> impl Strategy<Value = PathBuf> {
+    prop::collection::vec(prop_oneof!["", ".", "..", "[a-c-]{1,3}"], 0..5)
+        .prop_flat_map(|parts| (Just(parts), any::<bool>()))
+        .prop_map(|(parts, absolute)| {
+            let joined = parts.join("/");
+            PathBuf::from(if absolute { format!("/{joined}") } else { joined })
+        })
+}
+
+proptest! {
+    #[test]
+    fn path_order_and_roundtrip(a in path(), b in path()) {
+        let bytes_a = lexcode::to_bytes(&File(a.clone()))?;
+        let bytes_b = lexcode::to_bytes(&File(b.clone()))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<File>(&bytes_a)?.0, a);
+    }
+
+    #[test]
+    fn c_string_order_and_roundtrip(a in "[^\u{0}]{0,8}", b in "[^\u{0}]{0,8}") {
+        let (a, b) = (CString::new(a).unwrap(), CString::new(b).unwrap());
+        let bytes_a = lexcode::to_bytes(&C(a.clone()))?;
+        let bytes_b = lexcode::to_bytes(&C(b.clone()))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<C>(&bytes_a)?.0, a);
+    }
+}
+
+#[cfg(unix)]
+proptest! {
+    #[test]
+    fn os_string_order_and_roundtrip(a in prop::collection::vec(any::<u8>(), 0..8), b in prop::collection::vec(any::<u8>(), 0..8)) {
+        use std::os::unix::ffi::OsStringExt;
+        let (a, b) = (OsString::from_vec(a), OsString::from_vec(b));
+        let bytes_a = lexcode::to_bytes(&Name(a.clone()))?;
+        let bytes_b = lexcode::to_bytes(&Name(b.clone()))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Name>(&bytes_a)?.0, a);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_path_roundtrips() {
+    use std::os::unix::ffi::OsStringExt;
+    let path = PathBuf::from(OsString::from_vec(b"/data/\xff\xfe.bin".to_vec()));
+    assert!(lexcode::to_bytes(&path).is_err());
+    let bytes = lexcode::to_bytes(&File(path.clone())).unwrap();
+    assert_eq!(lexcode::from_bytes::<File>(&bytes).unwrap().0, path);
+}
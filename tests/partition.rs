@@ -0,0 +1,54 @@
+use lexcode::TailBytes;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn split_points_ascend_within_range(a in any::<(u32, String)>(), b in any::<(u32, String)>(), n in 0usize..20) {
+        prop_assume!(a != b);
+        let (low, high) = if a < b { (a, b) } else { (b, a) };
+        let points = lexcode::split_points(&low, &high, n).unwrap();
+        prop_assert_eq!(points.len(), n.saturating_sub(1));
+        let (low, high) = (lexcode::to_bytes(&low).unwrap(), lexcode::to_bytes(&high).unwrap());
+        prop_assert!(points.windows(2).all(|w| w[0] < w[1]));
+        prop_assert!(points.iter().all(|p| low < *p && *p < high));
+    }
+
+    #[test]
+    fn position_is_monotonic(x in any::<i64>(), y in any::<i64>(), low in any::<i64>(), high in any::<i64>()) {
+        let encode = |v: i64| lexcode::to_bytes(&v).unwrap();
+        let (low, high) = (encode(low), encode(high));
+        let (px, py) = (lexcode::position(&encode(x), &low, &high), lexcode::position(&encode(y), &low, &high));
+        prop_assert!((0.0..=1.0).contains(&px));
+        if x <= y {
+            prop_assert!(px <= py);
+        }
+    }
+}
+
+#[test]
+fn split_points_are_even_in_byte_space() {
+    let points = lexcode::split_points(&0u8, &120u8, 3).unwrap();
+    assert_eq!(points, [vec![40], vec![80]]);
+    // Adjacent keys get extra bytes of precision.
+    let points = lexcode::split_points(&1u8, &2u8, 4).unwrap();
+    assert_eq!(points, [vec![1, 0x40], vec![1, 0x80], vec![1, 0xC0]]);
+}
+
+#[test]
+fn split_points_rejects_empty_ranges() {
+    assert!(lexcode::split_points(&5u8, &5u8, 2).is_err());
+    assert!(lexcode::split_points(&6u8, &5u8, 2).is_err());
+    assert!(lexcode::split_points(&TailBytes(vec![1]), &TailBytes(vec![1, 0]), 2).is_err());
+}
+
+#[test]
+fn position_matches_split_points() {
+    let (low, high) = (lexcode::to_bytes(&(1u32, "a")).unwrap(), lexcode::to_bytes(&(1u32, "q")).unwrap());
+    let points = lexcode::split_points(&(1u32, "a"), &(1u32, "q"), 4).unwrap();
+    for (i, point) in points.iter().enumerate() {
+        let expected = (i + 1) as f64 / 4.0;
+        assert!((lexcode::position(point, &low, &high) - expected).abs() < 1e-9);
+    }
+    assert_eq!(lexcode::position(&[], &low, &high), 0.0);
+    assert_eq!(lexcode::position(&[0xFF], &low, &high), 1.0);
+}
@@ -0,0 +1,41 @@
+use lexcode::PathKey;
+use proptest::prelude::*;
+
+fn path() -> impl Strategy<Value = String> {
+    prop::collection::vec("[a-c./-]{0,3}", 0..5).prop_map(|components| format!("/{}", components.join("/")))
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in path(), b in path()) {
+        let (a, b) = (PathKey::new(a), PathKey::new(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<PathKey>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn prefix_matches_exactly_the_descendants(a in path(), b in path()) {
+        let (a, b) = (PathKey::new(a), PathKey::new(b));
+        let is_descendant = b.components().count() >= a.components().count()
+            && a.components().zip(b.components()).all(|(x, y)| x == y);
+        prop_assert_eq!(lexcode::to_bytes(&b)?.starts_with(&a.scan_prefix()), is_descendant);
+    }
+}
+
+#[test]
+fn component_order() {
+    let keys: Vec<Vec<u8>> = ["/a/b", "/a/b/c", "/a/b-c", "/a/c"]
+        .iter()
+        .map(|p| lexcode::to_bytes(&PathKey::new(p)).unwrap())
+        .collect();
+    assert!(keys.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn normalization() {
+    assert_eq!(PathKey::new("a//b/").as_str(), "/a/b");
+    assert_eq!(PathKey::new("").as_str(), "/");
+    assert_eq!(lexcode::to_bytes(&PathKey::new("/")).unwrap(), [0x00]);
+}
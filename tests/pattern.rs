@@ -0,0 +1,41 @@
+use lexcode::{Descending, KeyPattern};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn matches_like_decoded_comparison(a in 0u8..4, b in any::<i64>(), c in "[a-z]{0,3}", lo in "[a-z]{0,2}", hi in "[a-z]{0,2}") {
+        let pattern = KeyPattern::new()
+            .exact(&2u8).unwrap()
+            .any::<i64>()
+            .range(lo.clone()..=hi.clone()).unwrap();
+        let key = lexcode::to_bytes(&(a, b, &c, 99u32)).unwrap();
+        prop_assert_eq!(pattern.matches(&key), a == 2 && lo <= c && c <= hi);
+    }
+
+    #[test]
+    fn ranges_follow_descending_order(x in any::<i32>(), lo in any::<i32>(), hi in any::<i32>()) {
+        let pattern = KeyPattern::new().range(Descending(hi)..Descending(lo)).unwrap();
+        let key = lexcode::to_bytes(&Descending(x)).unwrap();
+        prop_assert_eq!(pattern.matches(&key), lo < x && x <= hi);
+    }
+}
+
+#[test]
+fn short_or_malformed_keys_do_not_match() {
+    let pattern = KeyPattern::new().exact(&1u8).unwrap().any::<String>();
+    assert!(!pattern.matches(&[1]));
+    assert!(!pattern.matches(&[1, b'a']));
+    assert!(pattern.matches(&lexcode::to_bytes(&(1u8, "a")).unwrap()));
+    assert!(KeyPattern::new().matches(&[]));
+}
+
+#[test]
+fn prefix_is_leading_exact_components() {
+    let pattern = KeyPattern::new()
+        .exact(&7u64).unwrap()
+        .exact("eu").unwrap()
+        .any::<u8>()
+        .exact(&1u8).unwrap();
+    assert_eq!(pattern.prefix(), lexcode::to_bytes(&(7u64, "eu")).unwrap());
+    assert!(KeyPattern::new().any::<u8>().prefix().is_empty());
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "primitive-types")]
+
+use primitive_types::{U256, U512};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Wide(#[serde(with = "lexcode::primitive_types::u256")] U256);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Wider(#[serde(with = "lexcode::primitive_types::u512")] U512);
+
+fn u256() -> impl Strategy<Value = U256> {
+    (any::<[u8; 32]>(), 0usize..=32).prop_map(|(bytes, zeros)| {
+        let mut bytes = bytes;
+        bytes[..zeros].fill(0);
+        U256::from_big_endian(&bytes)
+    })
+}
+
+proptest! {
+    #[test]
+    fn u256_order_and_roundtrip(a in u256(), b in u256()) {
+        let bytes_a = lexcode::to_bytes(&Wide(a))?;
+        let bytes_b = lexcode::to_bytes(&Wide(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Wide>(&bytes_a)?.0, a);
+    }
+
+    #[test]
+    fn widths_share_an_encoding(a in u256()) {
+        let wide = lexcode::to_bytes(&Wide(a))?;
+        let wider = lexcode::to_bytes(&Wider(U512::from(a)))?;
+        prop_assert_eq!(wide, wider);
+    }
+}
+
+#[test]
+fn oversized_value_is_rejected() {
+    let bytes = lexcode::to_bytes(&Wider(U512::MAX)).unwrap();
+    assert!(lexcode::from_bytes::<Wide>(&bytes).is_err());
+}
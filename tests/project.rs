@@ -0,0 +1,28 @@
+use lexcode::{project, Descending};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn projects_each_tuple_field(a in any::<u64>(), b in ".*", c in any::<Vec<i16>>(), d in any::<Option<bool>>()) {
+        let key = lexcode::to_bytes(&(a, &b, Descending(&c), d)).unwrap();
+        type Row = (u64, String, Descending<Vec<i16>>, Option<bool>);
+        prop_assert_eq!(project::<Row, 0>(&key).unwrap(), a);
+        prop_assert_eq!(project::<Row, 1>(&key).unwrap(), b);
+        prop_assert_eq!(project::<Row, 2>(&key).unwrap().0, c);
+        prop_assert_eq!(project::<Row, 3>(&key).unwrap(), d);
+    }
+}
+
+#[test]
+fn eight_element_tuples() {
+    let key = lexcode::to_bytes(&(0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8)).unwrap();
+    type Row = (u8, u8, u8, u8, u8, u8, u8, u8);
+    assert_eq!(project::<Row, 7>(&key).unwrap(), 7);
+    assert_eq!(project::<Row, 4>(&key).unwrap(), 4);
+}
+
+#[test]
+fn fails_on_short_input() {
+    let key = lexcode::to_bytes(&(1u8,)).unwrap();
+    assert!(project::<(u8, u8), 1>(&key).is_err());
+}
@@ -302,8 +302,8 @@ mod prop_tests {
   fn fixed_bytes_identity_encoding() {
       // Every byte value 0x00..=0xFF should be stored as-is
       let mut arr = [0u8; 256];
-      for i in 0..256 {
-          arr[i] = i as u8;
+      for (i, slot) in arr.iter_mut().enumerate() {
+          *slot = i as u8;
       }
       let fb = FixedBytes(arr);
       let bytes = lexcode::to_bytes(&fb).unwrap();
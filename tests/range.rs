@@ -0,0 +1,96 @@
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn successor_is_tightest(key in prop::collection::vec(any::<u8>(), 0..6),
+                             other in prop::collection::vec(any::<u8>(), 0..7)) {
+        let next = lexcode::successor(&key);
+        prop_assert!(key < next);
+        // Nothing lies strictly between a key and its successor.
+        prop_assert!(!(key < other && other < next));
+    }
+
+    #[test]
+    fn prefix_successor_bounds_extensions(prefix in prop::collection::vec(prop_oneof![Just(0xFFu8), any::<u8>()], 0..5),
+                                          other in prop::collection::vec(prop_oneof![Just(0xFFu8), any::<u8>()], 0..7)) {
+        match lexcode::prefix_successor(&prefix) {
+            Some(end) => {
+                let in_range = prefix <= other && other < end;
+                prop_assert_eq!(in_range, other.starts_with(&prefix));
+            }
+            None => prop_assert!(prefix.iter().all(|&b| b == 0xFF)),
+        }
+    }
+
+    #[test]
+    fn range_contains_matches_value_order(lo in any::<i16>(), hi in any::<i16>(), v in any::<i16>()) {
+        let key = lexcode::to_bytes(&v)?;
+        prop_assert_eq!(lexcode::range_contains(&lexcode::range(lo..hi)?, &key), (lo..hi).contains(&v));
+        prop_assert_eq!(lexcode::range_contains(&lexcode::range(lo..=hi)?, &key), (lo..=hi).contains(&v));
+        prop_assert_eq!(lexcode::range_contains(&lexcode::range(lo..)?, &key), v >= lo);
+        prop_assert_eq!(lexcode::range_contains(&lexcode::range(..=hi)?, &key), v <= hi);
+    }
+}
+
+#[test]
+fn prefix_successor_edge_cases() {
+    assert_eq!(lexcode::prefix_successor(&[]), None);
+    assert_eq!(lexcode::prefix_successor(&[0xFF, 0xFF]), None);
+    assert_eq!(lexcode::prefix_successor(&[0x00, 0xFE, 0xFF]), Some(vec![0x00, 0xFF]));
+}
+
+proptest! {
+    #[test]
+    fn prefix_range_covers_tuple_prefix(user in any::<u64>(), other in any::<u64>(), ts in any::<i64>(), name in ".*") {
+        let (start, end) = lexcode::prefix_range(&(user,)).unwrap();
+        let key = lexcode::to_bytes(&(other, ts, &name)).unwrap();
+        let in_range = start <= key && key < end;
+        prop_assert_eq!(in_range, other == user);
+    }
+}
+
+proptest! {
+    #[test]
+    fn truncated_bounds_cover_shared_prefix(key in prop::collection::vec(prop_oneof![Just(0xFFu8), any::<u8>()], 0..8),
+                                            other in prop::collection::vec(prop_oneof![Just(0xFFu8), any::<u8>()], 0..8),
+                                            max_len in 0usize..6) {
+        let (low, high) = lexcode::truncate_for_bound(&key, max_len);
+        prop_assert!(low.len() <= max_len && high.as_ref().is_none_or(|h| h.len() <= max_len));
+        let covered = low <= other && high.is_none_or(|h| other < h);
+        prop_assert!(covered || !other.starts_with(&low));
+        prop_assert!(low <= key);
+    }
+}
+
+#[test]
+fn prefix_range_without_upper_bound() {
+    assert!(lexcode::prefix_range(&lexcode::Descending(0u8)).is_err());
+    assert!(lexcode::prefix_range(&()).is_err());
+}
+
+proptest! {
+    #[test]
+    fn midpoint_is_strictly_between(a in prop::collection::vec(prop_oneof![Just(0x00u8), Just(0xFF), any::<u8>()], 0..5),
+                                    b in prop::collection::vec(prop_oneof![Just(0x00u8), Just(0xFF), any::<u8>()], 0..5)) {
+        match lexcode::midpoint(&a, &b) {
+            Some(mid) => prop_assert!(a < mid && mid < b),
+            None => prop_assert!(a >= b || b == lexcode::successor(&a)),
+        }
+    }
+
+    #[test]
+    fn midpoint_of_encoded_keys(x in any::<u64>(), y in any::<u64>()) {
+        let (a, b) = (lexcode::to_bytes(&x.min(y)).unwrap(), lexcode::to_bytes(&x.max(y)).unwrap());
+        let mid = lexcode::midpoint(&a, &b);
+        prop_assert_eq!(mid.is_some(), x != y);
+    }
+}
+
+#[test]
+fn midpoint_edge_cases() {
+    assert_eq!(lexcode::midpoint(&[], &[0x00]), None);
+    assert_eq!(lexcode::midpoint(&[], &[0x00, 0x00]), Some(vec![0x00]));
+    assert_eq!(lexcode::midpoint(&[0x01], &[0x02]), Some(vec![0x01, 0x80]));
+    assert_eq!(lexcode::midpoint(&[0x01, 0xFF], &[0x02]), Some(vec![0x01, 0xFF, 0x80]));
+    assert_eq!(lexcode::midpoint(&[0x10], &[0x20]), Some(vec![0x18]));
+}
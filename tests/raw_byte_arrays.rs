@@ -0,0 +1,66 @@
+use lexcode::{Descending, LexConfig};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+enum Tag {
+    Plain,
+    Byte(u8),
+    Pair(u8, [u8; 2]),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Record {
+    version: u8,
+    hash: [u8; 8],
+    nested: ([u8; 2], Option<u8>, Vec<u8>),
+    tag: Tag,
+    order: Descending<[u8; 3]>,
+}
+
+fn record() -> impl Strategy<Value = Record> {
+    let tag = prop_oneof![
+        Just(Tag::Plain),
+        any::<u8>().prop_map(Tag::Byte),
+        any::<(u8, [u8; 2])>().prop_map(|(a, b)| Tag::Pair(a, b)),
+    ];
+    (any::<u8>(), any::<[u8; 8]>(), any::<([u8; 2], Option<u8>, Vec<u8>)>(), tag, any::<[u8; 3]>())
+        .prop_map(|(version, hash, nested, tag, order)| Record { version, hash, nested, tag, order: Descending(order) })
+}
+
+fn config() -> LexConfig {
+    LexConfig::new().raw_byte_arrays(true)
+}
+
+proptest! {
+    #[test]
+    fn roundtrip_and_order(a in record(), b in record()) {
+        let (ka, kb) = (lexcode::to_bytes_with_config(&a, &config())?, lexcode::to_bytes_with_config(&b, &config())?);
+        prop_assert_eq!(ka.cmp(&kb), a.cmp(&b));
+        prop_assert_eq!(lexcode::from_bytes_with_config::<Record>(&ka, &config())?, a);
+    }
+
+    #[test]
+    fn arrays_are_their_bytes(hash in any::<[u8; 32]>()) {
+        let bytes = lexcode::to_bytes_with_config(&hash, &config())?;
+        prop_assert_eq!(&bytes, &hash.to_vec());
+        prop_assert_eq!(bytes, lexcode::to_bytes(&lexcode::FixedBytes(hash))?);
+    }
+}
+
+#[test]
+fn only_direct_tuple_elements_are_raw() {
+    let value = (200u8, Some(200u8), vec![200u8], Tag::Byte(200));
+    let raw = lexcode::to_bytes_with_config(&value, &config()).unwrap();
+    let plain = lexcode::to_bytes(&value).unwrap();
+    assert_eq!(raw[0], 200);
+    assert_eq!(raw[1..], plain[2..]);
+}
+
+#[test]
+fn off_by_default() {
+    let value = [0x80u8; 4];
+    assert_eq!(lexcode::to_bytes(&value).unwrap().len(), 8);
+    let mut deserializer = lexcode::Deserializer::from_bytes(&value).raw_byte_arrays(true);
+    assert_eq!(<[u8; 4]>::deserialize(&mut deserializer).unwrap(), value);
+}
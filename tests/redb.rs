@@ -0,0 +1,57 @@
+#![cfg(feature = "redb")]
+
+use lexcode::redb::Lex;
+use lexcode::Descending;
+use proptest::prelude::*;
+use redb::backends::InMemoryBackend;
+use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+
+type K = (String, Descending<i32>);
+
+const TABLE: TableDefinition<Lex<K>, Lex<Vec<u8>>> = TableDefinition::new("table");
+
+proptest! {
+    #[test]
+    fn tables_iterate_in_key_order(keys in prop::collection::btree_set(("[ab]{0,2}", any::<i32>()), 0..8)) {
+        let db = Database::builder().create_with_backend(InMemoryBackend::new()).unwrap();
+        let tx = db.begin_write().unwrap();
+        {
+            let mut table = tx.open_table(TABLE).unwrap();
+            for (s, n) in &keys {
+                table.insert((s.clone(), Descending(*n)), n.to_be_bytes().to_vec()).unwrap();
+            }
+        }
+        tx.commit().unwrap();
+
+        let table = db.begin_read().unwrap().open_table(TABLE).unwrap();
+        let stored: Vec<(K, Vec<u8>)> = table.iter().unwrap().map(|row| {
+            let (key, value) = row.unwrap();
+            (key.value(), value.value())
+        }).collect();
+        let mut expected: Vec<(K, Vec<u8>)> =
+            keys.iter().map(|(s, n)| ((s.clone(), Descending(*n)), n.to_be_bytes().to_vec())).collect();
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        prop_assert_eq!(stored, expected);
+    }
+}
+
+#[test]
+fn ranges_take_typed_bounds() {
+    let db = Database::builder().create_with_backend(InMemoryBackend::new()).unwrap();
+    let tx = db.begin_write().unwrap();
+    {
+        let mut table = tx.open_table(TABLE).unwrap();
+        for (s, n) in [("a", 1), ("b", 3), ("b", 2), ("b", -1), ("c", 0)] {
+            table.insert((s.to_string(), Descending(n)), Vec::new()).unwrap();
+        }
+    }
+    tx.commit().unwrap();
+
+    let table = db.begin_read().unwrap().open_table(TABLE).unwrap();
+    let hits: Vec<i32> = table
+        .range(("b".to_string(), Descending(2))..=("b".to_string(), Descending(i32::MIN)))
+        .unwrap()
+        .map(|row| row.unwrap().0.value().1 .0)
+        .collect();
+    assert_eq!(hits, [2, -1]);
+}
@@ -0,0 +1,26 @@
+use lexcode::ReverseTimestamp;
+use proptest::prelude::*;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+proptest! {
+    #[test]
+    fn latest_first_and_roundtrip(a in any::<u64>(), b in any::<u64>()) {
+        let (a, b) = (ReverseTimestamp(a), ReverseTimestamp(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(bytes_a.len(), 8);
+        prop_assert_eq!(b.0.cmp(&a.0), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<ReverseTimestamp>(&bytes_a)?, a);
+    }
+}
+
+#[test]
+fn system_time_conversions() {
+    let time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123) + Duration::from_micros(456);
+    let ts = ReverseTimestamp::from(time);
+    assert_eq!(ts.as_millis(), 1_700_000_000_123);
+    assert_eq!(SystemTime::from(ts), UNIX_EPOCH + Duration::from_millis(1_700_000_000_123));
+    assert_eq!(ReverseTimestamp::from(UNIX_EPOCH - Duration::from_secs(1)), ReverseTimestamp(0));
+    assert!(ReverseTimestamp::now() < ts);
+}
@@ -0,0 +1,33 @@
+use lexcode::ReversedDomain;
+use proptest::prelude::*;
+
+fn domain() -> impl Strategy<Value = String> {
+    prop::collection::vec("[a-z0-9-]{1,6}", 1..5).prop_map(|labels| labels.join("."))
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in domain(), b in domain()) {
+        let (a, b) = (ReversedDomain::new(a), ReversedDomain::new(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<ReversedDomain>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn prefix_matches_exactly_the_subdomains(a in domain(), b in domain()) {
+        let (a, b) = (ReversedDomain::new(a), ReversedDomain::new(b));
+        let is_sub = b.as_str() == a.as_str() || b.as_str().ends_with(&format!(".{}", a.as_str()));
+        prop_assert_eq!(lexcode::to_bytes(&b)?.starts_with(&a.scan_prefix()), is_sub);
+    }
+}
+
+#[test]
+fn normalizes_case_and_root_dot() {
+    assert_eq!(ReversedDomain::new("WWW.Example.COM."), ReversedDomain::new("www.example.com"));
+    let domain = ReversedDomain::new("www.example.com");
+    let labels: Vec<&str> = domain.labels().collect();
+    assert_eq!(labels, ["com", "example", "www"]);
+    assert_eq!(ReversedDomain::new("").labels().count(), 0);
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "rocksdb")]
+
+use lexcode::rocksdb::{prefix_extractor, scan, Iter};
+use rocksdb::{Options, DB};
+use std::path::PathBuf;
+
+struct TempDb {
+    db: Option<DB>,
+    path: PathBuf,
+}
+
+impl TempDb {
+    fn open(name: &str) -> TempDb {
+        let path = std::env::temp_dir().join(format!("lexcode-{name}-{}", std::process::id()));
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.set_prefix_extractor(prefix_extractor::<u64>("user_id"));
+        let db = DB::open(&options, &path).unwrap();
+        for user in 1u64..=3 {
+            for ts in [-5i64, 0, 5] {
+                let key = lexcode::to_bytes(&(user, ts)).unwrap();
+                db.put(key, lexcode::to_bytes(&format!("{user}@{ts}")).unwrap()).unwrap();
+            }
+        }
+        TempDb { db: Some(db), path }
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        drop(self.db.take());
+        let _ = DB::destroy(&Options::default(), &self.path);
+    }
+}
+
+fn keys(rows: impl Iterator<Item = lexcode::Result<((u64, i64), String)>>) -> Vec<(u64, i64)> {
+    rows.map(|row| row.unwrap().0).collect()
+}
+
+#[test]
+fn scan_follows_typed_bounds() {
+    let temp = TempDb::open("scan");
+    let db = temp.db.as_ref().unwrap();
+    let rows = scan::<(u64, i64), String, _>(db, (1u64, 0i64)..=(2, 0)).unwrap();
+    assert_eq!(keys(rows), [(1, 0), (1, 5), (2, -5), (2, 0)]);
+
+    let rows = scan::<(u64, i64), String, _>(db, (std::ops::Bound::Excluded((2u64, -5i64)), std::ops::Bound::Unbounded))
+        .unwrap();
+    assert_eq!(keys(rows), [(2, 0), (2, 5), (3, -5), (3, 0), (3, 5)]);
+
+    let ((user, ts), value) = scan::<(u64, i64), String, _>(db, ..).unwrap().next().unwrap().unwrap();
+    assert_eq!(value, format!("{user}@{ts}"));
+}
+
+#[test]
+fn prefix_iteration_stays_within_the_leading_component() {
+    let temp = TempDb::open("prefix");
+    let db = temp.db.as_ref().unwrap();
+    let rows = Iter::<(u64, i64), String, _>::new(db.prefix_iterator(lexcode::to_bytes(&2u64).unwrap()));
+    assert_eq!(keys(rows), [(2, -5), (2, 0), (2, 5)]);
+}
+
+#[test]
+fn undecodable_pairs_are_errors() {
+    let temp = TempDb::open("errors");
+    let db = temp.db.as_ref().unwrap();
+    let mut rows = scan::<(u64, i64), bool, _>(db, ..).unwrap();
+    assert!(rows.next().unwrap().is_err());
+}
@@ -0,0 +1,141 @@
+use lexcode::{Descending, Scan, ScanPlan};
+use proptest::prelude::*;
+use std::ops::Bound;
+
+type K = (u8, String, u8);
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![Just(Op::Eq), Just(Op::Lt), Just(Op::Le), Just(Op::Gt), Just(Op::Ge)]
+}
+
+fn holds<T: Ord + ?Sized>(op: Op, actual: &T, value: &T) -> bool {
+    match op {
+        Op::Eq => actual == value,
+        Op::Lt => actual < value,
+        Op::Le => actual <= value,
+        Op::Gt => actual > value,
+        Op::Ge => actual >= value,
+    }
+}
+
+fn apply<T: serde::Serialize + ?Sized>(scan: Scan<K>, op: Op, field: usize, value: &T) -> Scan<K> {
+    match op {
+        Op::Eq => scan.eq(field, value),
+        Op::Lt => scan.lt(field, value),
+        Op::Le => scan.le(field, value),
+        Op::Gt => scan.gt(field, value),
+        Op::Ge => scan.ge(field, value),
+    }
+    .unwrap()
+}
+
+fn selected(plan: &ScanPlan<K>, key: &K) -> bool {
+    let bytes = lexcode::to_bytes(key).unwrap();
+    let in_range = plan.contains(&bytes);
+    in_range && plan.matches(&bytes)
+}
+
+fn all_keys() -> Vec<K> {
+    let mut keys = Vec::new();
+    for a in 0..4 {
+        for b in ["", "a", "ab", "b"] {
+            for c in [0, 1, 2, 255] {
+                keys.push((a, b.to_string(), c));
+            }
+        }
+    }
+    keys
+}
+
+proptest! {
+    #[test]
+    fn plan_selects_exactly_the_matching_keys(
+        predicates in prop::collection::vec((0usize..3, op(), 0u8..4, prop::sample::select(vec!["", "a", "ab", "b"])), 0..5),
+    ) {
+        let mut scan = Scan::<K>::on();
+        for &(field, op, n, s) in &predicates {
+            scan = match field {
+                1 => apply(scan, op, 1, s),
+                _ => apply(scan, op, field, &n),
+            };
+        }
+        let plan = scan.plan();
+        for key in all_keys() {
+            let expected = predicates.iter().all(|&(field, op, n, s)| match field {
+                0 => holds(op, &key.0, &n),
+                1 => holds(op, key.1.as_str(), s),
+                _ => holds(op, &key.2, &n),
+            });
+            prop_assert_eq!(selected(&plan, &key), expected, "{:?}", key);
+        }
+    }
+}
+
+#[test]
+fn one_of_gives_a_range_per_value() {
+    let plan = Scan::<K>::on()
+        .one_of(0, &[3u8, 1])
+        .unwrap()
+        .one_of(1, ["x", "y"])
+        .unwrap()
+        .plan();
+    assert_eq!(plan.ranges().len(), 4);
+    assert!(plan.is_exact());
+    let starts: Vec<_> = plan
+        .ranges()
+        .iter()
+        .map(|(start, _)| match start {
+            Bound::Included(start) => start.clone(),
+            other => panic!("unexpected start {other:?}"),
+        })
+        .collect();
+    assert!(starts.is_sorted());
+    assert!(selected(&plan, &(3, "y".into(), 9)));
+    assert!(!selected(&plan, &(2, "y".into(), 9)));
+}
+
+#[test]
+fn predicates_after_a_gap_are_residual() {
+    let plan = Scan::<K>::on().eq(0, &1u8).unwrap().eq(2, &5u8).unwrap().plan();
+    assert_eq!(plan.ranges().to_vec(), [{
+        let (start, end) = lexcode::prefix_range(&(1u8,)).unwrap();
+        (Bound::Included(start), Bound::Excluded(end))
+    }]);
+    assert!(!plan.is_exact());
+    assert!(plan.matches(&lexcode::to_bytes(&(1u8, "q", 5u8)).unwrap()));
+    assert!(!plan.matches(&lexcode::to_bytes(&(1u8, "q", 6u8)).unwrap()));
+    assert!(!plan.matches(b"\x01"));
+}
+
+#[test]
+fn contradictions_give_no_ranges() {
+    let plan = Scan::<K>::on().eq(0, &1u8).unwrap().eq(0, &2u8).unwrap().plan();
+    assert!(plan.ranges().is_empty());
+    let plan = Scan::<K>::on().gt(1, "m").unwrap().le(1, "m").unwrap().plan();
+    assert!(plan.ranges().is_empty());
+    assert_eq!(Scan::<K>::on().plan().ranges().len(), 1);
+}
+
+#[test]
+fn bounds_on_descending_components_follow_their_order() {
+    let plan = Scan::<(u8, Descending<u32>)>::on()
+        .eq(0, &1u8)
+        .unwrap()
+        .ge(1, &Descending(20u32))
+        .unwrap()
+        .plan();
+    let range = &plan.ranges()[0];
+    for (ts, expected) in [(10u32, true), (20, true), (21, false)] {
+        let key = lexcode::to_bytes(&(1u8, Descending(ts))).unwrap();
+        assert_eq!(lexcode::range_contains(range, &key), expected);
+    }
+}
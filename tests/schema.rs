@@ -0,0 +1,65 @@
+use lexcode::{FixedBytes, Schema, Value};
+use serde::Serialize;
+
+#[derive(Serialize)]
+enum Event {
+  Created,
+  Renamed(String),
+  Moved { from: u32, to: u32 },
+}
+
+#[derive(Serialize)]
+struct Row {
+  id: u64,
+  tags: Vec<String>,
+  hash: FixedBytes<4>,
+  event: Event,
+}
+
+fn roundtrip<T: Serialize>(schema: &str, value: &T) -> Value {
+  let schema: Schema = schema.parse().unwrap();
+  let bytes = lexcode::to_bytes(value).unwrap();
+  let decoded = schema.decode(&bytes).unwrap();
+  assert_eq!(lexcode::to_bytes(&decoded).unwrap(), bytes, "re-encoding {schema} differs");
+  decoded
+}
+
+#[test]
+fn schema_decodes_and_reencodes_identically() {
+  roundtrip("(u8, i64, f64, char, bool, unit)", &(200u8, -9i64, 1.5f64, 'x', true, ()));
+  roundtrip("[option<str>]", &vec![Some("a"), None]);
+  roundtrip("{str: [u16]}", &std::collections::BTreeMap::from([("k", vec![1u16, 2])]));
+  let row = Row {
+    id: 42,
+    tags: vec!["x".into()],
+    hash: FixedBytes([1, 2, 3, 4]),
+    event: Event::Moved { from: 1, to: 2 },
+  };
+  let decoded = roundtrip("(u64, [str], fixed<4>, enum<unit, str, (u32, u32)>)", &row);
+  let Value::Tuple(fields) = decoded else { panic!("expected a tuple") };
+  assert_eq!(fields[2], Value::Fixed(vec![1, 2, 3, 4]));
+  assert_eq!(
+    fields[3],
+    Value::Variant(2, Box::new(Value::Tuple(vec![Value::UInt(1), Value::UInt(2)])))
+  );
+  roundtrip("enum<unit, str, (u32, u32)>", &Event::Created);
+  roundtrip("enum<unit, str, (u32, u32)>", &Event::Renamed("b".into()));
+}
+
+#[test]
+fn schema_display_roundtrips() {
+  for text in ["unit", "(u64, [str], fixed<32>)", "{i32: option<bytes>}", "enum<unit, (f32, char)>"] {
+    let schema: Schema = text.parse().unwrap();
+    assert_eq!(schema.to_string(), text);
+  }
+}
+
+#[test]
+fn schema_rejects_bad_input() {
+  assert!("(u64,".parse::<Schema>().is_err());
+  assert!("u63".parse::<Schema>().is_err());
+  assert!("[u8] x".parse::<Schema>().is_err());
+  let schema: Schema = "u8".parse().unwrap();
+  assert!(schema.decode(&lexcode::to_bytes(&300u16).unwrap()).is_err());
+  assert!(schema.decode(&lexcode::to_bytes(&(1u8, 2u8)).unwrap()).is_err());
+}
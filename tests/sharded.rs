@@ -0,0 +1,40 @@
+use lexcode::Sharded;
+use proptest::prelude::*;
+
+type Key = Sharded<16, (u64, i64)>;
+
+proptest! {
+    #[test]
+    fn roundtrip_and_order_within_shard(a in any::<(u64, i64)>(), b in any::<(u64, i64)>()) {
+        let (ka, kb) = (Key::new(a), Key::new(b));
+        let (ba, bb) = (lexcode::to_bytes(&ka)?, lexcode::to_bytes(&kb)?);
+        prop_assert_eq!(lexcode::from_bytes::<Key>(&ba)?.into_inner(), a);
+        if ka.shard()? == kb.shard()? {
+            prop_assert_eq!(ba.cmp(&bb), a.cmp(&b));
+        }
+    }
+
+    #[test]
+    fn prefix_ranges_find_every_key(user in any::<u64>(), other in any::<u64>(), ts in any::<i64>()) {
+        let key = lexcode::to_bytes(&Key::new((other, ts)))?;
+        let ranges = Key::prefix_ranges(&(user,))?;
+        let hits = ranges.iter().filter(|(start, end)| *start <= key && key < *end).count();
+        prop_assert_eq!(hits, usize::from(user == other));
+    }
+}
+
+#[test]
+fn sequential_keys_spread_over_shards() {
+    let mut counts = [0; 16];
+    for ts in 0..1600i64 {
+        counts[Key::new((1, ts)).shard().unwrap() as usize] += 1;
+    }
+    assert!(counts.iter().all(|&count| count > 50), "{counts:?}");
+}
+
+#[test]
+fn rejects_wrong_shard() {
+    let mut bytes = lexcode::to_bytes(&Key::new((1, 2))).unwrap();
+    bytes[0] = (bytes[0] + 1) % 16;
+    assert!(lexcode::from_bytes::<Key>(&bytes).is_err());
+}
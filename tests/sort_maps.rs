@@ -0,0 +1,33 @@
+use lexcode::Serializer;
+use proptest::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+fn encode<T: Serialize>(value: &T, serializer: Serializer) -> Vec<u8> {
+    let mut serializer = serializer;
+    value.serialize(&mut serializer).unwrap();
+    serializer.into_bytes()
+}
+
+proptest! {
+    #[test]
+    fn hash_map_matches_btree_map(entries in prop::collection::vec((any::<i32>(), "[a-c]{0,3}"), 0..8)) {
+        let hash: HashMap<i32, String> = entries.iter().cloned().collect();
+        let btree: BTreeMap<i32, String> = entries.into_iter().collect();
+        let sorted = encode(&hash, Serializer::new().sort_maps(true));
+        prop_assert_eq!(&sorted, &lexcode::to_bytes(&btree)?);
+        prop_assert_eq!(lexcode::from_bytes::<HashMap<i32, String>>(&sorted)?, hash);
+    }
+
+    #[test]
+    fn nested_maps_are_sorted(entries in prop::collection::vec(("[a-c]{0,2}", any::<u8>(), any::<u8>()), 0..8)) {
+        let mut hash: HashMap<String, HashMap<u8, u8>> = HashMap::new();
+        let mut btree: BTreeMap<String, BTreeMap<u8, u8>> = BTreeMap::new();
+        for (outer, k, v) in entries {
+            hash.entry(outer.clone()).or_default().insert(k, v);
+            btree.entry(outer).or_default().insert(k, v);
+        }
+        let sorted = encode(&(1u8, &hash, 2u8), Serializer::new().sort_maps(true));
+        prop_assert_eq!(sorted, lexcode::to_bytes(&(1u8, &btree, 2u8))?);
+    }
+}
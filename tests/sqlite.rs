@@ -0,0 +1,52 @@
+#![cfg(feature = "sqlite")]
+
+use lexcode::sqlite::{create_collation, Lex};
+use lexcode::Descending;
+use proptest::prelude::*;
+use rusqlite::Connection;
+
+type K = (Option<i32>, Descending<String>);
+
+fn key() -> impl Strategy<Value = K> {
+    (any::<Option<i32>>(), "[a\0\u{FF}]{0,3}").prop_map(|(n, s)| (n, Descending(s)))
+}
+
+proptest! {
+    #[test]
+    fn order_by_follows_key_order(keys in prop::collection::vec(key(), 0..8)) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE t (k BLOB)", []).unwrap();
+        for k in &keys {
+            conn.execute("INSERT INTO t VALUES (?1)", [Lex(k)]).unwrap();
+        }
+        let mut stmt = conn.prepare("SELECT k FROM t ORDER BY k").unwrap();
+        let stored: Vec<K> = stmt.query_map([], |row| Ok(row.get::<_, Lex<K>>(0)?.0)).unwrap().map(Result::unwrap).collect();
+        let mut expected = keys;
+        expected.sort();
+        prop_assert_eq!(stored, expected);
+    }
+}
+
+#[test]
+fn collation_orders_text_by_utf8_bytes_in_utf16_databases() {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch("PRAGMA encoding = 'UTF-16le'; CREATE TABLE t (s TEXT)").unwrap();
+    create_collation(&conn).unwrap();
+    for s in ["\u{10000}", "\u{FFFD}", "a"] {
+        conn.execute("INSERT INTO t VALUES (?1)", [s]).unwrap();
+    }
+    let sorted = |sql: &str| -> Vec<String> {
+        let mut stmt = conn.prepare(sql).unwrap();
+        stmt.query_map([], |row| row.get(0)).unwrap().map(Result::unwrap).collect()
+    };
+    assert_eq!(sorted("SELECT s FROM t ORDER BY s COLLATE LEXCODE"), ["a", "\u{FFFD}", "\u{10000}"]);
+    assert_eq!(sorted("SELECT s FROM t ORDER BY s"), ["\u{10000}", "a", "\u{FFFD}"]);
+}
+
+#[test]
+fn reading_rejects_other_types_and_bad_encodings() {
+    let conn = Connection::open_in_memory().unwrap();
+    assert!(conn.query_row("SELECT 'text'", [], |row| row.get::<_, Lex<String>>(0)).is_err());
+    assert!(conn.query_row("SELECT x'02'", [], |row| row.get::<_, Lex<bool>>(0)).is_err());
+    assert!(conn.query_row("SELECT x'01'", [], |row| row.get::<_, Lex<bool>>(0)).unwrap().0);
+}
@@ -0,0 +1,105 @@
+use lexcode::varint::{decode_sqlite4, encode_sqlite4, VarintFormat};
+use lexcode::{Descending, Error, LexConfig};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Row {
+    table: u32,
+    id: u64,
+    version: Descending<u16>,
+    delta: i64,
+    small: Option<u8>,
+    wide: u128,
+}
+
+fn row() -> impl Strategy<Value = Row> {
+    let small = prop_oneof![0u64..300, 2200u64..2400, 67800u64..67900];
+    (small.clone(), prop_oneof![small, any::<u64>()], any::<u16>(), any::<i64>(), any::<Option<u8>>(), any::<u64>())
+        .prop_map(|(table, id, version, delta, small, wide)| Row {
+            table: table as u32,
+            id,
+            version: Descending(version),
+            delta,
+            small,
+            wide: wide as u128,
+        })
+}
+
+fn sqlite4(v: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_sqlite4(v, &mut out);
+    out
+}
+
+fn config() -> LexConfig {
+    LexConfig::new().varint_format(VarintFormat::Sqlite4)
+}
+
+proptest! {
+    #[test]
+    fn roundtrip_and_order(a in any::<u64>(), b in any::<u64>(), rest in prop::collection::vec(any::<u8>(), 0..3)) {
+        let mut bytes = sqlite4(a);
+        let len = bytes.len();
+        prop_assert!(len <= 9);
+        prop_assert_eq!(bytes.cmp(&sqlite4(b)), a.cmp(&b));
+        bytes.extend_from_slice(&rest);
+        prop_assert_eq!(decode_sqlite4(&bytes)?, (a, len));
+    }
+
+    #[test]
+    fn config_roundtrip_and_order(a in row(), b in row()) {
+        let (ka, kb) = (lexcode::to_bytes_with_config(&a, &config())?, lexcode::to_bytes_with_config(&b, &config())?);
+        prop_assert_eq!(ka.cmp(&kb), a.cmp(&b));
+        prop_assert_eq!(lexcode::from_bytes_with_config::<Row>(&ka, &config())?, a);
+    }
+}
+
+#[test]
+fn known_encodings() {
+    let cases: &[(u64, &[u8])] = &[
+        (0, &[0]),
+        (240, &[240]),
+        (241, &[241, 1]),
+        (2287, &[248, 255]),
+        (2288, &[249, 0, 0]),
+        (67823, &[249, 255, 255]),
+        (67824, &[250, 1, 8, 240]),
+        (16777215, &[250, 255, 255, 255]),
+        (16777216, &[251, 1, 0, 0, 0]),
+        (u64::MAX, &[255; 9]),
+    ];
+    for &(v, bytes) in cases {
+        assert_eq!(sqlite4(v), bytes, "{v}");
+        assert_eq!(decode_sqlite4(bytes).unwrap(), (v, bytes.len()));
+    }
+}
+
+#[test]
+fn unsigned_fields_use_the_format_and_others_do_not() {
+    let bytes = lexcode::to_bytes_with_config(&(241u64, -1i32, 'a'), &config()).unwrap();
+    let mut expected = vec![241, 1];
+    expected.extend(lexcode::to_bytes(&(-1i32, 'a')).unwrap());
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn malformed_input_is_an_error() {
+    assert!(matches!(decode_sqlite4(&[]), Err(Error::Eof)));
+    assert!(matches!(decode_sqlite4(&[249, 0]), Err(Error::Eof)));
+    assert!(matches!(decode_sqlite4(&[255; 8]), Err(Error::Eof)));
+    // Longer than needed: 5 in three and in four bytes.
+    assert!(matches!(decode_sqlite4(&[250, 0, 0, 5]), Err(Error::Message(_))));
+    assert!(matches!(decode_sqlite4(&[251, 0, 0, 0, 5]), Err(Error::Message(_))));
+    let wide = lexcode::to_bytes_with_config(&(u64::MAX as u128 + 1), &config());
+    assert!(wide.is_err());
+}
+
+#[test]
+fn variant_indexes_stay_lexcode_varints() {
+    for index in [128u32, 200, 300, 70_000] {
+        let mut serializer = lexcode::Serializer::with_config(config());
+        serde::Serializer::serialize_unit_variant(&mut serializer, "E", index, "V").unwrap();
+        assert_eq!(serializer.into_bytes(), lexcode::to_bytes(&index).unwrap());
+    }
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "storekey")]
+
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Event {
+    Login { user: u64, at: i64 },
+    Logout(u64),
+    Purge,
+}
+
+fn event() -> impl Strategy<Value = Event> {
+    prop_oneof![
+        (any::<u64>(), any::<i64>()).prop_map(|(user, at)| Event::Login { user, at }),
+        any::<u64>().prop_map(Event::Logout),
+        Just(Event::Purge),
+    ]
+}
+
+type Row = (String, Option<i32>, bool, f64, Event);
+
+fn row() -> impl Strategy<Value = Row> {
+    ("[a-z]{0,4}", any::<Option<i32>>(), any::<bool>(), -1e9f64..1e9, event())
+}
+
+proptest! {
+    #[test]
+    fn migrated_keys_match_native_encoding(row in row()) {
+        let old = storekey::serialize(&row).unwrap();
+        prop_assert_eq!(lexcode::storekey::from_bytes::<Row>(&old)?, row.clone());
+        prop_assert_eq!(lexcode::storekey::migrate::<Row>(&old)?, lexcode::to_bytes(&row)?);
+    }
+
+    #[test]
+    fn migration_keeps_the_order_of_keys_without_byte_slices(a in row(), b in row()) {
+        let old = (storekey::serialize(&a).unwrap(), storekey::serialize(&b).unwrap());
+        let new = (lexcode::storekey::migrate::<Row>(&old.0)?, lexcode::storekey::migrate::<Row>(&old.1)?);
+        prop_assert_eq!(old.0.cmp(&old.1), new.0.cmp(&new.1));
+    }
+}
+
+#[test]
+fn trailing_bytes_and_wrong_types_are_rejected() {
+    let mut old = storekey::serialize(&(7u32, "seven")).unwrap();
+    assert!(lexcode::storekey::migrate::<(u32, String)>(&old).is_ok());
+    assert!(lexcode::storekey::migrate::<u32>(&old).is_err());
+    old.push(0);
+    assert!(lexcode::storekey::migrate::<(u32, String)>(&old).is_err());
+}
@@ -0,0 +1,47 @@
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Stamp(#[serde(with = "lexcode::system_time")] SystemTime);
+
+fn system_time() -> impl Strategy<Value = SystemTime> {
+    (any::<bool>(), 0u64..10_000_000_000, 0u32..1_000_000_000).prop_map(|(before, secs, nanos)| {
+        let offset = Duration::new(secs, nanos);
+        if before { UNIX_EPOCH - offset } else { UNIX_EPOCH + offset }
+    })
+}
+
+proptest! {
+    #[test]
+    fn duration_sorts_by_default(a in any::<(u64, u32)>(), b in any::<(u64, u32)>()) {
+        let a = Duration::new(a.0 / 2, a.1 % 1_000_000_000);
+        let b = Duration::new(b.0 / 2, b.1 % 1_000_000_000);
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Duration>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn system_time_order_and_roundtrip(a in system_time(), b in system_time()) {
+        let bytes_a = lexcode::to_bytes(&Stamp(a))?;
+        let bytes_b = lexcode::to_bytes(&Stamp(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Stamp>(&bytes_a)?.0, a);
+    }
+
+    #[test]
+    fn default_system_time_sorts_after_epoch(a in system_time(), b in system_time()) {
+        prop_assume!(a >= UNIX_EPOCH && b >= UNIX_EPOCH);
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+    }
+}
+
+#[test]
+fn default_system_time_rejects_pre_epoch() {
+    assert!(lexcode::to_bytes(&(UNIX_EPOCH - Duration::from_secs(1))).is_err());
+    assert!(lexcode::to_bytes(&Stamp(UNIX_EPOCH - Duration::from_secs(1))).is_ok());
+}
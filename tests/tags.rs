@@ -0,0 +1,52 @@
+use lexcode::Tags;
+use proptest::prelude::*;
+
+fn tags() -> impl Strategy<Value = Tags> {
+    prop::collection::vec(("[a-c]{0,2}", "[a-c\0]{0,2}"), 0..4).prop_map(|pairs| pairs.into_iter().collect())
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in tags(), b in tags()) {
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Tags>(&bytes_a)?, a);
+    }
+
+    #[test]
+    fn insertion_order_does_not_matter(pairs in prop::collection::vec(("[a-c]{0,2}", "[a-c]{0,2}"), 0..5)) {
+        let forward: Tags = pairs.iter().cloned().collect();
+        let mut unique: Vec<_> = forward.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        unique.reverse();
+        let backward: Tags = unique.into_iter().collect();
+        prop_assert_eq!(lexcode::to_bytes(&forward)?, lexcode::to_bytes(&backward)?);
+    }
+
+    #[test]
+    fn prefix_matches_sets_extending_it_with_later_keys(a in tags(), b in tags()) {
+        let extends = a.iter().zip(b.iter()).all(|(x, y)| x == y) && b.len() >= a.len();
+        let key = lexcode::to_bytes(&(&b, 7u8))?;
+        prop_assert_eq!(key.starts_with(&a.prefix_bytes()), extends);
+    }
+}
+
+#[test]
+fn decoding_rejects_unsorted_or_repeated_keys() {
+    let unsorted = lexcode::to_bytes(&vec![("b", "1"), ("a", "2")]).unwrap();
+    assert!(lexcode::from_bytes::<Tags>(&unsorted).is_err());
+    let repeated = lexcode::to_bytes(&vec![("a", "1"), ("a", "2")]).unwrap();
+    assert!(lexcode::from_bytes::<Tags>(&repeated).is_err());
+}
+
+#[test]
+fn series_keys_group_by_tag_set() {
+    let a1: Tags = [("host", "a1")].into_iter().collect();
+    let a1_eu: Tags = [("host", "a1"), ("region", "eu")].into_iter().collect();
+    let keys = [
+        lexcode::to_bytes(&("cpu", &a1, 5i64)).unwrap(),
+        lexcode::to_bytes(&("cpu", &a1, 9i64)).unwrap(),
+        lexcode::to_bytes(&("cpu", &a1_eu, 1i64)).unwrap(),
+    ];
+    assert!(keys.windows(2).all(|w| w[0] < w[1]));
+}
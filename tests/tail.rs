@@ -0,0 +1,45 @@
+use lexcode::{Descending, TailBytes, TailString};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in (any::<u8>(), prop::collection::vec(any::<u8>(), 0..6)),
+                           b in (any::<u8>(), prop::collection::vec(any::<u8>(), 0..6))) {
+        let bytes_a = lexcode::to_bytes(&(a.0, TailBytes(a.1.clone())))?;
+        let bytes_b = lexcode::to_bytes(&(b.0, TailBytes(b.1.clone())))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        let (id, tail): (u8, TailBytes) = lexcode::from_bytes(&bytes_a)?;
+        prop_assert_eq!((id, tail.0), a);
+    }
+
+    #[test]
+    fn string_roundtrip(prefix in "[a-z]{0,4}", s in "\\PC{0,8}") {
+        let bytes = lexcode::to_bytes(&(&prefix, TailString(s.clone())))?;
+        prop_assert_eq!(lexcode::from_bytes::<(String, TailString)>(&bytes)?, (prefix, TailString(s)));
+    }
+}
+
+#[test]
+fn empty_tail_is_zero_bytes() {
+    let bytes = lexcode::to_bytes(&(1u8, TailBytes::default())).unwrap();
+    assert_eq!(bytes, [1]);
+    assert_eq!(lexcode::from_bytes::<(u8, TailBytes)>(&bytes).unwrap(), (1, TailBytes::default()));
+}
+
+#[test]
+fn tail_followed_by_another_field_fails() {
+    let bytes = lexcode::to_bytes(&(TailBytes(vec![1, 2]), 3u8)).unwrap();
+    assert!(lexcode::from_bytes::<(TailBytes, u8)>(&bytes).is_err());
+}
+
+#[test]
+fn descending_tail_is_rejected() {
+    assert!(lexcode::to_bytes(&Descending(TailString("abc".into()))).is_err());
+    assert!(lexcode::to_bytes(&(1u8, Descending((2u8, TailBytes(vec![3]))))).is_err());
+    assert!(lexcode::to_bytes(&(Descending(1u8), TailBytes(vec![3]))).is_ok());
+}
+
+#[test]
+fn invalid_utf8_is_rejected() {
+    assert!(lexcode::from_bytes::<TailString>(&[0xFF]).is_err());
+}
@@ -0,0 +1,64 @@
+#![cfg(all(feature = "time", feature = "chrono"))]
+
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use time::{Date, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Day(#[serde(with = "lexcode::time::date")] Date);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Primitive(#[serde(with = "lexcode::time::primitive_date_time")] PrimitiveDateTime);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Offset(#[serde(with = "lexcode::time::offset_date_time")] OffsetDateTime);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ChronoDay(#[serde(with = "lexcode::chrono::naive_date")] chrono::NaiveDate);
+
+fn date() -> impl Strategy<Value = Date> {
+    (Date::MIN.to_julian_day()..=Date::MAX.to_julian_day()).prop_map(|d| Date::from_julian_day(d).unwrap())
+}
+
+fn date_time() -> impl Strategy<Value = OffsetDateTime> {
+    (-100_000_000_000i64..100_000_000_000, 0u32..1_000_000_000, -86_399i32..86_400).prop_filter_map(
+        "out of range",
+        |(secs, nanos, offset)| {
+            let offset = UtcOffset::from_whole_seconds(offset).ok()?;
+            OffsetDateTime::from_unix_timestamp(secs).ok()?.replace_nanosecond(nanos).ok()?.checked_to_offset(offset)
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn date_order_and_roundtrip(a in date(), b in date()) {
+        let bytes_a = lexcode::to_bytes(&Day(a))?;
+        let bytes_b = lexcode::to_bytes(&Day(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Day>(&bytes_a)?.0, a);
+    }
+
+    #[test]
+    fn offset_date_time_order_and_roundtrip(a in date_time(), b in date_time()) {
+        let bytes_a = lexcode::to_bytes(&Offset(a))?;
+        let bytes_b = lexcode::to_bytes(&Offset(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        let decoded = lexcode::from_bytes::<Offset>(&bytes_a)?.0;
+        prop_assert_eq!(decoded, a);
+        prop_assert_eq!(decoded.offset(), UtcOffset::UTC);
+
+        let utc = a.to_offset(UtcOffset::UTC);
+        let primitive = PrimitiveDateTime::new(utc.date(), utc.time());
+        let bytes = lexcode::to_bytes(&Primitive(primitive))?;
+        prop_assert_eq!(&bytes, &bytes_a);
+        prop_assert_eq!(lexcode::from_bytes::<Primitive>(&bytes)?.0, primitive);
+    }
+}
+
+#[test]
+fn date_matches_chrono_layout() {
+    let date = Date::from_calendar_date(2024, time::Month::February, 29).unwrap();
+    let chrono = chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+    assert_eq!(lexcode::to_bytes(&Day(date)).unwrap(), lexcode::to_bytes(&ChronoDay(chrono)).unwrap());
+}
@@ -0,0 +1,58 @@
+use lexcode::TimeBucket;
+use proptest::prelude::*;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+type Hourly = TimeBucket<3600, i64>;
+type Daily = TimeBucket<86400, u64>;
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in any::<i64>(), b in any::<i64>()) {
+        let bytes_a = lexcode::to_bytes(&Hourly::new(a))?;
+        let bytes_b = lexcode::to_bytes(&Hourly::new(b))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(*lexcode::from_bytes::<Hourly>(&bytes_a)?.get(), a);
+    }
+
+    #[test]
+    fn same_bucket_shares_prefix(day in 0u64..100_000, a in 0u64..86400, b in 0u64..86400) {
+        let bytes_a = lexcode::to_bytes(&Daily::new(day * 86400 + a))?;
+        let bytes_b = lexcode::to_bytes(&Daily::new(day * 86400 + b))?;
+        let prefix = lexcode::to_bytes(&(day * 86400))?;
+        prop_assert!(bytes_a.starts_with(&prefix) && bytes_b.starts_with(&prefix));
+    }
+}
+
+#[test]
+fn negative_values_round_down() {
+    assert_eq!(Hourly::new(-1).bucket(), -3600);
+    assert_eq!(Hourly::new(-3600).bucket(), -3600);
+    let before = UNIX_EPOCH - Duration::from_millis(1);
+    assert_eq!(TimeBucket::<60, SystemTime>::new(before).bucket(), UNIX_EPOCH - Duration::from_secs(60));
+}
+
+#[test]
+fn mismatched_bucket_is_rejected() {
+    let bytes = lexcode::to_bytes(&(0u64, 86400u64)).unwrap();
+    assert!(lexcode::from_bytes::<Daily>(&bytes).is_err());
+}
+
+#[test]
+fn extremes_saturate_instead_of_overflowing() {
+    assert_eq!(Hourly::new(i64::MIN).bucket(), i64::MIN);
+    assert_eq!(Hourly::new(i64::MIN + 1).bucket(), i64::MIN);
+    for value in [i64::MIN, i64::MIN + 1, i64::MAX] {
+        let bytes = lexcode::to_bytes(&Hourly::new(value)).unwrap();
+        assert_eq!(*lexcode::from_bytes::<Hourly>(&bytes).unwrap().get(), value);
+    }
+    let bytes = lexcode::to_bytes(&(i64::MIN, i64::MIN)).unwrap();
+    assert_eq!(*lexcode::from_bytes::<Hourly>(&bytes).unwrap().get(), i64::MIN);
+    assert_eq!(TimeBucket::<{ u64::MAX }, i64>::new(i64::MIN).bucket(), i64::MIN);
+    assert_eq!(Daily::new(u64::MAX).bucket(), u64::MAX - u64::MAX % 86400);
+
+    // The earliest time the platform can represent, if any this far back.
+    if let Some(earliest) = UNIX_EPOCH.checked_sub(Duration::from_secs(i64::MAX as u64)) {
+        let bucket = TimeBucket::<3600, SystemTime>::new(earliest).bucket();
+        assert!(bucket <= earliest);
+    }
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "transcode")]
+
+use lexcode::transcode::{transcode, transcode_json_to_lex, transcode_with_schema};
+use lexcode::{FixedBytes, Schema, Value};
+use proptest::prelude::*;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+enum Event {
+    Login(u32),
+    Logout,
+}
+
+type Row = (u64, i32, String, Option<f64>, Vec<i16>, bool, char, Event);
+
+const SCHEMA: &str = "(u64, i32, str, option<f64>, [i16], bool, char, enum<u32, unit>)";
+
+fn row() -> impl Strategy<Value = Row> {
+    (
+        any::<u64>(),
+        any::<i32>(),
+        ".{0,4}",
+        prop::option::of((-4000i32..4000).prop_map(|v| v as f64 / 8.0)),
+        prop::collection::vec(any::<i16>(), 0..4),
+        any::<bool>(),
+        any::<char>(),
+        prop_oneof![any::<u32>().prop_map(Event::Login), Just(Event::Logout)],
+    )
+}
+
+/// The JSON form `transcode_with_schema` reads: enum variants keyed by
+/// their index.
+fn json(row: &Row) -> String {
+    let event = match row.7 {
+        Event::Login(id) => serde_json::json!({ "0": id }),
+        Event::Logout => serde_json::json!({ "1": null }),
+    };
+    let value = serde_json::json!([row.0, row.1, row.2, row.3, row.4, row.5, row.6, event]);
+    value.to_string()
+}
+
+proptest! {
+    #[test]
+    fn json_with_a_schema_matches_to_bytes(row in row()) {
+        let schema: Schema = SCHEMA.parse()?;
+        prop_assert_eq!(transcode_json_to_lex(&json(&row), Some(&schema))?, lexcode::to_bytes(&row)?);
+    }
+
+    #[test]
+    fn cbor_with_a_schema_matches_to_bytes(row in row()) {
+        let schema: Schema = SCHEMA.parse()?;
+        let json: serde_json::Value = serde_json::from_str(&json(&row)).unwrap();
+        let cbor = serde_cbor::to_vec(&json).unwrap();
+        let mut deserializer = serde_cbor::Deserializer::from_slice(&cbor);
+        prop_assert_eq!(transcode_with_schema(&mut deserializer, &schema)?, lexcode::to_bytes(&row)?);
+    }
+
+    #[test]
+    fn json_without_a_schema_matches_to_bytes_of_the_json_value(row in row()) {
+        let value: serde_json::Value = serde_json::from_str(&json(&row)).unwrap();
+        prop_assert_eq!(transcode_json_to_lex(&value.to_string(), None)?, lexcode::to_bytes(&value)?);
+    }
+}
+
+#[test]
+fn bytes_read_from_arrays_and_byte_strings() {
+    let schema: Schema = "(bytes, fixed<2>)".parse().unwrap();
+    let expected = lexcode::to_bytes(&(Value::Bytes(vec![1, 0]), FixedBytes([9u8, 8]))).unwrap();
+    assert_eq!(transcode_json_to_lex("[[1, 0], [9, 8]]", Some(&schema)).unwrap(), expected);
+
+    let value = (serde_cbor::Value::Bytes(vec![1, 0]), serde_cbor::Value::Bytes(vec![9, 8]));
+    let cbor = serde_cbor::to_vec(&value).unwrap();
+    let mut deserializer = serde_cbor::Deserializer::from_slice(&cbor);
+    assert_eq!(transcode_with_schema(&mut deserializer, &schema).unwrap(), expected);
+}
+
+#[test]
+fn untyped_transcoding_goes_through_the_serializer() {
+    let mut deserializer = serde_json::Deserializer::from_str(r#"[1, "a"]"#);
+    let bytes = transcode(&mut deserializer).unwrap();
+    let expected = Value::Seq(vec![Value::UInt(1), Value::String("a".into())]);
+    assert_eq!(bytes, lexcode::to_bytes(&expected).unwrap());
+}
+
+#[test]
+fn out_of_range_numbers_are_an_error() {
+    let schema: Schema = "(u8, i8)".parse().unwrap();
+    assert!(transcode_json_to_lex("[255, -128]", Some(&schema)).is_ok());
+    assert!(transcode_json_to_lex("[256, 0]", Some(&schema)).is_err());
+    assert!(transcode_json_to_lex("[-1, 0]", Some(&schema)).is_err());
+    assert!(transcode_json_to_lex("[0, 128]", Some(&schema)).is_err());
+}
+
+#[test]
+fn shape_mismatches_and_trailing_input_are_an_error() {
+    let schema: Schema = "(u64, char)".parse().unwrap();
+    assert!(transcode_json_to_lex(r#"[1, "ab"]"#, Some(&schema)).is_err());
+    assert!(transcode_json_to_lex(r#"[1.5, "a"]"#, Some(&schema)).is_err());
+    assert!(transcode_json_to_lex(r#"{"a": 1}"#, Some(&schema)).is_err());
+    assert!(transcode_json_to_lex(r#"[1, "a"] 2"#, Some(&schema)).is_err());
+    assert!(transcode_json_to_lex("[1] 2", None).is_err());
+}
@@ -0,0 +1,41 @@
+use lexcode::Ulid;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn order_roundtrip_and_text(a in any::<u128>(), b in any::<u128>()) {
+        let (a, b) = (Ulid::from(a), Ulid::from(b));
+        let bytes_a = lexcode::to_bytes(&a)?;
+        let bytes_b = lexcode::to_bytes(&b)?;
+        prop_assert_eq!(bytes_a.len(), 16);
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(a.to_string().cmp(&b.to_string()), a.cmp(&b));
+        prop_assert_eq!(lexcode::from_bytes::<Ulid>(&bytes_a)?, a);
+        prop_assert_eq!(a.to_string().parse::<Ulid>()?, a);
+    }
+
+    #[test]
+    fn parts_roundtrip(ts in 0u64..1 << 48, random in any::<u128>()) {
+        let ulid = Ulid::from_parts(ts, random);
+        prop_assert_eq!(ulid.timestamp_ms(), ts);
+        prop_assert_eq!(ulid.random(), random & ((1 << 80) - 1));
+    }
+}
+
+#[test]
+fn generated_ids_are_unique_and_current() {
+    let a = Ulid::new();
+    let b = Ulid::new();
+    assert_ne!(a, b);
+    assert!(a.timestamp_ms() > 1_700_000_000_000);
+}
+
+#[test]
+fn parse_is_lenient_and_checked() {
+    let ulid: Ulid = "01hf7yat00000000000000001g".parse().unwrap();
+    assert_eq!(ulid, "01HF7YAT00000000000000001G".parse().unwrap());
+    assert_eq!("0IL0000000000000000000000O".parse::<Ulid>().unwrap(), "01100000000000000000000000".parse().unwrap());
+    assert!("8ZZZZZZZZZZZZZZZZZZZZZZZZZ".parse::<Ulid>().is_err());
+    assert!("01HF7YAT0000000000000000U0".parse::<Ulid>().is_err());
+    assert!("01HF7YAT".parse::<Ulid>().is_err());
+}
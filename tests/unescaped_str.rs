@@ -0,0 +1,26 @@
+use lexcode::UnescapedStr;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in "[a-c\u{1}é]{0,6}", b in "[a-c\u{1}é]{0,6}") {
+        let (a, b) = (UnescapedStr::new(a).unwrap(), UnescapedStr::new(b).unwrap());
+        let bytes_a = lexcode::to_bytes(&(&a, 7u8))?;
+        let bytes_b = lexcode::to_bytes(&(&b, 7u8))?;
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<(UnescapedStr, u8)>(&bytes_a)?, (a, 7));
+    }
+}
+
+#[test]
+fn one_byte_shorter_than_str() {
+    let unescaped = lexcode::to_bytes(&UnescapedStr::new("key").unwrap()).unwrap();
+    assert_eq!(unescaped.len() + 1, lexcode::to_bytes(&"key").unwrap().len());
+}
+
+#[test]
+fn rejects_nul_and_missing_terminator() {
+    assert!(UnescapedStr::new("\0").is_err());
+    assert!(lexcode::from_bytes::<UnescapedStr>(b"abc").is_err());
+    assert!(lexcode::from_bytes::<UnescapedStr>(&[0xFF, 0x00]).is_err());
+}
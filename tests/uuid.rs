@@ -0,0 +1,31 @@
+#![cfg(feature = "uuid")]
+
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Id(#[serde(with = "lexcode::uuid")] Uuid);
+
+proptest! {
+    #[test]
+    fn raw_order_and_roundtrip(a in any::<u128>(), b in any::<u128>()) {
+        let (a, b) = (Uuid::from_u128(a), Uuid::from_u128(b));
+        let bytes_a = lexcode::to_bytes(&Id(a))?;
+        let bytes_b = lexcode::to_bytes(&Id(b))?;
+        prop_assert_eq!(&bytes_a[..], a.as_bytes());
+        prop_assert_eq!(a.cmp(&b), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<Id>(&bytes_a)?.0, a);
+    }
+}
+
+#[test]
+fn composes_in_tuples() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Key(u8, #[serde(with = "lexcode::uuid")] Uuid, String);
+
+    let key = Key(3, Uuid::max(), "x".into());
+    let bytes = lexcode::to_bytes(&key).unwrap();
+    assert_eq!(bytes.len(), 1 + 16 + 3);
+    assert_eq!(lexcode::from_bytes::<Key>(&bytes).unwrap(), key);
+}
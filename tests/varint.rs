@@ -0,0 +1,244 @@
+use lexcode::varint::{
+    decode_i32, decode_i64, decode_sint, decode_sint_from_reader, decode_u32, decode_u64, decode_uint,
+    decode_uint_from_reader, encode_i32, encode_i64, encode_sint, encode_sint_to_array, encode_u32, encode_u64,
+    encode_uint, encode_uint_to_array, encoded_len_sint, encoded_len_uint, MAX_LEN,
+};
+use lexcode::Error;
+use proptest::prelude::*;
+use std::io::{ErrorKind, Read};
+
+fn uint(v: u128) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_uint(v, &mut out);
+    out
+}
+
+fn sint(v: i128) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_sint(v, &mut out);
+    out
+}
+
+/// Bytes that are mostly the start of a valid encoding near a level
+/// boundary, with some damage.
+fn near_encodings() -> impl Strategy<Value = Vec<u8>> {
+    let value = prop_oneof![any::<u128>(), (0u32..128).prop_map(|shift| 1u128 << shift), any::<u64>().prop_map(u128::from)];
+    prop_oneof![
+        (value.clone(), any::<bool>(), 0usize..3).prop_map(|(v, signed, cut)| {
+            let mut bytes = if signed { sint(v as i128) } else { uint(v) };
+            bytes.truncate(bytes.len().saturating_sub(cut));
+            bytes
+        }),
+        prop::collection::vec(prop_oneof![Just(0x00u8), Just(0x7F), Just(0x80), Just(0xFF), any::<u8>()], 0..12),
+    ]
+}
+
+fn same<T: PartialEq + std::fmt::Debug>(fast: lexcode::Result<(T, usize)>, general: lexcode::Result<(T, usize)>) -> Result<(), TestCaseError> {
+    match (fast, general) {
+        (Ok(fast), Ok(general)) => prop_assert_eq!(fast, general),
+        (Err(Error::Eof), Err(Error::Eof)) | (Err(Error::Message(_)), Err(Error::Message(_))) => {}
+        (fast, general) => prop_assert!(false, "{:?} != {:?}", fast, general),
+    }
+    Ok(())
+}
+
+fn narrow<T: TryFrom<W>, W>(decoded: lexcode::Result<(W, usize)>) -> lexcode::Result<(T, usize)> {
+    let (v, len) = decoded?;
+    let v = T::try_from(v).map_err(|_| Error::Message("overflow".into()))?;
+    Ok((v, len))
+}
+
+proptest! {
+    #[test]
+    fn uint_roundtrips_and_leaves_the_rest(v in any::<u128>(), rest in prop::collection::vec(any::<u8>(), 0..4)) {
+        let mut bytes = uint(v);
+        let len = bytes.len();
+        prop_assert!(len <= MAX_LEN);
+        bytes.extend_from_slice(&rest);
+        prop_assert_eq!(decode_uint(&bytes)?, (v, len));
+    }
+
+    #[test]
+    fn sint_roundtrips_and_leaves_the_rest(v in any::<i128>(), rest in prop::collection::vec(any::<u8>(), 0..4)) {
+        let mut bytes = sint(v);
+        let len = bytes.len();
+        prop_assert!(len <= MAX_LEN);
+        bytes.extend_from_slice(&rest);
+        prop_assert_eq!(decode_sint(&bytes)?, (v, len));
+    }
+
+    #[test]
+    fn uint_order_is_preserved(a in any::<u64>(), b in any::<u128>()) {
+        let a = a as u128;
+        prop_assert_eq!(uint(a).cmp(&uint(b)), a.cmp(&b));
+    }
+
+    #[test]
+    fn sint_order_is_preserved(a in any::<i64>(), b in any::<i128>()) {
+        let a = a as i128;
+        prop_assert_eq!(sint(a).cmp(&sint(b)), a.cmp(&b));
+    }
+
+    #[test]
+    fn matches_the_serde_encoding(u in any::<u64>(), i in any::<i32>()) {
+        let mut bytes = uint(u as u128);
+        encode_sint(i as i128, &mut bytes);
+        prop_assert_eq!(&bytes, &lexcode::to_bytes(&(u, i))?);
+        prop_assert_eq!(lexcode::from_bytes::<(u64, i32)>(&bytes)?, (u, i));
+    }
+
+    #[test]
+    fn arrays_match_the_vec_encoding(u in any::<u128>(), i in any::<i128>()) {
+        let (bytes, len) = encode_uint_to_array(u);
+        prop_assert_eq!(&bytes[..len], &uint(u)[..]);
+        prop_assert!(bytes[len..].iter().all(|&b| b == 0));
+        let (bytes, len) = encode_sint_to_array(i);
+        prop_assert_eq!(&bytes[..len], &sint(i)[..]);
+        prop_assert!(bytes[len..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn encoded_len_is_the_length_of_the_encoding(u in any::<u128>(), i in any::<i128>(), shift in 0u32..128) {
+        prop_assert_eq!(encoded_len_uint(u), uint(u).len());
+        prop_assert_eq!(encoded_len_uint(u >> shift), uint(u >> shift).len());
+        prop_assert_eq!(encoded_len_sint(i), sint(i).len());
+        prop_assert_eq!(encoded_len_sint(i >> shift), sint(i >> shift).len());
+    }
+
+    #[test]
+    fn fast_paths_encode_like_the_general_ones(u in any::<u64>(), i in any::<i64>()) {
+        let mut bytes = Vec::new();
+        encode_u64(u, &mut bytes);
+        prop_assert_eq!(&bytes, &uint(u as u128));
+        bytes.clear();
+        encode_u32(u as u32, &mut bytes);
+        prop_assert_eq!(&bytes, &uint(u as u32 as u128));
+        bytes.clear();
+        encode_i64(i, &mut bytes);
+        prop_assert_eq!(&bytes, &sint(i as i128));
+        bytes.clear();
+        encode_i32(i as i32, &mut bytes);
+        prop_assert_eq!(&bytes, &sint(i as i32 as i128));
+    }
+
+    #[test]
+    fn fast_paths_decode_like_the_general_ones(bytes in near_encodings()) {
+        same(decode_u64(&bytes), narrow(decode_uint(&bytes)))?;
+        same(decode_u32(&bytes), narrow(decode_uint(&bytes)))?;
+        same(decode_i64(&bytes), narrow(decode_sint(&bytes)))?;
+        same(decode_i32(&bytes), narrow(decode_sint(&bytes)))?;
+    }
+
+    #[test]
+    fn readers_consume_exactly_one_integer(u in any::<u128>(), i in any::<i128>(), rest in prop::collection::vec(any::<u8>(), 0..4)) {
+        let mut bytes = uint(u);
+        encode_sint(i, &mut bytes);
+        bytes.extend_from_slice(&rest);
+        let mut reader = &bytes[..];
+        prop_assert_eq!(decode_uint_from_reader(&mut reader)?, u);
+        prop_assert_eq!(decode_sint_from_reader(&mut reader)?, i);
+        prop_assert_eq!(reader, &rest[..]);
+    }
+}
+
+#[test]
+fn extremes_take_max_len() {
+    assert_eq!(uint(u128::MAX).len(), MAX_LEN);
+    assert_eq!(sint(i128::MIN).len(), MAX_LEN);
+    assert_eq!(sint(i128::MAX).len(), MAX_LEN);
+    assert_eq!(encoded_len_uint(u128::MAX), MAX_LEN);
+    assert_eq!(encoded_len_sint(i128::MIN), MAX_LEN);
+    assert_eq!(encoded_len_sint(i128::MAX), MAX_LEN);
+    assert_eq!(uint(127), [0x7F]);
+    assert_eq!(sint(-64), [0x40]);
+    assert_eq!(sint(63), [0xBF]);
+}
+
+#[test]
+fn fast_paths_cover_the_extremes() {
+    for v in [0, 127, 128, u64::MAX - 1, u64::MAX] {
+        let mut bytes = Vec::new();
+        encode_u64(v, &mut bytes);
+        assert_eq!(bytes, uint(v as u128));
+        assert_eq!(decode_u64(&bytes).unwrap(), (v, bytes.len()));
+    }
+    for v in [i64::MIN, i64::MIN + 1, -65, -64, 63, 64, i64::MAX] {
+        let mut bytes = Vec::new();
+        encode_i64(v, &mut bytes);
+        assert_eq!(bytes, sint(v as i128));
+        assert_eq!(decode_i64(&bytes).unwrap(), (v, bytes.len()));
+    }
+    assert!(matches!(decode_u64(&uint(u64::MAX as u128 + 1)), Err(Error::Message(_))));
+    assert!(matches!(decode_i64(&sint(i64::MIN as i128 - 1)), Err(Error::Message(_))));
+    assert!(matches!(decode_i64(&sint(i64::MAX as i128 + 1)), Err(Error::Message(_))));
+    assert!(matches!(decode_u32(&uint(u32::MAX as u128 + 1)), Err(Error::Message(_))));
+}
+
+#[test]
+fn encodings_are_usable_in_constants() {
+    const SMALL: [u8; 1] = lexcode::const_encode_u64(7);
+    const MAX: [u8; encoded_len_uint(u64::MAX as u128)] = lexcode::const_encode_u64(u64::MAX);
+    const NEGATIVE: ([u8; MAX_LEN], usize) = encode_sint_to_array(-100_000);
+    const HUGE: ([u8; MAX_LEN], usize) = encode_uint_to_array(u128::MAX);
+    assert_eq!(SMALL, [7]);
+    assert_eq!(MAX.to_vec(), uint(u64::MAX as u128));
+    assert_eq!(NEGATIVE.0[..NEGATIVE.1], sint(-100_000));
+    assert_eq!(HUGE.0[..HUGE.1], uint(u128::MAX));
+}
+
+#[test]
+#[should_panic(expected = "encoded_len_uint")]
+fn const_encode_checks_the_length() {
+    let _: [u8; 2] = lexcode::const_encode_u64(std::hint::black_box(7));
+}
+
+#[test]
+fn truncated_input_is_eof() {
+    for v in [128u128, 1 << 40, u128::MAX] {
+        let bytes = uint(v);
+        assert!(matches!(decode_uint(&bytes[..bytes.len() - 1]), Err(Error::Eof)));
+    }
+    for v in [-65i128, 64, i128::MIN, i128::MAX] {
+        let bytes = sint(v);
+        assert!(matches!(decode_sint(&bytes[..bytes.len() - 1]), Err(Error::Eof)));
+    }
+    assert!(matches!(decode_uint(&[]), Err(Error::Eof)));
+    assert!(matches!(decode_sint(&[]), Err(Error::Eof)));
+}
+
+#[test]
+fn out_of_range_encodings_are_an_error() {
+    assert!(matches!(decode_uint(&[0xFF; MAX_LEN]), Err(Error::Message(_))));
+    assert!(matches!(decode_sint(&[0xFF; MAX_LEN]), Err(Error::Message(_))));
+    assert!(matches!(decode_sint(&[0x00; MAX_LEN]), Err(Error::Message(_))));
+}
+
+#[test]
+fn readers_report_truncation_and_overflow() {
+    for bytes in [uint(1 << 40), uint(u128::MAX)] {
+        let err = decode_uint_from_reader(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+    for bytes in [sint(i128::MIN), sint(i128::MAX), sint(-100_000)] {
+        let err = decode_sint_from_reader(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+    assert_eq!(decode_uint_from_reader(&[0xFF; MAX_LEN][..]).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(decode_sint_from_reader(&[0x00; MAX_LEN][..]).unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn readers_do_not_read_past_the_integer() {
+    // A reader that fails once the integer's bytes are used up.
+    let bytes = sint(-1 << 80);
+    let mut reader = (&bytes[..]).chain(FailingReader);
+    assert_eq!(decode_sint_from_reader(&mut reader).unwrap(), -1 << 80);
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+        Err(ErrorKind::Other.into())
+    }
+}
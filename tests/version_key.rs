@@ -0,0 +1,68 @@
+#![cfg(feature = "semver")]
+
+use lexcode::VersionKey;
+use proptest::prelude::*;
+use semver::Version;
+
+fn version() -> impl Strategy<Value = Version> {
+    let ident = prop_oneof!["0|[1-9][0-9]{0,2}", "[a-z][a-z0-9-]{0,3}"];
+    (
+        0u64..20,
+        0u64..20,
+        0u64..20,
+        prop::collection::vec(ident, 0..3),
+        prop::option::of("[a-z0-9]{1,3}"),
+    )
+        .prop_map(|(major, minor, patch, pre, build)| {
+            let mut s = format!("{major}.{minor}.{patch}");
+            if !pre.is_empty() {
+                s += &format!("-{}", pre.join("."));
+            }
+            if let Some(build) = build {
+                s += &format!("+{build}");
+            }
+            s.parse().unwrap()
+        })
+}
+
+proptest! {
+    #[test]
+    fn order_and_roundtrip(a in version(), b in version()) {
+        let bytes_a = lexcode::to_bytes(&VersionKey(a.clone()))?;
+        let bytes_b = lexcode::to_bytes(&VersionKey(b.clone()))?;
+        prop_assert_eq!(VersionKey(a.clone()).cmp(&VersionKey(b.clone())), bytes_a.cmp(&bytes_b));
+        prop_assert_eq!(lexcode::from_bytes::<VersionKey>(&bytes_a)?.0, a.clone());
+        if a.cmp_precedence(&b).is_ne() {
+            prop_assert_eq!(a.cmp_precedence(&b), bytes_a.cmp(&bytes_b));
+        }
+    }
+}
+
+#[test]
+fn spec_precedence_example() {
+    let ordered = [
+        "1.0.0-alpha",
+        "1.0.0-alpha.1",
+        "1.0.0-alpha.beta",
+        "1.0.0-beta",
+        "1.0.0-beta.2",
+        "1.0.0-beta.11",
+        "1.0.0-rc.1",
+        "1.0.0",
+    ];
+    let keys: Vec<Vec<u8>> = ordered
+        .iter()
+        .map(|v| lexcode::to_bytes(&VersionKey(v.parse().unwrap())).unwrap())
+        .collect();
+    assert!(keys.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn huge_leading_zero_counts_are_an_error() {
+    let mut bytes = lexcode::to_bytes(&VersionKey("5.5.5-0".parse().unwrap())).unwrap();
+    // The leading zero count of the numeric identifier `0`, raised to 2^40.
+    let zeros = bytes.iter().rposition(|&b| b == 1).unwrap();
+    let huge = lexcode::to_bytes(&(1u64 << 40)).unwrap();
+    bytes.splice(zeros..=zeros, huge);
+    assert!(lexcode::from_bytes::<VersionKey>(&bytes).is_err());
+}